@@ -0,0 +1,149 @@
+//! Reads the ABIs forge compiles into `out/` and lists each contract's functions, events and
+//! errors with their signatures and selectors.
+
+use alloy::json_abi::JsonAbi;
+use color_eyre::{eyre::eyre, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Function,
+    Event,
+    Error,
+}
+
+impl Kind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Kind::Function => "function",
+            Kind::Event => "event",
+            Kind::Error => "error",
+        }
+    }
+}
+
+pub struct Entry {
+    pub kind: Kind,
+    /// Human-readable, with parameter names and outputs.
+    pub signature: String,
+    /// 4-byte selector for functions and errors; the 32-byte topic for events.
+    pub selector: String,
+}
+
+pub struct Contract {
+    pub name: String,
+    /// Source file, relative to the project root.
+    pub source: PathBuf,
+    pub entries: Vec<Entry>,
+    pub abi: JsonAbi,
+}
+
+impl Contract {
+    /// Whether the contract comes from a dependency under `lib/`.
+    pub fn is_dependency(&self) -> bool {
+        self.source.starts_with("lib")
+    }
+}
+
+#[derive(Deserialize)]
+struct Artifact {
+    abi: JsonAbi,
+    #[serde(default)]
+    metadata: Value,
+}
+
+/// Contracts with a non-empty ABI, the project's own first. Tests, scripts and forge-std are
+/// left out.
+pub fn load(root: &Path) -> Result<Vec<Contract>> {
+    let out = root.join("out");
+    let dirs = fs::read_dir(&out).map_err(|_| {
+        eyre!(
+            "No {} yet: build the contracts first (forge build)",
+            out.display()
+        )
+    })?;
+    let mut contracts = Vec::new();
+    for dir in dirs.filter_map(|entry| entry.ok()) {
+        let dir_name = dir.file_name().to_string_lossy().to_string();
+        if !dir_name.ends_with(".sol")
+            || dir_name.ends_with(".t.sol")
+            || dir_name.ends_with(".s.sol")
+        {
+            continue;
+        }
+        let Ok(files) = fs::read_dir(dir.path()) else {
+            continue;
+        };
+        for file in files.filter_map(|entry| entry.ok()) {
+            let path = file.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let Some(artifact) = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<Artifact>(&content).ok())
+            else {
+                continue;
+            };
+            let Some(contract) = contract(&path, &dir_name, artifact) else {
+                continue;
+            };
+            if !contract.source.starts_with("lib/forge-std") {
+                contracts.push(contract);
+            }
+        }
+    }
+    contracts.sort_by(|a, b| {
+        (a.is_dependency(), &a.source, &a.name).cmp(&(b.is_dependency(), &b.source, &b.name))
+    });
+    Ok(contracts)
+}
+
+fn contract(path: &Path, dir_name: &str, artifact: Artifact) -> Option<Contract> {
+    // `Counter.json`, or `Counter.0.8.26.json` when several compiler versions were used
+    let stem = path.file_stem()?.to_string_lossy();
+    let name = stem.split('.').next()?.to_string();
+    let source = artifact.metadata["settings"]["compilationTarget"]
+        .as_object()
+        .and_then(|target| target.keys().next())
+        .map_or_else(|| PathBuf::from(dir_name), PathBuf::from);
+
+    let abi = artifact.abi;
+    let mut entries: Vec<Entry> = abi
+        .functions()
+        .map(|function| Entry {
+            kind: Kind::Function,
+            signature: without_keyword(function.full_signature()),
+            selector: function.selector().to_string(),
+        })
+        .collect();
+    entries.extend(abi.events().map(|event| Entry {
+        kind: Kind::Event,
+        signature: without_keyword(event.full_signature()),
+        selector: event.selector().to_string(),
+    }));
+    entries.extend(abi.errors().map(|error| Entry {
+        kind: Kind::Error,
+        signature: error.signature(),
+        selector: error.selector().to_string(),
+    }));
+    (!entries.is_empty()).then_some(Contract {
+        name,
+        source,
+        entries,
+        abi,
+    })
+}
+
+/// alloy's full signatures start with `function `/`event `, which the list shows as the kind.
+fn without_keyword(signature: String) -> String {
+    match signature.split_once(' ') {
+        Some((_, rest)) => rest.to_string(),
+        None => signature,
+    }
+}
@@ -0,0 +1,104 @@
+//! Where the project's contracts were deployed, per chain, in `addresses.json` at the project
+//! root, and their addresses as `<NAME>_ADDRESS` lines in its `.env` for scripts and the host.
+
+use crate::broadcast::Deployed;
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path};
+
+pub const FILE_NAME: &str = "addresses.json";
+pub const ENV_FILE: &str = ".env";
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub address: String,
+    pub tx_hash: String,
+    /// The script that deployed it.
+    pub script: String,
+    /// RFC 3339 time it was recorded.
+    pub deployed_at: String,
+}
+
+/// Contracts by name, by chain ID.
+pub type AddressBook = BTreeMap<u64, BTreeMap<String, Entry>>;
+
+pub fn load(root: &Path) -> Result<AddressBook> {
+    let Ok(content) = fs::read_to_string(root.join(FILE_NAME)) else {
+        return Ok(AddressBook::new());
+    };
+    serde_json::from_str(&content).map_err(|e| eyre!("Invalid {}: {}", FILE_NAME, e))
+}
+
+/// Adds the contracts a broadcast of `script` created on `chain_id`, replacing earlier
+/// deployments of the same contracts there.
+pub fn record(root: &Path, chain_id: u64, script: &Path, contracts: &[Deployed]) -> Result<()> {
+    let mut book = load(root)?;
+    let deployed_at = chrono::Utc::now().to_rfc3339();
+    let chain = book.entry(chain_id).or_default();
+    for contract in contracts {
+        chain.insert(
+            contract.name.clone(),
+            Entry {
+                address: contract.address.clone(),
+                tx_hash: contract.tx_hash.clone(),
+                script: script.display().to_string(),
+                deployed_at: deployed_at.clone(),
+            },
+        );
+    }
+    fs::write(
+        root.join(FILE_NAME),
+        serde_json::to_string_pretty(&book)? + "\n",
+    )?;
+    Ok(())
+}
+
+/// The variable a contract's address goes in, e.g. `COUNTER_ADDRESS` for `Counter`.
+pub fn env_var(contract: &str) -> String {
+    let mut name = String::new();
+    let mut previous_lower = false;
+    for c in contract.chars() {
+        if c.is_ascii_uppercase() && previous_lower {
+            name.push('_');
+        }
+        previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        name.push(c.to_ascii_uppercase());
+    }
+    name.push_str("_ADDRESS");
+    name
+}
+
+/// Sets each contract's variable in the project's `.env`, keeping the rest of the file as it
+/// is. Returns the variables set.
+pub fn write_env(root: &Path, contracts: &[Deployed]) -> Result<Vec<String>> {
+    let file = root.join(ENV_FILE);
+    let mut lines: Vec<String> = fs::read_to_string(&file)
+        .unwrap_or_default()
+        .lines()
+        .map(String::from)
+        .collect();
+    let mut set = Vec::new();
+    for contract in contracts {
+        let var = env_var(&contract.name);
+        let existing = lines.iter().position(|l| {
+            let l = l.trim_start();
+            let l = l.strip_prefix("export ").unwrap_or(l);
+            l.split_once('=').is_some_and(|(key, _)| key.trim() == var)
+        });
+        match existing {
+            // `export` stays, for files that are sourced
+            Some(index) => {
+                let export = if lines[index].trim_start().starts_with("export ") {
+                    "export "
+                } else {
+                    ""
+                };
+                lines[index] = format!("{}{}={}", export, var, contract.address);
+            }
+            None => lines.push(format!("{}={}", var, contract.address)),
+        }
+        set.push(var);
+    }
+    fs::write(&file, lines.join("\n") + "\n")?;
+    Ok(set)
+}
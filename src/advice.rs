@@ -0,0 +1,101 @@
+//! Known ways steps fail, recognised in a failed command's output, each with what to do about
+//! it. Shown next to the raw error, which stays the source of truth.
+
+#[derive(Debug)]
+pub struct Advice {
+    pub problem: &'static str,
+    pub fix: &'static str,
+}
+
+struct Signature {
+    /// Any one of these, lowercased, marks the failure...
+    any: &'static [&'static str],
+    /// ...as long as all of these appear somewhere too.
+    all: &'static [&'static str],
+    advice: Advice,
+}
+
+/// Checked in order; the first match wins.
+const CATALOG: &[Signature] = &[
+    Signature {
+        any: &[
+            "address already in use",
+            "addrinuse",
+            "os error 98",
+            "os error 48",
+        ],
+        all: &[],
+        advice: Advice {
+            problem: "A port the step needs is taken, usually 8545 by another anvil",
+            fix: "Stop whatever is listening (`lsof -i :8545` shows it), or deploy to that chain instead",
+        },
+    },
+    Signature {
+        any: &[
+            "cannot connect to the docker daemon",
+            "is the docker daemon running",
+            "docker.sock: connect: no such file",
+        ],
+        all: &[],
+        advice: Advice {
+            problem: "Docker isn't running",
+            fix: "Start Docker Desktop, or `sudo systemctl start docker`; Groth16 proving and reproducible guest builds need it",
+        },
+    },
+    Signature {
+        any: &[
+            "unauthorized",
+            "status code 401",
+            "invalid api key",
+            "403 forbidden",
+        ],
+        all: &["bonsai"],
+        advice: Advice {
+            problem: "Bonsai rejected the API key",
+            fix: "Check the key under Manage credentials or in BONSAI_API_KEY; keys are requested at https://bonsai.xyz/apply",
+        },
+    },
+    Signature {
+        any: &[
+            "could not read username",
+            "could not read password",
+            "terminal prompts disabled",
+            "authentication failed",
+            "permission denied (publickey)",
+            "host key verification failed",
+        ],
+        all: &[],
+        advice: Advice {
+            problem: "Git couldn't authenticate while fetching a repository or submodule",
+            fix: "The template's submodules are public over HTTPS; look for `url.*.insteadOf` rules rewriting them to SSH in `git config --global --list`, or set up credentials for a private fork",
+        },
+    },
+    Signature {
+        any: &[
+            "toolchain 'risc0' is not installed",
+            "risc0 toolchain could not be found",
+            "could not find r0vm",
+            "r0vm: command not found",
+            "r0vm not found",
+            "cargo-risczero: command not found",
+            "no such command: `risczero`",
+        ],
+        all: &[],
+        advice: Advice {
+            problem: "Part of the RISC Zero toolchain is missing or out of date",
+            fix: "Run `rzup install` to install the toolchain, r0vm and cargo-risczero at matching versions",
+        },
+    },
+];
+
+/// The advice for the first known failure in `output`, if any.
+pub fn diagnose(output: &[String]) -> Option<&'static Advice> {
+    let text = output.join("\n").to_lowercase();
+    CATALOG
+        .iter()
+        .find(|signature| {
+            signature.any.iter().any(|pattern| text.contains(pattern))
+                && signature.all.iter().all(|pattern| text.contains(pattern))
+        })
+        .map(|signature| &signature.advice)
+}
@@ -1,34 +1,160 @@
-use chrono;
-use color_eyre::Result;
-use crossterm::{
-    event::{self, DisableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind},
-    execute,
-    terminal::{disable_raw_mode, LeaveAlternateScreen},
+use crate::{
+    abi, address_book, apple_silicon, artifacts,
+    batch::{self, Batch},
+    benchmark, bonsai, boundless, broadcast, cargo_config, cargo_generate,
+    chain::{self, Chain},
+    chain_presets::ChainPreset,
+    chain_state,
+    command_error::{CommandError, FailureReason},
+    compat, contract_checks, crash,
+    credentials::{CredentialKind, Credentials},
+    demo, dev_keys, docs, drift, events,
+    exit_code::Outcome,
+    formatting, guest_build,
+    guest_image::{self, GuestImage},
+    health, history, http, journal, junit, lint,
+    log_presets::LogPreset,
+    manifest, monorepo, networks, nix,
+    output::{self, OutputLine, Timestamps, Verbosity},
+    palette::{Action, Palette},
+    paths, platform,
+    project_file::{self, ProjectFile},
+    projects::{self, RecentProject},
+    prover::ProverBackend,
+    queue::{self, Queue},
+    receipt::{self, Inspection},
+    redact, replay, resources,
+    rpc_presets::{self, Network, RpcProvider},
+    scaffold::{self, ScaffoldKind},
+    scripts, self_update,
+    session::{self, Session},
+    settings::{LogLevel, ProgressView, Settings},
+    signals,
+    signer::{self, Signer, SignerKind},
+    solc,
+    step_logs::StepLogs,
+    summary::ProjectSummary,
+    supervisor,
+    tasks::{self, Task, TaskStatus},
+    template_cache,
+    templates::{self, Template},
+    test_wallet::TestWallet,
+    timeouts::Timeouts,
+    tool_versions, tools, transaction,
+    transfer::Transfer,
+    tutorial, upgrade,
+    verify::{Verifier, VerifierKind},
+    watch, workspace,
+};
+use alloy::{
+    json_abi::Function,
+    primitives::{Address, Bytes, U256},
 };
+use color_eyre::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use ratatui::{
     prelude::*,
     style::Stylize,
     text::Line,
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
-use regex;
-use std::{fs, panic, path::Path, path::PathBuf, process::Command};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::mpsc,
+};
 
-#[derive(Default)]
+/// How many projects of a batch are listed at once.
+const BATCH_ROWS: usize = 16;
+
+#[derive(Debug, Default)]
 pub enum AppState {
     #[default]
     CheckingDependencies,
+    InstallingCargoRisczero,
     EnteringProjectName,
     ConfirmOverwrite,
     Installing(InstallStep),
     Success,
     TestMenu,
     EnteringBonsaiKey, // New state for API key input
+    ConfirmingPublicRpc,
+    /// Offering to fetch the solc version a forge build was missing, then retry.
+    ConfirmingSolcInstall,
+    InstallingSolc,
+    DeploySetup,
+    /// Running the deploy script without broadcasting, to show what it would do.
+    SimulatingDeploy,
+    ConfirmingDeploy,
+    Deploying,
+    /// The contracts a broadcast just created.
+    DeployedContracts,
+    Scripts,
+    ScriptSetup,
+    RunningScript,
+    CheckingHealth,
+    Health,
+    CheckingContracts,
+    ContractChecks,
+    FetchingTemplate,
+    TemplateDrift,
+    Scaffolding(ScaffoldKind),
+    GuestImages,
+    Abi,
+    Events,
+    TransactionBuilder,
+    SendingTransaction,
+    InspectingReceipt,
+    BoundlessRequest,
+    SubmittingBoundlessRequest,
+    Watching,
+    Queue,
+    RunningQueue,
+    Benchmark,
+    Benchmarking,
+    TestHistory,
+    Format,
+    Formatting,
+    Linting,
+    Lint,
+    AnvilControls,
+    ChoosingChain,
+    ChoosingExamples,
+    /// Every template the toolchain can build, to pick the one to scaffold.
+    SelectingTemplate,
+    /// The git URL and branch to clone templates from instead of risc0-ethereum.
+    EnteringTemplateRepo,
+    /// Picking risc0-ethereum versions before the manifests are rewritten.
+    ChoosingVersions,
+    /// Picking one of the project's networks before a test or deployment.
+    ChoosingNetwork,
+    BonsaiAccount,
+    Dashboard,
+    UpgradingProject,
+    /// Offering to pick up where the last session left off.
+    RestoringSession,
+    UnlockingCredentials,
+    Credentials,
+    EditingCredential,
+    /// A freshly generated testnet wallet, with where to fund it.
+    TestWallet,
+    Settings,
     Testing(E2ETestStep),
+    /// Every project of a batch has been tried.
+    Batch,
+    /// Cloning the cargo-generate template from `--template`.
+    FetchingCargoTemplate,
+    /// Asking for the values the cargo-generate template declares.
+    TemplatePrompts,
+    GeneratingFromTemplate,
     Finished,
 }
 
+#[derive(Debug)]
 pub enum InstallStep {
     CloningRepo,
     SettingUpSparse,
@@ -37,7 +163,49 @@ pub enum InstallStep {
     SettingUpForge,
 }
 
-#[derive(Clone)]
+impl InstallStep {
+    pub const ALL: &'static [InstallStep] = &[
+        InstallStep::CloningRepo,
+        InstallStep::SettingUpSparse,
+        InstallStep::MovingFiles,
+        InstallStep::UpdatingDependencies,
+        InstallStep::SettingUpForge,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            InstallStep::CloningRepo => "Downloading Template",
+            InstallStep::SettingUpSparse => "Extracting the Template",
+            InstallStep::MovingFiles => "Setting Up Project Structure",
+            InstallStep::UpdatingDependencies => "Configuring Dependencies",
+            InstallStep::SettingUpForge => "Installing Forge Components",
+        }
+    }
+
+    /// Short machine-friendly name, used in summaries and logs.
+    pub fn label(&self) -> &'static str {
+        match self {
+            InstallStep::CloningRepo => "cloning-repo",
+            InstallStep::SettingUpSparse => "setting-up-sparse",
+            InstallStep::MovingFiles => "moving-files",
+            InstallStep::UpdatingDependencies => "updating-dependencies",
+            InstallStep::SettingUpForge => "setting-up-forge",
+        }
+    }
+
+    /// Position in the install, from 1, for ordering the step logs.
+    pub fn number(&self) -> usize {
+        match self {
+            InstallStep::CloningRepo => 1,
+            InstallStep::SettingUpSparse => 2,
+            InstallStep::MovingFiles => 3,
+            InstallStep::UpdatingDependencies => 4,
+            InstallStep::SettingUpForge => 5,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
 pub enum E2ETestStep {
     PreparingEnvironment, // Set up env vars
     StartingAnvil,        // Start anvil
@@ -45,968 +213,9768 @@ pub enum E2ETestStep {
     Cleanup,              // Clean up processes
 }
 
-pub struct App {
-    state: AppState,
-    project_name: String,
-    status_message: String,
-    rust_installed: bool,
-    foundry_installed: bool,
-    risc0_version: Option<String>,
-    command_output: Vec<String>,
-    output_scroll: u16,
-    pending_redraw: bool,
-    selected_menu_item: usize,
-    confirm_menu_item: usize,
-    test_env: Option<TestEnvironment>, // Add this to store test-related data
-    bonsai_api_key: String,            // Add this field
+impl E2ETestStep {
+    pub const ALL: &'static [E2ETestStep] = &[
+        E2ETestStep::PreparingEnvironment,
+        E2ETestStep::StartingAnvil,
+        E2ETestStep::RunningTest,
+        E2ETestStep::Cleanup,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            E2ETestStep::PreparingEnvironment => "Preparing environment",
+            E2ETestStep::StartingAnvil => "Starting anvil",
+            E2ETestStep::RunningTest => "Running test",
+            E2ETestStep::Cleanup => "Cleaning up",
+        }
+    }
+
+    /// Short machine-friendly name, used in test reports.
+    pub fn label(&self) -> &'static str {
+        match self {
+            E2ETestStep::PreparingEnvironment => "preparing-environment",
+            E2ETestStep::StartingAnvil => "starting-anvil",
+            E2ETestStep::RunningTest => "running-test",
+            E2ETestStep::Cleanup => "cleanup",
+        }
+    }
 }
 
-struct TestEnvironment {
-    eth_rpc_url: String,
-    eth_wallet_address: String,
-    eth_wallet_private_key: String,
-    bonsai_api_key: String,
-    bonsai_api_url: String,
-    anvil_process: Option<std::process::Child>,
+/// Entries of the testing toolbox menu, in display order.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ToolboxItem {
+    EndToEndTest,
+    Deploy,
+    Scripts,
+    Health,
+    CheckContracts,
+    TemplateDrift,
+    AddGuest,
+    AddContract,
+    GuestImages,
+    Abi,
+    Events,
+    Transaction,
+    InspectReceipt,
+    BoundlessRequest,
+    Watch,
+    Queue,
+    Benchmark,
+    History,
+    Format,
+    Lint,
+    NixFlake,
+    AnvilControls,
+    Chain,
+    BonsaiAccount,
+    Projects,
+    Credentials,
+    TestWallet,
+    Settings,
+    SelfUpdate,
+    Exit,
 }
 
-impl App {
-    pub fn new() -> Self {
-        // Set up panic hook to restore terminal on crash and kill anvil
-        panic::set_hook(Box::new(|panic_info| {
-            let _ = disable_raw_mode();
-            let _ = execute!(std::io::stdout(), LeaveAlternateScreen, DisableMouseCapture);
-            // Try to kill anvil if it's running
-            let _ = Command::new("pkill").arg("anvil").output();
-            eprintln!("Panic occurred: {:?}", panic_info);
-        }));
+impl ToolboxItem {
+    pub const ALL: &'static [ToolboxItem] = &[
+        ToolboxItem::EndToEndTest,
+        ToolboxItem::Deploy,
+        ToolboxItem::Scripts,
+        ToolboxItem::Health,
+        ToolboxItem::CheckContracts,
+        ToolboxItem::TemplateDrift,
+        ToolboxItem::AddGuest,
+        ToolboxItem::AddContract,
+        ToolboxItem::GuestImages,
+        ToolboxItem::Abi,
+        ToolboxItem::Events,
+        ToolboxItem::Transaction,
+        ToolboxItem::InspectReceipt,
+        ToolboxItem::BoundlessRequest,
+        ToolboxItem::Watch,
+        ToolboxItem::Queue,
+        ToolboxItem::Benchmark,
+        ToolboxItem::History,
+        ToolboxItem::Format,
+        ToolboxItem::Lint,
+        ToolboxItem::NixFlake,
+        ToolboxItem::AnvilControls,
+        ToolboxItem::Chain,
+        ToolboxItem::BonsaiAccount,
+        ToolboxItem::Projects,
+        ToolboxItem::Credentials,
+        ToolboxItem::TestWallet,
+        ToolboxItem::Settings,
+        ToolboxItem::SelfUpdate,
+        ToolboxItem::Exit,
+    ];
 
-        Self {
-            state: AppState::CheckingDependencies,
-            project_name: String::new(),
-            status_message: String::from("Checking dependencies..."),
-            rust_installed: false,
-            foundry_installed: false,
-            risc0_version: None,
-            command_output: Vec::new(),
-            output_scroll: 0,
-            pending_redraw: false,
-            selected_menu_item: 0,
-            confirm_menu_item: 0,
-            test_env: None,
-            bonsai_api_key: String::new(), // Add this field
+    pub fn label(self) -> &'static str {
+        match self {
+            ToolboxItem::EndToEndTest => "🔧 Run end-to-end test with Anvil",
+            ToolboxItem::Deploy => "🚀 Deploy contracts",
+            ToolboxItem::Scripts => "🧾 Run forge scripts",
+            ToolboxItem::Health => "🩺 Project health check",
+            ToolboxItem::CheckContracts => "📏 Check contract sizes and gas",
+            ToolboxItem::TemplateDrift => "🔀 Compare with template",
+            ToolboxItem::AddGuest => "➕ Add guest program",
+            ToolboxItem::AddContract => "📜 Add contract",
+            ToolboxItem::GuestImages => "🧬 Guest image IDs and ELFs",
+            ToolboxItem::Abi => "🔣 Contract ABIs and selectors",
+            ToolboxItem::Events => "📡 Contract events",
+            ToolboxItem::Transaction => "✉️ Send a transaction",
+            ToolboxItem::InspectReceipt => "🔍 Inspect a receipt or journal",
+            ToolboxItem::BoundlessRequest => "🛰️ Submit a Boundless proof request",
+            ToolboxItem::Watch => "👀 Watch and rebuild on changes",
+            ToolboxItem::Queue => "🗂️ Task queue",
+            ToolboxItem::Benchmark => "⏱️ Benchmark proving backends",
+            ToolboxItem::History => "🕘 Test history",
+            ToolboxItem::Format => "🧹 Format project",
+            ToolboxItem::Lint => "📎 Lint with clippy",
+            ToolboxItem::NixFlake => "❄️ Generate Nix flake",
+            ToolboxItem::AnvilControls => "⛏️ Anvil time and block controls",
+            ToolboxItem::Chain => "🌐 Choose chain",
+            ToolboxItem::BonsaiAccount => "☁️ Bonsai account and quota",
+            ToolboxItem::Projects => "📋 All projects",
+            ToolboxItem::Credentials => "🔑 Manage credentials",
+            ToolboxItem::TestWallet => "👛 Generate test wallet",
+            ToolboxItem::Settings => "⚙️ Settings",
+            ToolboxItem::SelfUpdate => "⬆️ Update steel-tui",
+            ToolboxItem::Exit => "🚪 Exit",
         }
     }
 
-    fn add_output(&mut self, output: String) {
-        // Just add the raw line to the output
-        self.command_output.push(output);
-        self.pending_redraw = true;
+    /// Entries that can't do anything without the network.
+    pub fn needs_network(self) -> bool {
+        matches!(
+            self,
+            ToolboxItem::BonsaiAccount | ToolboxItem::BoundlessRequest | ToolboxItem::SelfUpdate
+        )
     }
+}
 
-    fn run_command(
-        &mut self,
-        command: &mut Command,
-        description: &str,
-        terminal: &mut Terminal<impl Backend>,
-    ) -> Result<()> {
-        self.status_message = description.to_string();
+/// A cargo-generate template the project is made from instead of a Steel example.
+struct CargoTemplate {
+    /// A git URL or a local directory.
+    source: String,
+    /// Once fetched.
+    template: Option<cargo_generate::Template>,
+    /// One per placeholder, as typed or picked.
+    values: Vec<String>,
+    field: usize,
+}
 
-        // Force a redraw before running the command
-        terminal.draw(|frame| self.ui(frame))?;
+/// Rebuilding on every source change.
+struct WatchSession {
+    watcher: watch::Watcher,
+    rebuilds: u32,
+    /// Whether the last rebuild passed, and when it finished.
+    last: Option<(bool, String)>,
+}
 
-        // Configure the command with piped output
-        command.stdout(std::process::Stdio::piped());
-        command.stderr(std::process::Stdio::piped());
+/// The benchmark's inputs, progress, and results.
+struct BenchmarkForm {
+    runs: u32,
+    /// Whether each of `Backend::ALL` is included.
+    backends: [bool; 3],
+    /// 0 = runs, then one field per backend.
+    field: usize,
+    /// Backend and run number in progress.
+    current: Option<(benchmark::Backend, u32)>,
+    results: Vec<benchmark::Run>,
+}
 
-        let mut child = command.spawn()?;
+impl Default for BenchmarkForm {
+    fn default() -> Self {
+        Self {
+            runs: 3,
+            backends: [true, true, false],
+            field: 0,
+            current: None,
+            results: Vec::new(),
+        }
+    }
+}
 
-        use std::io::{BufRead, BufReader};
+impl BenchmarkForm {
+    const FIELDS: usize = 1 + benchmark::Backend::ALL.len();
+    const MAX_RUNS: u32 = 20;
 
-        // Handle stdout
-        if let Some(stdout) = child.stdout.take() {
-            let reader = BufReader::new(stdout);
-            for line in reader.lines() {
-                if let Ok(line) = line {
-                    self.add_output(line);
-                    terminal.draw(|frame| self.ui(frame))?;
-                }
-            }
-        }
+    fn selected(&self) -> Vec<benchmark::Backend> {
+        benchmark::Backend::ALL
+            .iter()
+            .zip(self.backends)
+            .filter(|(_, selected)| *selected)
+            .map(|(backend, _)| *backend)
+            .collect()
+    }
 
-        // Handle stderr
-        if let Some(stderr) = child.stderr.take() {
-            let reader = BufReader::new(stderr);
-            for line in reader.lines() {
-                if let Ok(line) = line {
-                    self.add_output(line);
-                    terminal.draw(|frame| self.ui(frame))?;
-                }
-            }
-        }
+    fn next_field(&mut self, step: usize) {
+        self.field = (self.field + step) % Self::FIELDS;
+    }
+}
 
-        let status = child.wait()?;
-        if !status.success() {
-            return Err(color_eyre::eyre::eyre!("Command failed"));
-        }
+/// What the last formatting run changed, or in check mode would have changed.
+struct FormatReport {
+    check: bool,
+    files: Vec<(formatting::Tool, PathBuf)>,
+    /// Formatters that failed for another reason, e.g. a syntax error.
+    errors: Vec<String>,
+}
 
-        // Force another redraw after adding output
-        terminal.draw(|frame| self.ui(frame))?;
+/// Contract sizes and test gas against the limits deployments run into.
+struct ContractReport {
+    sizes: Vec<contract_checks::Finding>,
+    gas: Vec<contract_checks::Finding>,
+    block_gas_limit: u64,
+    /// Where the block gas limit is from.
+    limit_source: String,
+}
 
-        Ok(())
+impl ContractReport {
+    fn count(&self, verdict: contract_checks::Verdict) -> usize {
+        self.sizes
+            .iter()
+            .chain(&self.gas)
+            .filter(|finding| finding.verdict == verdict)
+            .count()
     }
+}
 
-    fn check_dependency(
-        &mut self,
-        cmd: &str,
-        args: &[&str],
-        success_msg: &str,
-        error_msg: &str,
-    ) -> bool {
-        match Command::new(cmd).args(args).output() {
-            Ok(_) => {
-                self.status_message = format!("✓ {}", success_msg);
-                true
-            }
-            Err(_) => {
-                self.status_message = format!("✗ {}", error_msg);
-                false
+/// Recorded test runs, and up to two picked to compare.
+struct HistoryView {
+    runs: Vec<artifacts::Record>,
+    selected: usize,
+    /// Indices into `runs`, in the order they were picked.
+    marked: Vec<usize>,
+}
+
+impl HistoryView {
+    /// Picks or unpicks the selected run; picking a third drops the oldest pick.
+    fn toggle(&mut self) {
+        if let Some(at) = self.marked.iter().position(|&i| i == self.selected) {
+            self.marked.remove(at);
+        } else if self.selected < self.runs.len() {
+            if self.marked.len() == 2 {
+                self.marked.remove(0);
             }
+            self.marked.push(self.selected);
         }
     }
 
-    fn check_rust(&mut self) -> bool {
-        self.check_dependency(
-            "rustc",
-            &["--version"],
-            "Rust is installed",
-            "Rust not found. Visit: https://www.rust-lang.org/tools/install",
-        )
+    /// The picked runs, older first.
+    fn pair(&self) -> Option<(&artifacts::Record, &artifacts::Record)> {
+        let [a, b] = self.marked[..] else {
+            return None;
+        };
+        // Newest first in the list, so the higher index ran earlier
+        let (older, newer) = (a.max(b), a.min(b));
+        Some((&self.runs[older], &self.runs[newer]))
     }
+}
 
-    fn check_foundry(&mut self) -> bool {
-        self.check_dependency(
-            "forge",
-            &["--version"],
-            "Foundry is installed",
-            "Foundry not found. Visit: https://book.getfoundry.sh/getting-started/installation",
-        )
+/// Clippy's diagnostics from the last lint run, sorted by file.
+struct LintView {
+    /// Each with the index in the command output where it's rendered.
+    diagnostics: Vec<(lint::Diagnostic, usize)>,
+    selected: usize,
+}
+
+impl LintView {
+    fn count(&self, level: lint::Level) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|(d, _)| d.level == level)
+            .count()
     }
+}
 
-    fn check_risc0(&mut self) -> bool {
-        let output = Command::new("cargo")
-            .arg("risczero")
-            .arg("--version")
-            .output();
+/// The receipt inspector's inputs and what it found last.
+#[derive(Default)]
+struct InspectForm {
+    path: String,
+    /// Index into the project's own structs; one past the end shows the journal as hex.
+    journal_type: usize,
+    /// Address of the verifier a Groth16 seal is checked against; empty skips the check.
+    verifier: String,
+    /// 0 = path, 1 = journal type, 2 = verifier.
+    field: usize,
+    structs: Option<journal::Structs>,
+    result: Option<Result<Inspection, String>>,
+}
 
-        match output {
-            Ok(output) => {
-                if let Ok(version) = String::from_utf8(output.stdout) {
-                    // Extract version number using regex or string operations
-                    if version.contains("1.2.") {
-                        self.risc0_version = Some(version.trim().to_string());
-                        self.status_message = String::from("✓ RISC0 1.2.x detected");
-                        return true;
-                    }
-                }
-                self.status_message =
-                    String::from("✗ Unsupported RISC0 version. Version 1.2 is required");
-                false
-            }
-            Err(_) => {
-                self.status_message = String::from(
-                    "✗ RISC0 not found. Visit: https://dev.risczero.com/api/zkvm/install",
-                );
-                false
-            }
+impl InspectForm {
+    const FIELDS: usize = 3;
+
+    fn new(root: &std::path::Path) -> Self {
+        let structs = journal::Structs::load(root);
+        Self {
+            // Steel templates commit a struct called Journal
+            journal_type: structs.own.iter().position(|s| s == "Journal").unwrap_or(0),
+            structs: Some(structs),
+            ..Self::default()
         }
     }
 
-    fn clone_repository(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
-        // If directory exists, remove it first
-        if Path::new(&self.project_name).exists() {
-            self.add_output(format!(
-                "Removing existing directory '{}'...",
-                self.project_name
-            ));
-            fs::remove_dir_all(&self.project_name)?;
-        }
+    fn types(&self) -> &[String] {
+        self.structs.as_ref().map_or(&[], |s| &s.own)
+    }
 
-        self.run_command(
-            Command::new("git").args([
-                "clone",
-                "-b",
-                "release-1.3",
-                "https://github.com/risc0/risc0-ethereum.git",
-                &self.project_name,
-                "--single-branch",
-                "--depth",
-                "1",
-            ]),
-            &format!("Cloning repository into '{}'...", self.project_name),
-            terminal,
-        )
+    /// The struct the journal is decoded as, or `None` for raw hex.
+    fn journal_type(&self) -> Option<&str> {
+        self.types().get(self.journal_type).map(String::as_str)
     }
 
-    fn setup_sparse_checkout(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
-        // Change to project directory
-        std::env::set_current_dir(&self.project_name)?;
+    fn next_field(&mut self, step: usize) {
+        self.field = (self.field + step) % Self::FIELDS;
+    }
+}
 
-        self.run_command(
-            Command::new("git").args(["sparse-checkout", "set", "examples/erc20-counter"]),
-            "Setting up sparse checkout...",
-            terminal,
-        )?;
+/// What to ask the Boundless market to prove.
+#[derive(Default)]
+struct BoundlessForm {
+    /// Index into the project's guests.
+    guest: usize,
+    /// File with the guest's input bytes, relative to the project.
+    input: String,
+    /// 0 = guest, 1 = input.
+    field: usize,
+}
 
-        self.run_command(
-            Command::new("git").arg("checkout"),
-            "Checking out files...",
-            terminal,
-        )?;
+impl BoundlessForm {
+    const FIELDS: usize = 2;
+}
 
-        if !Path::new("examples/erc20-counter").exists() {
-            return Err(color_eyre::eyre::eyre!(
-                "examples/erc20-counter directory not found after checkout"
-            ));
+/// The Settings screen's fields, saved on Enter.
+#[derive(Default)]
+struct SettingsForm {
+    proxy: String,
+    rpc_provider: usize,
+    rpc_network: usize,
+    rpc_api_key: String,
+    full_submodule_history: bool,
+    skip_template_refresh: bool,
+    cargo_mirror: String,
+    cargo_git_fetch_with_cli: bool,
+    /// Saved in the project rather than with the other settings.
+    dev_mode: bool,
+    tool_versions: usize,
+    reduced_motion: bool,
+    log_level: usize,
+    log_overrides: String,
+    progress_view: ProgressView,
+    /// 0 = proxy, 1 = RPC provider, 2 = network, 3 = API key, 4 = submodule history,
+    /// 5 = cargo mirror, 6 = git fetch with CLI, 7 = dev mode, 8 = tool versions file,
+    /// 9 = motion, 10 = log level, 11 = per-crate log levels, 12 = progress view,
+    /// 13 = template cache.
+    field: usize,
+}
+
+impl SettingsForm {
+    const FIELDS: usize = 14;
+
+    fn new(settings: &Settings, rpc_api_key: &str, dev_mode: bool) -> Self {
+        Self {
+            proxy: settings.proxy.clone().unwrap_or_default(),
+            rpc_provider: RpcProvider::ALL
+                .iter()
+                .position(|p| *p == settings.rpc_provider)
+                .unwrap_or(0),
+            rpc_network: Network::ALL
+                .iter()
+                .position(|n| *n == settings.rpc_network)
+                .unwrap_or(0),
+            rpc_api_key: rpc_api_key.to_string(),
+            full_submodule_history: settings.full_submodule_history,
+            skip_template_refresh: settings.skip_template_refresh,
+            cargo_mirror: settings.cargo_mirror.clone().unwrap_or_default(),
+            cargo_git_fetch_with_cli: settings.cargo_git_fetch_with_cli,
+            dev_mode,
+            tool_versions: tool_versions::Format::ALL
+                .iter()
+                .position(|f| *f == settings.tool_versions)
+                .unwrap_or(0),
+            reduced_motion: settings.reduced_motion,
+            log_level: LogLevel::ALL
+                .iter()
+                .position(|l| *l == settings.log_level)
+                .unwrap_or(0),
+            log_overrides: settings.log_overrides().to_string(),
+            progress_view: settings.progress_view,
+            field: 0,
         }
+    }
 
-        Ok(())
+    fn rpc_provider(&self) -> RpcProvider {
+        RpcProvider::ALL[self.rpc_provider]
     }
 
-    fn move_files(&mut self) -> Result<()> {
-        self.add_output("Moving template files to root directory...".to_string());
+    fn rpc_network(&self) -> Network {
+        Network::ALL[self.rpc_network]
+    }
 
-        // Move erc20-counter out of examples/
-        fs::rename("examples/erc20-counter", "./erc20-counter")?;
+    fn tool_versions(&self) -> tool_versions::Format {
+        tool_versions::Format::ALL[self.tool_versions]
+    }
 
-        // Remove examples directory
-        fs::remove_dir_all("examples")?;
+    fn log_level(&self) -> LogLevel {
+        LogLevel::ALL[self.log_level]
+    }
 
-        // Remove all files in root (but keep directories)
-        for entry in fs::read_dir(".")? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.is_file() {
-                fs::remove_file(path)?;
+    /// Moves `step` fields forward (wrapping), skipping fields the current choices don't use.
+    fn next_field(&mut self, step: usize) {
+        loop {
+            self.field = (self.field + step) % Self::FIELDS;
+            let applies = match self.field {
+                2 => self.rpc_provider() != RpcProvider::Local,
+                3 => self.rpc_provider().needs_api_key(),
+                _ => true,
+            };
+            if applies {
+                break;
             }
         }
+    }
+}
 
-        // Move all contents from erc20-counter to root (including hidden files)
-        for entry in fs::read_dir("erc20-counter")? {
-            let entry = entry?;
-            let source = entry.path();
-            let file_name = source.file_name().unwrap();
-            let target = PathBuf::from(".").join(file_name);
-            fs::rename(source, target)?;
-        }
+/// Input for the repository new projects are cloned from instead of risc0-ethereum.
+#[derive(Default)]
+struct RepoForm {
+    url: String,
+    branch: String,
+    /// 0 = URL, 1 = branch.
+    field: usize,
+}
 
-        // Remove the now-empty erc20-counter directory
-        fs::remove_dir("erc20-counter")?;
+/// Input for a new entry on the Credentials screen.
+#[derive(Default)]
+struct CredentialForm {
+    kind: usize,
+    name: String,
+    secret: String,
+    /// 0 = kind, 1 = name, 2 = secret.
+    field: usize,
+}
 
-        self.add_output("✓ Project structure set up successfully".to_string());
-        Ok(())
+/// Input for the deploy wizard.
+#[derive(Default)]
+struct DeployForm {
+    rpc_url: String,
+    kind: usize,
+    detail: String,
+    password: String,
+    verifier: usize,
+    verifier_detail: String,
+    /// 0 = RPC URL, 1 = signer kind, 2 = detail, 3 = password, 4 = verifier, 5 = verifier detail.
+    field: usize,
+}
+
+impl DeployForm {
+    const FIELDS: usize = 6;
+
+    fn kind(&self) -> SignerKind {
+        SignerKind::ALL[self.kind]
     }
 
-    fn update_dependencies(&mut self) -> Result<()> {
-        let cargo_files = self.find_cargo_toml_files(".")?;
+    fn verifier(&self) -> VerifierKind {
+        VerifierKind::ALL[self.verifier]
+    }
 
-        self.add_output("Updating Cargo.toml files with git dependencies...".to_string());
+    /// Moves `step` fields forward (wrapping), skipping fields the current choices don't use.
+    fn next_field(&mut self, step: usize) {
+        loop {
+            self.field = (self.field + step) % Self::FIELDS;
+            let applies = match self.field {
+                2 => self.kind().detail_label().is_some(),
+                3 => self.kind().needs_password(),
+                5 => self.verifier().detail_label().is_some(),
+                _ => true,
+            };
+            if applies {
+                break;
+            }
+        }
+    }
+}
 
-        for file_path in cargo_files {
-            let mut content = fs::read_to_string(&file_path)?;
-            let is_apps = file_path.to_string_lossy().contains("/apps/");
-            let is_workspace = content.contains("[workspace]");
+/// Flags for one of the project's Forge scripts, kept per script while the app runs.
+#[derive(Default)]
+struct ScriptForm {
+    rpc_url: String,
+    broadcast: bool,
+    verify: bool,
+    /// 0 = RPC URL, 1 = broadcast, 2 = verify.
+    field: usize,
+}
 
-            if is_workspace && content.contains("[workspace.dependencies]") {
-                // For workspace manifests, do direct string replacements to match the expected format
-                content = content
-                    .replace(
-                        "risc0-build-ethereum = { path = \"../../build\" }",
-                        "risc0-build-ethereum = { git = \"https://github.com/risc0/risc0-ethereum\", branch = \"release-1.3\" }"
-                    )
-                    .replace(
-                        "risc0-ethereum-contracts = { path = \"../../contracts\" }",
-                        "risc0-ethereum-contracts = { git = \"https://github.com/risc0/risc0-ethereum\", branch = \"release-1.3\" }"
-                    )
-                    .replace(
-                        "risc0-steel = { path = \"../../crates/steel\" }",
-                        "risc0-steel = { git = \"https://github.com/risc0/risc0-ethereum\", branch = \"release-1.3\" }"
-                    );
-            } else if is_workspace {
-                // Fallback: use regex with multi-line flag for workspace dependencies
-                let re_ws_build = regex::Regex::new(
-                    r#"(?m)^\s*risc0-build-ethereum\s*=\s*\{\s*path\s*=\s*".*"\s*\}"#,
-                )
-                .unwrap();
-                let re_ws_contracts = regex::Regex::new(
-                    r#"(?m)^\s*risc0-ethereum-contracts\s*=\s*\{\s*path\s*=\s*".*"\s*\}"#,
-                )
-                .unwrap();
-                let re_ws_steel =
-                    regex::Regex::new(r#"(?m)^\s*risc0-steel\s*=\s*\{\s*path\s*=\s*".*"\s*\}"#)
-                        .unwrap();
-
-                content = re_ws_build.replace_all(&content,
-                    "risc0-build-ethereum = { git = \"https://github.com/risc0/risc0-ethereum\", branch = \"release-1.3\" }"
-                ).to_string();
-                content = re_ws_contracts.replace_all(&content,
-                    "risc0-ethereum-contracts = { git = \"https://github.com/risc0/risc0-ethereum\", branch = \"release-1.3\" }"
-                ).to_string();
-                content = re_ws_steel.replace_all(&content,
-                    "risc0-steel = { git = \"https://github.com/risc0/risc0-ethereum\", branch = \"release-1.3\" }"
-                ).to_string();
-            } else {
-                // Handle regular dependencies using regex with multi-line flag
-                let re_build = regex::Regex::new(r#"(?m)^risc0-build-ethereum\s*=.*$"#).unwrap();
-                let re_contracts =
-                    regex::Regex::new(r#"(?m)^risc0-ethereum-contracts\s*=.*$"#).unwrap();
-                let re_steel = regex::Regex::new(r#"(?m)^risc0-steel\s*=.*$"#).unwrap();
-
-                let risc0_build_ethereum = "risc0-build-ethereum = { git = \"https://github.com/risc0/risc0-ethereum\", branch = \"release-1.3\" }";
-                let risc0_ethereum_contracts = "risc0-ethereum-contracts = { git = \"https://github.com/risc0/risc0-ethereum\", branch = \"release-1.3\" }";
-                let risc0_steel = if is_apps {
-                    "risc0-steel = { git = \"https://github.com/risc0/risc0-ethereum\", branch = \"release-1.3\", features = [\"host\"] }"
-                } else {
-                    "risc0-steel = { git = \"https://github.com/risc0/risc0-ethereum\", branch = \"release-1.3\" }"
-                };
+impl ScriptForm {
+    const FIELDS: usize = 3;
 
-                content = re_build
-                    .replace_all(&content, risc0_build_ethereum)
-                    .to_string();
-                content = re_contracts
-                    .replace_all(&content, risc0_ethereum_contracts)
-                    .to_string();
-                content = re_steel.replace_all(&content, risc0_steel).to_string();
+    /// Moves `step` fields forward (wrapping); verifying needs a broadcast.
+    fn next_field(&mut self, step: usize) {
+        loop {
+            self.field = (self.field + step) % Self::FIELDS;
+            if self.field != 2 || self.broadcast {
+                break;
             }
-
-            fs::write(&file_path, content)?;
-            self.add_output(format!("Updated dependencies in: {}", file_path.display()));
         }
+    }
 
-        self.add_output(
-            "✓ All Cargo.toml files have been updated with git dependencies.".to_string(),
-        );
-        Ok(())
+    fn toggle(&mut self) {
+        match self.field {
+            1 => {
+                self.broadcast = !self.broadcast;
+                self.verify &= self.broadcast;
+            }
+            2 => self.verify = !self.verify,
+            _ => {}
+        }
     }
+}
 
-    fn setup_forge(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
-        self.add_output("Starting Forge setup (this may take a few minutes)...".to_string());
+/// A deployed contract the transaction builder can call.
+struct TxContract {
+    name: String,
+    address: Address,
+    functions: Vec<Function>,
+}
 
-        // Remove existing git directory and init new one
-        let _ = fs::remove_dir_all(".git");
+/// Calldata and gas estimate for the transaction builder's current inputs.
+struct TxPreview {
+    calldata: Bytes,
+    value: U256,
+    gas: std::result::Result<u64, String>,
+    /// What a view function returned.
+    result: Option<std::result::Result<String, String>>,
+}
 
-        // Initialize git repo
-        self.run_command(
-            Command::new("git").args(&["init"]),
-            "Initializing git repository...",
-            terminal,
-        )?;
+/// Which input of the transaction builder a field index refers to.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TxField {
+    Contract,
+    Function,
+    Arg(usize),
+    Value,
+    Signer,
+    Detail,
+    Password,
+}
 
-        // Create lib directory
-        fs::create_dir_all("lib")?;
+/// Input for the transaction builder.
+#[derive(Default)]
+struct TxForm {
+    rpc_url: String,
+    contracts: Vec<TxContract>,
+    contract: usize,
+    function: usize,
+    args: Vec<String>,
+    /// Ether sent along, for payable functions.
+    value: String,
+    kind: usize,
+    detail: String,
+    password: String,
+    /// Contract, function, one per argument, then value, signer kind, detail and password.
+    field: usize,
+    /// Cleared by any edit, so what's sent is what was previewed.
+    preview: Option<std::result::Result<TxPreview, String>>,
+}
 
-        // Add forge-std
-        self.add_output("Adding forge-std (1/3)...".to_string());
-        self.run_command(
-            Command::new("git").args(&[
-                "submodule",
-                "add",
-                "https://github.com/foundry-rs/forge-std",
-                "lib/forge-std",
-            ]),
-            "Cloning forge-std...",
-            terminal,
-        )?;
+impl TxForm {
+    fn fields(&self) -> usize {
+        6 + self.args.len()
+    }
 
-        // Add OpenZeppelin
-        self.add_output("Adding OpenZeppelin (2/3)...".to_string());
-        self.run_command(
-            Command::new("git").args(&[
-                "submodule",
-                "add",
-                "https://github.com/OpenZeppelin/openzeppelin-contracts",
-                "lib/openzeppelin-contracts",
-            ]),
-            "Cloning OpenZeppelin...",
-            terminal,
-        )?;
+    fn field_kind(&self, index: usize) -> TxField {
+        let args = self.args.len();
+        match index {
+            0 => TxField::Contract,
+            1 => TxField::Function,
+            i if i < 2 + args => TxField::Arg(i - 2),
+            i if i == 2 + args => TxField::Value,
+            i if i == 3 + args => TxField::Signer,
+            i if i == 4 + args => TxField::Detail,
+            _ => TxField::Password,
+        }
+    }
 
-        // Add risc0-ethereum
-        self.add_output("Adding risc0-ethereum (3/3)...".to_string());
-        self.run_command(
-            Command::new("git").args(&[
-                "submodule",
-                "add",
-                "-b",
-                "release-1.3",
-                "https://github.com/risc0/risc0-ethereum",
-                "lib/risc0-ethereum",
-            ]),
-            "Cloning risc0-ethereum...",
-            terminal,
-        )?;
+    fn current(&self) -> TxField {
+        self.field_kind(self.field)
+    }
 
-        // Update submodules
-        self.add_output("Updating submodules recursively (this may take a while)...".to_string());
-        self.run_command(
-            Command::new("git").args(&["submodule", "update", "--init", "--recursive", "--quiet"]),
-            "Updating submodules...",
-            terminal,
-        )?;
+    fn selected_contract(&self) -> Option<&TxContract> {
+        self.contracts.get(self.contract)
+    }
 
-        // Reset git index
-        self.run_command(
-            Command::new("git").args(&["reset"]),
-            "Resetting git index...",
-            terminal,
-        )?;
+    fn selected_function(&self) -> Option<&Function> {
+        self.selected_contract()?.functions.get(self.function)
+    }
 
-        // Update remappings.txt
-        if Path::new("remappings.txt").exists() {
-            let mut content = fs::read_to_string("remappings.txt")?;
+    fn kind(&self) -> SignerKind {
+        SignerKind::ALL[self.kind]
+    }
 
-            // Update existing remappings
-            content = content
-                .replace(
-                    "forge-std/=../../lib/forge-std/src/",
-                    "forge-std/=lib/forge-std/src/",
-                )
-                .replace(
-                    "openzeppelin/=../../lib/openzeppelin-contracts/",
-                    "openzeppelin/=lib/openzeppelin-contracts/",
-                )
-                .replace(
-                    "risc0/=../../contracts/src/",
-                    "risc0/=lib/risc0-ethereum/contracts/src/",
-                );
+    /// Empties the arguments for the newly selected function.
+    fn reset_args(&mut self) {
+        let inputs = self.selected_function().map_or(0, |f| f.inputs.len());
+        self.args = vec![String::new(); inputs];
+        self.value.clear();
+        self.preview = None;
+    }
 
-            // Add OpenZeppelin contracts remapping if not present
-            if !content.contains("openzeppelin-contracts/=") {
-                content.push_str("\nopenzeppelin-contracts/=lib/openzeppelin-contracts/contracts");
+    /// Moves `step` fields forward (wrapping), skipping fields the current choices don't use.
+    fn next_field(&mut self, step: usize) {
+        let view = self.selected_function().is_some_and(transaction::is_view);
+        let payable = self
+            .selected_function()
+            .is_some_and(transaction::is_payable);
+        loop {
+            self.field = (self.field + step) % self.fields();
+            let applies = match self.current() {
+                TxField::Value => payable,
+                TxField::Signer => !view,
+                TxField::Detail => !view && self.kind().detail_label().is_some(),
+                TxField::Password => !view && self.kind().needs_password(),
+                _ => true,
+            };
+            if applies {
+                break;
             }
+        }
+    }
 
-            fs::write("remappings.txt", content)?;
-            self.add_output("✓ Updated remappings.txt".to_string());
+    /// The account sending, when it's known before any password is entered.
+    fn from(&self) -> Option<Address> {
+        match self.kind() {
+            SignerKind::PrivateKey => chain::address_of(&self.detail).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// A transaction confirmed on the transaction builder, waiting to be sent.
+struct PendingTx {
+    rpc_url: String,
+    signer: Signer,
+    /// e.g. `Counter.increment(bytes,bytes)`.
+    label: String,
+    to: Address,
+    calldata: Bytes,
+    value: U256,
+    gas: u64,
+}
+
+/// The risc0-ethereum version each of the release's crates is taken from.
+struct VersionForm {
+    release: &'static compat::Release,
+    /// Published release tags, newest first.
+    tags: Vec<String>,
+    /// Per crate of the release, an index into `tags`; `None` keeps the head of the branch.
+    picks: Vec<Option<usize>>,
+    /// The crate being changed.
+    field: usize,
+}
+
+impl VersionForm {
+    fn new(release: &'static compat::Release, tags: Vec<String>) -> Self {
+        Self {
+            release,
+            tags,
+            picks: vec![None; release.crates.len()],
+            field: 0,
+        }
+    }
+
+    fn tag(&self, index: usize) -> Option<&str> {
+        self.picks[index].map(|i| self.tags[i].as_str())
+    }
+
+    /// Steps the current crate through the branch head, then each tag from newest to oldest.
+    fn cycle(&mut self, forward: bool) {
+        let options = self.tags.len() + 1;
+        let current = self.picks[self.field].map_or(0, |i| i + 1);
+        let next = if forward {
+            (current + 1) % options
         } else {
-            self.add_output("Warning: remappings.txt not found".to_string());
+            (current + options - 1) % options
+        };
+        self.picks[self.field] = next.checked_sub(1);
+    }
+
+    /// Whether the crates would come from more than one revision of the repository.
+    fn mixed(&self) -> bool {
+        self.picks.windows(2).any(|pair| pair[0] != pair[1])
+    }
+
+    fn next_field(&mut self, step: usize) {
+        self.field = (self.field + step) % self.picks.len();
+    }
+}
+
+/// What the Bonsai account screen last fetched. Each part fails independently, so a quota
+/// error doesn't hide that the API itself is up.
+struct BonsaiPanel {
+    availability: std::result::Result<bonsai::Availability, String>,
+    quotas: std::result::Result<bonsai::Quotas, String>,
+    sessions: Vec<(String, std::result::Result<bonsai::SessionStatus, String>)>,
+}
+
+/// Controls for the running anvil: what's typed into each field and the chain they act on.
+struct AnvilPanel {
+    chain: Chain,
+    /// 0 = mine, 1 = increase time, 2 = interval mining, 3 = impersonate.
+    field: usize,
+    blocks: String,
+    seconds: String,
+    interval: String,
+    address: String,
+    impersonating: Vec<String>,
+    /// Number and timestamp of the latest block.
+    latest: std::result::Result<(u64, u64), String>,
+    last: Option<std::result::Result<String, String>>,
+}
+
+impl AnvilPanel {
+    const FIELDS: usize = 4;
+
+    fn new(chain: Chain) -> Self {
+        let latest = chain.latest_block().map_err(|e| e.to_string());
+        Self {
+            chain,
+            field: 0,
+            blocks: String::from("1"),
+            seconds: String::from("3600"),
+            interval: String::from("0"),
+            address: String::new(),
+            impersonating: Vec::new(),
+            latest,
+            last: None,
         }
+    }
 
-        // Update foundry.toml
-        if Path::new("foundry.toml").exists() {
-            let mut content = fs::read_to_string("foundry.toml")?;
+    fn next_field(&mut self, step: usize) {
+        self.field = (self.field + step) % Self::FIELDS;
+    }
 
-            // Update libs path
-            content = content.replace(
-                "libs = [\"../../lib\", \"../../contracts/src\"]",
-                "libs = [\"lib\"]",
-            );
+    fn input(&mut self) -> &mut String {
+        match self.field {
+            0 => &mut self.blocks,
+            1 => &mut self.seconds,
+            2 => &mut self.interval,
+            _ => &mut self.address,
+        }
+    }
 
-            // Add auto_detect_remappings = false under [profile.default]
-            if !content.contains("auto_detect_remappings") {
-                if content.contains("[profile.default]") {
-                    content = content.replace(
-                        "[profile.default]",
-                        "[profile.default]\nauto_detect_remappings = false",
-                    );
+    /// Runs the focused field's action.
+    fn apply(&mut self) -> Result<String> {
+        let number = |value: &str, what: &str| {
+            value
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| color_eyre::eyre::eyre!("{} must be a whole number", what))
+        };
+        let done = match self.field {
+            0 => {
+                let blocks = number(&self.blocks, "Blocks")?;
+                self.chain.mine(blocks)?;
+                format!("Mined {} block(s)", blocks)
+            }
+            1 => {
+                let seconds = number(&self.seconds, "Seconds")?;
+                let offset = self.chain.increase_time(seconds)?;
+                // The new time only shows up in the next block
+                self.chain.mine(1)?;
+                format!(
+                    "Moved time forward {}s (offset now {}s) and mined a block",
+                    seconds, offset
+                )
+            }
+            2 => {
+                let interval = number(&self.interval, "Interval")?;
+                self.chain.set_interval_mining(interval)?;
+                if interval == 0 {
+                    String::from("Mining a block for every transaction")
                 } else {
-                    content.push_str("\n[profile.default]\nauto_detect_remappings = false");
+                    format!("Mining a block every {}s", interval)
+                }
+            }
+            _ => {
+                let address = self.address.trim().to_string();
+                let active = self
+                    .impersonating
+                    .iter()
+                    .any(|a| a.eq_ignore_ascii_case(&address));
+                self.chain.impersonate(&address, !active)?;
+                if active {
+                    self.impersonating
+                        .retain(|a| !a.eq_ignore_ascii_case(&address));
+                    format!("Stopped impersonating {}", address)
+                } else {
+                    self.impersonating.push(address.clone());
+                    format!("Impersonating {}", address)
                 }
             }
+        };
+        self.latest = self.chain.latest_block().map_err(|e| e.to_string());
+        Ok(done)
+    }
+}
 
-            fs::write("foundry.toml", content)?;
-            self.add_output("✓ Updated foundry.toml".to_string());
-        } else {
-            self.add_output("Warning: foundry.toml not found".to_string());
+/// One project on the dashboard, with what was found out about it when the list was loaded.
+struct DashboardRow {
+    project: RecentProject,
+    branch: Option<String>,
+    anvil_running: bool,
+}
+
+/// Every known project at a glance.
+struct Dashboard {
+    rows: Vec<DashboardRow>,
+    selected: usize,
+    /// Newest risc0-ethereum release, looked up in the background.
+    latest: Option<String>,
+    latest_check: Option<mpsc::Receiver<Option<String>>>,
+    /// Esc returns to the welcome screen rather than the toolbox.
+    from_welcome: bool,
+}
+
+impl Dashboard {
+    fn load(from_welcome: bool, offline: bool) -> Self {
+        let anvil_dirs = resources::working_dirs_of("anvil");
+        let rows = projects::load()
+            .into_iter()
+            .map(|project| DashboardRow {
+                branch: project.pinned_branch(),
+                anvil_running: anvil_dirs.iter().any(|dir| dir.starts_with(&project.path)),
+                project,
+            })
+            .collect();
+        let latest_check = (!offline).then(|| {
+            let (tx, rx) = mpsc::channel();
+            std::thread::spawn(move || {
+                let _ = tx.send(upgrade::latest_release().ok());
+            });
+            rx
+        });
+        Self {
+            rows,
+            selected: 0,
+            latest: None,
+            latest_check,
+            from_welcome,
         }
+    }
 
-        self.add_output("Forge setup completed successfully".to_string());
-        Ok(())
+    fn selected(&self) -> Option<&RecentProject> {
+        self.rows.get(self.selected).map(|row| &row.project)
     }
+}
 
-    fn find_cargo_toml_files(&self, dir: &str) -> Result<Vec<PathBuf>> {
-        let mut cargo_files = Vec::new();
+/// The project compared with a fresh checkout of its template.
+struct DriftView {
+    template_root: PathBuf,
+    files: Vec<drift::FileDrift>,
+    /// Files marked to be pulled from the template.
+    marked: Vec<bool>,
+    selected: usize,
+}
 
-        for entry in fs::read_dir(dir)? {
-            let entry = entry?;
-            let path = entry.path();
+/// What a deployment would send, from forge's dry run.
+struct DeployPreview {
+    transactions: Vec<broadcast::Sent>,
+    /// Current gas price in wei.
+    gas_price: u128,
+}
 
-            if path.is_dir() {
-                cargo_files.extend(self.find_cargo_toml_files(path.to_str().unwrap())?);
-            } else if path.file_name().unwrap() == "Cargo.toml" {
-                cargo_files.push(path);
-            }
-        }
+impl DeployPreview {
+    fn gas(&self) -> u64 {
+        self.transactions.iter().filter_map(|tx| tx.gas).sum()
+    }
 
-        Ok(cargo_files)
+    /// Upper bound in wei: the gas limits at the current gas price.
+    fn cost(&self) -> U256 {
+        U256::from(self.gas()) * U256::from(self.gas_price)
     }
+}
 
-    fn handle_error(&mut self) -> Result<()> {
-        self.status_message.push_str("\nPress Esc to exit");
-        loop {
-            if event::poll(std::time::Duration::from_millis(50))? {
-                if let Event::Key(key) = event::read()? {
-                    if key.code == KeyCode::Esc {
-                        disable_raw_mode()?;
-                        execute!(std::io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
-                        eprintln!("Error: {}", self.status_message);
-                        std::process::exit(1);
-                    }
-                }
+/// Contracts a broadcast created, and the screen to go back to from their table.
+struct DeployedView {
+    chain_id: u64,
+    contracts: Vec<broadcast::Deployed>,
+    back: ToolboxItem,
+}
+
+/// A deployment that's been configured and is about to run.
+struct Deployment {
+    rpc_url: String,
+    signer: Signer,
+    verifier: Option<Verifier>,
+}
+
+/// How a watched child process ended.
+enum Watch {
+    Finished,
+    TimedOut,
+    Retry,
+    Aborted,
+}
+
+pub struct App {
+    state: AppState,
+    project_name: String,
+    status_message: String,
+    rust_installed: bool,
+    foundry_installed: bool,
+    risc0_version: Option<String>,
+    /// cargo works but has no `risczero` subcommand, so the plugin can be installed with it.
+    cargo_risczero_missing: bool,
+    /// Whether the Apple Silicon gotchas have been looked for and reported.
+    platform_checked: bool,
+    /// Learn mode: explain each step as it happens.
+    learn: bool,
+    /// Show the docs for the current screen beside it.
+    show_docs: bool,
+    /// Where to write a JUnit report of each test run, from `--junit`.
+    junit: Option<PathBuf>,
+    /// The last session, until it's restored or turned down.
+    previous_session: Option<Session>,
+    /// Rendering for recordings, from `--demo`.
+    demo: Option<demo::Demo>,
+    /// Key presses played back from `--replay` as if typed.
+    replay: Option<replay::Script>,
+    /// Where the keys pressed are written, from `--record`.
+    recorder: Option<replay::Recorder>,
+    /// Projects to create one after another, from `--batch`.
+    batch: Option<Batch>,
+    /// The cargo-generate template from `--template`, if there is one.
+    cargo_template: Option<CargoTemplate>,
+    /// Steps of the test run in progress, for the JUnit report.
+    test_report: Option<junit::Suite>,
+    /// Where the test run in progress keeps what it leaves behind.
+    artifacts: Option<artifacts::Run>,
+    /// Releases and templates the installed toolchain can build, and which one is picked.
+    template_choices: Vec<(&'static compat::Release, Template)>,
+    template_choice: usize,
+    /// Highlighted on the template list.
+    template_selected: usize,
+    /// Templates come from `summary.repository` at `summary.branch`, e.g. a fork, instead of
+    /// risc0-ethereum at the release branch.
+    custom_repository: bool,
+    repo_form: RepoForm,
+    command_output: Vec<OutputLine>,
+    verbosity: Verbosity,
+    timestamps: Timestamps,
+    /// When the command or tasks now running started, for relative timestamps.
+    step_started: std::time::Instant,
+    ci: bool,
+    overwrite: bool,
+    update_check: Option<mpsc::Receiver<String>>,
+    update_notice: Option<String>,
+    /// A self-update running in the background.
+    self_update: Option<mpsc::Receiver<self_update::UpdateEvent>>,
+    offline: bool,
+    timeouts: Timeouts,
+    stuck_for: Option<std::time::Duration>,
+    last_error: Option<CommandError>,
+    step_logs: StepLogs,
+    /// The log of the step that failed last, linked from the error screen.
+    failed_log: Option<PathBuf>,
+    /// The Ctrl+P command palette, while it's open.
+    palette: Option<Palette>,
+    /// Directory the app was launched from; projects are created relative to it.
+    base_dir: PathBuf,
+    /// The git repository the app was launched in, if any.
+    enclosing_repo: Option<PathBuf>,
+    /// Add new projects to `enclosing_repo` instead of making each a repository of its own.
+    in_repo: bool,
+    /// Commands currently running side by side, shown as progress rows.
+    tasks: Vec<Task>,
+    /// git's transfer progress in the running command, shown while cloning.
+    transfer: Transfer,
+    parallelism: usize,
+    resources: resources::Monitor,
+    output_scroll: u16,
+    pending_redraw: bool,
+    selected_menu_item: usize,
+    confirm_menu_item: usize,
+    test_env: Option<TestEnvironment>, // Add this to store test-related data
+    bonsai_api_key: String,            // Add this field
+    prover: ProverBackend,
+    /// Proof requests on the Boundless market, when proving there.
+    boundless: Option<boundless::Tracker>,
+    /// Unlocked credentials store, and the passphrase to save it with.
+    credentials: Option<Credentials>,
+    credentials_passphrase: String,
+    selected_credential: usize,
+    credential_form: CredentialForm,
+    /// Generate a test wallet once the credentials store is unlocked.
+    wallet_after_unlock: bool,
+    test_wallet: Option<TestWallet>,
+    /// Live state of the chain under test, shown in the status bar.
+    chain_monitor: Option<chain::Monitor>,
+    /// Typed confirmation for using well-known dev keys against a public RPC.
+    confirm_input: String,
+    public_rpc_url: String,
+    /// Where to go once the public RPC warning is confirmed.
+    after_confirm: Option<AppState>,
+    /// The solc version being offered or installed.
+    solc_version: Option<String>,
+    /// Logging for the next test run only.
+    log_preset: Option<LogPreset>,
+    deploy_form: DeployForm,
+    deployment: Option<Deployment>,
+    deploy_preview: Option<DeployPreview>,
+    deployed: Option<DeployedView>,
+    tx_form: TxForm,
+    pending_tx: Option<PendingTx>,
+    bonsai_panel: Option<BonsaiPanel>,
+    /// Results of the last health check.
+    health: Vec<health::Finding>,
+    contract_report: Option<ContractReport>,
+    drift: Option<DriftView>,
+    inspect_form: InspectForm,
+    boundless_form: BoundlessForm,
+    watch: Option<WatchSession>,
+    queue: Queue,
+    /// Action added to the queue next.
+    queue_action: usize,
+    benchmark: BenchmarkForm,
+    /// Report instead of reformatting.
+    format_check: bool,
+    format_report: Option<FormatReport>,
+    lint: Option<LintView>,
+    history: HistoryView,
+    anvil_panel: Option<AnvilPanel>,
+    version_form: Option<VersionForm>,
+    /// Highlighted entry of the chain picker.
+    chain_selected: usize,
+    /// The chain saved in the project, if it was made for one; see [`App::chain`].
+    project_chain: Option<ChainPreset>,
+    /// The project's networks while one is being picked, and what it's picked for.
+    networks: Vec<networks::Network>,
+    network_selected: usize,
+    network_for: Option<ToolboxItem>,
+    /// The network tests and deployments run on, once picked.
+    network: Option<networks::Network>,
+    /// The project's Forge scripts, the highlighted one, and the flags set for each.
+    scripts: Vec<PathBuf>,
+    script_selected: usize,
+    script_forms: BTreeMap<PathBuf, ScriptForm>,
+    /// Highlighted entry of the example picker.
+    example_selected: usize,
+    /// Phases of the guest build inside the running `cargo build`.
+    guest_build: Option<guest_build::Progress>,
+    /// Guests found after the last build.
+    guest_images: Vec<GuestImage>,
+    /// Compiled contracts from `out/`.
+    abi_contracts: Vec<abi::Contract>,
+    /// Highlighted entry, counted across all contracts.
+    abi_selected: usize,
+    /// Logs of the deployed contracts, captured after the last test run or deployment.
+    event_log: Option<events::EventLog>,
+    /// How many rows back from the newest the event table is scrolled.
+    events_scroll: usize,
+    /// Name typed on the scaffolding screen.
+    scaffold_name: String,
+    settings: Settings,
+    settings_form: SettingsForm,
+    /// API key for the RPC provider preset, for this session. Saved with the credentials.
+    rpc_api_key: String,
+    dashboard: Option<Dashboard>,
+    /// Project the dashboard asked to upgrade.
+    upgrade_target: Option<PathBuf>,
+    /// Previously created projects, offered on the welcome screen.
+    recent_projects: Vec<RecentProject>,
+    selected_recent: Option<usize>,
+    last_test_failed: bool,
+    summary: ProjectSummary,
+    project_created: bool,
+}
+
+struct TestEnvironment {
+    eth_rpc_url: String,
+    eth_wallet_address: String,
+    eth_wallet_private_key: String,
+    bonsai_api_key: String,
+    bonsai_api_url: String,
+    prover: ProverBackend,
+    anvil_pid: Option<u32>,
+    /// Addresses of contracts restored from a saved chain state, by variable name.
+    restored: Vec<(String, String)>,
+}
+
+impl TestEnvironment {
+    /// Variables the test script reads. Pass with `Command::envs`, never `std::env::set_var`.
+    /// A Boundless host finds the market through the `BOUNDLESS_*` variables we inherit.
+    fn vars(&self) -> Vec<(&'static str, &str)> {
+        let mut vars = vec![
+            ("ETH_RPC_URL", self.eth_rpc_url.as_str()),
+            ("ETH_WALLET_ADDRESS", self.eth_wallet_address.as_str()),
+            (
+                "ETH_WALLET_PRIVATE_KEY",
+                self.eth_wallet_private_key.as_str(),
+            ),
+        ];
+        match self.prover {
+            ProverBackend::Bonsai => {
+                vars.push(("BONSAI_API_KEY", &self.bonsai_api_key));
+                vars.push(("BONSAI_API_URL", &self.bonsai_api_url));
             }
+            ProverBackend::Local => vars.push(("RISC0_PROVER", "local")),
+            ProverBackend::Boundless => {}
         }
+        vars
     }
+}
 
-    fn cleanup_test(&mut self) {
-        if let Some(test_env) = &mut self.test_env {
-            // Kill anvil process if it exists
-            if let Some(mut child) = test_env.anvil_process.take() {
-                let _ = child.kill();
-            }
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl App {
+    pub fn new() -> Self {
+        Self {
+            state: AppState::CheckingDependencies,
+            project_name: String::new(),
+            status_message: String::from("Checking dependencies..."),
+            rust_installed: false,
+            platform_checked: false,
+            foundry_installed: false,
+            risc0_version: None,
+            cargo_risczero_missing: false,
+            learn: false,
+            show_docs: false,
+            junit: None,
+            previous_session: None,
+            demo: None,
+            replay: None,
+            recorder: None,
+            batch: None,
+            cargo_template: None,
+            test_report: None,
+            artifacts: None,
+            template_choices: Vec::new(),
+            template_choice: 0,
+            template_selected: 0,
+            custom_repository: false,
+            repo_form: RepoForm::default(),
+            command_output: Vec::new(),
+            verbosity: Verbosity::default(),
+            timestamps: Timestamps::default(),
+            step_started: std::time::Instant::now(),
+            ci: false,
+            overwrite: false,
+            update_check: None,
+            self_update: None,
+            update_notice: None,
+            offline: false,
+            timeouts: Timeouts::default(),
+            stuck_for: None,
+            last_error: None,
+            step_logs: StepLogs::default(),
+            failed_log: None,
+            palette: None,
+            base_dir: std::env::current_dir().unwrap_or_default(),
+            enclosing_repo: None,
+            in_repo: false,
+            tasks: Vec::new(),
+            transfer: Transfer::default(),
+            parallelism: 3,
+            resources: resources::Monitor::default(),
+            output_scroll: 0,
+            pending_redraw: false,
+            selected_menu_item: 0,
+            confirm_menu_item: 0,
+            test_env: None,
+            bonsai_api_key: String::new(), // Add this field
+            prover: ProverBackend::default(),
+            boundless: None,
+            credentials: None,
+            credentials_passphrase: String::new(),
+            selected_credential: 0,
+            credential_form: CredentialForm::default(),
+            wallet_after_unlock: false,
+            test_wallet: None,
+            chain_monitor: None,
+            confirm_input: String::new(),
+            public_rpc_url: String::new(),
+            after_confirm: None,
+            solc_version: None,
+            log_preset: None,
+            deploy_form: DeployForm::default(),
+            deployment: None,
+            deploy_preview: None,
+            deployed: None,
+            tx_form: TxForm::default(),
+            pending_tx: None,
+            bonsai_panel: None,
+            health: Vec::new(),
+            contract_report: None,
+            drift: None,
+            scaffold_name: String::new(),
+            guest_images: Vec::new(),
+            abi_contracts: Vec::new(),
+            abi_selected: 0,
+            event_log: None,
+            events_scroll: 0,
+            inspect_form: InspectForm::default(),
+            boundless_form: BoundlessForm::default(),
+            watch: None,
+            queue: Queue::default(),
+            queue_action: 0,
+            benchmark: BenchmarkForm::default(),
+            format_check: false,
+            format_report: None,
+            lint: None,
+            history: HistoryView {
+                runs: Vec::new(),
+                selected: 0,
+                marked: Vec::new(),
+            },
+            anvil_panel: None,
+            version_form: None,
+            chain_selected: 0,
+            project_chain: None,
+            networks: Vec::new(),
+            network_selected: 0,
+            network_for: None,
+            network: None,
+            scripts: Vec::new(),
+            script_selected: 0,
+            script_forms: BTreeMap::new(),
+            example_selected: 0,
+            guest_build: None,
+            settings: Settings::default(),
+            settings_form: SettingsForm::default(),
+            rpc_api_key: String::new(),
+            dashboard: None,
+            upgrade_target: None,
+            recent_projects: Vec::new(),
+            selected_recent: None,
+            last_test_failed: false,
+            summary: ProjectSummary {
+                template: String::from("erc20-counter"),
+                repository: String::from("https://github.com/risc0/risc0-ethereum.git"),
+                branch: String::from("release-1.3"),
+                ..Default::default()
+            },
+            project_created: false,
         }
-        // Also try pkill just to be sure
-        let _ = Command::new("pkill").arg("anvil").output();
     }
 
-    fn handle_test_step(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
-        if let Some(test_env) = &mut self.test_env {
-            match self.state {
-                AppState::Testing(E2ETestStep::PreparingEnvironment) => {
-                    // Set environment variables
-                    std::env::set_var("ETH_RPC_URL", &test_env.eth_rpc_url);
-                    std::env::set_var("ETH_WALLET_ADDRESS", &test_env.eth_wallet_address);
-                    std::env::set_var("ETH_WALLET_PRIVATE_KEY", &test_env.eth_wallet_private_key);
-                    std::env::set_var("BONSAI_API_KEY", &test_env.bonsai_api_key);
-                    std::env::set_var("BONSAI_API_URL", &test_env.bonsai_api_url);
+    /// Sets how much captured output is shown.
+    pub fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
 
-                    self.status_message =
-                        String::from("Environment variables set, starting Anvil...");
-                    self.state = AppState::Testing(E2ETestStep::StartingAnvil);
-                }
-                AppState::Testing(E2ETestStep::StartingAnvil) => {
-                    self.status_message = String::from("Starting local Ethereum chain...");
+    /// Writes a JUnit report to `path` after each test run and headless install.
+    pub fn with_junit(mut self, path: Option<PathBuf>) -> Self {
+        self.junit = path;
+        self
+    }
+
+    /// Offers to restore the last session once the dependency check passes.
+    pub fn with_previous_session(mut self, session: Option<Session>) -> Self {
+        self.previous_session = session;
+        self
+    }
 
-                    // Kill any existing anvil process first
-                    let _ = Command::new("pkill").arg("anvil").output();
+    /// Renders for recording, e.g. with `--demo`.
+    pub fn with_demo(mut self, demo: Option<demo::Demo>) -> Self {
+        self.demo = demo;
+        self
+    }
 
-                    // Start new anvil process without any flags
-                    let child = Command::new("anvil")
-                        .stdout(std::process::Stdio::null())
-                        .stderr(std::process::Stdio::null())
-                        .spawn()?;
+    /// Plays back a script of key presses, e.g. from `--replay`.
+    pub fn with_replay(mut self, script: Option<replay::Script>) -> Self {
+        self.replay = script;
+        self
+    }
 
-                    test_env.anvil_process = Some(child);
+    /// Writes the keys pressed to a script `--replay` can play back.
+    pub fn with_recorder(mut self, recorder: Option<replay::Recorder>) -> Self {
+        self.recorder = recorder;
+        self
+    }
 
-                    // Wait a moment for anvil to start
-                    std::thread::sleep(std::time::Duration::from_secs(2));
+    /// Switches to non-interactive CI output. Use with [`App::run_headless`].
+    pub fn with_ci(mut self, ci: bool) -> Self {
+        self.ci = ci;
+        self
+    }
 
-                    // Verify anvil is running by trying to connect
-                    match Command::new("curl")
-                        .arg("-X")
-                        .arg("POST")
-                        .arg("-H")
-                        .arg("Content-Type: application/json")
-                        .arg("-d")
-                        .arg("{\"jsonrpc\":\"2.0\",\"method\":\"eth_blockNumber\",\"params\":[],\"id\":1}")
-                        .arg("http://localhost:8545")
-                        .output()
-                    {
-                        Ok(output) if output.status.success() => {
-                            self.status_message = String::from("✓ Local Ethereum chain started");
-                            self.state = AppState::Testing(E2ETestStep::RunningTest);
-                        }
-                        _ => {
-                            return Err(color_eyre::eyre::eyre!("Failed to start Anvil. Please make sure it's installed and try again."));
-                        }
-                    }
-                }
-                AppState::Testing(E2ETestStep::RunningTest) => {
-                    self.status_message = String::from("Running end-to-end test...");
+    /// Pre-fills the project name, e.g. from `--name`.
+    pub fn with_project_name(mut self, name: Option<String>) -> Self {
+        if let Some(name) = name {
+            self.project_name = name;
+        }
+        self
+    }
 
-                    // First make sure we're in the workspace root
-                    let workspace_root = std::path::PathBuf::from("/Users/sasha/Developer/tui");
-                    std::env::set_current_dir(&workspace_root)?;
+    /// Makes projects from a cargo-generate template at `source`, a git URL or a directory,
+    /// instead of a Steel example.
+    pub fn with_cargo_template(mut self, source: Option<String>) -> Self {
+        self.cargo_template = source.map(|source| CargoTemplate {
+            source,
+            template: None,
+            values: Vec::new(),
+            field: 0,
+        });
+        self
+    }
 
-                    // Then change to project directory
-                    self.add_output(format!(
-                        "Changing to project directory: {}",
-                        self.project_name
-                    ));
-                    std::env::set_current_dir(&self.project_name)?;
+    /// Creates every project in `batch` instead of asking for one.
+    pub fn with_batch(mut self, batch: Option<Batch>) -> Self {
+        self.batch = batch;
+        self
+    }
 
-                    // First run cargo build to generate the ImageID.sol contract
-                    self.run_command(
-                        Command::new("cargo")
-                            .arg("build")
-                            .env("RUST_LOG", "info,risc0_steel=debug"),
-                        "Building project to generate contracts...",
-                        terminal,
-                    )?;
+    /// The git repository around the working directory, which new projects are added to
+    /// with `in_repo` (e.g. from `--in-repo`) or when chosen on the welcome screen.
+    pub fn with_enclosing_repo(mut self, repo: Option<PathBuf>, in_repo: bool) -> Self {
+        self.in_repo = in_repo && repo.is_some();
+        self.enclosing_repo = repo;
+        self
+    }
 
-                    // Run forge build to compile Solidity contracts
-                    self.run_command(
-                        Command::new("forge").arg("build"),
-                        "Compiling Solidity contracts...",
-                        terminal,
-                    )?;
+    /// Allows replacing an existing project directory without asking.
+    pub fn with_overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
 
-                    // Make the test script executable
-                    self.run_command(
-                        Command::new("chmod").arg("+x").arg("e2e-test.sh"),
-                        "Making test script executable...",
-                        terminal,
-                    )?;
+    /// Looks for a newer release in the background and shows a banner if one exists.
+    /// Never runs offline, so call [`App::with_offline`] first.
+    pub fn with_update_check(mut self, enabled: bool) -> Self {
+        self.update_check = (enabled && !self.offline).then(self_update::spawn_update_check);
+        self
+    }
 
-                    // Set up environment variables
-                    std::env::set_var("BONSAI_API_URL", "https://api.bonsai.xyz");
-                    std::env::set_var(
-                        "ETH_WALLET_ADDRESS",
-                        "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
-                    );
-                    std::env::set_var(
-                        "ETH_WALLET_PRIVATE_KEY",
-                        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
-                    );
-                    std::env::set_var("ETH_RPC_URL", "http://localhost:8545");
+    /// Works from the template cache and disables everything that needs the network.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        if offline {
+            self.prover = ProverBackend::Local;
+        }
+        self
+    }
 
-                    // Then run the e2e test script
-                    self.run_command(
-                        Command::new("bash")
-                            .arg("e2e-test.sh")
-                            .env("RUST_LOG", "info,risc0_steel=debug"),
-                        "Running end-to-end test script...",
-                        terminal,
-                    )?;
+    pub fn with_timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
 
-                    self.status_message = String::from("✓ End-to-end test completed successfully");
-                    self.state = AppState::Testing(E2ETestStep::Cleanup);
-                }
-                AppState::Testing(E2ETestStep::Cleanup) => {
-                    self.status_message = String::from("Cleaning up...");
-                    self.cleanup_test();
-                    self.status_message = String::from("✓ Cleanup completed");
-                    self.state = AppState::TestMenu;
-                }
-                _ => {}
+    /// Saved user settings. Process-wide ones should already be applied.
+    pub fn with_settings(mut self, settings: Settings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Starts in the toolbox of an existing project instead of the creation wizard.
+    pub fn with_existing_project(mut self, project: Option<RecentProject>) -> Self {
+        if let Some(project) = project {
+            if !projects::load().iter().any(|p| p.path == project.path) {
+                let _ = projects::record(project.clone());
             }
+            self.open_project(&project);
+            self.status_message = format!(
+                "Found '{}' in the current directory. Select test to run:",
+                project.name()
+            );
         }
-        Ok(())
+        self
     }
 
-    fn handle_key_event(&mut self, key: KeyEvent) -> Result<bool> {
-        if key.kind != KeyEventKind::Press {
-            return Ok(false);
-        }
+    /// Maximum number of independent commands run at once.
+    pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism.max(1);
+        self
+    }
+
+    /// Keeps cargo from trying to fetch anything while offline.
+    fn cargo_offline_env(&self) -> Option<(&'static str, &'static str)> {
+        self.offline.then_some(("CARGO_NET_OFFLINE", "true"))
+    }
+
+    /// Absolute path of the project being created or tested. Every command that operates on
+    /// the project runs here via `current_dir`; the process-wide working directory never changes.
+    fn project_root(&self) -> PathBuf {
+        self.base_dir.join(&self.project_name)
+    }
+
+    /// Refuses to replace the project directory when it's the directory projects are created
+    /// in, or one of its parents.
+    fn check_replaceable(&self, root: &Path) -> Result<()> {
+        let root = platform::canonicalize(root)?;
+        let base = platform::canonicalize(&self.base_dir)?;
+        if base.starts_with(&root) {
+            return Err(color_eyre::eyre::eyre!(
+                "Refusing to replace {}: it contains the working directory",
+                root.display()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Adds a message from the app itself. These are shown at every verbosity level.
+    fn add_output(&mut self, output: String) {
+        let output = redact::redact(&output);
+        crash::record_output(&output);
+        self.emit(&output);
+        self.command_output
+            .push(OutputLine::new(output, Verbosity::Quiet, self.step_started));
+        self.pending_redraw = true;
+    }
+
+    /// Adds a line captured from a child process, tagged by how noisy it is.
+    fn add_command_output(&mut self, output: String) {
+        if matches!(self.state, AppState::Linting) {
+            match lint::parse(&output) {
+                lint::Message::Diagnostic(diagnostic) => {
+                    let at = self.command_output.len();
+                    let Some(view) = self.lint.as_mut() else {
+                        return;
+                    };
+                    // Cargo reports a diagnostic again for every target that includes the file
+                    if view
+                        .diagnostics
+                        .iter()
+                        .any(|(known, _)| known.rendered == diagnostic.rendered)
+                    {
+                        return;
+                    }
+                    let rendered = diagnostic.rendered.clone();
+                    view.diagnostics.push((diagnostic, at));
+                    for line in rendered.lines() {
+                        self.add_command_output(line.to_string());
+                    }
+                    return;
+                }
+                lint::Message::Other => return,
+                lint::Message::Text => {}
+            }
+        }
+        let output = redact::redact(&output);
+        crash::record_output(&output);
+        let level = output::classify(&output);
+        if level <= self.verbosity {
+            self.emit(&output);
+        }
+        if let Some(progress) = self.guest_build.as_mut() {
+            progress.observe(&output);
+        }
+        self.transfer.observe(&output);
+        if let Some(run) = self.artifacts.as_mut() {
+            run.observe(&output);
+        }
+        let request = self.boundless.as_mut().and_then(|t| t.observe(&output));
+        self.command_output
+            .push(OutputLine::new(output, level, self.step_started));
+        self.pending_redraw = true;
+        if let Some(id) = request {
+            self.add_output(format!("Tracking Boundless request {:#x}", id));
+        }
+    }
+
+    /// Forwards new guest build output. Returns whether the progress panel needs a redraw, which
+    /// is always the case while a step's spinner is turning.
+    fn poll_guest_build(&mut self) -> bool {
+        let Some(progress) = self.guest_build.as_mut() else {
+            return false;
+        };
+        let lines = progress.poll();
+        let running = progress.running();
+        let changed = !lines.is_empty();
+        for line in lines {
+            self.add_command_output(line);
+        }
+        changed || running
+    }
+
+    /// Checks tracked Boundless requests and reports any that moved on. Returns whether
+    /// anything changed.
+    fn poll_boundless(&mut self) -> bool {
+        let Some(tracker) = self.boundless.as_mut() else {
+            return false;
+        };
+        let changes = tracker.poll();
+        let changed = !changes.is_empty();
+        for change in changes {
+            self.add_output(change);
+        }
+        changed
+    }
+
+    /// Takes in the chain monitor's latest checks, reporting when the node stops answering.
+    /// Returns whether there was anything new.
+    fn poll_chain_monitor(&mut self) -> bool {
+        let Some(monitor) = self.chain_monitor.as_mut() else {
+            return false;
+        };
+        let before = monitor.describe();
+        if let Some(e) = monitor.poll() {
+            let message = format!("{}: {}", monitor.describe(), e);
+            self.add_output(message);
+            return true;
+        }
+        monitor.describe() != before
+    }
+
+    fn visible_output(&self) -> impl Iterator<Item = &OutputLine> {
+        let verbosity = match self.settings.progress_view {
+            ProgressView::Detailed => self.verbosity,
+            ProgressView::Compact => Verbosity::Quiet,
+        };
+        self.command_output
+            .iter()
+            .filter(move |line| line.level <= verbosity)
+    }
+
+    /// The compact view's rows for the install or test in progress: each step's status and
+    /// title. Empty in the detailed view.
+    fn step_rows(&self) -> Vec<(TaskStatus, &'static str)> {
+        if self.settings.progress_view != ProgressView::Compact {
+            return Vec::new();
+        }
+        let status = |i: usize, current: usize| match i.cmp(&current) {
+            std::cmp::Ordering::Less => TaskStatus::Done,
+            std::cmp::Ordering::Equal => TaskStatus::Running,
+            std::cmp::Ordering::Greater => TaskStatus::Pending,
+        };
+        match &self.state {
+            AppState::Installing(step) => InstallStep::ALL
+                .iter()
+                .map(|s| (status(s.number(), step.number()), s.title()))
+                .collect(),
+            AppState::Testing(step) => {
+                let current = E2ETestStep::ALL
+                    .iter()
+                    .position(|s| s.label() == step.label())
+                    .unwrap_or(0);
+                E2ETestStep::ALL
+                    .iter()
+                    .enumerate()
+                    .map(|(i, s)| (status(i, current), s.title()))
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn run_command(
+        &mut self,
+        command: &mut Command,
+        description: &str,
+        terminal: &mut Terminal<impl Backend>,
+    ) -> Result<()> {
+        self.status_message = description.to_string();
+        self.step_started = std::time::Instant::now();
+        self.emit(description);
+
+        // Force a redraw before running the command
+        self.redraw(terminal)?;
+
+        // Configure the command with piped output
+        command.stdout(std::process::Stdio::piped());
+        command.stderr(std::process::Stdio::piped());
+
+        let program = command.get_program().to_string_lossy().to_string();
+        let limit = self.timeouts.limit_for(&program);
+        self.transfer = Transfer::default();
+
+        loop {
+            let started = std::time::Instant::now();
+            self.step_started = started;
+            let first_line = self.command_output.len();
+            let mut child = supervisor::prepare(command).spawn()?;
+            supervisor::track_foreground(&program, &child);
+            let output = tasks::stream_output(&mut child);
+
+            let watch = self.watch_child(&output, limit, terminal);
+            if !matches!(watch, Ok(Watch::Finished)) {
+                platform::terminate_tree(child.id());
+                let _ = child.kill();
+            }
+            let status = child.wait();
+            supervisor::untrack(child.id());
+            self.stuck_for = None;
+
+            let reason = match watch? {
+                Watch::Finished => {
+                    let status = status?;
+                    if status.success() {
+                        // Force another redraw after adding output
+                        self.redraw(terminal)?;
+                        return Ok(());
+                    }
+                    FailureReason::Exited(status.code())
+                }
+                Watch::TimedOut => FailureReason::TimedOut,
+                Watch::Aborted => FailureReason::Aborted,
+                Watch::Retry => {
+                    self.add_output(format!("Killed '{}', retrying...", program));
+                    continue;
+                }
+            };
+
+            let captured: Vec<String> = self.command_output[first_line..]
+                .iter()
+                .map(|line| line.text.clone())
+                .collect();
+            let error = CommandError::new(command, reason, started.elapsed(), &captured);
+            self.advise(&error);
+            return Err(error.into());
+        }
+    }
+
+    /// Runs a `cargo build`, following the guest build inside it as sub-steps.
+    fn run_cargo_build(
+        &mut self,
+        command: &mut Command,
+        description: &str,
+        terminal: &mut Terminal<impl Backend>,
+    ) -> Result<()> {
+        let progress = guest_build::Progress::start();
+        let (key, log) = progress.env();
+        command.env(key, log);
+        self.guest_build = Some(progress);
+        let result = self.run_command(command, description, terminal);
+        // Whatever the guest build logged after cargo's last line
+        if let Some(mut progress) = self.guest_build.take() {
+            for line in progress.poll() {
+                self.add_command_output(line);
+            }
+            if let Some(summary) = progress.finish() {
+                self.add_output(summary);
+            }
+        }
+        result
+    }
+
+    /// Runs independent commands concurrently, at most `self.parallelism` at a time, showing
+    /// one progress row per task. Stops starting new tasks after the first failure.
+    fn run_tasks(
+        &mut self,
+        tasks: Vec<Task>,
+        description: &str,
+        terminal: &mut Terminal<impl Backend>,
+    ) -> Result<()> {
+        self.status_message = description.to_string();
+        self.step_started = std::time::Instant::now();
+        self.emit(description);
+        self.tasks = tasks;
+        let mut failure: Option<CommandError> = None;
+
+        loop {
+            // Start pending tasks while there's capacity
+            let running = self
+                .tasks
+                .iter()
+                .filter(|t| t.status == TaskStatus::Running)
+                .count();
+            let mut capacity = self.parallelism.saturating_sub(running);
+            for task in self
+                .tasks
+                .iter_mut()
+                .filter(|t| t.status == TaskStatus::Pending)
+            {
+                if capacity == 0 || failure.is_some() {
+                    break;
+                }
+                task.command
+                    .stdout(std::process::Stdio::piped())
+                    .stderr(std::process::Stdio::piped());
+                let mut child = supervisor::prepare(&mut task.command).spawn()?;
+                supervisor::track_foreground(&task.label, &child);
+                task.output = Some(tasks::stream_output(&mut child));
+                task.child = Some(child);
+                task.started = Some(std::time::Instant::now());
+                task.status = TaskStatus::Running;
+                capacity -= 1;
+            }
+
+            // Collect output and reap finished tasks
+            let mut lines = Vec::new();
+            for task in self
+                .tasks
+                .iter_mut()
+                .filter(|t| t.status == TaskStatus::Running)
+            {
+                let started = task.started.unwrap_or_else(std::time::Instant::now);
+                let limit = self
+                    .timeouts
+                    .limit_for(&task.command.get_program().to_string_lossy());
+                let Some(child) = task.child.as_mut() else {
+                    continue;
+                };
+
+                let reason = match child.try_wait()? {
+                    Some(status) if status.success() => None,
+                    Some(status) => Some(FailureReason::Exited(status.code())),
+                    None if signals::interrupted() || started.elapsed() >= limit => {
+                        platform::terminate_tree(child.id());
+                        let _ = child.kill();
+                        let _ = child.wait();
+                        Some(if signals::interrupted() {
+                            FailureReason::Aborted
+                        } else {
+                            FailureReason::TimedOut
+                        })
+                    }
+                    None => {
+                        if let Some(output) = &task.output {
+                            for line in output.try_iter() {
+                                lines.push(format!("[{}] {}", task.label, line));
+                                task.captured.push(line.clone());
+                                task.last_line = if task.transfer.observe(&line) {
+                                    task.transfer.describe()
+                                } else {
+                                    line
+                                };
+                            }
+                        }
+                        continue;
+                    }
+                };
+
+                supervisor::untrack(child.id());
+                // The pipes close once the process is gone, so this drains what's left
+                if let Some(output) = task.output.take() {
+                    for line in output.iter() {
+                        lines.push(format!("[{}] {}", task.label, line));
+                        task.captured.push(line.clone());
+                        task.last_line = if task.transfer.observe(&line) {
+                            task.transfer.describe()
+                        } else {
+                            line
+                        };
+                    }
+                }
+                task.child = None;
+
+                match reason {
+                    None => task.status = TaskStatus::Done,
+                    Some(reason) => {
+                        task.status = TaskStatus::Failed;
+                        if failure.is_none() {
+                            failure = Some(CommandError::new(
+                                &task.command,
+                                reason,
+                                started.elapsed(),
+                                &task.captured,
+                            ));
+                        }
+                    }
+                }
+            }
+            for line in lines {
+                self.add_command_output(line);
+            }
+
+            let active = self.tasks.iter().any(|t| t.status == TaskStatus::Running);
+            let pending = self.tasks.iter().any(|t| t.status == TaskStatus::Pending);
+            if !active && (!pending || failure.is_some()) {
+                break;
+            }
+            self.resources.sample();
+            self.poll_chain_monitor();
+            self.redraw(terminal)?;
+            if !self.ci && event::poll(std::time::Duration::ZERO)? {
+                if let Event::Key(key) = event::read()? {
+                    if signals::is_interrupt_key(&key) {
+                        signals::interrupt();
+                    }
+                }
+            }
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+
+        self.tasks.clear();
+        match failure {
+            Some(error) => {
+                self.advise(&error);
+                Err(error.into())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Puts the suggested fix for a known failure next to the raw error.
+    fn advise(&mut self, error: &CommandError) {
+        if let Some(advice) = error.advice {
+            self.add_output(format!("💡 {}. {}", advice.problem, advice.fix));
+        }
+    }
+
+    /// Pumps a child's output into the pane until it closes, the hard limit is hit, or the
+    /// user reacts to the stuck-step prompt.
+    fn watch_child(
+        &mut self,
+        output: &mpsc::Receiver<String>,
+        limit: std::time::Duration,
+        terminal: &mut Terminal<impl Backend>,
+    ) -> Result<Watch> {
+        let started = std::time::Instant::now();
+        let mut last_output = started;
+
+        loop {
+            match output.recv_timeout(std::time::Duration::from_millis(50)) {
+                Ok(line) => {
+                    self.add_command_output(line);
+                    last_output = std::time::Instant::now();
+                    self.stuck_for = None;
+                    self.redraw(terminal)?;
+                    continue;
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(Watch::Finished),
+            }
+
+            if started.elapsed() >= limit {
+                return Ok(Watch::TimedOut);
+            }
+            if self.resources.sample()
+                | self.poll_boundless()
+                | self.poll_guest_build()
+                | self.poll_chain_monitor()
+            {
+                self.redraw(terminal)?;
+            }
+
+            if signals::interrupted() {
+                return Ok(Watch::Aborted);
+            }
+
+            let silent = last_output.elapsed();
+            if silent >= self.timeouts.stuck_after {
+                if self.stuck_for.is_none() {
+                    self.emit(&format!(
+                        "⚠ No output for {}s, step appears stuck (hard limit {}s)",
+                        silent.as_secs(),
+                        limit.as_secs()
+                    ));
+                    if let Some(usage) = self.resources.summary() {
+                        self.emit(&format!("  Resource usage: {}", usage));
+                    }
+                }
+                self.stuck_for = Some(silent);
+                self.redraw(terminal)?;
+            }
+            if self.ci {
+                // Nobody to ask; the hard limit decides
+                continue;
+            }
+
+            if event::poll(std::time::Duration::ZERO)? {
+                if let Event::Key(key) = event::read()? {
+                    if signals::is_interrupt_key(&key) {
+                        signals::interrupt();
+                        return Ok(Watch::Aborted);
+                    }
+                    if key.kind != KeyEventKind::Press || self.stuck_for.is_none() {
+                        continue;
+                    }
+                    match key.code {
+                        KeyCode::Char('w') => {
+                            last_output = std::time::Instant::now();
+                            self.stuck_for = None;
+                            self.redraw(terminal)?;
+                        }
+                        KeyCode::Char('k') => return Ok(Watch::Retry),
+                        KeyCode::Char('a') | KeyCode::Esc => return Ok(Watch::Aborted),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_dependency(
+        &mut self,
+        cmd: &str,
+        args: &[&str],
+        success_msg: &str,
+        error_msg: &str,
+    ) -> bool {
+        match Command::new(cmd).args(args).output() {
+            Ok(_) => {
+                self.status_message = format!("✓ {}", success_msg);
+                true
+            }
+            Err(_) => {
+                self.status_message = format!("✗ {}", error_msg);
+                false
+            }
+        }
+    }
+
+    fn check_rust(&mut self) -> bool {
+        self.check_dependency(
+            "rustc",
+            &["--version"],
+            "Rust is installed",
+            apple_silicon::install_hint("rust")
+                .unwrap_or("Rust not found. Visit: https://www.rust-lang.org/tools/install"),
+        )
+    }
+
+    fn check_foundry(&mut self) -> bool {
+        self.check_dependency(
+            "forge",
+            &["--version"],
+            "Foundry is installed",
+            apple_silicon::install_hint("foundry").unwrap_or(
+                "Foundry not found. Visit: https://book.getfoundry.sh/getting-started/installation",
+            ),
+        )
+    }
+
+    fn check_risc0(&mut self) -> bool {
+        let output = Command::new("cargo")
+            .arg("risczero")
+            .arg("--version")
+            .output();
+
+        match output {
+            Ok(output)
+                if !output.status.success()
+                    && String::from_utf8_lossy(&output.stderr).contains("no such command") =>
+            {
+                self.cargo_risczero_missing = true;
+                self.status_message = format!(
+                    "✗ cargo-risczero not found. Press i to install {} with cargo, Esc to exit",
+                    self.cargo_risczero_requirement()
+                );
+                false
+            }
+            Ok(output) => {
+                self.cargo_risczero_missing = false;
+                let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                let releases = compat::toolchain_version(&version)
+                    .map(compat::compatible)
+                    .unwrap_or_default();
+                if releases.is_empty() {
+                    self.status_message = format!(
+                        "✗ Unsupported RISC0 version '{}'. Supported: {}",
+                        version,
+                        compat::supported()
+                    );
+                    return false;
+                }
+                let templates = templates::load().unwrap_or_else(|e| {
+                    self.add_output(format!("⚠ {}; using the bundled templates", e));
+                    templates::bundled()
+                });
+                self.template_choices = releases
+                    .into_iter()
+                    .flat_map(|release| {
+                        templates
+                            .iter()
+                            .filter(move |template| {
+                                template
+                                    .release()
+                                    .is_some_and(|r| r.branch == release.branch)
+                            })
+                            .map(move |template| (release, template.clone()))
+                    })
+                    .collect();
+                self.choose_template(0);
+                self.status_message = format!("✓ {} detected", version);
+                self.risc0_version = Some(version);
+                true
+            }
+            Err(_) => {
+                self.status_message = format!(
+                    "✗ {}",
+                    apple_silicon::install_hint("risc0").unwrap_or(
+                        "RISC0 not found. Visit: https://dev.risczero.com/api/zkvm/install"
+                    )
+                );
+                false
+            }
+        }
+    }
+
+    /// Lists what's known to go wrong on this machine, with how to fix it, before the
+    /// dependency checks run into it. Only Apple Silicon has anything to report.
+    fn report_platform_problems(&mut self) {
+        for finding in apple_silicon::checks() {
+            if let Err(problem) = finding.outcome {
+                self.add_output(format!("⚠ {}: {}", finding.check, problem.detail));
+                self.add_output(format!("  → {}", problem.fix));
+            }
+        }
+    }
+
+    /// Versions of cargo-risczero that build the selected template, e.g. `~1.2`.
+    fn cargo_risczero_requirement(&self) -> String {
+        let (major, minor) = self
+            .release()
+            .map_or(compat::RELEASES[0].toolchain, |release| release.toolchain);
+        format!("~{}.{}", major, minor)
+    }
+
+    /// Installs only the cargo plugin, with cargo-binstall's prebuilt binaries when it's
+    /// available and from source otherwise, then checks the toolchain again.
+    fn install_cargo_risczero(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let requirement = self.cargo_risczero_requirement();
+        let mut install = Command::new("cargo");
+        if tools::version("cargo", &["binstall", "-V"]).is_ok() {
+            install.args(["binstall", "--no-confirm", "cargo-risczero", "--version"]);
+        } else {
+            install.args(["install", "--locked", "cargo-risczero", "--version"]);
+        }
+        install.arg(&requirement);
+        self.run_command(
+            &mut install,
+            &format!("Installing cargo-risczero {}...", requirement),
+            terminal,
+        )?;
+        if !self.check_risc0() {
+            return Err(color_eyre::eyre::eyre!(
+                "cargo-risczero was installed but isn't usable: {}",
+                self.status_message
+            ));
+        }
+        self.add_output(format!(
+            "✓ Installed {}",
+            self.risc0_version.as_deref().unwrap_or("cargo-risczero")
+        ));
+        Ok(())
+    }
+
+    /// Picks the release and template new projects are created from.
+    fn choose_template(&mut self, index: usize) {
+        if let Some((_, template)) = self.template_choices.get(index) {
+            self.template_choice = index;
+            if !self.custom_repository {
+                self.summary.repository = template.repository.clone();
+                self.summary.branch = template.branch.clone();
+            }
+            self.summary.template = template.name.clone();
+            // Examples differ between releases
+            self.summary.examples.clear();
+        }
+    }
+
+    /// The release whose rules the project follows: the one its branch is, or for another
+    /// repository's branch, the one the template was picked from.
+    fn release(&self) -> Option<&'static compat::Release> {
+        compat::release(&self.summary.branch).or_else(|| {
+            self.template_choices
+                .get(self.template_choice)
+                .map(|(release, _)| *release)
+        })
+    }
+
+    /// Where the project's risc0-ethereum crates come from: the repository the template is
+    /// cloned from, when it isn't risc0-ethereum's own.
+    fn crate_repository(&self) -> &str {
+        if manifest::is_risc0_ethereum(&self.summary.repository) {
+            manifest::RISC0_ETHEREUM_REPO
+        } else {
+            &self.summary.repository
+        }
+    }
+
+    /// The chosen template's entry in `templates.toml`.
+    fn template(&self) -> Option<&Template> {
+        self.template_choices
+            .get(self.template_choice)
+            .map(|(_, template)| template)
+    }
+
+    /// Clones templates from the repository in the form, or from risc0-ethereum again when
+    /// the URL is left empty.
+    fn apply_repo_form(&mut self) -> std::result::Result<(), String> {
+        let url = self.repo_form.url.trim().to_string();
+        let branch = self.repo_form.branch.trim().to_string();
+        if url.is_empty() {
+            self.custom_repository = false;
+            self.choose_template(self.template_choice);
+            return Ok(());
+        }
+        if branch.is_empty() {
+            return Err(String::from("A branch is required"));
+        }
+        if url.starts_with('-') || branch.starts_with('-') {
+            return Err(String::from("The URL and branch can't start with '-'"));
+        }
+        self.custom_repository = true;
+        self.summary.repository = url;
+        self.summary.branch = branch;
+        self.summary.tags.clear();
+        Ok(())
+    }
+
+    /// The examples of the chosen release that can be combined into one workspace.
+    fn combinable_examples(&self) -> &'static [&'static str] {
+        self.template_choices
+            .get(self.template_choice)
+            .map_or(&[], |(release, _)| release.examples)
+    }
+
+    fn clone_repository(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        // If directory exists, remove it first
+        let root = self.project_root();
+        if root.exists() {
+            self.check_replaceable(&root)?;
+            self.add_output(format!(
+                "Removing existing directory '{}'...",
+                self.project_name
+            ));
+            fs::remove_dir_all(&root)?;
+        }
+
+        let entry = template_cache::template_entry(&self.summary.repository);
+        // A branch the cache doesn't hold yet has to be fetched, whatever Settings say
+        let cached = !self.offline
+            && template_cache::is_cached(&entry)
+            && (!self.settings.skip_template_refresh
+                || template_cache::has_branch(&entry, &self.summary.branch));
+        let source = if cached {
+            self.refresh_template_cache(&entry, terminal);
+            template_cache::repo_path(&entry)
+                .to_string_lossy()
+                .to_string()
+        } else {
+            template_cache::clone_source(&entry, &self.summary.repository, self.offline)?
+        };
+        if self.offline {
+            self.add_output(format!("Offline: using the cached template in {}", source));
+        }
+        let local = self.offline || cached;
+        let mut clone = http::git();
+        clone.args([
+            "clone",
+            "-b",
+            &self.summary.branch,
+            &source,
+            &root.to_string_lossy(),
+            "--single-branch",
+        ]);
+        // Local clones ignore --depth, and the cache is shallow already
+        if !local {
+            clone.args(["--depth", "1"]);
+        }
+        // git only reports transfer progress to a tty unless asked explicitly
+        clone.arg("--progress");
+
+        self.run_command(
+            &mut clone,
+            &format!("Cloning repository into '{}'...", self.project_name),
+            terminal,
+        )?;
+
+        self.summary.project_path = platform::canonicalize(&root)?;
+        if !local {
+            if let Err(e) = template_cache::store(&entry, &root) {
+                self.add_output(format!("⚠ {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Brings the cached template up to date before a project is cloned from it, unless
+    /// Settings say not to. Without the network, the cached copy is used as it is.
+    fn refresh_template_cache(&mut self, entry: &str, terminal: &mut Terminal<impl Backend>) {
+        if self.settings.skip_template_refresh {
+            self.add_output(String::from(
+                "Using the cached template without refreshing it",
+            ));
+            return;
+        }
+        let mut fetch =
+            template_cache::refresh_command(entry, &self.summary.repository, &self.summary.branch);
+        if let Err(e) = self.run_command(&mut fetch, "Refreshing the cached template...", terminal)
+        {
+            self.add_output(format!(
+                "⚠ Could not refresh the cached template ({}); using it as it is",
+                e
+            ));
+        }
+    }
+
+    /// Where the template lives in its repository, e.g. `examples/erc20-counter`.
+    fn template_path(&self) -> String {
+        self.template().map_or_else(
+            || format!("examples/{}", self.summary.template),
+            |template| template.path.trim_matches('/').to_string(),
+        )
+    }
+
+    /// The directories of the repository the project is made of: the template's, or every
+    /// example's of a multi-example workspace. Empty when it's the whole repository.
+    fn sparse_paths(&self) -> Vec<String> {
+        if !self.summary.examples.is_empty() {
+            return self
+                .summary
+                .examples
+                .iter()
+                .map(|example| format!("examples/{}", example))
+                .collect();
+        }
+        match self.template() {
+            Some(template) if template.is_whole_repository() => Vec::new(),
+            _ => vec![self.template_path()],
+        }
+    }
+
+    fn setup_sparse_checkout(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let root = self.project_root();
+        let paths = self.sparse_paths();
+
+        if paths.is_empty() {
+            self.add_output(String::from("The template is the whole repository"));
+        } else {
+            self.run_command(
+                http::git()
+                    .args(["sparse-checkout", "set"])
+                    .args(&paths)
+                    .current_dir(&root),
+                "Setting up sparse checkout...",
+                terminal,
+            )?;
+
+            self.run_command(
+                http::git().arg("checkout").current_dir(&root),
+                "Checking out files...",
+                terminal,
+            )?;
+        }
+
+        if let Some(path) = paths.iter().find(|path| !root.join(path).exists()) {
+            return Err(color_eyre::eyre::eyre!(
+                "{} directory not found after checkout",
+                path
+            ));
+        }
+
+        // Remember the exact template revision before setup_forge discards the clone's history
+        let rev = http::git()
+            .args(["rev-parse", "HEAD"])
+            .current_dir(&root)
+            .output()?;
+        if rev.status.success() {
+            self.summary.revision = Some(String::from_utf8_lossy(&rev.stdout).trim().to_string());
+        }
+
+        Ok(())
+    }
+
+    fn move_files(&mut self) -> Result<()> {
+        let root = self.project_root();
+        if !self.summary.examples.is_empty() {
+            self.add_output(String::from(
+                "Moving each example into its own directory...",
+            ));
+            for line in workspace::arrange(&root, &self.summary.examples)? {
+                self.add_output(line);
+            }
+            return Ok(());
+        }
+        if self.sparse_paths().is_empty() {
+            return Ok(());
+        }
+
+        self.add_output("Moving template files to root directory...".to_string());
+        let path = self.template_path();
+        let staging = root.join(".steel-tui-template");
+
+        // Move the template out of the repository's directories, e.g. examples/. A template at
+        // the top of the repository, e.g. `my-example`, leaves none behind.
+        fs::rename(root.join(&path), &staging)?;
+        if let Some((top, _)) = path.split_once('/') {
+            fs::remove_dir_all(root.join(top))?;
+        }
+
+        // Remove all files in root (but keep directories)
+        for entry in fs::read_dir(&root)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.is_file() {
+                fs::remove_file(path)?;
+            }
+        }
+
+        // Move all contents from the template to root (including hidden files)
+        for entry in fs::read_dir(&staging)? {
+            let entry = entry?;
+            let source = entry.path();
+            let file_name = source.file_name().unwrap();
+            let target = root.join(file_name);
+            fs::rename(source, target)?;
+        }
+
+        // Remove the now-empty template directory
+        fs::remove_dir(&staging)?;
+
+        self.add_output("✓ Project structure set up successfully".to_string());
+        Ok(())
+    }
+
+    /// Adds what a template generated on top of an example has beyond the example.
+    fn generate_variant(&mut self) -> Result<()> {
+        let root = self.project_root();
+        self.project_chain = None;
+        let changes = match self.summary.template.as_str() {
+            "event-query" => {
+                self.add_output(String::from("Adding the event-query guest and host..."));
+                scaffold::add_event_query(&root)?
+            }
+            "history" => {
+                self.add_output(String::from(
+                    "Adding the historical_balance guest and host...",
+                ));
+                scaffold::add_history(&root)?
+            }
+            "op-stack" => {
+                self.add_output(String::from("Setting the project up for Base Sepolia..."));
+                self.project_chain = Some(ChainPreset::BaseSepolia);
+                scaffold::add_op_stack(&root, ChainPreset::BaseSepolia)?
+            }
+            _ => Vec::new(),
+        };
+        for change in changes {
+            self.add_output(format!("✓ {}", change));
+        }
+        Ok(())
+    }
+
+    /// Points the project's cargo at the configured registry mirror, if any.
+    fn write_cargo_config(&mut self) -> Result<()> {
+        if let Some(file) = cargo_config::write(
+            &self.project_root(),
+            self.settings.cargo_mirror.as_deref(),
+            self.settings.cargo_git_fetch_with_cli,
+        )? {
+            self.add_output(format!("✓ Wrote cargo settings to {}", file.display()));
+        }
+        Ok(())
+    }
+
+    /// Offers the published tags of risc0-ethereum for the release's crates. Without them, e.g.
+    /// offline, the crates stay on the head of the branch.
+    fn choose_versions(&mut self) {
+        // The tags are risc0-ethereum's, whatever another repository has
+        if self.custom_repository {
+            return;
+        }
+        let Some(release) = compat::release(&self.summary.branch) else {
+            return;
+        };
+        match manifest::release_tags() {
+            Ok(tags) if !tags.is_empty() => {
+                self.version_form = Some(VersionForm::new(release, tags));
+                self.state = AppState::ChoosingVersions;
+                self.status_message = String::from("Choose the risc0-ethereum versions:");
+            }
+            Ok(_) => {}
+            Err(e) => self.add_output(format!(
+                "⚠ {}; using the head of {}",
+                e, self.summary.branch
+            )),
+        }
+    }
+
+    fn update_dependencies(&mut self) -> Result<()> {
+        let cargo_files = manifest::find_cargo_toml_files(&self.project_root())?;
+        let release = self.release().ok_or_else(|| {
+            color_eyre::eyre::eyre!("No dependency rules for branch {}", self.summary.branch)
+        })?;
+
+        self.add_output("Updating Cargo.toml files with git dependencies...".to_string());
+
+        for file_path in cargo_files {
+            let mut content = fs::read_to_string(&file_path)?;
+            // By component, since Windows paths use `\`. Examples without contracts call their
+            // host crate `host`
+            let is_apps = file_path
+                .strip_prefix(self.project_root())
+                .is_ok_and(|relative| {
+                    relative
+                        .components()
+                        .any(|c| c.as_os_str() == "apps" || c.as_os_str() == "host")
+                });
+            let is_workspace = content.contains("[workspace]");
+
+            for krate in release.crates {
+                let name = regex::escape(krate.name);
+                let tag = self.summary.tags.get(krate.name).map(String::as_str);
+                if is_workspace {
+                    // Workspace manifests point at the crates by path; match any indentation
+                    let path = regex::Regex::new(&format!(
+                        r#"(?m)^[ \t]*{}\s*=\s*\{{\s*path\s*=\s*".*"\s*\}}"#,
+                        name
+                    ))
+                    .expect("valid regex");
+                    let dependency = compat::dependency(
+                        krate,
+                        false,
+                        self.crate_repository(),
+                        &self.summary.branch,
+                        tag,
+                    );
+                    content = path
+                        .replace_all(&content, regex::NoExpand(&dependency))
+                        .to_string();
+                } else {
+                    let line = regex::Regex::new(&format!(r#"(?m)^{}\s*=.*$"#, name))
+                        .expect("valid regex");
+                    let dependency = compat::dependency(
+                        krate,
+                        is_apps,
+                        self.crate_repository(),
+                        &self.summary.branch,
+                        tag,
+                    );
+                    content = line
+                        .replace_all(&content, regex::NoExpand(&dependency))
+                        .to_string();
+                }
+            }
+            // Crates without a rule come from the revision risc0-steel does, which they build on
+            let depth = file_path
+                .parent()
+                .and_then(|dir| dir.strip_prefix(self.project_root()).ok())
+                .map_or(0, |dir| dir.components().count());
+            let (rewritten, others) = compat::rewrite_repository_paths(
+                &content,
+                depth,
+                self.crate_repository(),
+                &self.summary.branch,
+                self.summary.tags.get("risc0-steel").map(String::as_str),
+            );
+            content = rewritten;
+            for name in others {
+                self.add_output(format!("Pointed {} at git as well", name));
+            }
+
+            fs::write(&file_path, content)?;
+            self.add_output(format!("Updated dependencies in: {}", file_path.display()));
+            self.summary.manifests.push(file_path);
+        }
+
+        self.add_output(
+            "✓ All Cargo.toml files have been updated with git dependencies.".to_string(),
+        );
+        Ok(())
+    }
+
+    fn setup_forge(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        self.add_output("Starting Forge setup (this may take a few minutes)...".to_string());
+        let root = self.project_root();
+
+        // Remove the template's git directory; the project gets a repository of its own, or
+        // joins the one it was created in
+        let _ = fs::remove_dir_all(root.join(".git"));
+        let repo = self.enclosing_repo.clone().filter(|_| self.in_repo);
+        match &repo {
+            Some(repo) => self.add_output(format!(
+                "Adding the project to the git repository at {}",
+                repo.display()
+            )),
+            None => self.run_command(
+                http::git().args(["init"]).current_dir(&root),
+                "Initializing git repository...",
+                terminal,
+            )?,
+        }
+
+        // Create lib directory
+        fs::create_dir_all(root.join("lib"))?;
+
+        // Clone the submodule repositories concurrently. Registering them afterwards only
+        // touches the index, which git can't do in parallel, but it's quick.
+        // Another repository's branch needn't exist upstream; its release's does
+        let branch = self
+            .release()
+            .map_or(self.summary.branch.clone(), |release| {
+                release.branch.to_string()
+            });
+        let submodules = [
+            ("forge-std", "https://github.com/foundry-rs/forge-std", None),
+            (
+                "openzeppelin-contracts",
+                "https://github.com/OpenZeppelin/openzeppelin-contracts",
+                None,
+            ),
+            (
+                "risc0-ethereum",
+                manifest::RISC0_ETHEREUM_REPO,
+                Some(branch.as_str()),
+            ),
+        ];
+        let submodule_command =
+            |subcommand: &[&str], url: &str, name: &str, branch: Option<&str>| {
+                let mut command = http::git();
+                command.args(subcommand);
+                if let Some(branch) = branch {
+                    command.args(["-b", branch]);
+                }
+                command
+                    .args([url, &format!("lib/{}", name)])
+                    .current_dir(&root);
+                command
+            };
+
+        // Local clones ignore --depth, so the cached copies are cloned whole
+        let shallow = !self.settings.full_submodule_history && !self.offline;
+        let mut clones = Vec::new();
+        for (name, url, branch) in &submodules {
+            let source = template_cache::clone_source(name, url, self.offline)?;
+            let mut command = submodule_command(&["clone", "--progress"], &source, name, *branch);
+            if shallow {
+                command.args(["--depth", "1"]);
+            }
+            clones.push(Task::new(name, command));
+        }
+        self.run_tasks(clones, "Cloning Forge dependencies...", terminal)?;
+
+        for (i, (name, url, branch)) in submodules.iter().enumerate() {
+            self.add_output(format!(
+                "Adding {} ({}/{})...",
+                name,
+                i + 1,
+                submodules.len()
+            ));
+            self.run_command(
+                &mut submodule_command(&["submodule", "add"], url, name, *branch),
+                &format!("Registering {}...", name),
+                terminal,
+            )?;
+        }
+
+        // Update submodules
+        if self.offline {
+            // Nested submodules only matter for the dependencies' own tests
+            self.add_output(String::from(
+                "Offline: skipping nested submodules of the Forge dependencies",
+            ));
+        } else {
+            self.add_output(
+                "Updating submodules recursively (this may take a while)...".to_string(),
+            );
+            let mut update = http::git();
+            update
+                .args(["submodule", "update", "--init", "--recursive", "--progress"])
+                .current_dir(&root);
+            if shallow {
+                update.args(["--depth", "1"]);
+            }
+            // Only ours, not every submodule of an enclosing repository
+            update.arg("--");
+            for (name, _, _) in &submodules {
+                update.arg(format!("lib/{}", name));
+            }
+            self.run_command(&mut update, "Updating submodules...", terminal)?;
+            for (name, _, _) in &submodules {
+                if let Err(e) = template_cache::store(name, &root.join("lib").join(name)) {
+                    self.add_output(format!("⚠ {}", e));
+                }
+            }
+        }
+
+        // Reset git index, in an enclosing repository only for what we added
+        let mut reset = http::git();
+        reset.arg("reset").current_dir(&root);
+        if repo.is_some() {
+            reset.args(["-q", "--", ".", ":/.gitmodules"]);
+        }
+        self.run_command(&mut reset, "Resetting git index...", terminal)?;
+
+        // Point the Forge configuration at lib/, which examples of a workspace share
+        if self.summary.examples.is_empty() {
+            self.point_forge_at_lib(&root, "lib")?;
+        } else {
+            for example in self.summary.examples.clone() {
+                let dir = root.join(example);
+                if dir.join("foundry.toml").exists() {
+                    self.point_forge_at_lib(&dir, "../lib")?;
+                }
+            }
+        }
+
+        if let Some(repo) = &repo {
+            if let Some(manifest) = monorepo::exclude_from_workspace(repo, &root)? {
+                self.add_output(format!(
+                    "✓ Excluded the project from the Cargo workspace in {}",
+                    manifest.display()
+                ));
+            }
+        }
+
+        self.add_output("Forge setup completed successfully".to_string());
+        Ok(())
+    }
+
+    /// Points the remappings and foundry.toml in `dir` at the Forge dependencies in `lib`,
+    /// relative to `dir`, instead of the template repository's.
+    fn point_forge_at_lib(&mut self, dir: &Path, lib: &str) -> Result<()> {
+        // Update remappings.txt
+        let remappings = dir.join("remappings.txt");
+        if remappings.exists() {
+            let mut content = fs::read_to_string(&remappings)?;
+
+            // Update existing remappings
+            content = content
+                .replace(
+                    "forge-std/=../../lib/forge-std/src/",
+                    &format!("forge-std/={}/forge-std/src/", lib),
+                )
+                .replace(
+                    "openzeppelin/=../../lib/openzeppelin-contracts/",
+                    &format!("openzeppelin/={}/openzeppelin-contracts/", lib),
+                )
+                .replace(
+                    "risc0/=../../contracts/src/",
+                    &format!("risc0/={}/risc0-ethereum/contracts/src/", lib),
+                );
+
+            // Add OpenZeppelin contracts remapping if not present
+            if !content.contains("openzeppelin-contracts/=") {
+                content.push_str(&format!(
+                    "\nopenzeppelin-contracts/={}/openzeppelin-contracts/contracts",
+                    lib
+                ));
+            }
+
+            fs::write(&remappings, content)?;
+            self.add_output("✓ Updated remappings.txt".to_string());
+        } else {
+            self.add_output("Warning: remappings.txt not found".to_string());
+        }
+
+        // Update foundry.toml
+        let foundry_toml = dir.join("foundry.toml");
+        if foundry_toml.exists() {
+            let mut content = fs::read_to_string(&foundry_toml)?;
+
+            // Update libs path
+            content = content.replace(
+                "libs = [\"../../lib\", \"../../contracts/src\"]",
+                &format!("libs = [\"{}\"]", lib),
+            );
+
+            // Add auto_detect_remappings = false under [profile.default]
+            if !content.contains("auto_detect_remappings") {
+                if content.contains("[profile.default]") {
+                    content = content.replace(
+                        "[profile.default]",
+                        "[profile.default]\nauto_detect_remappings = false",
+                    );
+                } else {
+                    content.push_str("\n[profile.default]\nauto_detect_remappings = false");
+                }
+            }
+
+            fs::write(&foundry_toml, content)?;
+            self.add_output("✓ Updated foundry.toml".to_string());
+        } else {
+            self.add_output("Warning: foundry.toml not found".to_string());
+        }
+        Ok(())
+    }
+
+    /// Waits for the user to acknowledge a fatal error. The caller is responsible for exiting.
+    fn handle_error(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        self.status_message.push_str("\nPress Esc to exit");
+        terminal.draw(|frame| self.ui(frame))?;
+        loop {
+            if signals::interrupted() {
+                return Ok(());
+            }
+            if event::poll(std::time::Duration::from_millis(50))? {
+                if let Event::Key(key) = event::read()? {
+                    if key.code == KeyCode::Esc || signals::is_interrupt_key(&key) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    /// The Bonsai API key from this session, the credentials store, or the environment.
+    fn bonsai_key(&self) -> Option<String> {
+        let stored = self
+            .credentials
+            .as_ref()
+            .and_then(|c| c.get(CredentialKind::BonsaiApiKey))
+            .map(|c| c.secret.clone());
+        Some(self.bonsai_api_key.clone())
+            .filter(|key| !key.is_empty())
+            .or(stored)
+            .or_else(|| std::env::var("BONSAI_API_KEY").ok())
+            .filter(|key| !key.is_empty())
+    }
+
+    fn refresh_bonsai_panel(&mut self, api_key: &str) {
+        redact::register(api_key);
+        let version = self
+            .risc0_version
+            .as_deref()
+            .and_then(|v| v.split_whitespace().last())
+            .unwrap_or("1.2.0");
+        let api_url =
+            std::env::var("BONSAI_API_URL").unwrap_or_else(|_| bonsai::DEFAULT_API_URL.to_string());
+        let client = bonsai::Client::new(&api_url, api_key, version);
+
+        let session_ids =
+            bonsai::session_ids(self.command_output.iter().map(|line| line.text.as_str()), 5);
+        self.bonsai_panel = Some(BonsaiPanel {
+            availability: client.availability().map_err(|e| e.to_string()),
+            quotas: client.quotas().map_err(|e| e.to_string()),
+            sessions: session_ids
+                .into_iter()
+                .map(|id| {
+                    let status = client.session_status(&id).map_err(|e| e.to_string());
+                    (id, status)
+                })
+                .collect(),
+        });
+    }
+
+    /// Checks that `rpc_url` answers, and reports where the chain is and what the wallet holds.
+    fn connect_chain(&mut self, rpc_url: &str, name: &str, wallet: &str) -> Result<()> {
+        let chain = Chain::connect(rpc_url)?;
+        let status = chain.status()?;
+        self.chain_monitor = Some(chain::Monitor::start(rpc_url, name, status));
+        self.add_output(format!("✓ {}", status.describe(name)));
+        match chain.balance(wallet) {
+            Ok(balance) => self.add_output(format!(
+                "Wallet {} holds {}",
+                wallet,
+                chain::format_ether(balance)
+            )),
+            Err(e) => self.add_output(format!("Could not read wallet balance: {}", e)),
+        }
+        Ok(())
+    }
+
+    /// Moves to `next`, unless a well-known dev key is about to be used against a public RPC,
+    /// in which case the user has to confirm first.
+    fn confirm_dev_key_use(&mut self, private_key: &str, rpc_url: &str, next: AppState) {
+        if dev_keys::is_dev_key(private_key) && !dev_keys::is_local_rpc(rpc_url) {
+            self.public_rpc_url = rpc_url.to_string();
+            self.confirm_input.clear();
+            self.after_confirm = Some(next);
+            self.state = AppState::ConfirmingPublicRpc;
+            self.status_message = String::from("⚠ Well-known development key with a public RPC");
+        } else {
+            self.state = next;
+        }
+    }
+
+    /// After a failed step: if forge was missing a solc version, offers to fetch it and retry
+    /// with `retry`. Returns whether it did.
+    fn offer_solc(&mut self, first_line: usize, retry: AppState) -> bool {
+        let lines: Vec<String> = self.command_output[first_line.min(self.command_output.len())..]
+            .iter()
+            .map(|line| line.text.clone())
+            .collect();
+        let Some(version) = solc::missing_version(&self.project_root(), &lines) else {
+            return false;
+        };
+        if self.ci {
+            self.add_output(format!(
+                "solc {} isn't installed; `forge build --use {}` fetches it",
+                version, version
+            ));
+            return false;
+        }
+        self.status_message = format!("solc {} isn't installed", version);
+        self.solc_version = Some(version);
+        self.after_confirm = Some(retry);
+        self.state = AppState::ConfirmingSolcInstall;
+        true
+    }
+
+    /// Fetches the offered solc through forge. A build that fails for other reasons after the
+    /// download still counts; the retried step reports those.
+    fn install_solc(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let Some(version) = self.solc_version.take() else {
+            return Ok(());
+        };
+        let mut command = solc::install_command(&version);
+        command.current_dir(self.project_root());
+        let result = self.run_command(
+            &mut command,
+            &format!("Installing solc {}...", version),
+            terminal,
+        );
+        if solc::installed(&version) {
+            self.add_output(format!("✓ Installed solc {}", version));
+            return Ok(());
+        }
+        result?;
+        Err(color_eyre::eyre::eyre!(
+            "forge built with solc {} but it isn't in svm's directory",
+            version
+        ))
+    }
+
+    /// The forge script that deploys the project's contracts.
+    fn deploy_script(&self) -> Option<PathBuf> {
+        let dir = self.project_root().join("script");
+        let preferred = dir.join("DeployCounter.s.sol");
+        if preferred.exists() {
+            return Some(preferred);
+        }
+        let mut scripts: Vec<PathBuf> = fs::read_dir(&dir)
+            .ok()?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.to_string_lossy().ends_with(".s.sol"))
+            .collect();
+        scripts.sort();
+        scripts.into_iter().next()
+    }
+
+    /// The deploy script relative to the project root, as forge names its broadcast files.
+    fn relative_deploy_script(&self) -> Result<PathBuf> {
+        let root = self.project_root();
+        let script = self.deploy_script().ok_or_else(|| {
+            color_eyre::eyre::eyre!(
+                "No deploy script found in {}",
+                root.join("script").display()
+            )
+        })?;
+        Ok(script.strip_prefix(&root).unwrap_or(&script).to_path_buf())
+    }
+
+    fn deploy_command(&self, deployment: &Deployment, script: &Path, broadcast: bool) -> Command {
+        let mut command = Command::new("forge");
+        command
+            .arg("script")
+            .arg(script)
+            .args(["--rpc-url", &deployment.rpc_url]);
+        if broadcast {
+            command.arg("--broadcast");
+        }
+        command
+            .args(deployment.signer.forge_args())
+            .envs(deployment.signer.env())
+            .envs(self.chain_env())
+            .env("ETH_RPC_URL", &deployment.rpc_url)
+            .current_dir(self.project_root());
+        command
+    }
+
+    /// Runs the deploy script without `--broadcast` and reads what it would send from forge's
+    /// dry run, for the user to confirm before anything is signed.
+    fn simulate_deploy(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let Some(deployment) = &self.deployment else {
+            return Ok(());
+        };
+        let script = self.relative_deploy_script()?;
+        let rpc_url = deployment.rpc_url.clone();
+        let mut command = self.deploy_command(deployment, &script, false);
+        self.add_output(format!(
+            "Simulating {} against {}",
+            script.display(),
+            rpc_url
+        ));
+        self.run_command(&mut command, "Simulating the deployment...", terminal)?;
+
+        let status = Chain::connect(&rpc_url)?.status()?;
+        let transactions =
+            broadcast::transactions(&self.project_root(), &script, status.chain_id, false)?;
+        self.deploy_preview = Some(DeployPreview {
+            transactions,
+            gas_price: status.gas_price,
+        });
+        Ok(())
+    }
+
+    fn run_deploy(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let Some(deployment) = self.deployment.take() else {
+            return Ok(());
+        };
+        self.deploy_preview = None;
+        let script = self.relative_deploy_script()?;
+
+        self.add_output(format!(
+            "Deploying {} to {} with {}",
+            script.display(),
+            deployment.rpc_url,
+            deployment.signer.describe()
+        ));
+        let mut command = self.deploy_command(&deployment, &script, true);
+        self.run_command(&mut command, "Deploying contracts...", terminal)?;
+        self.status_message = String::from("✓ Contracts deployed");
+        self.record_deployment(&deployment.rpc_url, &script, ToolboxItem::Deploy);
+        if dev_keys::is_local_rpc(&deployment.rpc_url) {
+            self.save_chain_state(&deployment.rpc_url);
+        }
+        self.capture_events(&deployment.rpc_url);
+
+        if let Some(verifier) = &deployment.verifier {
+            if let Err(e) = self.verify_deployment(&deployment, verifier, &script, terminal) {
+                self.status_message =
+                    format!("✓ Contracts deployed, but verification failed: {}", e);
+                self.add_output(format!("Verification failed: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    fn open_scripts(&mut self) {
+        self.scripts = scripts::discover(&self.project_root());
+        if self.scripts.is_empty() {
+            self.status_message = format!(
+                "✗ No *.s.sol scripts in {}",
+                self.project_root().join("script").display()
+            );
+            return;
+        }
+        self.script_selected = self
+            .script_selected
+            .min(self.scripts.len().saturating_sub(1));
+        self.state = AppState::Scripts;
+        self.status_message = String::from("Choose a script to run");
+    }
+
+    /// The form for the highlighted script, set up with the current RPC the first time.
+    fn script_form(&mut self) -> Option<&mut ScriptForm> {
+        let script = self.scripts.get(self.script_selected)?.clone();
+        if !self.script_forms.contains_key(&script) {
+            let form = ScriptForm {
+                rpc_url: self.rpc_url(),
+                ..ScriptForm::default()
+            };
+            self.script_forms.insert(script.clone(), form);
+        }
+        self.script_forms.get_mut(&script)
+    }
+
+    /// Who a script sends from: the network's wallet unless it needs a password, else anvil's
+    /// first account on a local chain, else the stored deployment key. `None` leaves it to the
+    /// script and forge.
+    fn script_signer(&self, rpc_url: &str) -> Result<Option<Signer>> {
+        let wallet = match self.network.as_ref().map(networks::Network::wallet) {
+            Some(wallet) => wallet?.filter(|(kind, _)| !kind.needs_password()),
+            None => None,
+        };
+        let (kind, detail) = match wallet {
+            Some(wallet) => wallet,
+            None if dev_keys::is_local_rpc(rpc_url) => (
+                SignerKind::PrivateKey,
+                dev_keys::ANVIL_PRIVATE_KEYS[0].to_string(),
+            ),
+            None => match self.stored_key() {
+                Some(key) => (SignerKind::PrivateKey, key),
+                None => return Ok(None),
+            },
+        };
+        Signer::new(kind, &detail, "").map(Some)
+    }
+
+    fn run_script(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let Some(script) = self.scripts.get(self.script_selected).cloned() else {
+            return Ok(());
+        };
+        let Some(form) = self.script_forms.get(&script) else {
+            return Ok(());
+        };
+        let (rpc_url, broadcast, verify) =
+            (form.rpc_url.trim().to_string(), form.broadcast, form.verify);
+        let root = self.project_root();
+        let signer = self.script_signer(&rpc_url)?;
+
+        let mut command = Command::new("forge");
+        command
+            .arg("script")
+            .arg(&script)
+            .args(["--rpc-url", &rpc_url]);
+        if broadcast {
+            command.arg("--broadcast");
+        }
+        if verify {
+            command.arg("--verify");
+        }
+        if let Some(signer) = &signer {
+            command.args(signer.forge_args()).envs(signer.env());
+        }
+        command
+            .envs(self.chain_env())
+            .env("ETH_RPC_URL", &rpc_url)
+            .current_dir(&root);
+        self.add_output(format!(
+            "Running {} against {}{}",
+            script.display(),
+            rpc_url,
+            match &signer {
+                Some(signer) => format!(" with {}", signer.describe()),
+                None => String::new(),
+            }
+        ));
+        self.run_command(
+            &mut command,
+            &format!("Running {}...", script.display()),
+            terminal,
+        )?;
+
+        let chain_id = Chain::connect(&rpc_url)?.chain_id()?;
+        // Scripts that send nothing leave no run file
+        let sent = if broadcast::run_file(&root, &script, chain_id, broadcast).exists() {
+            broadcast::transactions(&root, &script, chain_id, broadcast)?
+        } else {
+            Vec::new()
+        };
+        let verb = if broadcast { "sent" } else { "would send" };
+        self.add_output(format!(
+            "{} {} {} transaction(s)",
+            script.display(),
+            verb,
+            sent.len()
+        ));
+        for tx in &sent {
+            self.add_output(format!("  {}", tx.describe()));
+        }
+        if broadcast {
+            self.record_deployment(&rpc_url, &script, ToolboxItem::Scripts);
+        }
+        self.status_message = format!(
+            "✓ {} {} {} transaction(s)",
+            script.display(),
+            verb,
+            sent.len()
+        );
+        Ok(())
+    }
+
+    /// Reads the contracts a broadcast of `script` created into the address book and the
+    /// project's `.env`, and keeps them for the table shown next.
+    fn record_deployment(&mut self, rpc_url: &str, script: &Path, back: ToolboxItem) {
+        let root = self.project_root();
+        let recorded = Chain::connect(rpc_url)
+            .and_then(|chain| chain.chain_id())
+            .and_then(|chain_id| {
+                let contracts = broadcast::deployed_contracts(&root, script, chain_id)?;
+                if contracts.is_empty() {
+                    return Ok(None);
+                }
+                address_book::record(&root, chain_id, script, &contracts)?;
+                let vars = address_book::write_env(&root, &contracts)?;
+                Ok(Some((chain_id, contracts, vars)))
+            });
+        match recorded {
+            Ok(Some((chain_id, contracts, vars))) => {
+                self.add_output(format!(
+                    "Recorded {} contract(s) in {} and set {} in {}",
+                    contracts.len(),
+                    address_book::FILE_NAME,
+                    vars.join(", "),
+                    address_book::ENV_FILE
+                ));
+                self.deployed = Some(DeployedView {
+                    chain_id,
+                    contracts,
+                    back,
+                });
+            }
+            Ok(None) => {}
+            Err(e) => self.add_output(format!("Could not record the deployed addresses: {}", e)),
+        }
+    }
+
+    /// Where a run that may have broadcast goes next: the table of what it deployed, if
+    /// anything, else `otherwise`.
+    fn after_broadcast(&mut self, otherwise: AppState) {
+        self.state = if self.deployed.is_some() {
+            AppState::DeployedContracts
+        } else {
+            otherwise
+        };
+    }
+
+    /// Saves a local anvil's state with the contracts the deploy script created, for the next
+    /// end-to-end test to restore.
+    fn save_chain_state(&mut self, rpc_url: &str) {
+        let root = self.project_root();
+        let saved = Chain::connect(rpc_url).and_then(|chain| {
+            let contracts = match self.deploy_script() {
+                Some(script) => {
+                    let script = script.strip_prefix(&root).unwrap_or(&script).to_path_buf();
+                    broadcast::deployed_contracts(&root, &script, chain.chain_id()?)
+                        .unwrap_or_default()
+                }
+                None => Vec::new(),
+            };
+            chain_state::save(&root, &chain, &contracts)?;
+            Ok(contracts.len())
+        });
+        match saved {
+            Ok(count) => self.add_output(format!(
+                "Saved the chain state with {} contract(s); the next end-to-end test restores it",
+                count
+            )),
+            Err(e) => self.add_output(format!("Could not save the chain state: {}", e)),
+        }
+    }
+
+    /// Name and address of each contract the deploy script created on the chain at `rpc_url`,
+    /// plus those restored from a saved anvil state or, on other chains, in the address book.
+    fn deployed_contracts(&self, rpc_url: &str) -> Result<Vec<(String, String)>> {
+        let root = self.project_root();
+        let chain_id = Chain::connect(rpc_url)?.chain_id()?;
+        let mut contracts: Vec<(String, String)> = self
+            .deploy_script()
+            .and_then(|script| broadcast::deployed_contracts(&root, &script, chain_id).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|contract| (contract.name, contract.address))
+            .collect();
+        if dev_keys::is_local_rpc(rpc_url) {
+            for saved in chain_state::load(&root)
+                .map(|s| s.contracts)
+                .unwrap_or_default()
+            {
+                if !contracts
+                    .iter()
+                    .any(|(_, address)| address.eq_ignore_ascii_case(&saved.address))
+                {
+                    contracts.push((saved.name, saved.address));
+                }
+            }
+        } else {
+            // A local chain starts over when anvil restarts, so only others' are looked up
+            let book = address_book::load(&root)?;
+            for (name, entry) in book.get(&chain_id).into_iter().flatten() {
+                if !contracts.iter().any(|(known, _)| known == name) {
+                    contracts.push((name.clone(), entry.address.clone()));
+                }
+            }
+        }
+        Ok(contracts)
+    }
+
+    /// Fills the transaction builder with the contracts deployed on `rpc_url`.
+    fn open_tx_form(&mut self, rpc_url: &str) -> Result<()> {
+        let compiled = abi::load(&self.project_root())?;
+        let contracts: Vec<TxContract> = self
+            .deployed_contracts(rpc_url)?
+            .into_iter()
+            .filter_map(|(name, address)| {
+                let functions: Vec<Function> = compiled
+                    .iter()
+                    .find(|contract| contract.name == name)?
+                    .abi
+                    .functions()
+                    .cloned()
+                    .collect();
+                let address = address.parse().ok()?;
+                (!functions.is_empty()).then_some(TxContract {
+                    name,
+                    address,
+                    functions,
+                })
+            })
+            .collect();
+        if contracts.is_empty() {
+            return Err(color_eyre::eyre::eyre!(
+                "No deployed contracts with a compiled ABI: deploy or run the end-to-end test first"
+            ));
+        }
+        let stored_key = self.stored_key();
+        self.tx_form = TxForm {
+            rpc_url: rpc_url.to_string(),
+            contracts,
+            detail: stored_key.unwrap_or_else(|| {
+                if dev_keys::is_local_rpc(rpc_url) {
+                    dev_keys::ANVIL_PRIVATE_KEYS[0].to_string()
+                } else {
+                    String::new()
+                }
+            }),
+            ..TxForm::default()
+        };
+        self.tx_form.reset_args();
+        Ok(())
+    }
+
+    /// Encodes the transaction builder's call and estimates its gas; view functions are
+    /// called right away.
+    fn preview_transaction(&mut self) {
+        let form = &self.tx_form;
+        let (Some(contract), Some(function)) = (form.selected_contract(), form.selected_function())
+        else {
+            return;
+        };
+        let preview = (|| {
+            let calldata = transaction::calldata(function, &form.args)?;
+            let value = transaction::value(&form.value)?;
+            let chain = Chain::connect(&form.rpc_url)?;
+            let from = form.from();
+            let result = transaction::is_view(function).then(|| {
+                chain
+                    .call_from(from, contract.address, calldata.clone(), value)
+                    .and_then(|output| transaction::decode_output(function, &output))
+                    .map_err(|e| e.to_string())
+            });
+            let gas = chain
+                .estimate_gas(from, contract.address, calldata.clone(), value)
+                .map_err(|e| e.to_string());
+            Ok(TxPreview {
+                calldata,
+                value,
+                gas,
+                result,
+            })
+        })()
+        .map_err(|e: color_eyre::Report| e.to_string());
+        self.tx_form.preview = Some(preview);
+    }
+
+    /// Asks the Boundless market to prove the chosen guest; the tracker picks the request ID
+    /// out of the CLI's output.
+    fn submit_boundless_request(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let root = self.project_root();
+        let guest = self
+            .guest_images
+            .get(self.boundless_form.guest)
+            .ok_or_else(|| color_eyre::eyre::eyre!("No guest to prove"))?;
+        let name = guest.name.clone();
+        let elf = guest
+            .elf_path
+            .clone()
+            .ok_or_else(|| color_eyre::eyre::eyre!("{} has no ELF", name))?;
+        let input = root.join(self.boundless_form.input.trim());
+        let mut command = boundless::submit_command(&elf, &input);
+        command.current_dir(&root);
+        self.run_command(
+            &mut command,
+            &format!("Submitting a request to prove {}...", name),
+            terminal,
+        )?;
+        self.status_message = format!("✓ Submitted a request to prove {}", name);
+        Ok(())
+    }
+
+    /// Sends the transaction confirmed on the builder and waits for it to be mined. Held keys
+    /// sign here; signers forge drives itself go through `cast send`.
+    fn send_transaction(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let Some(tx) = self.pending_tx.take() else {
+            return Ok(());
+        };
+        self.add_output(format!(
+            "Sending {} to {} with {}",
+            tx.label,
+            tx.to,
+            tx.signer.describe()
+        ));
+        let Some(key) = tx.signer.private_key() else {
+            let mut command = Command::new("cast");
+            command
+                .arg("send")
+                .arg(tx.to.to_string())
+                .arg(tx.calldata.to_string())
+                .args(["--value", &tx.value.to_string()])
+                .args(["--gas-limit", &tx.gas.to_string()])
+                .args(["--rpc-url", &tx.rpc_url])
+                .args(tx.signer.forge_args());
+            self.run_command(&mut command, &format!("Sending {}...", tx.label), terminal)?;
+            self.status_message = format!("✓ {} sent", tx.label);
+            return Ok(());
+        };
+
+        let chain = Chain::connect(&tx.rpc_url)?;
+        let hash = chain.send_signed(key, tx.to, tx.calldata, tx.value, tx.gas)?;
+        self.add_output(format!("Transaction {}", hash));
+        self.status_message = format!("Waiting for {} to be mined...", tx.label);
+        self.redraw(terminal)?;
+        let started = std::time::Instant::now();
+        let receipt = loop {
+            if let Some(receipt) = chain.receipt(&hash.to_string())? {
+                break receipt;
+            }
+            if signals::interrupted() || started.elapsed() > std::time::Duration::from_secs(120) {
+                return Err(color_eyre::eyre::eyre!(
+                    "{} was sent but isn't mined yet; check it later",
+                    hash
+                ));
+            }
+            std::thread::sleep(std::time::Duration::from_millis(500));
+        };
+        let block = receipt.block_number.unwrap_or_default();
+        if receipt.status() {
+            self.status_message = format!("✓ {} mined in block {}", tx.label, block);
+            self.add_output(format!(
+                "✓ Mined in block {}, {} gas used",
+                block, receipt.gas_used
+            ));
+        } else {
+            self.status_message = format!("✗ {} reverted", tx.label);
+            self.add_output(format!(
+                "✗ Reverted in block {}, {} gas used",
+                block, receipt.gas_used
+            ));
+        }
+        Ok(())
+    }
+
+    /// Starts following the deployed contracts' logs on `rpc_url`.
+    fn open_event_log(&mut self, rpc_url: &str) -> Result<()> {
+        let contracts = self.deployed_contracts(rpc_url)?;
+        let mut log = events::EventLog::new(&self.project_root(), rpc_url, &contracts)?;
+        log.poll();
+        if let Some(e) = &log.error {
+            return Err(color_eyre::eyre::eyre!("{}", e));
+        }
+        self.events_scroll = 0;
+        self.event_log = Some(log);
+        Ok(())
+    }
+
+    /// Reads the events a test run or deployment left on-chain and shows the latest.
+    fn capture_events(&mut self, rpc_url: &str) {
+        if let Err(e) = self.open_event_log(rpc_url) {
+            self.add_output(format!("Could not read contract events: {}", e));
+            return;
+        }
+        let Some(log) = &self.event_log else {
+            return;
+        };
+        let mut lines = vec![format!(
+            "{} event(s) from {}",
+            log.rows.len(),
+            log.watched
+                .iter()
+                .map(|w| w.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )];
+        let shown = log.rows.len().saturating_sub(5);
+        lines.extend(
+            log.rows[shown..]
+                .iter()
+                .map(|row| format!("  {}", row.describe())),
+        );
+        if !log.rows.is_empty() {
+            lines.push(String::from("  All of them: Toolbox → Contract events"));
+        }
+        for line in lines {
+            self.add_output(line);
+        }
+    }
+
+    /// Loads the state saved after the last local deployment into the anvil just started,
+    /// returning the restored contracts' addresses by variable name.
+    fn restore_chain_state(&mut self, rpc_url: &str) -> Vec<(String, String)> {
+        let Some(saved) = chain_state::load(&self.project_root()) else {
+            return Vec::new();
+        };
+        match Chain::connect(rpc_url).and_then(|chain| saved.restore(&chain)) {
+            Ok(()) => {
+                self.add_output(format!("✓ Restored the chain state saved {}", saved.saved));
+                saved
+                    .contracts
+                    .iter()
+                    .map(|contract| {
+                        let var = contract.env_var();
+                        self.add_output(format!(
+                            "  {} at {} (exported as {})",
+                            contract.name, contract.address, var
+                        ));
+                        (var, contract.address.clone())
+                    })
+                    .collect()
+            }
+            Err(e) => {
+                self.add_output(format!(
+                    "Could not restore the saved chain state, starting empty: {}",
+                    e
+                ));
+                Vec::new()
+            }
+        }
+    }
+
+    /// Verifies every contract the deploy script created.
+    fn verify_deployment(
+        &mut self,
+        deployment: &Deployment,
+        verifier: &Verifier,
+        script: &std::path::Path,
+        terminal: &mut Terminal<impl Backend>,
+    ) -> Result<()> {
+        let root = self.project_root();
+        let chain_id = Chain::connect(&deployment.rpc_url)?.chain_id()?;
+        let contracts = broadcast::deployed_contracts(&root, script, chain_id)?;
+        if contracts.is_empty() {
+            self.add_output(String::from("No contract creations found to verify"));
+            return Ok(());
+        }
+
+        for contract in &contracts {
+            let mut command = verifier.command(
+                &contract.address,
+                &contract.name,
+                chain_id,
+                &deployment.rpc_url,
+            );
+            command.current_dir(&root);
+            self.run_command(
+                &mut command,
+                &format!(
+                    "Verifying {} at {} on {}...",
+                    contract.name,
+                    contract.address,
+                    verifier.name()
+                ),
+                terminal,
+            )?;
+        }
+        self.status_message = format!("✓ Contracts deployed and verified on {}", verifier.name());
+        Ok(())
+    }
+
+    fn rpc_api_key(&self) -> String {
+        let provider = self.settings.rpc_provider.label();
+        let stored = self.credentials.as_ref().and_then(|c| {
+            c.entries
+                .iter()
+                .find(|e| e.kind == CredentialKind::RpcProviderKey && e.name == provider)
+                .map(|e| e.secret.clone())
+        });
+        Some(self.rpc_api_key.clone())
+            .filter(|key| !key.is_empty())
+            .or(stored)
+            .unwrap_or_default()
+    }
+
+    /// RPC endpoint for tests and deployments: `ETH_RPC_URL`, else the provider preset from
+    /// settings, else anvil on localhost.
+    fn rpc_url(&self) -> String {
+        if let Some(url) = self.network.as_ref().and_then(|n| n.rpc_url.clone()) {
+            return url;
+        }
+        let chain = self.chain();
+        std::env::var("ETH_RPC_URL")
+            .ok()
+            .filter(|url| !url.is_empty())
+            .or_else(|| {
+                // A network or project made for a chain asks the provider for that chain
+                let network = if self.network.is_some() || self.project_chain.is_some() {
+                    chain.network()?
+                } else {
+                    self.settings.rpc_network
+                };
+                rpc_presets::url(self.settings.rpc_provider, network, &self.rpc_api_key())
+            })
+            .unwrap_or_else(|| chain.default_rpc())
+    }
+
+    /// The chain tests and deployments target: the picked network's, the project's own, else
+    /// the one from the toolbox.
+    fn chain(&self) -> ChainPreset {
+        self.network
+            .as_ref()
+            .map(|network| network.chain)
+            .or(self.project_chain)
+            .unwrap_or(self.settings.chain)
+    }
+
+    /// The chosen chain's details for scripts, except those the user exported themselves.
+    fn chain_env(&self) -> Vec<(&'static str, String)> {
+        let router = self
+            .network
+            .as_ref()
+            .and_then(networks::Network::verifier_router);
+        self.chain()
+            .vars()
+            .into_iter()
+            .map(|(name, value)| match (name, &router) {
+                ("VERIFIER_ROUTER_ADDRESS", Some(router)) => (name, router.clone()),
+                _ => (name, value),
+            })
+            .filter(|(name, _)| std::env::var_os(name).is_none())
+            .collect()
+    }
+
+    /// The verifier a receipt's seal is checked against: the network's router, or on a chain
+    /// without one, a verifier the deploy script created.
+    fn default_verifier(&self) -> String {
+        if let Some(router) = self
+            .network
+            .as_ref()
+            .and_then(networks::Network::verifier_router)
+            .or_else(|| self.chain().verifier_router().map(str::to_string))
+        {
+            return router;
+        }
+        self.deployed_contracts(&self.rpc_url())
+            .unwrap_or_default()
+            .into_iter()
+            .find(|(name, _)| name.contains("Verifier"))
+            .map(|(_, address)| address)
+            .unwrap_or_default()
+    }
+
+    fn write_project_file(&mut self) -> Result<()> {
+        let options = project_file::Options {
+            offline: self.offline,
+            parallelism: self.parallelism,
+            dev_mode: supervisor::dev_mode(),
+            chain: self.project_chain,
+        };
+        ProjectFile::new(&self.summary, options).save(&self.project_root())?;
+        self.add_output(format!("Wrote {}", project_file::FILE_NAME));
+        Ok(())
+    }
+
+    /// Starts a test or deployment by picking one of the project's networks.
+    fn choose_network(&mut self, item: ToolboxItem) {
+        match networks::load(&self.project_root()) {
+            Ok(networks) => {
+                self.network_selected = self
+                    .network
+                    .as_ref()
+                    .and_then(|current| networks.iter().position(|n| n.name == current.name))
+                    .unwrap_or(0);
+                self.networks = networks;
+                self.network_for = Some(item);
+                self.state = AppState::ChoosingNetwork;
+                self.status_message = String::from("Choose the network");
+            }
+            Err(e) => self.status_message = format!("✗ {}", e),
+        }
+    }
+
+    fn start_deploy_setup(&mut self) {
+        let wallet = match self
+            .network
+            .as_ref()
+            .map_or(Ok(None), networks::Network::wallet)
+        {
+            Ok(wallet) => wallet,
+            Err(e) => {
+                self.state = AppState::TestMenu;
+                self.status_message = format!("✗ {}", e);
+                return;
+            }
+        };
+        let (kind, detail) = match wallet {
+            Some((kind, detail)) => (
+                SignerKind::ALL.iter().position(|k| *k == kind).unwrap_or(0),
+                detail,
+            ),
+            None => (0, self.stored_key().unwrap_or_default()),
+        };
+        self.deploy_form = DeployForm {
+            rpc_url: self.rpc_url(),
+            kind,
+            detail,
+            ..DeployForm::default()
+        };
+        self.state = AppState::DeploySetup;
+        self.status_message = String::from("Configure the deployment");
+    }
+
+    /// The account the end-to-end test sends from: the network's wallet, which has to be a key
+    /// the test script can use, else anvil's first account.
+    fn test_wallet(&self) -> Result<(String, String)> {
+        let network = self.network.as_ref();
+        match network.map_or(Ok(None), networks::Network::wallet)? {
+            None => Ok((
+                dev_keys::ANVIL_ADDRESS.to_string(),
+                dev_keys::ANVIL_PRIVATE_KEYS[0].to_string(),
+            )),
+            Some((SignerKind::PrivateKey, key)) => {
+                redact::register(&key);
+                Ok((chain::address_of(&key)?.to_string(), key))
+            }
+            Some((kind, _)) => Err(color_eyre::eyre::eyre!(
+                "The end-to-end test needs a private key, but {}'s wallet is a {}",
+                network.map_or("the network", |n| n.name.as_str()),
+                kind.label()
+            )),
+        }
+    }
+
+    fn start_test_setup(&mut self) {
+        self.state = AppState::EnteringBonsaiKey;
+        self.status_message = String::from("Choose a prover for the end-to-end test");
+        self.bonsai_api_key = self
+            .credentials
+            .as_ref()
+            .and_then(|c| c.get(CredentialKind::BonsaiApiKey))
+            .map(|c| c.secret.clone())
+            .unwrap_or_default();
+    }
+
+    fn open_dashboard(&mut self, from_welcome: bool) {
+        let selected = self.dashboard.as_ref().map_or(0, |d| d.selected);
+        let mut dashboard = Dashboard::load(from_welcome, self.offline);
+        dashboard.selected = selected.min(dashboard.rows.len().saturating_sub(1));
+        self.status_message = format!("{} known project(s)", dashboard.rows.len());
+        self.dashboard = Some(dashboard);
+        self.state = AppState::Dashboard;
+    }
+
+    /// Runs the static health checks, then `cargo check`, whose output is streamed.
+    fn run_health_check(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let root = self.project_root();
+        self.health = health::check(&root);
+
+        let mut command = Command::new("cargo");
+        command
+            .arg("check")
+            // Guest builds are slow and `cargo check` doesn't need the guest binaries
+            .env("RISC0_SKIP_BUILD", "1")
+            .envs(self.cargo_offline_env())
+            .current_dir(&root);
+        let build = match self.run_command(&mut command, "Running cargo check...", terminal) {
+            Ok(()) => health::Finding::ok("cargo check", "Passes"),
+            Err(e) => health::Finding::problem(
+                "cargo check",
+                e.to_string(),
+                "Fix the errors shown in the output pane",
+            ),
+        };
+        self.health.push(build);
+
+        let problems = self.health.iter().filter(|f| !f.passed()).count();
+        self.status_message = if problems == 0 {
+            String::from("✓ Project is healthy")
+        } else {
+            format!("✗ {} problem(s) found", problems)
+        };
+        Ok(())
+    }
+
+    /// Measures the contracts with `forge build --sizes` and the gas of their tests with
+    /// `forge test`, against the code size limits and the chain's block gas limit.
+    fn run_contract_checks(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let root = self.project_root();
+        self.contract_report = None;
+
+        let first_line = self.command_output.len();
+        let mut command = Command::new("forge");
+        command.args(["build", "--sizes"]).current_dir(&root);
+        self.run_command(&mut command, "Measuring contract sizes...", terminal)?;
+        let lines: Vec<String> = self.command_output[first_line..]
+            .iter()
+            .map(|line| line.text.clone())
+            .collect();
+        let sizes = contract_checks::sizes(&lines);
+        if sizes.is_empty() {
+            self.add_output(String::from("forge build --sizes listed no contracts"));
+        }
+
+        let rpc_url = self.rpc_url();
+        let (block_gas_limit, limit_source) =
+            match Chain::connect(&rpc_url).and_then(|chain| chain.block_gas_limit()) {
+                Ok(limit) => (limit, format!("{} at {}", self.chain().label(), rpc_url)),
+                Err(_) => (
+                    contract_checks::DEFAULT_BLOCK_GAS_LIMIT,
+                    format!("Ethereum's; {} didn't answer", rpc_url),
+                ),
+            };
+
+        let first_line = self.command_output.len();
+        let mut command = Command::new("forge");
+        command
+            .args(["test", "-vv"])
+            .envs(self.chain_env())
+            .current_dir(&root);
+        let tested = self.run_command(&mut command, "Measuring test gas...", terminal);
+        let lines: Vec<String> = self.command_output[first_line..]
+            .iter()
+            .map(|line| line.text.clone())
+            .collect();
+        let tests = contract_checks::test_gas(&lines);
+        if let Err(e) = tested {
+            // Failing tests still report their gas
+            if tests.is_empty() {
+                return Err(e);
+            }
+            self.add_output(format!("Some tests failed: {}", e));
+        }
+
+        let report = ContractReport {
+            sizes: contract_checks::size_findings(&sizes),
+            gas: contract_checks::gas_findings(&tests, block_gas_limit, 5),
+            block_gas_limit,
+            limit_source,
+        };
+        let over = report.count(contract_checks::Verdict::Over);
+        let near = report.count(contract_checks::Verdict::Near);
+        self.status_message = match (over, near) {
+            (0, 0) => String::from("✓ Every contract fits and every test fits in a block"),
+            (0, near) => format!("⚠ {} close to a limit", near),
+            (over, _) => format!("✗ {} over a limit", over),
+        };
+        self.contract_report = Some(report);
+        Ok(())
+    }
+
+    /// Runs the builds pending changes call for, if they've settled.
+    fn run_watch_builds(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let Some(session) = &mut self.watch else {
+            return Ok(());
+        };
+        let builds = session.watcher.due();
+        if builds.is_empty() {
+            return Ok(());
+        }
+        let changed = session
+            .watcher
+            .last_change
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        self.add_output(format!("━━ {} changed, rebuilding ━━", changed));
+
+        let root = self.project_root();
+        let started = std::time::Instant::now();
+        let mut failure = None;
+        for build in &builds {
+            let mut command = match build {
+                watch::Build::Cargo => {
+                    let mut command = Command::new("cargo");
+                    command.arg("build").envs(self.cargo_offline_env());
+                    command
+                }
+                watch::Build::Forge => {
+                    let mut command = Command::new("forge");
+                    command
+                        .arg("build")
+                        .args(self.offline.then_some("--offline"));
+                    command
+                }
+            };
+            command.current_dir(&root);
+            let description = format!("Running {}...", build.label());
+            let result = match build {
+                watch::Build::Cargo => self.run_cargo_build(&mut command, &description, terminal),
+                watch::Build::Forge => self.run_command(&mut command, &description, terminal),
+            };
+            if let Err(e) = result {
+                failure = Some(format!("{} failed: {}", build.label(), e));
+                break;
+            }
+        }
+
+        let at = chrono::Local::now().format("%H:%M:%S").to_string();
+        let header = match &failure {
+            None => format!(
+                "━━ ✓ {} passed in {}s ━━",
+                builds
+                    .iter()
+                    .map(|b| b.label())
+                    .collect::<Vec<_>>()
+                    .join(" and "),
+                started.elapsed().as_secs()
+            ),
+            Some(failure) => format!("━━ ✗ {} ━━", failure),
+        };
+        self.add_output(header);
+        self.status_message = String::from("Watching for changes to Rust and Solidity sources");
+        if let Some(session) = &mut self.watch {
+            session.rebuilds += 1;
+            session.last = Some((failure.is_none(), at));
+        }
+        Ok(())
+    }
+
+    /// Builds once, then runs the end-to-end script the chosen number of times per backend
+    /// against a local chain, recording wall time and cycle counts.
+    fn run_benchmark(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let root = self.project_root();
+        let backends = self.benchmark.selected();
+        let runs = self.benchmark.runs;
+        platform::require_foundry(true)?;
+        let bonsai_api_key = self.bonsai_key().unwrap_or_default();
+        redact::register(&bonsai_api_key);
+        self.benchmark.results.clear();
+
+        self.run_cargo_build(
+            Command::new("cargo")
+                .arg("build")
+                .envs(self.cargo_offline_env())
+                .current_dir(&root),
+            "Building project...",
+            terminal,
+        )?;
+        self.run_command(
+            Command::new("forge")
+                .arg("build")
+                .args(self.offline.then_some("--offline"))
+                .current_dir(&root),
+            "Compiling Solidity contracts...",
+            terminal,
+        )?;
+        platform::make_executable(&root.join("e2e-test.sh"))?;
+
+        // Always a local chain: the benchmark is about proving, and it deploys every run
+        let rpc_url = dev_keys::LOCAL_RPC_URL;
+        let anvil = self.start_local_anvil()?;
+
+        let result = (|| {
+            for &backend in &backends {
+                for run in 1..=runs {
+                    self.benchmark.current = Some((backend, run));
+                    let first_line = self.command_output.len();
+                    let started = std::time::Instant::now();
+                    let mut script = platform::bash_script("e2e-test.sh")?;
+                    script
+                        .env("ETH_RPC_URL", rpc_url)
+                        .env("ETH_WALLET_ADDRESS", dev_keys::ANVIL_ADDRESS)
+                        .env("ETH_WALLET_PRIVATE_KEY", dev_keys::ANVIL_PRIVATE_KEYS[0])
+                        .envs(backend.vars(&bonsai_api_key))
+                        .envs(self.cargo_offline_env())
+                        .current_dir(&root);
+                    let outcome = self.run_command(
+                        &mut script,
+                        &format!("{}: run {} of {}...", backend.label(), run, runs),
+                        terminal,
+                    );
+                    let lines: Vec<String> = self.command_output[first_line..]
+                        .iter()
+                        .map(|line| line.text.clone())
+                        .collect();
+                    let (cycles, user_cycles) = benchmark::cycles(&lines);
+                    self.benchmark.results.push(benchmark::Run {
+                        backend,
+                        wall: started.elapsed(),
+                        passed: outcome.is_ok(),
+                        cycles,
+                        user_cycles,
+                    });
+                    if let Err(e) = outcome {
+                        if signals::interrupted() {
+                            return Err(e);
+                        }
+                        // Later runs would fail the same way; move on to the next backend
+                        self.add_output(format!("✗ {} failed: {}", backend.label(), e));
+                        break;
+                    }
+                }
+            }
+            Ok(())
+        })();
+
+        if let Some(pid) = anvil {
+            supervisor::terminate(pid);
+        }
+        result
+    }
+
+    /// Starts anvil at the default local RPC unless something already answers there. Returns
+    /// the PID of the one started, for the caller to stop.
+    fn start_local_anvil(&self) -> Result<Option<u32>> {
+        if Chain::connect(dev_keys::LOCAL_RPC_URL)
+            .and_then(|chain| chain.status())
+            .is_ok()
+        {
+            return Ok(None);
+        }
+        let (stdout, stderr) = self.anvil_log();
+        let pid = supervisor::spawn_background(
+            "anvil",
+            Command::new("anvil")
+                .current_dir(self.project_root())
+                .stdout(stdout)
+                .stderr(stderr),
+        )?;
+        std::thread::sleep(std::time::Duration::from_secs(2));
+        Ok(Some(pid))
+    }
+
+    /// Runs the next pending entry of the queue. Returns whether the queue should go on.
+    fn run_queue_entry(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<bool> {
+        let Some(index) = self.queue.next_pending() else {
+            return Ok(false);
+        };
+        let action = self.queue.entries[index].action;
+        self.queue.entries[index].status = TaskStatus::Running;
+        if self.artifacts.is_none() {
+            self.start_artifacts();
+        }
+        let started = std::time::Instant::now();
+        let result = self.run_action(action, terminal);
+        let report = self
+            .test_report
+            .get_or_insert_with(|| junit::Suite::new("queue"));
+        match &result {
+            Ok(()) => report.pass(action.label(), started.elapsed()),
+            Err(e) => report.fail(action.label(), started.elapsed(), e),
+        }
+        let entry = &mut self.queue.entries[index];
+        entry.took = Some(started.elapsed());
+        entry.status = if result.is_ok() {
+            TaskStatus::Done
+        } else {
+            TaskStatus::Failed
+        };
+        result?;
+        Ok(self.queue.next_pending().is_some())
+    }
+
+    fn run_action(
+        &mut self,
+        action: queue::Action,
+        terminal: &mut Terminal<impl Backend>,
+    ) -> Result<()> {
+        let root = self.project_root();
+        let dev_mode = benchmark::Backend::Dev.vars("");
+        match action {
+            queue::Action::CargoBuild => self.run_cargo_build(
+                Command::new("cargo")
+                    .arg("build")
+                    .envs(self.cargo_offline_env())
+                    .current_dir(&root),
+                "Building project...",
+                terminal,
+            ),
+            queue::Action::ForgeBuild => self.run_command(
+                Command::new("forge")
+                    .arg("build")
+                    .args(self.offline.then_some("--offline"))
+                    .current_dir(&root),
+                "Compiling Solidity contracts...",
+                terminal,
+            ),
+            queue::Action::EndToEndDev => {
+                platform::require_foundry(true)?;
+                platform::make_executable(&root.join("e2e-test.sh"))?;
+                let anvil = self.start_local_anvil()?;
+                let mut script = platform::bash_script("e2e-test.sh")?;
+                script
+                    .env("ETH_RPC_URL", dev_keys::LOCAL_RPC_URL)
+                    .env("ETH_WALLET_ADDRESS", dev_keys::ANVIL_ADDRESS)
+                    .env("ETH_WALLET_PRIVATE_KEY", dev_keys::ANVIL_PRIVATE_KEYS[0])
+                    .envs(dev_mode)
+                    .envs(self.cargo_offline_env())
+                    .current_dir(&root);
+                let result = self.run_command(
+                    &mut script,
+                    "Running end-to-end test in dev mode...",
+                    terminal,
+                );
+                if let Some(pid) = anvil {
+                    supervisor::terminate(pid);
+                }
+                result
+            }
+            queue::Action::ForgeTest => self.run_command(
+                Command::new("forge")
+                    .arg("test")
+                    .args(self.offline.then_some("--offline"))
+                    .current_dir(&root),
+                "Running forge tests...",
+                terminal,
+            ),
+            queue::Action::CargoTest => self.run_command(
+                Command::new("cargo")
+                    .arg("test")
+                    .envs(dev_mode)
+                    .envs(self.cargo_offline_env())
+                    .current_dir(&root),
+                "Running cargo tests in dev mode...",
+                terminal,
+            ),
+        }
+    }
+
+    /// Runs clippy on every target. Its diagnostics are picked out of the output as they come
+    /// in; a failure is only an error if clippy didn't get as far as reporting any.
+    fn run_lint(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        self.lint = Some(LintView {
+            diagnostics: Vec::new(),
+            selected: 0,
+        });
+        let mut command = Command::new("cargo");
+        command
+            .args(["clippy", "--all-targets", "--message-format=json"])
+            // The guests don't need building to lint the host
+            .env("RISC0_SKIP_BUILD", "1")
+            .envs(self.cargo_offline_env())
+            .current_dir(self.project_root());
+        let result = self.run_command(&mut command, "Running cargo clippy...", terminal);
+
+        let Some(view) = self.lint.as_mut() else {
+            return result;
+        };
+        view.diagnostics
+            .sort_by(|(a, _), (b, _)| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        match result {
+            Err(e) if view.count(lint::Level::Error) == 0 => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Runs each formatter over the project, in check mode reporting instead of changing files.
+    fn run_format(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<FormatReport> {
+        let root = self.project_root();
+        let check = self.format_check;
+        let mut report = FormatReport {
+            check,
+            files: Vec::new(),
+            errors: Vec::new(),
+        };
+        for &tool in formatting::Tool::ALL {
+            let before = formatting::Snapshot::take(&root, tool);
+            let first_line = self.command_output.len();
+            let result = self.run_command(
+                tool.command(check).current_dir(&root),
+                &format!("Running {}...", tool.label()),
+                terminal,
+            );
+            let files = if check {
+                let lines: Vec<String> = self.command_output[first_line..]
+                    .iter()
+                    .map(|line| line.text.clone())
+                    .collect();
+                formatting::violations(&root, tool, &lines)
+            } else {
+                before.changed(&formatting::Snapshot::take(&root, tool))
+            };
+            if let Err(e) = result {
+                if signals::interrupted() {
+                    return Err(e);
+                }
+                // Check mode fails whenever something needs formatting; that's the report
+                if !check || files.is_empty() {
+                    report.errors.push(format!("{}: {}", tool.label(), e));
+                }
+            }
+            report
+                .files
+                .extend(files.into_iter().map(|file| (tool, file)));
+        }
+        Ok(report)
+    }
+
+    /// Re-reads the generated image ID and ELF libraries.
+    fn refresh_guest_images(&mut self) {
+        self.guest_images = guest_image::read(&self.project_root());
+        self.status_message = if self.guest_images.is_empty() {
+            String::from("No ImageID.sol yet: build the project first (cargo build)")
+        } else {
+            format!("{} guest(s) built", self.guest_images.len())
+        };
+    }
+
+    /// Re-reads the ABIs of the compiled contracts.
+    fn refresh_abi(&mut self) {
+        match abi::load(&self.project_root()) {
+            Ok(contracts) => {
+                let entries: usize = contracts.iter().map(|c| c.entries.len()).sum();
+                self.status_message = if contracts.is_empty() {
+                    String::from("No compiled contracts in out/: run forge build first")
+                } else {
+                    format!("{} contract(s), {} entries", contracts.len(), entries)
+                };
+                self.abi_selected = self.abi_selected.min(entries.saturating_sub(1));
+                self.abi_contracts = contracts;
+            }
+            Err(e) => {
+                self.abi_contracts.clear();
+                self.status_message = e.to_string();
+            }
+        }
+    }
+
+    fn selected_abi_entry(&self) -> Option<&abi::Entry> {
+        self.abi_contracts
+            .iter()
+            .flat_map(|contract| &contract.entries)
+            .nth(self.abi_selected)
+    }
+
+    fn start_scaffolding(&mut self, kind: ScaffoldKind) {
+        self.scaffold_name.clear();
+        self.state = AppState::Scaffolding(kind);
+        self.status_message = format!("Name the new {}", kind.label());
+    }
+
+    /// Checks out the template the project was made from, at the branch it's pinned to, and
+    /// compares the two.
+    fn fetch_template(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let root = self.project_root();
+        let (template, repository) = match ProjectFile::load(&root)? {
+            Some(metadata) => (metadata.project.template, metadata.project.repository),
+            None => (
+                self.summary.template.clone(),
+                self.summary.repository.clone(),
+            ),
+        };
+        let branch = projects::pinned_branch(&root).unwrap_or_else(|| self.summary.branch.clone());
+        let example = templates::path_of(&template);
+
+        let checkout = paths::drift_dir().join(&self.project_name);
+        if checkout.exists() {
+            fs::remove_dir_all(&checkout)?;
+        }
+        fs::create_dir_all(paths::drift_dir())?;
+        let source = template_cache::clone_source(
+            &template_cache::template_entry(&repository),
+            &repository,
+            self.offline,
+        )?;
+        let mut clone = http::git();
+        clone
+            .args(["clone", "--no-checkout", "--single-branch", "-b", &branch])
+            .arg(&source)
+            .arg(&checkout);
+        if !self.offline {
+            clone.args(["--depth", "1"]);
+        }
+        self.run_command(
+            &mut clone,
+            &format!("Fetching {} of the template...", branch),
+            terminal,
+        )?;
+        let example = example.trim_matches('/');
+        if !matches!(example, "" | ".") {
+            self.run_command(
+                http::git()
+                    .args(["sparse-checkout", "set", example])
+                    .current_dir(&checkout),
+                "Setting up sparse checkout...",
+                terminal,
+            )?;
+        }
+        self.run_command(
+            http::git().arg("checkout").current_dir(&checkout),
+            "Checking out files...",
+            terminal,
+        )?;
+
+        let template_root = checkout.join(example);
+        let files = drift::compare(&root, &template_root)?;
+        self.status_message = if files.is_empty() {
+            format!("✓ Up to date with {} of the template", branch)
+        } else {
+            format!(
+                "{} file(s) differ from {} of the template",
+                files.len(),
+                branch
+            )
+        };
+        self.drift = Some(DriftView {
+            template_root,
+            marked: vec![false; files.len()],
+            files,
+            selected: 0,
+        });
+        Ok(())
+    }
+
+    /// Upgrades a project by running our own `upgrade` subcommand, so its output lands in the
+    /// output pane.
+    fn run_project_upgrade(
+        &mut self,
+        path: &std::path::Path,
+        terminal: &mut Terminal<impl Backend>,
+    ) -> Result<()> {
+        let mut command = Command::new(std::env::current_exe()?);
+        command.arg("upgrade").arg(path).args(["--yes", "--ci"]);
+        self.run_command(
+            &mut command,
+            &format!("Upgrading {}...", path.display()),
+            terminal,
+        )
+    }
+
+    /// Skips creation and goes straight to the toolbox of an existing project.
+    fn open_project(&mut self, project: &RecentProject) {
+        if let (Some(parent), Some(name)) = (project.path.parent(), project.path.file_name()) {
+            self.base_dir = parent.to_path_buf();
+            self.project_name = name.to_string_lossy().to_string();
+        }
+        self.summary.project_path = project.path.clone();
+        self.selected_recent = None;
+        self.state = AppState::TestMenu;
+        self.status_message = format!("Opened '{}'. Select test to run:", project.name());
+        self.load_project_options();
+    }
+
+    /// Picks up the dev mode toggle and the chain saved in the current project.
+    fn load_project_options(&mut self) {
+        let options = ProjectFile::load(&self.project_root())
+            .ok()
+            .flatten()
+            .map(|file| file.options)
+            .unwrap_or_default();
+        supervisor::set_dev_mode(options.dev_mode);
+        self.project_chain = options.chain;
+    }
+
+    /// Turns dev mode on or off and saves it in the project, if it has a project file.
+    fn set_dev_mode(&mut self, enabled: bool) {
+        supervisor::set_dev_mode(enabled);
+        let root = self.project_root();
+        let saved = match ProjectFile::load(&root) {
+            Ok(Some(mut file)) => {
+                file.options.dev_mode = enabled;
+                file.save(&root)
+            }
+            Ok(None) => Err(color_eyre::eyre::eyre!(
+                "the project has no {}",
+                project_file::FILE_NAME
+            )),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = saved {
+            self.status_message = format!(
+                "✓ Settings saved; dev mode applies to this session only ({})",
+                e
+            );
+        }
+    }
+
+    fn save_credentials(&mut self) {
+        let Some(credentials) = &self.credentials else {
+            return;
+        };
+        match credentials.save(&self.credentials_passphrase) {
+            Ok(()) => self.status_message = String::from("✓ Credentials saved"),
+            Err(e) => self.status_message = format!("✗ Failed to save credentials: {}", e),
+        }
+    }
+
+    /// The stored key signers start with: on a testnet the newest generated test wallet, if
+    /// any, else the deployment key. A test wallet is never offered on mainnet.
+    fn stored_key(&self) -> Option<String> {
+        let credentials = self.credentials.as_ref()?;
+        self.chain()
+            .is_testnet()
+            .then(|| credentials.get(CredentialKind::TestWallet))
+            .flatten()
+            .or_else(|| credentials.get(CredentialKind::DeploymentKey))
+            .map(|credential| credential.secret.clone())
+    }
+
+    /// Makes a fresh keypair and stores it as the preferred test wallet, for testnet flows to
+    /// sign with.
+    fn generate_test_wallet(&mut self) {
+        let Some(credentials) = self.credentials.as_mut() else {
+            return;
+        };
+        let wallet = TestWallet::generate();
+        let name = wallet.credential_name();
+        credentials.set(CredentialKind::TestWallet, &name, &wallet.private_key);
+        credentials.prefer(CredentialKind::TestWallet, &name);
+        self.add_output(format!("Generated test wallet {}", wallet.address));
+        self.test_wallet = Some(wallet);
+        self.state = AppState::TestWallet;
+        self.save_credentials();
+    }
+
+    /// Summary of the scaffolded project, available once installation has succeeded.
+    pub fn summary(&self) -> Option<&ProjectSummary> {
+        self.project_created.then_some(&self.summary)
+    }
+
+    /// The last status message, e.g. to print after the terminal has been restored.
+    pub fn status_message(&self) -> String {
+        redact::redact(&self.status_message)
+    }
+
+    /// What was done and what was left behind when a signal or Ctrl+C stopped the app.
+    pub fn interrupted_report(&self) -> Vec<String> {
+        let mut lines = vec![match &self.state {
+            AppState::Installing(step) => format!("Interrupted during '{}'.", step.label()),
+            AppState::Testing(_) => String::from("Interrupted during the end-to-end test."),
+            _ => String::from("Interrupted."),
+        }];
+        if !self.summary.steps.is_empty() {
+            let steps = self
+                .summary
+                .steps
+                .iter()
+                .map(|s| format!("{} ({:.1}s)", s.step, s.seconds))
+                .collect::<Vec<_>>();
+            lines.push(format!("Completed steps: {}", steps.join(", ")));
+        }
+        if matches!(self.state, AppState::Installing(_)) && self.project_root().exists() {
+            lines.push(format!(
+                "Partially created project left at {}",
+                self.project_root().display()
+            ));
+        }
+        lines
+    }
+
+    /// Maps the state the user quit from to a process outcome.
+    fn exit_outcome(&self) -> Outcome {
+        match self.state {
+            AppState::CheckingDependencies | AppState::InstallingCargoRisczero => {
+                Outcome::DependencyMissing
+            }
+            AppState::EnteringProjectName
+            | AppState::ChoosingExamples
+            | AppState::SelectingTemplate
+            | AppState::EnteringTemplateRepo
+            | AppState::ConfirmOverwrite => Outcome::UserAbort,
+            AppState::Installing(_) | AppState::ChoosingVersions => Outcome::InstallFailed,
+            AppState::Batch
+                if self
+                    .batch
+                    .as_ref()
+                    .is_some_and(|batch| batch.count(TaskStatus::Failed) > 0) =>
+            {
+                Outcome::InstallFailed
+            }
+            _ if self.last_test_failed => Outcome::TestFailed,
+            _ => Outcome::Success,
+        }
+    }
+
+    /// Starts updating in the background; [`App::poll_self_update`] follows it.
+    fn run_self_update(&mut self) {
+        if self.self_update.is_some() {
+            self.status_message = String::from("An update is already running");
+            return;
+        }
+        self.status_message = String::from("Checking for updates...");
+        self.self_update = Some(self_update::spawn_update());
+    }
+
+    /// Takes in the running self-update's log and progress. Returns whether anything changed.
+    fn poll_self_update(&mut self) -> bool {
+        let mut changed = false;
+        while let Some(event) = self.self_update.as_ref().map(|rx| rx.try_recv()) {
+            let event = match event {
+                Ok(event) => event,
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self_update::UpdateEvent::Done(Err(String::from("the update stopped")))
+                }
+            };
+            changed = true;
+            match event {
+                self_update::UpdateEvent::Log(line) => self.add_output(line),
+                self_update::UpdateEvent::Progress(done, total) => {
+                    self.status_message = match total {
+                        Some(total) if total > 0 => format!(
+                            "Downloading... {} of {} ({}%)",
+                            resources::format_bytes(done),
+                            resources::format_bytes(total),
+                            done * 100 / total
+                        ),
+                        _ => format!("Downloading... {}", resources::format_bytes(done)),
+                    };
+                }
+                self_update::UpdateEvent::Done(result) => {
+                    self.self_update = None;
+                    self.status_message = match result {
+                        Ok(true) => String::from("✓ steel-tui updated, restart to use it"),
+                        Ok(false) => String::from("✓ steel-tui is up to date"),
+                        Err(e) => {
+                            self.add_output(format!("Error: {}", e));
+                            String::from("✗ Update failed")
+                        }
+                    };
+                }
+            }
+        }
+        changed
+    }
+
+    fn cleanup_test(&mut self) {
+        if let Some(test_env) = &mut self.test_env {
+            // Kill the anvil instance we started, leaving any the user runs alone
+            if let Some(pid) = test_env.anvil_pid.take() {
+                supervisor::terminate(pid);
+            }
+        }
+        self.chain_monitor = None;
+    }
+
+    fn handle_test_step(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        if let Some(test_env) = &mut self.test_env {
+            match self.state {
+                AppState::Testing(E2ETestStep::PreparingEnvironment) => {
+                    platform::require_foundry(dev_keys::is_local_rpc(&test_env.eth_rpc_url))?;
+                    redact::register(&test_env.eth_wallet_private_key);
+                    redact::register(&test_env.bonsai_api_key);
+
+                    self.boundless = None;
+                    if test_env.prover == ProverBackend::Boundless {
+                        let rpc_url = test_env.eth_rpc_url.clone();
+                        self.boundless = boundless::Tracker::from_env(&rpc_url);
+                        match &self.boundless {
+                            Some(tracker) => {
+                                let market = tracker.market().to_string();
+                                self.add_output(format!(
+                                    "Watching proof requests on the Boundless market at {}",
+                                    market
+                                ));
+                            }
+                            None => self.add_output(String::from(
+                                "Set BOUNDLESS_MARKET_ADDRESS to follow Boundless proof requests",
+                            )),
+                        }
+                    }
+
+                    self.status_message = String::from("Environment prepared, starting Anvil...");
+                    self.state = AppState::Testing(E2ETestStep::StartingAnvil);
+                }
+                AppState::Testing(E2ETestStep::StartingAnvil)
+                    if !dev_keys::is_local_rpc(&test_env.eth_rpc_url) =>
+                {
+                    // An external node from ETH_RPC_URL; there's nothing to start
+                    let rpc_url = test_env.eth_rpc_url.clone();
+                    let wallet = test_env.eth_wallet_address.clone();
+                    self.add_output(format!("Using RPC endpoint {}", rpc_url));
+                    self.connect_chain(&rpc_url, "node", &wallet)?;
+                    self.state = AppState::Testing(E2ETestStep::RunningTest);
+                }
+                AppState::Testing(E2ETestStep::StartingAnvil) => {
+                    self.status_message = String::from("Starting local Ethereum chain...");
+
+                    let (stdout, stderr) = match &self.artifacts {
+                        Some(run) => run.log("anvil.log"),
+                        None => (std::process::Stdio::null(), std::process::Stdio::null()),
+                    };
+                    let pid = supervisor::spawn_background(
+                        "anvil",
+                        Command::new("anvil")
+                            // Lets the dashboard tell which project this anvil belongs to
+                            .current_dir(self.base_dir.join(&self.project_name))
+                            .stdout(stdout)
+                            .stderr(stderr),
+                    )?;
+                    test_env.anvil_pid = Some(pid);
+
+                    // Wait a moment for anvil to start
+                    std::thread::sleep(std::time::Duration::from_secs(2));
+
+                    if !supervisor::is_running(pid) {
+                        return Err(color_eyre::eyre::eyre!(
+                            "Anvil exited immediately. Is port 8545 already in use?"
+                        ));
+                    }
+
+                    // Verify anvil is answering requests
+                    let rpc_url = test_env.eth_rpc_url.clone();
+                    let wallet = test_env.eth_wallet_address.clone();
+                    if let Err(e) = self.connect_chain(&rpc_url, "anvil", &wallet) {
+                        return Err(color_eyre::eyre::eyre!(
+                            "Failed to start Anvil ({}). Please make sure it's installed and try again.",
+                            e
+                        ));
+                    }
+                    let restored = self.restore_chain_state(&rpc_url);
+                    if let Some(test_env) = &mut self.test_env {
+                        test_env.restored = restored;
+                    }
+                    self.status_message = String::from("✓ Local Ethereum chain started");
+                    self.state = AppState::Testing(E2ETestStep::RunningTest);
+                }
+                AppState::Testing(E2ETestStep::RunningTest) => {
+                    self.status_message = String::from("Running end-to-end test...");
+
+                    let root = self.project_root();
+                    self.add_output(format!("Running in project directory: {}", root.display()));
+
+                    // First run cargo build to generate the ImageID.sol contract
+                    self.run_cargo_build(
+                        Command::new("cargo")
+                            .arg("build")
+                            .envs(self.cargo_offline_env())
+                            .current_dir(&root),
+                        "Building project to generate contracts...",
+                        terminal,
+                    )?;
+                    self.guest_images = guest_image::read(&root);
+                    let described: Vec<String> =
+                        self.guest_images.iter().map(GuestImage::describe).collect();
+                    for line in described {
+                        self.add_output(format!("✓ {}", line));
+                    }
+
+                    // Run forge build to compile Solidity contracts
+                    self.run_command(
+                        Command::new("forge")
+                            .arg("build")
+                            .args(self.offline.then_some("--offline"))
+                            .current_dir(&root),
+                        "Compiling Solidity contracts...",
+                        terminal,
+                    )?;
+
+                    // Make the test script executable
+                    self.add_output("Making test script executable...".to_string());
+                    platform::make_executable(&root.join("e2e-test.sh"))?;
+
+                    // Then run the e2e test script. Secrets go only into its environment,
+                    // never into ours, so nothing else we spawn inherits them.
+                    let mut script = platform::bash_script("e2e-test.sh")?;
+                    script
+                        .envs(self.test_env.iter().flat_map(TestEnvironment::vars))
+                        .envs(self.test_env.iter().flat_map(|env| env.restored.clone()))
+                        .envs(self.chain_env())
+                        .envs(self.cargo_offline_env())
+                        .current_dir(&root);
+                    if let Some(preset) = self.log_preset.take() {
+                        self.add_output(format!("Logging: {}", preset.label()));
+                        script.envs(preset.vars());
+                    }
+                    self.run_command(&mut script, "Running end-to-end test script...", terminal)?;
+
+                    self.status_message = String::from("✓ End-to-end test completed successfully");
+                    // Only an anvil we started holds nothing but what the test deployed
+                    if let Some(rpc_url) = self
+                        .test_env
+                        .as_ref()
+                        .filter(|env| env.anvil_pid.is_some())
+                        .map(|env| env.eth_rpc_url.clone())
+                    {
+                        self.save_chain_state(&rpc_url);
+                    }
+                    // Before cleanup stops the anvil that holds them
+                    if let Some(rpc_url) = self.test_env.as_ref().map(|env| env.eth_rpc_url.clone())
+                    {
+                        self.capture_events(&rpc_url);
+                    }
+                    self.last_test_failed = false;
+                    let _ = projects::record_test(&root, true);
+                    self.state = AppState::Testing(E2ETestStep::Cleanup);
+                }
+                AppState::Testing(E2ETestStep::Cleanup) => {
+                    self.status_message = String::from("Cleaning up...");
+                    self.cleanup_test();
+                    self.status_message = String::from("✓ Cleanup completed");
+                    self.state = AppState::TestMenu;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Starts creating the named project, from the Steel template or a cargo-generate one.
+    fn start_install(&mut self) {
+        self.state = match self.cargo_template {
+            Some(_) => AppState::FetchingCargoTemplate,
+            None => AppState::Installing(InstallStep::CloningRepo),
+        };
+        self.status_message = format!("Installing project '{}'...", self.project_name);
+    }
+
+    /// Clones the cargo-generate template, unless it's a local directory, and reads what it
+    /// asks for.
+    fn fetch_cargo_template(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let Some(source) = self.cargo_template.as_ref().map(|t| t.source.clone()) else {
+            return Ok(());
+        };
+        let dir = if Path::new(&source).is_dir() {
+            PathBuf::from(&source)
+        } else if self.offline {
+            return Err(color_eyre::eyre::eyre!(
+                "{} is fetched with git, which needs the network",
+                source
+            ));
+        } else {
+            let name = source
+                .trim_end_matches('/')
+                .trim_end_matches(".git")
+                .rsplit(['/', ':'])
+                .next()
+                .unwrap_or("template")
+                .to_string();
+            let checkout = paths::cargo_template_dir().join(name);
+            if checkout.exists() {
+                fs::remove_dir_all(&checkout)?;
+            }
+            fs::create_dir_all(paths::cargo_template_dir())?;
+            self.run_command(
+                http::git()
+                    .args(["clone", "--depth", "1", "--progress"])
+                    .arg(&source)
+                    .arg(&checkout),
+                &format!("Fetching the template from {}...", source),
+                terminal,
+            )?;
+            checkout
+        };
+        let template = cargo_generate::Template::load(&dir)?;
+        let prompts = !template.placeholders.is_empty();
+        self.status_message = if prompts {
+            format!(
+                "Fill in the values the {} template asks for:",
+                template.name
+            )
+        } else {
+            format!("Generating '{}'...", self.project_name)
+        };
+        if let Some(cargo_template) = self.cargo_template.as_mut() {
+            cargo_template.values = template
+                .placeholders
+                .iter()
+                .map(|placeholder| placeholder.default.clone())
+                .collect();
+            cargo_template.field = 0;
+            cargo_template.template = Some(template);
+        }
+        self.state = if prompts {
+            AppState::TemplatePrompts
+        } else {
+            AppState::GeneratingFromTemplate
+        };
+        Ok(())
+    }
+
+    /// Renders the cargo-generate template into the project directory and gives the project a
+    /// repository, as cargo-generate would.
+    fn generate_from_template(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let Some(CargoTemplate {
+            source,
+            template: Some(template),
+            values,
+            ..
+        }) = &self.cargo_template
+        else {
+            return Ok(());
+        };
+        let mut vars = cargo_generate::builtins(&self.project_name);
+        for (placeholder, value) in template.placeholders.iter().zip(values) {
+            vars.insert(placeholder.name.clone(), value.clone());
+        }
+        let root = self.project_root();
+        let removing = root.exists();
+        if removing {
+            self.check_replaceable(&root)?;
+            fs::remove_dir_all(&root)?;
+        }
+        let report = template.generate(&root, &vars)?;
+        let (name, source) = (template.name.clone(), source.clone());
+        if removing {
+            self.add_output(format!(
+                "Replaced existing directory '{}'",
+                self.project_name
+            ));
+        }
+        for line in report {
+            self.add_output(line);
+        }
+        match self.enclosing_repo.clone().filter(|_| self.in_repo) {
+            Some(repo) => self.add_output(format!(
+                "Adding the project to the git repository at {}",
+                repo.display()
+            )),
+            None => self.run_command(
+                http::git().args(["init"]).current_dir(&root),
+                "Initializing git repository...",
+                terminal,
+            )?,
+        }
+
+        self.summary.project_path = platform::canonicalize(&root)?;
+        self.summary.template = name;
+        self.summary.repository = source;
+        self.summary.branch = String::new();
+        self.project_created = true;
+        let project = RecentProject {
+            path: self.summary.project_path.clone(),
+            template: self.summary.template.clone(),
+            branch: self.summary.branch.clone(),
+            created: chrono::Utc::now().to_rfc3339(),
+            last_test: None,
+        };
+        if let Err(e) = projects::record(project) {
+            self.add_output(format!("⚠ Could not record the project: {}", e));
+        }
+        self.state = AppState::Success;
+        self.status_message = format!("✓ Project '{}' created successfully!", self.project_name);
+        self.emit(&self.status_message);
+        Ok(())
+    }
+
+    /// The welcome screen, where a project is named or a recent one picked.
+    fn start_new_project(&mut self) {
+        self.state = AppState::EnteringProjectName;
+        self.status_message = String::from("Enter project name (press Enter when done):");
+        self.recent_projects = projects::load();
+    }
+
+    /// Reopens the last session's project with its prover settings and output, on the screen
+    /// it was on.
+    fn restore_session(&mut self) {
+        let Some(previous) = self.previous_session.take() else {
+            return;
+        };
+        session::clear();
+        let Some(project) = projects::detect(&previous.project) else {
+            self.start_new_project();
+            self.status_message = format!(
+                "✗ {} is no longer a Steel project. Enter project name:",
+                previous.project.display()
+            );
+            return;
+        };
+        self.open_project(&project);
+        self.prover = previous.prover;
+        self.command_output = previous
+            .output
+            .into_iter()
+            .map(OutputLine::restored)
+            .collect();
+        self.status_message = format!("✓ Restored '{}'. Select test to run:", project.name());
+        if let Some(screen) = previous.screen {
+            self.selected_menu_item = ToolboxItem::ALL
+                .iter()
+                .position(|item| *item == screen)
+                .unwrap_or(0);
+            // Only screens that just show something are saved, so this can't start anything
+            let _ = self.open_tool(screen);
+        }
+    }
+
+    /// What to restore next time, if a project is open.
+    pub fn session(&self) -> Option<Session> {
+        if matches!(
+            self.state,
+            AppState::CheckingDependencies
+                | AppState::InstallingCargoRisczero
+                | AppState::RestoringSession
+                | AppState::EnteringProjectName
+                | AppState::ConfirmOverwrite
+                | AppState::Installing(_)
+        ) || !self.project_root().is_dir()
+        {
+            return None;
+        }
+        let screen = match self.state {
+            AppState::Queue | AppState::RunningQueue => Some(ToolboxItem::Queue),
+            AppState::Benchmark | AppState::Benchmarking => Some(ToolboxItem::Benchmark),
+            AppState::TestHistory => Some(ToolboxItem::History),
+            AppState::GuestImages => Some(ToolboxItem::GuestImages),
+            AppState::Abi => Some(ToolboxItem::Abi),
+            AppState::Format | AppState::Formatting => Some(ToolboxItem::Format),
+            AppState::Scripts => Some(ToolboxItem::Scripts),
+            AppState::ChoosingChain => Some(ToolboxItem::Chain),
+            AppState::Settings => Some(ToolboxItem::Settings),
+            _ => None,
+        };
+        let skip = self
+            .command_output
+            .len()
+            .saturating_sub(session::OUTPUT_TAIL);
+        Some(Session {
+            saved_at: chrono::Utc::now().to_rfc3339(),
+            project: self.project_root(),
+            screen,
+            prover: self.prover,
+            output: self
+                .command_output
+                .iter()
+                .skip(skip)
+                .map(|line| line.text.clone())
+                .collect(),
+        })
+    }
+
+    /// Opens a toolbox entry. Returns whether the app should exit.
+    fn open_tool(&mut self, item: ToolboxItem) -> Result<bool> {
+        match item {
+            ToolboxItem::EndToEndTest | ToolboxItem::Deploy => self.choose_network(item),
+            ToolboxItem::Scripts => self.open_scripts(),
+            ToolboxItem::Health => self.state = AppState::CheckingHealth,
+            ToolboxItem::CheckContracts => self.state = AppState::CheckingContracts,
+            ToolboxItem::TemplateDrift => self.state = AppState::FetchingTemplate,
+            ToolboxItem::AddGuest => self.start_scaffolding(ScaffoldKind::Guest),
+            ToolboxItem::AddContract => self.start_scaffolding(ScaffoldKind::Contract),
+            ToolboxItem::Watch => match watch::Watcher::start(&self.project_root()) {
+                Ok(watcher) => {
+                    self.watch = Some(WatchSession {
+                        watcher,
+                        rebuilds: 0,
+                        last: None,
+                    });
+                    self.state = AppState::Watching;
+                    self.status_message =
+                        String::from("Watching for changes to Rust and Solidity sources");
+                }
+                Err(e) => self.status_message = format!("✗ Could not watch the project: {}", e),
+            },
+            ToolboxItem::Queue => {
+                self.state = AppState::Queue;
+                self.status_message = String::from("Queue actions to run in order");
+            }
+            ToolboxItem::Benchmark => {
+                // Bonsai is only offered when there's a key to prove with
+                let bonsai = self.bonsai_key().is_some() && !self.offline;
+                self.benchmark.backends[2] &= bonsai;
+                self.state = AppState::Benchmark;
+                self.status_message = String::from("Benchmark proving backends");
+            }
+            ToolboxItem::History => {
+                self.history = HistoryView {
+                    runs: artifacts::history(&self.project_root()),
+                    selected: 0,
+                    marked: Vec::new(),
+                };
+                self.state = AppState::TestHistory;
+                self.status_message = String::from("Pick two runs to compare");
+            }
+            ToolboxItem::Chain => {
+                self.chain_selected = ChainPreset::ALL
+                    .iter()
+                    .position(|c| *c == self.chain())
+                    .unwrap_or(0);
+                self.state = AppState::ChoosingChain;
+                self.status_message = String::from("Choose the chain to test and deploy on");
+            }
+            ToolboxItem::AnvilControls => {
+                let rpc_url = self.rpc_url();
+                if !dev_keys::is_local_rpc(&rpc_url) {
+                    self.status_message = format!(
+                        "✗ Anvil controls only work on a local node, not {}",
+                        rpc_url
+                    );
+                } else {
+                    match Chain::connect(&rpc_url).and_then(|chain| {
+                        chain.latest_block()?;
+                        Ok(chain)
+                    }) {
+                        Ok(chain) => {
+                            self.anvil_panel = Some(AnvilPanel::new(chain));
+                            self.state = AppState::AnvilControls;
+                            self.status_message = format!("Controlling anvil at {}", rpc_url);
+                        }
+                        Err(e) => {
+                            self.status_message = format!(
+                                "✗ No anvil answering at {} ({}). Start one first",
+                                rpc_url, e
+                            )
+                        }
+                    }
+                }
+            }
+            ToolboxItem::Lint => {
+                self.state = AppState::Linting;
+                self.status_message = String::from("Running cargo clippy...");
+            }
+            ToolboxItem::Format => {
+                self.state = AppState::Format;
+                self.status_message = String::from("Format the project");
+            }
+            ToolboxItem::InspectReceipt => {
+                self.inspect_form = InspectForm::new(&self.project_root());
+                self.inspect_form.verifier = self.default_verifier();
+                self.state = AppState::InspectingReceipt;
+                self.status_message = String::from("Inspect a saved receipt or journal");
+            }
+            ToolboxItem::BoundlessRequest => {
+                self.refresh_guest_images();
+                self.boundless_form = BoundlessForm::default();
+                if self.boundless.is_none() {
+                    self.boundless = boundless::Tracker::from_env(&self.rpc_url());
+                }
+                self.state = AppState::BoundlessRequest;
+            }
+            ToolboxItem::GuestImages => {
+                self.refresh_guest_images();
+                self.state = AppState::GuestImages;
+            }
+            ToolboxItem::Events => {
+                if self.event_log.is_none() {
+                    let rpc_url = self.rpc_url();
+                    if let Err(e) = self.open_event_log(&rpc_url) {
+                        self.status_message = format!("✗ {}", e);
+                        return Ok(false);
+                    }
+                }
+                self.state = AppState::Events;
+                self.status_message = String::from("Following contract events");
+            }
+            ToolboxItem::Transaction => {
+                let rpc_url = self.rpc_url();
+                match self.open_tx_form(&rpc_url) {
+                    Ok(()) => {
+                        self.state = AppState::TransactionBuilder;
+                        self.status_message = format!("Build a transaction on {}", rpc_url);
+                    }
+                    Err(e) => self.status_message = format!("✗ {}", e),
+                }
+            }
+            ToolboxItem::Abi => {
+                self.abi_selected = 0;
+                self.refresh_abi();
+                self.state = AppState::Abi;
+            }
+            ToolboxItem::BonsaiAccount => match self.bonsai_key() {
+                Some(key) => {
+                    self.refresh_bonsai_panel(&key);
+                    self.state = AppState::BonsaiAccount;
+                    self.status_message = String::from("Bonsai account");
+                }
+                None => {
+                    self.status_message = String::from(
+                        "No Bonsai API key: set BONSAI_API_KEY or add one under Credentials",
+                    );
+                }
+            },
+            ToolboxItem::Projects => self.open_dashboard(false),
+            ToolboxItem::Credentials if self.credentials.is_some() => {
+                self.state = AppState::Credentials;
+                self.status_message = String::from("Credentials");
+            }
+            ToolboxItem::TestWallet if self.credentials.is_some() => self.generate_test_wallet(),
+            ToolboxItem::Credentials | ToolboxItem::TestWallet => {
+                self.wallet_after_unlock = item == ToolboxItem::TestWallet;
+                self.state = AppState::UnlockingCredentials;
+                self.credentials_passphrase.clear();
+                self.status_message = if Credentials::exists() {
+                    String::from("Enter the passphrase for your credentials store")
+                } else {
+                    String::from("Choose a passphrase for a new credentials store")
+                };
+            }
+            ToolboxItem::Settings => {
+                let api_key = self.rpc_api_key();
+                self.settings_form =
+                    SettingsForm::new(&self.settings, &api_key, supervisor::dev_mode());
+                self.state = AppState::Settings;
+                self.status_message = String::from("Settings");
+            }
+            ToolboxItem::NixFlake => {
+                match nix::generate(&self.project_root(), &self.project_name) {
+                    Ok(report) => {
+                        for line in report {
+                            self.add_output(line);
+                        }
+                        self.status_message = format!("✓ Wrote {}", nix::FILE_NAME);
+                    }
+                    Err(e) => self.status_message = format!("✗ {}", e),
+                }
+            }
+            ToolboxItem::SelfUpdate => self.run_self_update(),
+            ToolboxItem::Exit => return Ok(true),
+        }
+        Ok(false)
+    }
+
+    /// Whether the progress panel shows the Boundless requests.
+    fn tracking_boundless(&self) -> bool {
+        matches!(
+            self.state,
+            AppState::Testing(_)
+                | AppState::BoundlessRequest
+                | AppState::SubmittingBoundlessRequest
+        )
+    }
+
+    /// Whether the command palette can open: on screens that wait for input, not while
+    /// something runs or a step asks a question.
+    fn palette_available(&self) -> bool {
+        matches!(
+            self.state,
+            AppState::EnteringProjectName
+                | AppState::Success
+                | AppState::TestMenu
+                | AppState::Health
+                | AppState::ContractChecks
+                | AppState::TemplateDrift
+                | AppState::GuestImages
+                | AppState::Abi
+                | AppState::Events
+                | AppState::Queue
+                | AppState::Benchmark
+                | AppState::TestHistory
+                | AppState::Format
+                | AppState::BoundlessRequest
+                | AppState::Lint
+                | AppState::AnvilControls
+                | AppState::ChoosingChain
+                | AppState::BonsaiAccount
+                | AppState::Dashboard
+                | AppState::Credentials
+                | AppState::TestWallet
+                | AppState::Settings
+        )
+    }
+
+    /// Whether a project is open for the toolbox to work on.
+    fn project_open(&self) -> bool {
+        !matches!(self.state, AppState::EnteringProjectName)
+            && !self.project_name.is_empty()
+            && self.project_root().is_dir()
+    }
+
+    fn run_palette_action(&mut self, action: Action) -> Result<bool> {
+        match action {
+            Action::NewProject => {
+                self.project_name.clear();
+                self.command_output.clear();
+                self.start_new_project();
+            }
+            Action::Tool(item) if self.offline && item.needs_network() => {
+                self.status_message =
+                    String::from("Unavailable offline: this needs to reach the network");
+            }
+            Action::Tool(item) => {
+                if let Some(index) = ToolboxItem::ALL.iter().position(|i| *i == item) {
+                    self.selected_menu_item = index;
+                }
+                return self.open_tool(item);
+            }
+            Action::StartAnvil => {
+                self.status_message = match self.start_local_anvil() {
+                    Ok(Some(pid)) => format!(
+                        "✓ Started anvil (PID {}) at {}",
+                        pid,
+                        dev_keys::LOCAL_RPC_URL
+                    ),
+                    Ok(None) => {
+                        format!("anvil is already answering at {}", dev_keys::LOCAL_RPC_URL)
+                    }
+                    Err(e) => format!("✗ Could not start anvil: {}", e),
+                };
+            }
+            Action::ExportOutput => {
+                let output: Vec<String> = self
+                    .command_output
+                    .iter()
+                    .map(|line| line.text.clone())
+                    .collect();
+                self.status_message = match self.step_logs.write("output", &output) {
+                    Ok(path) => format!("✓ Wrote the output to {}", path.display()),
+                    Err(e) => format!("✗ Could not export the output: {}", e),
+                };
+            }
+        }
+        Ok(false)
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<bool> {
+        if key.kind != KeyEventKind::Press {
+            return Ok(false);
+        }
+        let ctrl_p =
+            key.code == KeyCode::Char('p') && key.modifiers.contains(KeyModifiers::CONTROL);
+        if let Some(palette) = self.palette.as_mut() {
+            match key.code {
+                _ if ctrl_p => self.palette = None,
+                KeyCode::Esc => self.palette = None,
+                KeyCode::Enter => {
+                    let chosen = palette.chosen();
+                    self.palette = None;
+                    if let Some(action) = chosen {
+                        return self.run_palette_action(action);
+                    }
+                }
+                KeyCode::Up => palette.up(),
+                KeyCode::Down => palette.down(),
+                KeyCode::Backspace => palette.pop(),
+                KeyCode::Char(c) => palette.push(c),
+                _ => {}
+            }
+            return Ok(false);
+        }
+        if ctrl_p && self.palette_available() {
+            self.palette = Some(Palette::new(self.project_open()));
+            return Ok(false);
+        }
+
+        match &self.state {
+            AppState::CheckingDependencies if key.code == KeyCode::Esc => return Ok(true),
+            AppState::CheckingDependencies
+                if key.code == KeyCode::Char('i') && self.cargo_risczero_missing =>
+            {
+                self.state = AppState::InstallingCargoRisczero;
+            }
+            AppState::RestoringSession => match key.code {
+                KeyCode::Enter | KeyCode::Char('y') => self.restore_session(),
+                KeyCode::Char('n') => {
+                    self.previous_session = None;
+                    session::clear();
+                    self.start_new_project();
+                }
+                KeyCode::Esc => return Ok(true),
+                _ => {}
+            },
+            AppState::ConfirmOverwrite => match key.code {
+                KeyCode::Enter => {
+                    match self.confirm_menu_item {
+                        0 => {
+                            // Go to testing toolbox
+                            self.state = AppState::TestMenu;
+                            self.status_message = String::from("Select test to run:");
+                            self.command_output.clear();
+                            self.load_project_options();
+                        }
+                        1 => {
+                            // Continue (overwrite)
+                            self.start_install();
+                        }
+                        2 => {
+                            // Exit
+                            return Ok(true);
+                        }
+                        _ => {}
+                    }
+                }
+                KeyCode::Up => {
+                    self.confirm_menu_item = self.confirm_menu_item.saturating_sub(1);
+                }
+                KeyCode::Down => {
+                    self.confirm_menu_item = (self.confirm_menu_item + 1).min(2);
+                }
+                KeyCode::Esc => return Ok(true),
+                _ => {}
+            },
+            AppState::Batch if key.code == KeyCode::Esc => return Ok(true),
+            AppState::TemplatePrompts => {
+                let Some(CargoTemplate {
+                    template: Some(template),
+                    values,
+                    field,
+                    ..
+                }) = self.cargo_template.as_mut()
+                else {
+                    return Ok(false);
+                };
+                let count = template.placeholders.len();
+                let placeholder = &template.placeholders[*field];
+                let value = &mut values[*field];
+                match key.code {
+                    KeyCode::Tab | KeyCode::Down => *field = (*field + 1) % count,
+                    KeyCode::BackTab | KeyCode::Up => *field = (*field + count - 1) % count,
+                    KeyCode::Left | KeyCode::Right => match &placeholder.kind {
+                        cargo_generate::Kind::Bool => {
+                            *value = (value.as_str() != "true").to_string();
+                        }
+                        cargo_generate::Kind::Choice(choices) => {
+                            let current = choices.iter().position(|c| c == value).unwrap_or(0);
+                            let step = if key.code == KeyCode::Left {
+                                choices.len() - 1
+                            } else {
+                                1
+                            };
+                            *value = choices[(current + step) % choices.len()].clone();
+                        }
+                        cargo_generate::Kind::Text => {}
+                    },
+                    KeyCode::Char(c) if placeholder.kind == cargo_generate::Kind::Text => {
+                        value.push(c);
+                    }
+                    KeyCode::Backspace if placeholder.kind == cargo_generate::Kind::Text => {
+                        value.pop();
+                    }
+                    KeyCode::Enter => {
+                        let problem = template
+                            .placeholders
+                            .iter()
+                            .zip(values.iter())
+                            .enumerate()
+                            .find_map(|(i, (p, v))| p.check(v).map(|e| (i, p.prompt.clone(), e)));
+                        match problem {
+                            Some((i, prompt, e)) => {
+                                *field = i;
+                                self.status_message = format!("✗ {}: {}", prompt, e);
+                            }
+                            None => {
+                                self.state = AppState::GeneratingFromTemplate;
+                                self.status_message =
+                                    format!("Generating '{}'...", self.project_name);
+                            }
+                        }
+                    }
+                    KeyCode::Esc => {
+                        self.state = AppState::EnteringProjectName;
+                        self.status_message =
+                            String::from("Enter project name (press Enter when done):");
+                    }
+                    _ => {}
+                }
+            }
+            AppState::Success => match key.code {
+                KeyCode::Enter => {
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Select test to run:");
+                    self.command_output.clear();
+                }
+                KeyCode::Esc => return Ok(true),
+                _ => {}
+            },
+            AppState::EnteringProjectName => match key.code {
+                KeyCode::F(3) => self.open_dashboard(true),
+                KeyCode::Enter if self.selected_recent.is_some() => {
+                    let index = self.selected_recent.unwrap_or_default();
+                    if let Some(project) = self.recent_projects.get(index).cloned() {
+                        self.open_project(&project);
+                    }
+                }
+                KeyCode::Down if !self.recent_projects.is_empty() => {
+                    let last = self.recent_projects.len().min(projects::SHOWN) - 1;
+                    self.selected_recent =
+                        Some(self.selected_recent.map_or(0, |i| (i + 1).min(last)));
+                }
+                KeyCode::Up => {
+                    // Moving up past the first project returns to the name input
+                    self.selected_recent = self.selected_recent.and_then(|i| i.checked_sub(1));
+                }
+                KeyCode::Enter if !self.project_name.is_empty() => {
+                    if self.project_root().exists() {
+                        self.state = AppState::ConfirmOverwrite;
+                        self.status_message = String::from("Directory exists. Overwrite?");
+                    } else {
+                        self.start_install();
+                    }
+                }
+                KeyCode::Tab if self.enclosing_repo.is_some() => self.in_repo = !self.in_repo,
+                KeyCode::F(5)
+                    if self.combinable_examples().len() > 1 && self.cargo_template.is_none() =>
+                {
+                    self.example_selected = 0;
+                    self.state = AppState::ChoosingExamples;
+                    self.status_message = String::from("Choose the examples to combine:");
+                }
+                KeyCode::F(9) if self.cargo_template.is_none() && self.batch.is_none() => {
+                    self.repo_form = if self.custom_repository {
+                        RepoForm {
+                            url: self.summary.repository.clone(),
+                            branch: self.summary.branch.clone(),
+                            field: 0,
+                        }
+                    } else {
+                        RepoForm {
+                            branch: self.summary.branch.clone(),
+                            ..Default::default()
+                        }
+                    };
+                    self.state = AppState::EnteringTemplateRepo;
+                    self.status_message =
+                        String::from("Clone templates from another repository, e.g. a fork:");
+                }
+                KeyCode::F(8)
+                    if self.template_choices.len() > 1 && self.cargo_template.is_none() =>
+                {
+                    self.template_selected = self.template_choice;
+                    self.state = AppState::SelectingTemplate;
+                    self.status_message = String::from("Choose the template to scaffold:");
+                }
+                KeyCode::Left | KeyCode::Right
+                    if self.template_choices.len() > 1 && self.cargo_template.is_none() =>
+                {
+                    let count = self.template_choices.len();
+                    let step = if key.code == KeyCode::Left {
+                        count - 1
+                    } else {
+                        1
+                    };
+                    self.choose_template((self.template_choice + step) % count);
+                }
+                KeyCode::Char(c) => {
+                    self.selected_recent = None;
+                    self.project_name.push(c);
+                }
+                KeyCode::Backspace => {
+                    self.selected_recent = None;
+                    self.project_name.pop();
+                }
+                KeyCode::Esc => return Ok(true),
+                _ => {}
+            },
+            AppState::TestMenu => match key.code {
+                KeyCode::Enter
+                    if self.offline
+                        && ToolboxItem::ALL[self.selected_menu_item].needs_network() =>
+                {
+                    self.status_message =
+                        String::from("Unavailable offline: this needs to reach the network");
+                }
+                KeyCode::Enter => return self.open_tool(ToolboxItem::ALL[self.selected_menu_item]),
+                KeyCode::Up => {
+                    self.selected_menu_item = self.selected_menu_item.saturating_sub(1);
+                }
+                KeyCode::Down => {
+                    self.selected_menu_item =
+                        (self.selected_menu_item + 1).min(ToolboxItem::ALL.len() - 1);
+                }
+                KeyCode::Char(c @ '1'..='9') => {
+                    let index = c as usize - '1' as usize;
+                    if let Some(&preset) = LogPreset::ALL.get(index) {
+                        if self.log_preset == Some(preset) {
+                            self.log_preset = None;
+                            self.status_message =
+                                String::from("Logging from Settings for the next test run");
+                        } else {
+                            self.log_preset = Some(preset);
+                            self.status_message =
+                                format!("{} for the next test run", preset.label());
+                        }
+                    }
+                }
+                KeyCode::Esc => return Ok(true),
+                _ => {}
+            },
+            AppState::EnteringBonsaiKey => {
+                match key.code {
+                    KeyCode::Tab => {
+                        self.prover = self.prover.next();
+                    }
+                    KeyCode::Enter if self.offline && self.prover != ProverBackend::Local => {
+                        self.status_message = format!(
+                            "{} needs the network; use the local prover while offline",
+                            self.prover.label()
+                        );
+                    }
+                    KeyCode::Enter
+                        if !self.prover.needs_bonsai_key() || !self.bonsai_api_key.is_empty() =>
+                    {
+                        let (address, key) = match self.test_wallet() {
+                            Ok(wallet) => wallet,
+                            Err(e) => {
+                                self.status_message = format!("✗ {}", e);
+                                return Ok(false);
+                            }
+                        };
+                        let test_env = TestEnvironment {
+                            // Lets the test run against a node the user already has running
+                            eth_rpc_url: self.rpc_url(),
+                            eth_wallet_address: address,
+                            eth_wallet_private_key: key,
+                            bonsai_api_key: self.bonsai_api_key.clone(),
+                            bonsai_api_url: String::from("https://api.bonsai.xyz"),
+                            prover: self.prover,
+                            anvil_pid: None,
+                            restored: Vec::new(),
+                        };
+                        self.status_message = String::from("Starting end-to-end test...");
+                        self.confirm_dev_key_use(
+                            &test_env.eth_wallet_private_key,
+                            &test_env.eth_rpc_url,
+                            AppState::Testing(E2ETestStep::PreparingEnvironment),
+                        );
+                        self.test_env = Some(test_env);
+                    }
+                    KeyCode::Char(c) => {
+                        self.bonsai_api_key.push(c);
+                    }
+                    KeyCode::Backspace => {
+                        self.bonsai_api_key.pop();
+                    }
+                    KeyCode::Esc => {
+                        self.state = AppState::TestMenu;
+                        self.status_message = String::from("Test cancelled");
+                    }
+                    _ => {}
+                }
+            }
+            AppState::ConfirmingSolcInstall => match key.code {
+                KeyCode::Enter | KeyCode::Char('y') => self.state = AppState::InstallingSolc,
+                KeyCode::Esc | KeyCode::Char('n') => {
+                    self.deployment = None;
+                    self.after_confirm = None;
+                    self.solc_version = None;
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Cancelled");
+                }
+                _ => {}
+            },
+            AppState::ConfirmingPublicRpc => match key.code {
+                KeyCode::Enter if self.confirm_input.trim() == "yes" => {
+                    self.state = self.after_confirm.take().unwrap_or(AppState::TestMenu);
+                    self.status_message = String::from("Continuing with the public RPC...");
+                }
+                KeyCode::Enter => {
+                    self.confirm_input.clear();
+                    self.status_message = String::from("Type 'yes' to continue, or Esc to cancel");
+                }
+                KeyCode::Char(c) => self.confirm_input.push(c),
+                KeyCode::Backspace => {
+                    self.confirm_input.pop();
+                }
+                KeyCode::Esc => {
+                    self.test_env = None;
+                    self.deployment = None;
+                    self.after_confirm = None;
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Cancelled");
+                }
+                _ => {}
+            },
+            AppState::Dashboard => {
+                let Some(dashboard) = &mut self.dashboard else {
+                    self.state = AppState::TestMenu;
+                    return Ok(false);
+                };
+                let selected = dashboard.selected().cloned();
+                match key.code {
+                    KeyCode::Up => dashboard.selected = dashboard.selected.saturating_sub(1),
+                    KeyCode::Down => {
+                        dashboard.selected =
+                            (dashboard.selected + 1).min(dashboard.rows.len().saturating_sub(1))
+                    }
+                    KeyCode::Char('r') => {
+                        let from_welcome = dashboard.from_welcome;
+                        self.open_dashboard(from_welcome);
+                    }
+                    KeyCode::Enter | KeyCode::Char('o') => {
+                        if let Some(project) = selected {
+                            self.open_project(&project);
+                        }
+                    }
+                    KeyCode::Char('t') => {
+                        if let Some(project) = selected {
+                            self.open_project(&project);
+                            self.start_test_setup();
+                        }
+                    }
+                    KeyCode::Char('h') => {
+                        if let Some(project) = selected {
+                            self.open_project(&project);
+                            self.state = AppState::CheckingHealth;
+                        }
+                    }
+                    KeyCode::Char('u') if self.offline => {
+                        self.status_message =
+                            String::from("Unavailable offline: upgrading needs the network");
+                    }
+                    KeyCode::Char('u') => {
+                        if let Some(project) = selected {
+                            self.upgrade_target = Some(project.path);
+                            self.state = AppState::UpgradingProject;
+                        }
+                    }
+                    KeyCode::Esc if dashboard.from_welcome => {
+                        self.state = AppState::EnteringProjectName;
+                        self.status_message =
+                            String::from("Enter project name (press Enter when done):");
+                        self.recent_projects = projects::load();
+                    }
+                    KeyCode::Esc => {
+                        self.state = AppState::TestMenu;
+                        self.status_message = String::from("Select test to run:");
+                    }
+                    _ => {}
+                }
+            }
+            AppState::TemplateDrift => {
+                let root = self.project_root();
+                let Some(view) = &mut self.drift else {
+                    self.state = AppState::TestMenu;
+                    return Ok(false);
+                };
+                match key.code {
+                    KeyCode::Up => view.selected = view.selected.saturating_sub(1),
+                    KeyCode::Down => {
+                        view.selected = (view.selected + 1).min(view.files.len().saturating_sub(1))
+                    }
+                    KeyCode::Char(' ') => {
+                        if let Some(marked) = view.marked.get_mut(view.selected) {
+                            *marked = !*marked;
+                        }
+                    }
+                    KeyCode::Enter => {
+                        if let Some(file) = view.files.get(view.selected) {
+                            let path = file.path.clone();
+                            match drift::diff(&root, &view.template_root, &path) {
+                                Ok(lines) => {
+                                    for line in lines {
+                                        self.add_output(line);
+                                    }
+                                }
+                                Err(e) => self.status_message = format!("✗ {}", e),
+                            }
+                        }
+                    }
+                    KeyCode::Char('p') => {
+                        let mut pulled = 0;
+                        let mut errors = Vec::new();
+                        for (file, _) in view.files.iter().zip(&view.marked).filter(|(_, m)| **m) {
+                            match drift::pull(&root, &view.template_root, &file.path) {
+                                Ok(()) => pulled += 1,
+                                Err(e) => errors.push(format!("{}: {}", file.path.display(), e)),
+                            }
+                        }
+                        // Drop what was pulled so the list shows what's left
+                        let mut marked = view.marked.iter();
+                        view.files.retain(|_| !marked.next().is_some_and(|m| *m));
+                        view.marked = vec![false; view.files.len()];
+                        view.selected = view.selected.min(view.files.len().saturating_sub(1));
+                        self.status_message =
+                            format!("✓ Pulled {} file(s) from the template", pulled);
+                        for error in errors {
+                            self.add_output(format!("✗ Could not pull {}", error));
+                        }
+                    }
+                    KeyCode::Char('r') => self.state = AppState::FetchingTemplate,
+                    KeyCode::Esc => {
+                        self.state = AppState::TestMenu;
+                        self.status_message = String::from("Select test to run:");
+                    }
+                    _ => {}
+                }
+            }
+            AppState::Scaffolding(kind) => match key.code {
+                KeyCode::Enter if !self.scaffold_name.is_empty() => {
+                    let kind = *kind;
+                    let name = self.scaffold_name.trim().to_string();
+                    match kind.generate(&self.project_root(), &name) {
+                        Ok(changes) => {
+                            for change in changes {
+                                self.add_output(format!("✓ {}", change));
+                            }
+                            self.status_message = format!("✓ Added {} '{}'", kind.label(), name);
+                            self.state = AppState::TestMenu;
+                        }
+                        Err(e) => self.status_message = format!("✗ {}", e),
+                    }
+                }
+                KeyCode::Char(c) => self.scaffold_name.push(c),
+                KeyCode::Backspace => {
+                    self.scaffold_name.pop();
+                }
+                KeyCode::Esc => {
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Select test to run:");
+                }
+                _ => {}
+            },
+            AppState::Watching if key.code == KeyCode::Esc => {
+                self.watch = None;
+                self.state = AppState::TestMenu;
+                self.status_message = String::from("Stopped watching. Select test to run:");
+            }
+            AppState::Benchmark => {
+                let bonsai = self.bonsai_key().is_some() && !self.offline;
+                let form = &mut self.benchmark;
+                match key.code {
+                    KeyCode::Tab => form.next_field(1),
+                    KeyCode::BackTab => form.next_field(BenchmarkForm::FIELDS - 1),
+                    KeyCode::Left if form.field == 0 => form.runs = (form.runs - 1).max(1),
+                    KeyCode::Right if form.field == 0 => {
+                        form.runs = (form.runs + 1).min(BenchmarkForm::MAX_RUNS)
+                    }
+                    KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if form.field > 0 => {
+                        let index = form.field - 1;
+                        if benchmark::Backend::ALL[index] == benchmark::Backend::Bonsai && !bonsai {
+                            self.status_message = String::from(if self.offline {
+                                "Bonsai needs the network"
+                            } else {
+                                "Add a Bonsai API key under Manage credentials to benchmark Bonsai"
+                            });
+                        } else {
+                            form.backends[index] = !form.backends[index];
+                        }
+                    }
+                    KeyCode::Enter if form.selected().is_empty() => {
+                        self.status_message = String::from("Select at least one backend");
+                    }
+                    KeyCode::Enter => {
+                        self.state = AppState::Benchmarking;
+                        self.status_message = String::from("Benchmarking...");
+                    }
+                    KeyCode::Esc => {
+                        self.state = AppState::TestMenu;
+                        self.status_message = String::from("Select test to run:");
+                    }
+                    _ => {}
+                }
+            }
+            AppState::Scripts => match key.code {
+                KeyCode::Up => self.script_selected = self.script_selected.saturating_sub(1),
+                KeyCode::Down => {
+                    self.script_selected =
+                        (self.script_selected + 1).min(self.scripts.len().saturating_sub(1))
+                }
+                KeyCode::Enter if self.script_form().is_some() => {
+                    self.state = AppState::ScriptSetup;
+                    self.status_message = String::from("Set the script's flags");
+                }
+                KeyCode::Esc => {
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Select test to run:");
+                }
+                _ => {}
+            },
+            AppState::ScriptSetup => {
+                let Some(form) = self.script_form() else {
+                    return Ok(false);
+                };
+                match key.code {
+                    KeyCode::Tab | KeyCode::Down => form.next_field(1),
+                    KeyCode::BackTab | KeyCode::Up => form.next_field(ScriptForm::FIELDS - 1),
+                    KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') if form.field > 0 => {
+                        form.toggle()
+                    }
+                    KeyCode::Char(c) if form.field == 0 => form.rpc_url.push(c),
+                    KeyCode::Backspace if form.field == 0 => {
+                        form.rpc_url.pop();
+                    }
+                    KeyCode::Enter if !form.rpc_url.trim().is_empty() => {
+                        self.state = AppState::RunningScript;
+                        self.status_message = String::from("Running script...");
+                    }
+                    KeyCode::Esc => {
+                        self.state = AppState::Scripts;
+                        self.status_message = String::from("Choose a script to run");
+                    }
+                    _ => {}
+                }
+            }
+            AppState::DeployedContracts => {
+                if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
+                    match self.deployed.take().map(|view| view.back) {
+                        Some(ToolboxItem::Scripts) => self.state = AppState::ScriptSetup,
+                        _ => {
+                            self.state = AppState::TestMenu;
+                            self.status_message = String::from("Select test to run:");
+                        }
+                    }
+                }
+            }
+            AppState::ConfirmingDeploy => match key.code {
+                KeyCode::Enter => {
+                    if let Some(deployment) = &self.deployment {
+                        self.status_message =
+                            format!("Deploying with {}...", deployment.signer.describe());
+                    }
+                    self.state = AppState::Deploying;
+                }
+                KeyCode::Esc => {
+                    self.deployment = None;
+                    self.deploy_preview = None;
+                    self.state = AppState::DeploySetup;
+                    self.status_message = String::from("Nothing was broadcast");
+                }
+                _ => {}
+            },
+            AppState::ChoosingNetwork => match key.code {
+                KeyCode::Up => self.network_selected = self.network_selected.saturating_sub(1),
+                KeyCode::Down => {
+                    self.network_selected =
+                        (self.network_selected + 1).min(self.networks.len().saturating_sub(1))
+                }
+                KeyCode::Enter => {
+                    self.network = self.networks.get(self.network_selected).cloned();
+                    match self.network_for.take() {
+                        Some(ToolboxItem::Deploy) => self.start_deploy_setup(),
+                        _ => self.start_test_setup(),
+                    }
+                }
+                KeyCode::Esc => {
+                    self.network_for = None;
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Select test to run:");
+                }
+                _ => {}
+            },
+            AppState::ChoosingVersions => {
+                let Some(form) = &mut self.version_form else {
+                    return Ok(false);
+                };
+                match key.code {
+                    KeyCode::Down | KeyCode::Tab => form.next_field(1),
+                    KeyCode::Up | KeyCode::BackTab => form.next_field(form.picks.len() - 1),
+                    KeyCode::Right => form.cycle(true),
+                    KeyCode::Left => form.cycle(false),
+                    KeyCode::Char('a') => {
+                        let pick = form.picks[form.field];
+                        form.picks.fill(pick);
+                    }
+                    KeyCode::Enter | KeyCode::Esc => {
+                        // Esc keeps every crate on the branch head
+                        if key.code == KeyCode::Enter {
+                            for (index, krate) in form.release.crates.iter().enumerate() {
+                                if let Some(tag) = form.tag(index) {
+                                    self.summary
+                                        .tags
+                                        .insert(krate.name.to_string(), tag.to_string());
+                                }
+                            }
+                        }
+                        self.version_form = None;
+                        let pinned: Vec<String> = self
+                            .summary
+                            .tags
+                            .iter()
+                            .map(|(name, tag)| format!("Pinning {} to {}", name, tag))
+                            .collect();
+                        for line in pinned {
+                            self.add_output(line);
+                        }
+                        self.state = AppState::Installing(InstallStep::UpdatingDependencies);
+                        self.status_message =
+                            format!("Installing project '{}'...", self.project_name);
+                    }
+                    _ => {}
+                }
+            }
+            AppState::EnteringTemplateRepo => {
+                let form = &mut self.repo_form;
+                match key.code {
+                    KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                        form.field = 1 - form.field
+                    }
+                    KeyCode::Char(c) if form.field == 0 => form.url.push(c),
+                    KeyCode::Char(c) => form.branch.push(c),
+                    KeyCode::Backspace if form.field == 0 => {
+                        form.url.pop();
+                    }
+                    KeyCode::Backspace => {
+                        form.branch.pop();
+                    }
+                    KeyCode::Enter => match self.apply_repo_form() {
+                        Ok(()) => {
+                            self.state = AppState::EnteringProjectName;
+                            self.status_message = if self.custom_repository {
+                                format!(
+                                    "Templates come from {} at {}",
+                                    self.summary.repository, self.summary.branch
+                                )
+                            } else {
+                                String::from("Templates come from risc0-ethereum")
+                            };
+                        }
+                        Err(e) => self.status_message = format!("✗ {}", e),
+                    },
+                    KeyCode::Esc => {
+                        self.state = AppState::EnteringProjectName;
+                        self.status_message = String::from("Enter project name:");
+                    }
+                    _ => {}
+                }
+            }
+            AppState::SelectingTemplate => match key.code {
+                KeyCode::Up => self.template_selected = self.template_selected.saturating_sub(1),
+                KeyCode::Down => {
+                    self.template_selected = (self.template_selected + 1)
+                        .min(self.template_choices.len().saturating_sub(1))
+                }
+                KeyCode::Enter | KeyCode::Esc => {
+                    if key.code == KeyCode::Enter {
+                        self.choose_template(self.template_selected);
+                    }
+                    self.state = AppState::EnteringProjectName;
+                    self.status_message = String::from("Enter project name:");
+                }
+                _ => {}
+            },
+            AppState::ChoosingExamples => {
+                let examples = self.combinable_examples();
+                match key.code {
+                    KeyCode::Up => self.example_selected = self.example_selected.saturating_sub(1),
+                    KeyCode::Down => {
+                        self.example_selected =
+                            (self.example_selected + 1).min(examples.len().saturating_sub(1))
+                    }
+                    KeyCode::Char(' ') => {
+                        if let Some(example) = examples.get(self.example_selected) {
+                            let picked = &mut self.summary.examples;
+                            match picked.iter().position(|e| e == example) {
+                                Some(i) => {
+                                    picked.remove(i);
+                                }
+                                None => {
+                                    picked.push(example.to_string());
+                                    // In the release's order, whatever order they were picked in
+                                    picked.sort_by_key(|e| examples.iter().position(|x| x == e));
+                                }
+                            }
+                        }
+                    }
+                    KeyCode::Enter if self.summary.examples.len() == 1 => {
+                        self.status_message = String::from(
+                            "Pick at least two examples, or none to use the template alone",
+                        );
+                    }
+                    KeyCode::Enter | KeyCode::Esc => {
+                        if self.summary.examples.len() < 2 {
+                            self.summary.examples.clear();
+                        }
+                        self.state = AppState::EnteringProjectName;
+                        self.status_message = if self.summary.examples.is_empty() {
+                            String::from("Enter project name:")
+                        } else {
+                            format!("Combining {}", self.summary.examples.join(" + "))
+                        };
+                    }
+                    _ => {}
+                }
+            }
+            AppState::ChoosingChain => match key.code {
+                KeyCode::Up => self.chain_selected = self.chain_selected.saturating_sub(1),
+                KeyCode::Down => {
+                    self.chain_selected = (self.chain_selected + 1).min(ChainPreset::ALL.len() - 1)
+                }
+                KeyCode::Enter if self.project_chain.is_some() => {
+                    let chain = ChainPreset::ALL[self.chain_selected];
+                    let root = self.project_root();
+                    let saved = ProjectFile::load(&root).and_then(|file| match file {
+                        Some(mut file) => {
+                            file.options.chain = Some(chain);
+                            file.save(&root)
+                        }
+                        None => Ok(()),
+                    });
+                    match saved {
+                        Ok(()) => {
+                            self.network = None;
+                            self.project_chain = Some(chain);
+                            self.state = AppState::TestMenu;
+                            self.status_message = format!(
+                                "✓ Using {} for this project ({})",
+                                chain.label(),
+                                self.rpc_url()
+                            );
+                        }
+                        Err(e) => self.status_message = format!("✗ Could not save: {}", e),
+                    }
+                }
+                KeyCode::Enter => {
+                    let chain = ChainPreset::ALL[self.chain_selected];
+                    // The chain picked here replaces a network picked before
+                    self.network = None;
+                    let mut settings = self.settings.clone();
+                    settings.chain = chain;
+                    // Keeps a configured RPC provider pointed at the same chain
+                    if let Some(network) = chain.network() {
+                        settings.rpc_network = network;
+                    }
+                    match settings.save() {
+                        Ok(()) => {
+                            self.settings = settings;
+                            self.state = AppState::TestMenu;
+                            self.status_message =
+                                format!("✓ Using {} ({})", chain.label(), self.rpc_url());
+                        }
+                        Err(e) => self.status_message = format!("✗ Could not save: {}", e),
+                    }
+                }
+                KeyCode::Esc => {
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Select test to run:");
+                }
+                _ => {}
+            },
+            AppState::AnvilControls => {
+                let Some(panel) = &mut self.anvil_panel else {
+                    self.state = AppState::TestMenu;
+                    return Ok(false);
+                };
+                match key.code {
+                    KeyCode::Tab => panel.next_field(1),
+                    KeyCode::BackTab => panel.next_field(AnvilPanel::FIELDS - 1),
+                    KeyCode::Char(c) if panel.field == 3 || c.is_ascii_digit() => {
+                        panel.input().push(c)
+                    }
+                    KeyCode::Backspace => {
+                        panel.input().pop();
+                    }
+                    KeyCode::Enter => {
+                        let result = panel.apply().map_err(|e| e.to_string());
+                        self.status_message = match &result {
+                            Ok(done) => format!("✓ {}", done),
+                            Err(e) => format!("✗ {}", e),
+                        };
+                        panel.last = Some(result);
+                    }
+                    KeyCode::Esc => {
+                        self.anvil_panel = None;
+                        self.state = AppState::TestMenu;
+                        self.status_message = String::from("Select test to run:");
+                    }
+                    _ => {}
+                }
+            }
+            AppState::Lint => {
+                let Some(view) = &mut self.lint else {
+                    self.state = AppState::TestMenu;
+                    return Ok(false);
+                };
+                match key.code {
+                    KeyCode::Up => view.selected = view.selected.saturating_sub(1),
+                    KeyCode::Down => {
+                        view.selected =
+                            (view.selected + 1).min(view.diagnostics.len().saturating_sub(1))
+                    }
+                    KeyCode::Enter => {
+                        if let Some((_, at)) = view.diagnostics.get(view.selected) {
+                            // Scroll counts the lines shown at the current verbosity
+                            let at = *at;
+                            self.output_scroll = self.command_output[..at]
+                                .iter()
+                                .filter(|line| line.level <= self.verbosity)
+                                .count() as u16;
+                        }
+                    }
+                    KeyCode::Char('r') => {
+                        self.state = AppState::Linting;
+                        self.status_message = String::from("Running cargo clippy...");
+                    }
+                    KeyCode::Esc => {
+                        self.state = AppState::TestMenu;
+                        self.status_message = String::from("Select test to run:");
+                    }
+                    _ => {}
+                }
+            }
+            AppState::Format => match key.code {
+                KeyCode::Tab | KeyCode::BackTab | KeyCode::Left | KeyCode::Right => {
+                    self.format_check = !self.format_check
+                }
+                KeyCode::Enter => {
+                    self.state = AppState::Formatting;
+                    self.status_message = String::from("Formatting...");
+                }
+                KeyCode::Esc => {
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Select test to run:");
+                }
+                _ => {}
+            },
+            AppState::InspectingReceipt => {
+                let form = &mut self.inspect_form;
+                let choices = form.types().len() + 1;
+                match key.code {
+                    KeyCode::Tab => form.next_field(1),
+                    KeyCode::BackTab => form.next_field(InspectForm::FIELDS - 1),
+                    KeyCode::Left if form.field == 1 => {
+                        form.journal_type = (form.journal_type + choices - 1) % choices
+                    }
+                    KeyCode::Right if form.field == 1 => {
+                        form.journal_type = (form.journal_type + 1) % choices
+                    }
+                    KeyCode::Char(c) if form.field == 0 => form.path.push(c),
+                    KeyCode::Char(c) if form.field == 2 => form.verifier.push(c),
+                    KeyCode::Backspace if form.field == 0 => {
+                        form.path.pop();
+                    }
+                    KeyCode::Backspace if form.field == 2 => {
+                        form.verifier.pop();
+                    }
+                    KeyCode::Enter if !form.path.trim().is_empty() => {
+                        let root = self.project_root();
+                        let guests = guest_image::read(&root);
+                        let path = root.join(self.inspect_form.path.trim());
+                        let verifier = self.inspect_form.verifier.trim().to_string();
+                        let mut result =
+                            receipt::inspect(&path, &guests).map_err(|e| e.to_string());
+                        if let Ok(inspection) = &mut result {
+                            if !inspection.checks.is_empty() && !verifier.is_empty() {
+                                let check = match Chain::connect(&self.rpc_url()) {
+                                    Ok(chain) => {
+                                        receipt::verify_seal(&chain, &verifier, inspection)
+                                    }
+                                    Err(e) => health::Finding::problem(
+                                        "Seal",
+                                        format!("Could not reach {}: {}", self.rpc_url(), e),
+                                        "Start the chain or pick a network in Settings",
+                                    ),
+                                };
+                                inspection.checks.push(check);
+                            }
+                        }
+                        self.status_message = match &result {
+                            Ok(inspection) => format!(
+                                "Loaded {} ({} journal bytes)",
+                                inspection.source,
+                                inspection.journal.len()
+                            ),
+                            Err(e) => format!("✗ {}", e),
+                        };
+                        self.inspect_form.result = Some(result);
+                    }
+                    KeyCode::Esc => {
+                        self.state = AppState::TestMenu;
+                        self.status_message = String::from("Select test to run:");
+                    }
+                    _ => {}
+                }
+            }
+            AppState::BoundlessRequest => {
+                let form = &mut self.boundless_form;
+                let guests = self.guest_images.len().max(1);
+                match key.code {
+                    KeyCode::Tab | KeyCode::BackTab => {
+                        form.field = (form.field + 1) % BoundlessForm::FIELDS
+                    }
+                    KeyCode::Left if form.field == 0 => {
+                        form.guest = (form.guest + guests - 1) % guests
+                    }
+                    KeyCode::Right if form.field == 0 => form.guest = (form.guest + 1) % guests,
+                    KeyCode::Char(c) if form.field == 1 => form.input.push(c),
+                    KeyCode::Backspace if form.field == 1 => {
+                        form.input.pop();
+                    }
+                    KeyCode::Enter => {
+                        let elf = self
+                            .guest_images
+                            .get(self.boundless_form.guest)
+                            .and_then(|g| g.elf_path.as_ref());
+                        if self.boundless.is_none() {
+                            self.status_message = String::from(
+                                "✗ Set BOUNDLESS_MARKET_ADDRESS to submit to the Boundless market",
+                            );
+                        } else if elf.is_none() {
+                            self.status_message = String::from(
+                                "✗ No guest ELF: build the project first (cargo build)",
+                            );
+                        } else if self.boundless_form.input.trim().is_empty() {
+                            self.status_message =
+                                String::from("✗ Enter the file with the guest's input");
+                        } else {
+                            self.state = AppState::SubmittingBoundlessRequest;
+                            self.status_message =
+                                String::from("Submitting a proof request to Boundless...");
+                        }
+                    }
+                    KeyCode::Esc => {
+                        self.state = AppState::TestMenu;
+                        self.status_message = String::from("Select test to run:");
+                    }
+                    _ => {}
+                }
+            }
+            AppState::GuestImages => match key.code {
+                KeyCode::Char('r') => self.refresh_guest_images(),
+                KeyCode::Esc => {
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Select test to run:");
+                }
+                _ => {}
+            },
+            AppState::TransactionBuilder => {
+                let form = &mut self.tx_form;
+                let field = form.current();
+                match key.code {
+                    KeyCode::Tab => form.next_field(1),
+                    KeyCode::BackTab => form.next_field(form.fields() - 1),
+                    KeyCode::Left | KeyCode::Right
+                        if matches!(
+                            field,
+                            TxField::Contract | TxField::Function | TxField::Signer
+                        ) =>
+                    {
+                        let count = match field {
+                            TxField::Contract => form.contracts.len(),
+                            TxField::Function => {
+                                form.selected_contract().map_or(1, |c| c.functions.len())
+                            }
+                            _ => SignerKind::ALL.len(),
+                        };
+                        let step = if key.code == KeyCode::Left {
+                            count - 1
+                        } else {
+                            1
+                        };
+                        match field {
+                            TxField::Contract => {
+                                form.contract = (form.contract + step) % count;
+                                form.function = 0;
+                                form.reset_args();
+                            }
+                            TxField::Function => {
+                                form.function = (form.function + step) % count;
+                                form.reset_args();
+                            }
+                            _ => {
+                                form.kind = (form.kind + step) % count;
+                                form.detail = match form.kind() {
+                                    SignerKind::CastAccount => signer::cast_accounts()
+                                        .into_iter()
+                                        .next()
+                                        .unwrap_or_default(),
+                                    _ => String::new(),
+                                };
+                                form.preview = None;
+                            }
+                        }
+                    }
+                    KeyCode::Char(c) => {
+                        match field {
+                            TxField::Arg(i) => form.args[i].push(c),
+                            TxField::Value => form.value.push(c),
+                            TxField::Detail => form.detail.push(c),
+                            TxField::Password => form.password.push(c),
+                            _ => return Ok(false),
+                        }
+                        if field != TxField::Password {
+                            form.preview = None;
+                        }
+                    }
+                    KeyCode::Backspace => {
+                        match field {
+                            TxField::Arg(i) => form.args[i].pop(),
+                            TxField::Value => form.value.pop(),
+                            TxField::Detail => form.detail.pop(),
+                            TxField::Password => form.password.pop(),
+                            _ => None,
+                        };
+                        if field != TxField::Password {
+                            form.preview = None;
+                        }
+                    }
+                    KeyCode::Enter => {
+                        let ready = match &form.preview {
+                            Some(Ok(preview)) => form
+                                .selected_function()
+                                .filter(|f| !transaction::is_view(f))
+                                .and(preview.gas.as_ref().ok())
+                                .is_some(),
+                            _ => false,
+                        };
+                        if !ready {
+                            self.preview_transaction();
+                            return Ok(false);
+                        }
+                        match Signer::new(form.kind(), &form.detail, &form.password) {
+                            Ok(signer) => {
+                                form.password.clear();
+                                let (Some(contract), Some(function), Some(Ok(preview))) = (
+                                    form.selected_contract(),
+                                    form.selected_function(),
+                                    &form.preview,
+                                ) else {
+                                    return Ok(false);
+                                };
+                                let key = signer.private_key().unwrap_or_default().to_string();
+                                let rpc_url = form.rpc_url.clone();
+                                self.pending_tx = Some(PendingTx {
+                                    rpc_url: rpc_url.clone(),
+                                    label: format!("{}.{}", contract.name, function.signature()),
+                                    to: contract.address,
+                                    calldata: preview.calldata.clone(),
+                                    value: preview.value,
+                                    // Headroom over the estimate, as cast and forge add
+                                    gas: preview.gas.as_ref().map_or(0, |gas| gas * 6 / 5),
+                                    signer,
+                                });
+                                form.preview = None;
+                                self.confirm_dev_key_use(
+                                    &key,
+                                    &rpc_url,
+                                    AppState::SendingTransaction,
+                                );
+                            }
+                            Err(e) => self.status_message = format!("✗ {}", e),
+                        }
+                    }
+                    KeyCode::Esc => {
+                        self.tx_form = TxForm::default();
+                        self.state = AppState::TestMenu;
+                        self.status_message = String::from("Select test to run:");
+                    }
+                    _ => {}
+                }
+            }
+            AppState::TestHistory => match key.code {
+                KeyCode::Up => self.history.selected = self.history.selected.saturating_sub(1),
+                KeyCode::Down => {
+                    self.history.selected =
+                        (self.history.selected + 1).min(self.history.runs.len().saturating_sub(1))
+                }
+                KeyCode::Char(' ') => self.history.toggle(),
+                KeyCode::Esc => {
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Select test to run:");
+                }
+                _ => {}
+            },
+            AppState::Queue => match key.code {
+                KeyCode::Left => {
+                    self.queue_action = (self.queue_action + queue::Action::ALL.len() - 1)
+                        % queue::Action::ALL.len()
+                }
+                KeyCode::Right => {
+                    self.queue_action = (self.queue_action + 1) % queue::Action::ALL.len()
+                }
+                KeyCode::Char('a') => {
+                    self.queue.push(queue::Action::ALL[self.queue_action]);
+                    self.queue.selected = self.queue.entries.len() - 1;
+                }
+                KeyCode::Char('p') => {
+                    for &action in queue::Action::PRESET {
+                        self.queue.push(action);
+                    }
+                }
+                KeyCode::Up => self.queue.selected = self.queue.selected.saturating_sub(1),
+                KeyCode::Down => {
+                    self.queue.selected =
+                        (self.queue.selected + 1).min(self.queue.entries.len().saturating_sub(1))
+                }
+                KeyCode::Char('u') => {
+                    self.queue.move_selected(true);
+                }
+                KeyCode::Char('d') => {
+                    self.queue.move_selected(false);
+                }
+                KeyCode::Char('x') | KeyCode::Delete => {
+                    let removed = self.queue.remove_selected();
+                    if !removed {
+                        self.status_message = String::from("Only queued actions can be removed");
+                    }
+                }
+                KeyCode::Char('c') => self.queue.clear_finished(),
+                KeyCode::Enter if self.queue.next_pending().is_some() => {
+                    self.state = AppState::RunningQueue;
+                    self.status_message = format!("Running {} action(s)...", self.queue.pending());
+                }
+                KeyCode::Esc => {
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Select test to run:");
+                }
+                _ => {}
+            },
+            AppState::Events => match key.code {
+                KeyCode::Up => {
+                    let rows = self.event_log.as_ref().map_or(0, |log| log.rows.len());
+                    self.events_scroll = (self.events_scroll + 1).min(rows.saturating_sub(1));
+                }
+                KeyCode::Down => self.events_scroll = self.events_scroll.saturating_sub(1),
+                KeyCode::Char('r') => {
+                    let rpc_url = self.rpc_url();
+                    self.status_message = match self.open_event_log(&rpc_url) {
+                        Ok(()) => format!("Following contract events on {}", rpc_url),
+                        Err(e) => format!("✗ {}", e),
+                    };
+                }
+                KeyCode::Esc => {
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Select test to run:");
+                }
+                _ => {}
+            },
+            AppState::Abi => match key.code {
+                KeyCode::Up => self.abi_selected = self.abi_selected.saturating_sub(1),
+                KeyCode::Down => {
+                    let entries: usize = self.abi_contracts.iter().map(|c| c.entries.len()).sum();
+                    self.abi_selected = (self.abi_selected + 1).min(entries.saturating_sub(1));
+                }
+                KeyCode::Enter | KeyCode::Char('c') | KeyCode::Char('s') => {
+                    if let Some(entry) = self.selected_abi_entry() {
+                        let (what, text) = if key.code == KeyCode::Char('s') {
+                            ("signature", entry.signature.clone())
+                        } else {
+                            ("selector", entry.selector.clone())
+                        };
+                        self.status_message = match platform::copy_to_clipboard(&text) {
+                            Ok(()) => format!("✓ Copied {} {}", what, text),
+                            Err(e) => format!("✗ Could not copy {}: {}", text, e),
+                        };
+                    }
+                }
+                KeyCode::Char('r') => self.refresh_abi(),
+                KeyCode::Esc => {
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Select test to run:");
+                }
+                _ => {}
+            },
+            AppState::ContractChecks => match key.code {
+                KeyCode::Char('r') => self.state = AppState::CheckingContracts,
+                KeyCode::Esc => {
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Select test to run:");
+                }
+                _ => {}
+            },
+            AppState::Health => match key.code {
+                KeyCode::Char('r') => self.state = AppState::CheckingHealth,
+                KeyCode::Esc => {
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Select test to run:");
+                }
+                _ => {}
+            },
+            AppState::BonsaiAccount => match key.code {
+                KeyCode::Char('r') => {
+                    if let Some(key) = self.bonsai_key() {
+                        self.refresh_bonsai_panel(&key);
+                    }
+                }
+                KeyCode::Esc => {
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Select test to run:");
+                }
+                _ => {}
+            },
+            AppState::Settings => {
+                let form = &mut self.settings_form;
+                match key.code {
+                    KeyCode::Tab => form.next_field(1),
+                    KeyCode::BackTab => form.next_field(SettingsForm::FIELDS - 1),
+                    KeyCode::Left | KeyCode::Right if form.field == 1 => {
+                        let step = if key.code == KeyCode::Left {
+                            RpcProvider::ALL.len() - 1
+                        } else {
+                            1
+                        };
+                        form.rpc_provider = (form.rpc_provider + step) % RpcProvider::ALL.len();
+                    }
+                    KeyCode::Left | KeyCode::Right if form.field == 2 => {
+                        let step = if key.code == KeyCode::Left {
+                            Network::ALL.len() - 1
+                        } else {
+                            1
+                        };
+                        form.rpc_network = (form.rpc_network + step) % Network::ALL.len();
+                    }
+                    KeyCode::Left | KeyCode::Right if form.field == 4 => {
+                        form.full_submodule_history = !form.full_submodule_history;
+                    }
+                    KeyCode::Left | KeyCode::Right if form.field == 6 => {
+                        form.cargo_git_fetch_with_cli = !form.cargo_git_fetch_with_cli;
+                    }
+                    KeyCode::Left | KeyCode::Right if form.field == 7 => {
+                        form.dev_mode = !form.dev_mode;
+                    }
+                    KeyCode::Left | KeyCode::Right if form.field == 9 => {
+                        form.reduced_motion = !form.reduced_motion;
+                    }
+                    KeyCode::Left | KeyCode::Right if form.field == 12 => {
+                        form.progress_view = form.progress_view.next();
+                    }
+                    KeyCode::Left | KeyCode::Right if form.field == 13 => {
+                        form.skip_template_refresh = !form.skip_template_refresh;
+                    }
+                    KeyCode::Left | KeyCode::Right if form.field == 8 => {
+                        let count = tool_versions::Format::ALL.len();
+                        let step = if key.code == KeyCode::Left {
+                            count - 1
+                        } else {
+                            1
+                        };
+                        form.tool_versions = (form.tool_versions + step) % count;
+                    }
+                    KeyCode::Left | KeyCode::Right if form.field == 10 => {
+                        let count = LogLevel::ALL.len();
+                        let step = if key.code == KeyCode::Left {
+                            count - 1
+                        } else {
+                            1
+                        };
+                        form.log_level = (form.log_level + step) % count;
+                    }
+                    KeyCode::Char(c) => match form.field {
+                        0 => form.proxy.push(c),
+                        3 => form.rpc_api_key.push(c),
+                        5 => form.cargo_mirror.push(c),
+                        11 => form.log_overrides.push(c),
+                        _ => {}
+                    },
+                    KeyCode::Backspace => {
+                        match form.field {
+                            0 => form.proxy.pop(),
+                            3 => form.rpc_api_key.pop(),
+                            5 => form.cargo_mirror.pop(),
+                            11 => form.log_overrides.pop(),
+                            _ => None,
+                        };
+                    }
+                    KeyCode::Enter => {
+                        let proxy = form.proxy.trim().to_string();
+                        if !proxy.is_empty() {
+                            if let Err(e) = http::validate_proxy(&proxy) {
+                                self.status_message = format!("✗ {}", e);
+                                return Ok(false);
+                            }
+                        }
+                        let cargo_mirror = form.cargo_mirror.trim().to_string();
+                        if !cargo_mirror.is_empty() {
+                            if let Err(e) = cargo_config::validate_mirror(&cargo_mirror) {
+                                self.status_message = format!("✗ {}", e);
+                                return Ok(false);
+                            }
+                        }
+                        let settings = Settings {
+                            proxy: Some(proxy).filter(|p| !p.is_empty()),
+                            rpc_provider: form.rpc_provider(),
+                            rpc_network: form.rpc_network(),
+                            chain: self.settings.chain,
+                            full_submodule_history: form.full_submodule_history,
+                            skip_template_refresh: form.skip_template_refresh,
+                            cargo_mirror: Some(cargo_mirror).filter(|m| !m.is_empty()),
+                            cargo_git_fetch_with_cli: form.cargo_git_fetch_with_cli,
+                            tool_versions: form.tool_versions(),
+                            reduced_motion: form.reduced_motion,
+                            progress_view: form.progress_view,
+                            log_level: form.log_level(),
+                            log_overrides: Some(form.log_overrides.trim().to_string()),
+                        };
+                        let api_key = form.rpc_api_key.trim().to_string();
+                        let dev_mode = form.dev_mode;
+                        match settings.save() {
+                            Ok(()) => {
+                                settings.apply();
+                                self.settings = settings;
+                                self.state = AppState::TestMenu;
+                                self.status_message = String::from("✓ Settings saved");
+                                if dev_mode != supervisor::dev_mode() {
+                                    self.set_dev_mode(dev_mode);
+                                }
+                                if self.settings.rpc_provider.needs_api_key() && !api_key.is_empty()
+                                {
+                                    redact::register(&api_key);
+                                    self.rpc_api_key = api_key.clone();
+                                    let provider = self.settings.rpc_provider.label();
+                                    match &mut self.credentials {
+                                        Some(credentials) => {
+                                            credentials.set(
+                                                CredentialKind::RpcProviderKey,
+                                                provider,
+                                                &api_key,
+                                            );
+                                            self.save_credentials();
+                                        }
+                                        None => {
+                                            self.status_message = String::from(
+                                                "✓ Settings saved; the API key is kept for this session only (unlock Credentials to store it)",
+                                            );
+                                        }
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                self.status_message = format!("✗ Could not save settings: {}", e)
+                            }
+                        }
+                    }
+                    KeyCode::Esc => {
+                        self.state = AppState::TestMenu;
+                        self.status_message = String::from("Settings unchanged");
+                    }
+                    _ => {}
+                }
+            }
+            AppState::DeploySetup => {
+                let form = &mut self.deploy_form;
+                match key.code {
+                    KeyCode::Tab => form.next_field(1),
+                    KeyCode::BackTab => form.next_field(DeployForm::FIELDS - 1),
+                    KeyCode::Left | KeyCode::Right if form.field == 4 => {
+                        let step = if key.code == KeyCode::Left {
+                            VerifierKind::ALL.len() - 1
+                        } else {
+                            1
+                        };
+                        form.verifier = (form.verifier + step) % VerifierKind::ALL.len();
+                        form.verifier_detail = match form.verifier() {
+                            VerifierKind::Etherscan => {
+                                std::env::var("ETHERSCAN_API_KEY").unwrap_or_default()
+                            }
+                            _ => String::new(),
+                        };
+                    }
+                    KeyCode::Left | KeyCode::Right if form.field == 1 => {
+                        let step = if key.code == KeyCode::Left {
+                            SignerKind::ALL.len() - 1
+                        } else {
+                            1
+                        };
+                        form.kind = (form.kind + step) % SignerKind::ALL.len();
+                        form.detail = match form.kind() {
+                            SignerKind::CastAccount => signer::cast_accounts()
+                                .into_iter()
+                                .next()
+                                .unwrap_or_default(),
+                            _ => String::new(),
+                        };
+                    }
+                    KeyCode::Char(c) => match form.field {
+                        0 => form.rpc_url.push(c),
+                        2 => form.detail.push(c),
+                        3 => form.password.push(c),
+                        5 => form.verifier_detail.push(c),
+                        _ => {}
+                    },
+                    KeyCode::Backspace => {
+                        match form.field {
+                            0 => form.rpc_url.pop(),
+                            2 => form.detail.pop(),
+                            3 => form.password.pop(),
+                            5 => form.verifier_detail.pop(),
+                            _ => None,
+                        };
+                    }
+                    KeyCode::Enter if !form.rpc_url.trim().is_empty() => {
+                        match Signer::new(form.kind(), &form.detail, &form.password) {
+                            Ok(signer) => {
+                                let rpc_url = form.rpc_url.trim().to_string();
+                                let key = signer.private_key().unwrap_or_default().to_string();
+                                form.password.clear();
+                                self.status_message = format!(
+                                    "Simulating the deployment with {}...",
+                                    signer.describe()
+                                );
+                                if form.verifier() == VerifierKind::Etherscan {
+                                    redact::register(&form.verifier_detail);
+                                }
+                                self.deployment = Some(Deployment {
+                                    rpc_url: rpc_url.clone(),
+                                    signer,
+                                    verifier: Verifier::new(form.verifier(), &form.verifier_detail),
+                                });
+                                self.confirm_dev_key_use(
+                                    &key,
+                                    &rpc_url,
+                                    AppState::SimulatingDeploy,
+                                );
+                            }
+                            Err(e) => self.status_message = format!("✗ {}", e),
+                        }
+                    }
+                    KeyCode::Esc => {
+                        self.deploy_form = DeployForm::default();
+                        self.state = AppState::TestMenu;
+                        self.status_message = String::from("Deployment cancelled");
+                    }
+                    _ => {}
+                }
+            }
+            AppState::UnlockingCredentials => match key.code {
+                KeyCode::Enter if !self.credentials_passphrase.is_empty() => {
+                    match Credentials::load(&self.credentials_passphrase) {
+                        Ok(credentials) => {
+                            self.credentials = Some(credentials);
+                            self.selected_credential = 0;
+                            if std::mem::take(&mut self.wallet_after_unlock) {
+                                self.generate_test_wallet();
+                            } else {
+                                self.state = AppState::Credentials;
+                                self.status_message = String::from("Credentials");
+                            }
+                        }
+                        Err(e) => {
+                            self.credentials_passphrase.clear();
+                            self.status_message = format!("✗ {}", e);
+                        }
+                    }
+                }
+                KeyCode::Char(c) => self.credentials_passphrase.push(c),
+                KeyCode::Backspace => {
+                    self.credentials_passphrase.pop();
+                }
+                KeyCode::Esc => {
+                    self.credentials_passphrase.clear();
+                    self.wallet_after_unlock = false;
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Select test to run:");
+                }
+                _ => {}
+            },
+            AppState::Credentials => {
+                let count = self.credentials.as_ref().map_or(0, |c| c.entries.len());
+                match key.code {
+                    KeyCode::Up => {
+                        self.selected_credential = self.selected_credential.saturating_sub(1);
+                    }
+                    KeyCode::Down => {
+                        self.selected_credential =
+                            (self.selected_credential + 1).min(count.saturating_sub(1));
+                    }
+                    KeyCode::Char('a') => {
+                        self.credential_form = CredentialForm::default();
+                        self.state = AppState::EditingCredential;
+                        self.status_message = String::from("Add a credential");
+                    }
+                    KeyCode::Char('g') => self.generate_test_wallet(),
+                    KeyCode::Char('d') | KeyCode::Delete if count > 0 => {
+                        if let Some(credentials) = self.credentials.as_mut() {
+                            credentials.remove(self.selected_credential);
+                        }
+                        self.selected_credential =
+                            self.selected_credential.min(count.saturating_sub(2));
+                        self.save_credentials();
+                    }
+                    KeyCode::Esc => {
+                        self.state = AppState::TestMenu;
+                        self.status_message = String::from("Select test to run:");
+                    }
+                    _ => {}
+                }
+            }
+            AppState::TestWallet => match key.code {
+                KeyCode::Char('c') => {
+                    if let Some(wallet) = &self.test_wallet {
+                        self.status_message = match platform::copy_to_clipboard(&wallet.address) {
+                            Ok(()) => String::from("✓ Address copied to the clipboard"),
+                            Err(e) => format!("✗ Could not copy the address: {}", e),
+                        };
+                    }
+                }
+                KeyCode::Esc => {
+                    self.state = AppState::TestMenu;
+                    self.status_message = String::from("Select test to run:");
+                }
+                _ => {}
+            },
+            AppState::EditingCredential => {
+                let form = &mut self.credential_form;
+                match key.code {
+                    KeyCode::Tab => form.field = (form.field + 1) % 3,
+                    KeyCode::BackTab => form.field = (form.field + 2) % 3,
+                    KeyCode::Left if form.field == 0 => {
+                        form.kind =
+                            (form.kind + CredentialKind::ALL.len() - 1) % CredentialKind::ALL.len();
+                    }
+                    KeyCode::Right if form.field == 0 => {
+                        form.kind = (form.kind + 1) % CredentialKind::ALL.len();
+                    }
+                    KeyCode::Char(c) if form.field == 1 => form.name.push(c),
+                    KeyCode::Char(c) if form.field == 2 => form.secret.push(c),
+                    KeyCode::Backspace if form.field == 1 => {
+                        form.name.pop();
+                    }
+                    KeyCode::Backspace if form.field == 2 => {
+                        form.secret.pop();
+                    }
+                    KeyCode::Enter if !form.secret.is_empty() => {
+                        let kind = CredentialKind::ALL[form.kind];
+                        let name = if form.name.trim().is_empty() {
+                            "default"
+                        } else {
+                            form.name.trim()
+                        };
+                        if let Some(credentials) = self.credentials.as_mut() {
+                            credentials.set(kind, name, form.secret.trim());
+                        }
+                        self.credential_form = CredentialForm::default();
+                        self.state = AppState::Credentials;
+                        self.status_message = String::from("Credentials");
+                        self.save_credentials();
+                    }
+                    KeyCode::Esc => {
+                        self.credential_form = CredentialForm::default();
+                        self.state = AppState::Credentials;
+                        self.status_message = String::from("Credentials");
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+
+        // Handle scrolling for output
+        match key.code {
+            KeyCode::PageUp if self.output_scroll > 0 => {
+                self.output_scroll = self.output_scroll.saturating_sub(1);
+            }
+            KeyCode::PageDown if self.visible_output().next().is_some() => {
+                self.output_scroll = self.output_scroll.saturating_add(1);
+            }
+            KeyCode::F(1) => self.show_docs = !self.show_docs,
+            KeyCode::F(2) => {
+                self.verbosity = self.verbosity.next();
+                self.output_scroll = 0;
+            }
+            KeyCode::F(6) => self.timestamps = self.timestamps.next(),
+            KeyCode::F(7) => self.toggle_progress_view(),
+            // Turned on from the welcome screen, skippable from anywhere
+            KeyCode::F(4) if self.learn || matches!(self.state, AppState::EnteringProjectName) => {
+                self.learn = !self.learn;
+            }
+            _ => {}
+        }
+
+        Ok(false)
+    }
+
+    /// Switches between the compact and detailed views and remembers the choice.
+    fn toggle_progress_view(&mut self) {
+        self.settings.progress_view = self.settings.progress_view.next();
+        self.settings_form.progress_view = self.settings.progress_view;
+        self.output_scroll = 0;
+        self.status_message = match self.settings.save() {
+            Ok(()) => format!("{} view", self.settings.progress_view.label()),
+            Err(e) => format!(
+                "{} view for this session only ({})",
+                self.settings.progress_view.label(),
+                e
+            ),
+        };
+    }
+
+    /// Runs the current install step and moves on to the next one.
+    fn advance_install(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let AppState::Installing(step) = &self.state else {
+            return Ok(());
+        };
+        crash::set_state(format!("{:?}", self.state));
+        let label = step.label();
+        let log_name = format!("{:02}-{}", step.number(), label);
+        let started = std::time::Instant::now();
+        let first_line = self.command_output.len();
+
+        let result = self.install_step(terminal);
+        let log = self.write_step_log(&log_name, first_line);
+        if result.is_err() {
+            self.failed_log = log;
+        }
+        result?;
+        self.summary.record_step(label, started.elapsed());
+        Ok(())
+    }
+
+    fn install_step(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let AppState::Installing(step) = &self.state else {
+            return Ok(());
+        };
+        match step {
+            InstallStep::CloningRepo => {
+                self.clone_repository(terminal)?;
+                self.state = AppState::Installing(InstallStep::SettingUpSparse);
+            }
+            InstallStep::SettingUpSparse => {
+                self.setup_sparse_checkout(terminal)?;
+                self.state = AppState::Installing(InstallStep::MovingFiles);
+            }
+            InstallStep::MovingFiles => {
+                self.move_files()?;
+                self.state = AppState::Installing(InstallStep::UpdatingDependencies);
+                if !self.ci && self.batch.is_none() {
+                    self.choose_versions();
+                }
+            }
+            InstallStep::UpdatingDependencies => {
+                self.update_dependencies()?;
+                self.generate_variant()?;
+                self.write_cargo_config()?;
+                self.state = AppState::Installing(InstallStep::SettingUpForge);
+            }
+            InstallStep::SettingUpForge => {
+                self.setup_forge(terminal)?;
+                self.run_post_install(terminal)?;
+                self.write_project_file()?;
+                networks::write_defaults(&self.project_root())?;
+                self.add_output(format!("Wrote {}", networks::FILE_NAME));
+                match tool_versions::write(&self.project_root(), self.settings.tool_versions) {
+                    Ok(Some(pinned)) => self.add_output(pinned),
+                    Ok(None) => {}
+                    Err(e) => self.add_output(format!("⚠ Could not pin tool versions: {}", e)),
+                }
+                self.project_created = true;
+                let project = RecentProject {
+                    path: self.summary.project_path.clone(),
+                    template: self.summary.template.clone(),
+                    branch: self.summary.branch.clone(),
+                    created: chrono::Utc::now().to_rfc3339(),
+                    last_test: None,
+                };
+                if let Err(e) = projects::record(project) {
+                    self.add_output(format!("⚠ Could not record the project: {}", e));
+                }
+                self.state = AppState::Success;
+                self.status_message =
+                    format!("✓ Project '{}' created successfully!", self.project_name);
+                self.emit(&self.status_message);
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs the commands the template's entry in `templates.toml` lists for a new project.
+    fn run_post_install(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        let commands = self
+            .template()
+            .map(|template| template.post_install.clone())
+            .unwrap_or_default();
+        let root = self.project_root();
+        for line in commands {
+            let mut words = line.split_whitespace();
+            let Some(program) = words.next() else {
+                continue;
+            };
+            self.run_command(
+                Command::new(program).args(words).current_dir(&root),
+                &format!("Running {}...", line),
+                terminal,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Writes the output since `first_line` to the step's own log file.
+    fn write_step_log(&mut self, name: &str, first_line: usize) -> Option<PathBuf> {
+        let output: Vec<String> = self
+            .command_output
+            .get(first_line..)
+            .unwrap_or_default()
+            .iter()
+            .map(|line| line.text.clone())
+            .collect();
+        match self.step_logs.write(name, &output) {
+            Ok(path) => Some(path),
+            Err(e) => {
+                self.add_output(format!("⚠ Could not write the step log: {}", e));
+                None
+            }
+        }
+    }
+
+    /// Non-interactive variant of [`App::run`] for CI: never prompts, prints timestamped plain
+    /// text instead of drawing, and fails fast with instructions when input would be required.
+    pub fn run_headless(&mut self) -> Result<Outcome> {
+        let mut report = junit::Suite::new("create-project");
+        let outcome = self.run_headless_steps(&mut report);
+        if !report.is_empty() {
+            self.test_report = Some(report);
+            self.finish_run();
+        }
+        outcome
+    }
+
+    fn run_headless_steps(&mut self, report: &mut junit::Suite) -> Result<Outcome> {
+        // Nothing is ever drawn in CI mode; the terminal only satisfies the step signatures
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(80, 24))?;
+
+        for (name, check) in [
+            ("check-rust", Self::check_rust as fn(&mut Self) -> bool),
+            ("check-foundry", Self::check_foundry),
+            ("check-risc0", Self::check_risc0),
+        ] {
+            let started = std::time::Instant::now();
+            let ok = check(self);
+            self.emit(&self.status_message);
+            if !ok {
+                report.fail_with(name, started.elapsed(), &self.status_message);
+                return Ok(Outcome::DependencyMissing);
+            }
+            report.pass(name, started.elapsed());
+        }
+
+        if self.batch.is_some() {
+            self.start_batch_entry();
+            return self.run_headless_batch(report, &mut terminal);
+        }
+
+        if self.project_name.is_empty() {
+            self.emit(
+                "✗ No project name given. Pass --name <NAME> when running non-interactively.",
+            );
+            return Ok(Outcome::MissingInput);
+        }
+        if self.project_root().exists() && !self.overwrite {
+            self.emit(&format!(
+                "✗ Directory '{}' already exists. Pass --overwrite to replace it.",
+                self.project_name
+            ));
+            return Ok(Outcome::MissingInput);
+        }
+
+        if self.cargo_template.is_some() {
+            return self.generate_headless(report, &mut terminal);
+        }
+
+        self.state = AppState::Installing(InstallStep::CloningRepo);
+        self.install_headless(report, &mut terminal)
+    }
+
+    /// Creates the project from the cargo-generate template, answering its prompts with their
+    /// defaults.
+    fn generate_headless(
+        &mut self,
+        report: &mut junit::Suite,
+        terminal: &mut Terminal<impl Backend>,
+    ) -> Result<Outcome> {
+        let started = std::time::Instant::now();
+        let result = self.fetch_cargo_template(terminal).and_then(|()| {
+            if let Some(CargoTemplate {
+                template: Some(template),
+                values,
+                ..
+            }) = &self.cargo_template
+            {
+                for (placeholder, value) in template.placeholders.iter().zip(values) {
+                    if let Some(problem) = placeholder.check(value) {
+                        return Err(color_eyre::eyre::eyre!(
+                            "{} has no usable default: {}",
+                            placeholder.name,
+                            problem
+                        ));
+                    }
+                }
+            }
+            self.generate_from_template(terminal)
+        });
+        match result {
+            Ok(()) => {
+                report.pass("generate", started.elapsed());
+                Ok(Outcome::Success)
+            }
+            Err(e) => {
+                report.fail("generate", started.elapsed(), &e);
+                self.emit(&format!("✗ Error: {}", e));
+                self.status_message = e.to_string();
+                Ok(Outcome::InstallFailed)
+            }
+        }
+    }
+
+    /// Runs the install steps of the project set up in `self.state`, reporting each.
+    fn install_headless(
+        &mut self,
+        report: &mut junit::Suite,
+        terminal: &mut Terminal<impl Backend>,
+    ) -> Result<Outcome> {
+        while let AppState::Installing(step) = &self.state {
+            let step = match self.batch {
+                Some(_) => format!("{}/{}", self.project_name, step.label()),
+                None => step.label().to_string(),
+            };
+            let started = std::time::Instant::now();
+            let result = self.advance_install(terminal);
+            match &result {
+                Ok(()) => report.pass(&step, started.elapsed()),
+                Err(e) => report.fail(&step, started.elapsed(), e),
+            }
+            if let Err(e) = result {
+                if signals::interrupted() {
+                    for line in self.interrupted_report() {
+                        self.emit(&line);
+                    }
+                    return Ok(Outcome::UserAbort);
+                }
+                match e.downcast_ref::<CommandError>() {
+                    Some(error) => {
+                        for line in error.report() {
+                            self.emit(&format!("✗ {}", line));
+                        }
+                    }
+                    None => self.emit(&format!("✗ Error: {}", e)),
+                }
+                if let Some(log) = &self.failed_log {
+                    self.emit(&format!("✗ Full log: {}", log.display()));
+                }
+                self.status_message = e.to_string();
+                return Ok(Outcome::InstallFailed);
+            }
+        }
+        Ok(Outcome::Success)
+    }
+
+    /// Creates every project of the batch, going on past the ones that fail.
+    fn run_headless_batch(
+        &mut self,
+        report: &mut junit::Suite,
+        terminal: &mut Terminal<impl Backend>,
+    ) -> Result<Outcome> {
+        while matches!(self.state, AppState::Installing(_)) {
+            match self.install_headless(report, terminal)? {
+                Outcome::Success => self.finish_batch_entry(None),
+                Outcome::UserAbort => return Ok(Outcome::UserAbort),
+                _ => self.finish_batch_entry(Some(self.status_message.clone())),
+            }
+        }
+        Ok(self.exit_outcome())
+    }
+
+    /// Sets up the next project of the batch and starts installing it, skipping any that
+    /// can't be created. Moves to the batch's results once there's none left.
+    fn start_batch_entry(&mut self) {
+        loop {
+            let Some(batch) = self.batch.as_mut() else {
+                return;
+            };
+            let Some(index) = batch.next_pending() else {
+                let created = batch.count(TaskStatus::Done);
+                let failed = batch.count(TaskStatus::Failed);
+                self.state = AppState::Batch;
+                self.status_message = match failed {
+                    0 => format!("✓ Created all {} projects · Esc: exit", created),
+                    _ => format!(
+                        "✗ Created {} of {} projects, {} failed · Esc: exit",
+                        created,
+                        created + failed,
+                        failed
+                    ),
+                };
+                self.emit(&self.status_message);
+                return;
+            };
+            let project = batch.start(index).clone();
+            let dir = batch.dir(&project);
+            match self.prepare_batch_project(&project, dir) {
+                Ok(()) => return,
+                Err(e) => self.finish_batch_entry(Some(e.to_string())),
+            }
+        }
+    }
+
+    fn prepare_batch_project(&mut self, project: &batch::Project, dir: PathBuf) -> Result<()> {
+        let template = project.template.as_deref().unwrap_or("erc20-counter");
+        let index = self
+            .template_choices
+            .iter()
+            .position(|(release, t)| {
+                t.name == template
+                    && project
+                        .branch
+                        .as_deref()
+                        .is_none_or(|b| b == release.branch)
+            })
+            .ok_or_else(|| {
+                color_eyre::eyre::eyre!(
+                    "{}{} can't be built with the installed toolchain",
+                    template,
+                    project
+                        .branch
+                        .as_ref()
+                        .map(|branch| format!(" on {}", branch))
+                        .unwrap_or_default()
+                )
+            })?;
+        self.choose_template(index);
+        self.base_dir = dir;
+        self.project_name = project.name.clone();
+        if self.project_root().exists() && !self.overwrite {
+            return Err(color_eyre::eyre::eyre!(
+                "already exists (pass --overwrite to replace it)"
+            ));
+        }
+        fs::create_dir_all(&self.base_dir)?;
+        self.project_created = false;
+        self.failed_log = None;
+        self.state = AppState::Installing(InstallStep::CloningRepo);
+        self.status_message = format!("Installing project '{}'...", self.project_name);
+        self.add_output(format!("Creating {}", self.project_root().display()));
+        Ok(())
+    }
+
+    /// Records how the project being created went and moves on to the next.
+    fn finish_batch_entry(&mut self, error: Option<String>) {
+        if let Some(error) = &error {
+            self.add_output(format!("✗ {}: {}", self.project_name, error));
+        }
+        if let Some(batch) = self.batch.as_mut() {
+            batch.finish(error);
+        }
+        self.start_batch_entry();
+    }
+
+    fn start_artifacts(&mut self) {
+        match artifacts::Run::start(&self.project_root()) {
+            Ok(run) => self.artifacts = Some(run),
+            Err(e) => self.add_output(format!("Could not create the artifacts directory: {}", e)),
+        }
+    }
+
+    /// Where anvil's output goes: the run's `anvil.log`, or nowhere outside a test run.
+    fn anvil_log(&self) -> (std::process::Stdio, std::process::Stdio) {
+        match &self.artifacts {
+            Some(run) => run.log("anvil.log"),
+            None => (std::process::Stdio::null(), std::process::Stdio::null()),
+        }
+    }
+
+    /// Wraps up a finished test run: writes its JUnit report, when `--junit` asked for one,
+    /// and gathers its artifacts.
+    fn finish_run(&mut self) {
+        let report = self.test_report.take();
+        if let (Some(report), Some(path)) = (&report, self.junit.clone()) {
+            match report.write(&path) {
+                Ok(()) => self.add_output(format!("Wrote JUnit report to {}", path.display())),
+                Err(e) => self.add_output(format!("Could not write JUnit report: {}", e)),
+            }
+        }
+
+        let Some(run) = self.artifacts.take() else {
+            return;
+        };
+        let record = self.run_record(report.as_ref());
+        let output: Vec<String> = self
+            .command_output
+            .iter()
+            .map(|line| line.text.clone())
+            .collect();
+        let dir = run
+            .dir
+            .strip_prefix(&self.base_dir)
+            .unwrap_or(&run.dir)
+            .display()
+            .to_string();
+        match run.collect(&self.project_root(), &output, record) {
+            Ok(kept) => {
+                self.add_output(format!("Artifacts saved to {} ({})", dir, kept.join(", ")))
+            }
+            Err(e) => self.add_output(format!("Could not save artifacts to {}: {}", dir, e)),
+        }
+    }
+
+    /// What the test history keeps about the run that just finished.
+    fn run_record(&self, report: Option<&junit::Suite>) -> artifacts::Record {
+        let kind = report.map_or("test", |report| report.name()).to_string();
+        let steps: Vec<artifacts::Step> = report
+            .map(|report| report.cases())
+            .unwrap_or_default()
+            .iter()
+            .map(|case| artifacts::Step {
+                name: case.name.clone(),
+                seconds: case.duration.as_secs_f64(),
+                passed: case.failure.is_none(),
+            })
+            .collect();
+        let output: Vec<String> = self
+            .command_output
+            .iter()
+            .map(|line| line.text.clone())
+            .collect();
+
+        let mut environment = std::collections::BTreeMap::new();
+        if let Some(env) = self.test_env.as_ref().filter(|_| kind == "end-to-end") {
+            environment.insert(String::from("prover"), env.prover.label().to_string());
+            environment.insert(String::from("RPC"), redact::redact(&env.eth_rpc_url));
+        }
+        environment.insert(
+            String::from("dev mode"),
+            String::from(if supervisor::dev_mode() { "on" } else { "off" }),
+        );
+        if let Some(branch) = projects::pinned_branch(&self.project_root()) {
+            environment.insert(String::from("risc0-ethereum"), branch);
+        }
+        for (tool, version) in tools::versions() {
+            if let Ok(version) = version {
+                environment.insert(tool.to_string(), version);
+            }
+        }
+
+        artifacts::Record {
+            id: String::new(),
+            kind,
+            passed: steps.iter().all(|step| step.passed),
+            steps,
+            proving_seconds: None,
+            cycles: benchmark::cycles(&output).0,
+            gas_used: None,
+            environment,
+        }
+    }
+
+    /// Prints a timestamped line in CI mode, where nothing is drawn.
+    fn emit(&self, line: &str) {
+        if self.ci {
+            println!(
+                "[{}] {}",
+                chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ"),
+                redact::redact(line)
+            );
+        }
+    }
+
+    fn redraw(&self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        if !self.ci && self.demo.as_ref().is_none_or(|demo| demo.frame_due()) {
+            terminal.draw(|frame| self.ui(frame))?;
+        }
+        Ok(())
+    }
+
+    pub fn run(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<Outcome> {
+        loop {
+            if let Some(dashboard) = &mut self.dashboard {
+                if let Some(latest) = dashboard.latest_check.as_ref().and_then(|rx| {
+                    match rx.try_recv() {
+                        Ok(latest) => Some(latest),
+                        // The lookup died without answering
+                        Err(mpsc::TryRecvError::Disconnected) => Some(None),
+                        Err(mpsc::TryRecvError::Empty) => None,
+                    }
+                }) {
+                    dashboard.latest = latest;
+                    dashboard.latest_check = None;
+                    self.pending_redraw = true;
+                }
+            }
+            if let Some(notice) = self.update_check.as_ref().and_then(|rx| rx.try_recv().ok()) {
+                self.update_notice = Some(notice);
+                self.update_check = None;
+            }
+            if signals::interrupted() {
+                self.status_message = String::from("Interrupted");
+                return Ok(Outcome::UserAbort);
+            }
+            let state = format!("{:?}", self.state);
+            // Hold each new screen long enough to read in a recording
+            if let Some(hold) = self.demo.as_mut().and_then(|demo| demo.entered(&state)) {
+                terminal.draw(|frame| self.ui(frame))?;
+                std::thread::sleep(hold);
+            }
+            crash::set_state(state);
+            // Keeps the stats of background children like anvil current while idle
+            if self.resources.sample() | self.poll_chain_monitor() | self.poll_self_update() {
+                self.pending_redraw = true;
+            }
+
+            if self.pending_redraw {
+                self.redraw(terminal)?;
+                self.pending_redraw = false;
+            }
+
+            // A scripted key stands in for one typed; otherwise check for events with a
+            // shorter timeout
+            let replayed = match &mut self.replay {
+                Some(script) => script.next(&self.status_message)?,
+                None => None,
+            };
+            if self
+                .replay
+                .as_ref()
+                .is_some_and(|script| script.is_finished())
+            {
+                self.replay = None;
+            }
+            let key = match replayed {
+                Some(key) => Some(key),
+                // ~60fps
+                None if event::poll(std::time::Duration::from_millis(16))? => {
+                    match event::read()? {
+                        Event::Key(key) => {
+                            if let Some(recorder) = &mut self.recorder {
+                                recorder.record(&key);
+                            }
+                            Some(key)
+                        }
+                        _ => None,
+                    }
+                }
+                None => None,
+            };
+            if let Some(key) = key {
+                if signals::is_interrupt_key(&key) {
+                    signals::interrupt();
+                    continue;
+                }
+                if self.handle_key_event(key)? {
+                    return Ok(self.exit_outcome());
+                }
+            }
+
+            match &self.state {
+                AppState::CheckingDependencies => {
+                    if !self.platform_checked {
+                        self.platform_checked = true;
+                        self.report_platform_problems();
+                    }
+                    if !self.rust_installed {
+                        self.rust_installed = self.check_rust();
+                    }
+                    if !self.foundry_installed {
+                        self.foundry_installed = self.check_foundry();
+                    }
+                    if self.risc0_version.is_none() {
+                        self.check_risc0();
+                    }
+
+                    if self.rust_installed && self.foundry_installed && self.risc0_version.is_some()
+                    {
+                        if self.batch.is_some() {
+                            self.start_batch_entry();
+                        } else if self.previous_session.is_some() {
+                            self.state = AppState::RestoringSession;
+                            self.status_message = String::from("Restore previous session?");
+                        } else {
+                            self.start_new_project();
+                        }
+                    }
+                }
+                AppState::InstallingCargoRisczero => {
+                    if let Err(e) = self.install_cargo_risczero(terminal) {
+                        self.status_message = format!("✗ Could not install cargo-risczero: {}", e);
+                        self.add_output(format!("Error: {}", e));
+                    }
+                    self.state = AppState::CheckingDependencies;
+                }
+                AppState::Installing(_) => {
+                    let result = self.advance_install(terminal);
+                    if self.batch.is_some() && !signals::interrupted() {
+                        match result {
+                            Err(e) => {
+                                if let Some(log) = self.failed_log.clone() {
+                                    self.add_output(format!("Full log: {}", log.display()));
+                                }
+                                self.finish_batch_entry(Some(e.to_string()))
+                            }
+                            Ok(()) if matches!(self.state, AppState::Success) => {
+                                self.finish_batch_entry(None)
+                            }
+                            Ok(()) => {}
+                        }
+                    } else if let Err(e) = result {
+                        if signals::interrupted() {
+                            continue;
+                        }
+                        self.last_error = e.downcast_ref::<CommandError>().cloned();
+                        self.status_message = format!("Error: {}", e);
+                        self.handle_error(terminal)?;
+                        return Ok(Outcome::InstallFailed);
+                    }
+                }
+                AppState::Success => {
+                    // Remove the automatic state transition on key press
+                    // The transition will now be handled in handle_key_event
+                }
+                AppState::FetchingCargoTemplate => {
+                    if let Err(e) = self.fetch_cargo_template(terminal) {
+                        self.status_message = format!("✗ Could not fetch the template: {}", e);
+                        self.add_output(format!("Error: {}", e));
+                        self.state = AppState::EnteringProjectName;
+                    }
+                }
+                AppState::GeneratingFromTemplate => {
+                    if let Err(e) = self.generate_from_template(terminal) {
+                        self.status_message = format!("✗ Could not generate the project: {}", e);
+                        self.add_output(format!("Error: {}", e));
+                        self.state = AppState::EnteringProjectName;
+                    }
+                }
+                AppState::CheckingContracts => {
+                    if let Err(e) = self.run_contract_checks(terminal) {
+                        self.status_message = format!("✗ Could not check the contracts: {}", e);
+                        self.add_output(format!("Error: {}", e));
+                    }
+                    self.state = AppState::ContractChecks;
+                }
+                AppState::CheckingHealth => {
+                    if let Err(e) = self.run_health_check(terminal) {
+                        self.status_message = format!("✗ Health check failed: {}", e);
+                        self.add_output(format!("Error: {}", e));
+                    }
+                    self.state = AppState::Health;
+                }
+                AppState::FetchingTemplate => match self.fetch_template(terminal) {
+                    Ok(()) => self.state = AppState::TemplateDrift,
+                    Err(e) => {
+                        self.status_message = format!("✗ Could not compare: {}", e);
+                        self.add_output(format!("Error: {}", e));
+                        self.state = AppState::TestMenu;
+                    }
+                },
+                AppState::Watching => self.run_watch_builds(terminal)?,
+                AppState::Events => {
+                    if let Some(log) = &mut self.event_log {
+                        if log.poll().is_some_and(|new| new > 0) || log.error.is_some() {
+                            self.pending_redraw = true;
+                        }
+                    }
+                }
+                AppState::Linting => {
+                    let result = self.run_lint(terminal);
+                    self.state = AppState::Lint;
+                    self.status_message = match (result, &self.lint) {
+                        (Err(e), _) => {
+                            self.add_output(format!("Error: {}", e));
+                            format!("✗ Clippy failed: {}", e)
+                        }
+                        (Ok(()), Some(view)) if view.diagnostics.is_empty() => {
+                            String::from("✓ Clippy found nothing")
+                        }
+                        (Ok(()), Some(view)) => format!(
+                            "Clippy: {} error(s), {} warning(s)",
+                            view.count(lint::Level::Error),
+                            view.count(lint::Level::Warning)
+                        ),
+                        (Ok(()), None) => String::new(),
+                    };
+                }
+                AppState::Formatting => {
+                    let report = self.run_format(terminal)?;
+                    self.status_message = match (report.check, report.files.len()) {
+                        _ if !report.errors.is_empty() => String::from("✗ A formatter failed"),
+                        (_, 0) => String::from("✓ Everything is formatted"),
+                        (true, n) => format!("✗ {} file(s) need formatting", n),
+                        (false, n) => format!("✓ Reformatted {} file(s)", n),
+                    };
+                    self.format_report = Some(report);
+                    self.state = AppState::Format;
+                }
+                AppState::RunningQueue => match self.run_queue_entry(terminal) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        self.status_message = String::from("✓ Queue finished");
+                        self.finish_run();
+                        self.state = AppState::Queue;
+                    }
+                    Err(e) => {
+                        self.status_message = format!(
+                            "✗ Queue stopped with {} action(s) left: {}",
+                            self.queue.pending(),
+                            e
+                        );
+                        self.add_output(format!("Error: {}", e));
+                        self.finish_run();
+                        self.state = AppState::Queue;
+                    }
+                },
+                AppState::Benchmarking => {
+                    match self.run_benchmark(terminal) {
+                        Ok(()) => {
+                            self.status_message = format!(
+                                "✓ Benchmark finished: {} run(s)",
+                                self.benchmark.results.len()
+                            )
+                        }
+                        Err(e) => {
+                            self.status_message = format!("✗ Benchmark stopped: {}", e);
+                            self.add_output(format!("Error: {}", e));
+                        }
+                    }
+                    self.benchmark.current = None;
+                    self.state = AppState::Benchmark;
+                }
+                AppState::UpgradingProject => {
+                    if let Some(path) = self.upgrade_target.take() {
+                        match self.run_project_upgrade(&path, terminal) {
+                            Ok(()) => self.add_output(format!("✓ Upgraded {}", path.display())),
+                            Err(e) => self.add_output(format!("✗ Upgrade failed: {}", e)),
+                        }
+                    }
+                    let from_welcome = self.dashboard.as_ref().is_some_and(|d| d.from_welcome);
+                    self.open_dashboard(from_welcome);
+                }
+                AppState::SubmittingBoundlessRequest => {
+                    if let Err(e) = self.submit_boundless_request(terminal) {
+                        self.status_message = format!("✗ Submitting the request failed: {}", e);
+                        self.add_output(format!("Error: {}", e));
+                    }
+                    self.state = AppState::BoundlessRequest;
+                }
+                AppState::SendingTransaction => {
+                    if let Err(e) = self.send_transaction(terminal) {
+                        self.status_message = format!("✗ Transaction failed: {}", e);
+                        self.add_output(format!("Error: {}", e));
+                    }
+                    self.state = AppState::TransactionBuilder;
+                }
+                AppState::SimulatingDeploy => {
+                    let first_line = self.command_output.len();
+                    match self.simulate_deploy(terminal) {
+                        Ok(()) => {
+                            self.state = AppState::ConfirmingDeploy;
+                            self.status_message =
+                                String::from("Review the simulated deployment before broadcasting");
+                        }
+                        Err(e) => {
+                            self.status_message = format!("✗ Simulation failed: {}", e);
+                            self.add_output(format!("Error: {}", e));
+                            if !self.offer_solc(first_line, AppState::SimulatingDeploy) {
+                                self.deployment = None;
+                                self.state = AppState::DeploySetup;
+                            }
+                        }
+                    }
+                }
+                AppState::InstallingSolc => match self.install_solc(terminal) {
+                    Ok(()) => {
+                        self.state = self.after_confirm.take().unwrap_or(AppState::TestMenu);
+                    }
+                    Err(e) => {
+                        self.status_message = format!("✗ Could not install solc: {}", e);
+                        self.add_output(format!("Error: {}", e));
+                        self.deployment = None;
+                        self.after_confirm = None;
+                        self.state = AppState::TestMenu;
+                    }
+                },
+                AppState::Deploying => {
+                    self.deployed = None;
+                    if let Err(e) = self.run_deploy(terminal) {
+                        self.status_message = format!("✗ Deployment failed: {}", e);
+                        self.add_output(format!("Error: {}", e));
+                    }
+                    self.after_broadcast(AppState::TestMenu);
+                }
+                AppState::RunningScript => {
+                    self.deployed = None;
+                    if let Err(e) = self.run_script(terminal) {
+                        self.status_message = format!("✗ Script failed: {}", e);
+                        self.add_output(format!("Error: {}", e));
+                    }
+                    self.after_broadcast(AppState::ScriptSetup);
+                }
+                AppState::Testing(step) => {
+                    let preparing = matches!(step, E2ETestStep::PreparingEnvironment);
+                    let step = step.label();
+                    if preparing {
+                        self.start_artifacts();
+                    }
+                    let started = std::time::Instant::now();
+                    let first_line = self.command_output.len();
+                    let result = self.handle_test_step(terminal);
+                    let log = self.write_step_log(&format!("e2e-{}", step), first_line);
+                    let report = self
+                        .test_report
+                        .get_or_insert_with(|| junit::Suite::new("end-to-end"));
+                    match &result {
+                        Ok(()) => report.pass(step, started.elapsed()),
+                        Err(e) => report.fail(step, started.elapsed(), e),
+                    }
+                    if let Err(e) = result {
+                        self.last_test_failed = true;
+                        let _ = projects::record_test(&self.project_root(), false);
+                        self.add_output(format!("Error: {}", e));
+                        if let Some(log) = log {
+                            self.add_output(format!("Full log: {}", log.display()));
+                        }
+                        self.cleanup_test();
+                        let retry = AppState::Testing(E2ETestStep::PreparingEnvironment);
+                        if !self.offer_solc(first_line, retry) {
+                            self.state = AppState::TestMenu;
+                        }
+                    }
+                    if !matches!(self.state, AppState::Testing(_)) {
+                        self.finish_run();
+                    }
+                }
+                AppState::Finished => break,
+                _ => {}
+            }
+
+            // Always draw at least once per loop
+            self.redraw(terminal)?;
+        }
+        Ok(self.exit_outcome())
+    }
+
+    /// Whether moving parts like spinners animate. They stand still with reduced motion, and in
+    /// demo mode so recordings come out the same every time.
+    fn animate(&self) -> bool {
+        self.demo.is_none() && !self.settings.reduced_motion
+    }
+
+    /// Whether the blinking text cursor is on right now. Always on when nothing animates.
+    fn cursor_shown(&self) -> bool {
+        !self.animate()
+            || (std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis()
+                / 500)
+                .is_multiple_of(2)
+    }
+
+    /// What learn mode explains on the current screen.
+    fn lesson(&self) -> Option<tutorial::Lesson> {
+        use tutorial::Lesson;
+        Some(match self.state {
+            AppState::EnteringProjectName => Lesson::Steel,
+            AppState::Installing(InstallStep::CloningRepo | InstallStep::SettingUpSparse) => {
+                Lesson::Template
+            }
+            AppState::Installing(InstallStep::MovingFiles) => Lesson::Layout,
+            AppState::Installing(InstallStep::UpdatingDependencies) => Lesson::Guest,
+            AppState::Installing(InstallStep::SettingUpForge) => Lesson::Verifier,
+            AppState::Success => Lesson::Toolbox,
+            AppState::EnteringBonsaiKey => Lesson::Bonsai,
+            AppState::Testing(_) => Lesson::EndToEnd,
+            _ => return None,
+        })
+    }
+
+    /// The docs page for the current screen.
+    fn doc(&self) -> Option<&'static str> {
+        Some(match self.state {
+            AppState::CheckingDependencies | AppState::InstallingCargoRisczero => {
+                docs::DEPENDENCIES
+            }
+            AppState::Installing(InstallStep::SettingUpForge) => docs::FORGE,
+            AppState::EnteringProjectName
+            | AppState::ChoosingExamples
+            | AppState::SelectingTemplate
+            | AppState::EnteringTemplateRepo
+            | AppState::ChoosingVersions
+            | AppState::Installing(_) => docs::TEMPLATE,
+            AppState::EnteringBonsaiKey => docs::BONSAI,
+            AppState::DeploySetup
+            | AppState::ConfirmingDeploy
+            | AppState::DeployedContracts
+            | AppState::ChoosingNetwork
+            | AppState::Scripts
+            | AppState::ScriptSetup => docs::DEPLOY,
+            AppState::Success | AppState::TestMenu => docs::TOOLBOX,
+            AppState::Settings => docs::SETTINGS,
+            _ => return None,
+        })
+    }
+
+    /// The lesson in a box in the bottom right corner of `area`, over whatever is there.
+    fn render_lesson(&self, frame: &mut Frame, area: Rect, lesson: tutorial::Lesson) {
+        let width = area.width.min(64);
+        let text_width = width.saturating_sub(4).max(1) as usize;
+        let mut lines = Vec::new();
+        let mut height = 0;
+        for paragraph in lesson.text() {
+            if !lines.is_empty() {
+                lines.push(Line::from(""));
+                height += 1;
+            }
+            height += paragraph.chars().count().div_ceil(text_width) as u16;
+            lines.push(Line::from(*paragraph));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from("F4: skip the tutorial").style(Style::default().fg(Color::Gray)));
+        let height = (height + 4).min(area.height.saturating_sub(2));
+        let popup = Rect {
+            x: area.x + area.width - width,
+            y: area.y + area.height.saturating_sub(height + 1),
+            width,
+            height,
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" Learn: {} ", lesson.title()));
+        frame.render_widget(Clear, popup);
+        frame.render_widget(
+            Paragraph::new(lines).block(block).wrap(Wrap { trim: true }),
+            popup,
+        );
+    }
+
+    /// Renders the user interface.
+    ///
+    /// This is where you add new widgets. See the following resources for more information:
+    /// - <https://docs.rs/ratatui/latest/ratatui/widgets/index.html>
+    /// - <https://github.com/ratatui/ratatui/tree/master/examples>
+    fn ui(&self, frame: &mut Frame) {
+        // The docs panel takes the right of the screen, everything else shares the left
+        let (area, docs_area) = match self.doc().filter(|_| self.show_docs) {
+            Some(page) if frame.area().width >= 80 => {
+                let [area, docs_area] =
+                    Layout::horizontal([Constraint::Percentage(64), Constraint::Percentage(36)])
+                        .areas(frame.area());
+                (area, Some((docs_area, page)))
+            }
+            _ => (frame.area(), None),
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Status line
+                Constraint::Min(3),    // Main content
+                Constraint::Length(1), // Help text
+            ])
+            .split(area);
+
+        // Render status line
+        let status = Paragraph::new(Line::from(vec![self.status_message().bold()]));
+        frame.render_widget(status, chunks[0]);
 
         match &self.state {
-            AppState::ConfirmOverwrite => match key.code {
-                KeyCode::Enter => {
-                    match self.confirm_menu_item {
-                        0 => {
-                            // Go to testing toolbox
-                            self.state = AppState::TestMenu;
-                            self.status_message = String::from("Select test to run:");
-                            self.command_output.clear();
+            AppState::EnteringBonsaiKey => {
+                let cursor_blink = self.cursor_shown();
+
+                let input_text = format!(
+                    "Bonsai API Key: {}{}",
+                    redact::mask_input(&self.bonsai_api_key),
+                    if cursor_blink { "█" } else { " " }
+                );
+
+                let mut content = vec![
+                    Line::from(format!("Prover: ◀ {} ▶", self.prover.label()))
+                        .style(Style::default().bold()),
+                    Line::from(""),
+                ];
+                match self.prover {
+                    ProverBackend::Bonsai => {
+                        content.push(Line::from(
+                            "Please enter your Bonsai API key to proceed with the end-to-end test.",
+                        ));
+                        content.push(Line::from(
+                            "This key is required to authenticate with the Bonsai service.",
+                        ));
+                        content.push(Line::from(""));
+                        content
+                            .push(Line::from(input_text).style(Style::default().fg(Color::Yellow)));
+                    }
+                    ProverBackend::Boundless => {
+                        content.push(Line::from(
+                            "The host submits proof requests to the Boundless market using the BOUNDLESS_* variables from your environment.",
+                        ));
+                        content.push(Line::from(
+                            "Requests it reports are followed until they're fulfilled.",
+                        ));
+                    }
+                    ProverBackend::Local => {
+                        content.push(Line::from("Proofs are generated on this machine."));
+                    }
+                }
+                content.push(Line::from(""));
+                content.push(Line::from(
+                    "Press Tab to change prover, Enter to continue, Esc to cancel",
+                ));
+
+                let input_block = Block::default().borders(Borders::ALL).title("Prover Setup");
+
+                let input = Paragraph::new(content)
+                    .block(input_block)
+                    .wrap(Wrap { trim: true });
+
+                frame.render_widget(input, chunks[1]);
+            }
+            AppState::ConfirmingSolcInstall => {
+                let version = self.solc_version.as_deref().unwrap_or_default();
+                let content = vec![
+                    Line::from(format!(
+                        "The build needs solc {}, which isn't installed.",
+                        version
+                    )),
+                    Line::from(""),
+                    Line::from(format!(
+                        "forge can download it (forge build --use {}) and the step runs again.",
+                        version
+                    )),
+                    Line::from(""),
+                    Line::from("Enter/y: install and retry · Esc/n: cancel")
+                        .style(Style::default().fg(Color::Yellow)),
+                ];
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title("Missing Solidity Compiler");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: true }),
+                    chunks[1],
+                );
+            }
+            AppState::ConfirmingPublicRpc => {
+                let rpc_url = &self.public_rpc_url;
+                let content = vec![
+                    Line::from("This would sign transactions with a well-known anvil key")
+                        .style(Style::default().fg(Color::Red).bold()),
+                    Line::from(format!("against a non-local RPC endpoint: {}", rpc_url))
+                        .style(Style::default().fg(Color::Red).bold()),
+                    Line::from(""),
+                    Line::from("This key is public. Anything sent to its address on a real"),
+                    Line::from("network can be taken by anyone. Only continue if this endpoint"),
+                    Line::from("is a private development chain."),
+                    Line::from(""),
+                    Line::from(format!("Type 'yes' to continue: {}█", self.confirm_input))
+                        .style(Style::default().fg(Color::Yellow)),
+                    Line::from(""),
+                    Line::from("Press Esc to cancel"),
+                ];
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Red))
+                    .title("⚠ Public RPC with Development Key");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: true }),
+                    chunks[1],
+                );
+            }
+            AppState::Dashboard => {
+                let mut content = Vec::new();
+                if let Some(dashboard) = &self.dashboard {
+                    let latest = match (&dashboard.latest, &dashboard.latest_check) {
+                        (Some(latest), _) => latest.clone(),
+                        (None, Some(_)) => String::from("checking..."),
+                        (None, None) if self.offline => String::from("unknown (offline)"),
+                        (None, None) => String::from("unknown"),
+                    };
+                    content.push(Line::from(format!(
+                        "Latest risc0-ethereum release: {}",
+                        latest
+                    )));
+                    content.push(Line::from(""));
+                    if dashboard.rows.is_empty() {
+                        content.push(Line::from(
+                            "No projects yet. Create one from the welcome screen.",
+                        ));
+                    }
+                    for (i, row) in dashboard.rows.iter().enumerate() {
+                        let selected = i == dashboard.selected;
+                        let branch = match (&row.branch, &dashboard.latest) {
+                            (Some(branch), Some(latest))
+                                if manifest::release_version(latest)
+                                    > manifest::release_version(branch) =>
+                            {
+                                format!("{} (↑ {})", branch, latest)
+                            }
+                            (Some(branch), _) => branch.clone(),
+                            (None, _) => String::from("unknown"),
+                        };
+                        let test = match &row.project.last_test {
+                            Some(test) if test.passed => {
+                                format!("✓ passed {}", test.at.get(..10).unwrap_or(&test.at))
+                            }
+                            Some(test) => {
+                                format!("✗ failed {}", test.at.get(..10).unwrap_or(&test.at))
+                            }
+                            None => String::from("– not tested"),
+                        };
+                        content.push(
+                            Line::from(format!(
+                                "{} {:<20} {:<28} {:<20} {}",
+                                if selected { "▶" } else { " " },
+                                row.project.name(),
+                                branch,
+                                test,
+                                if row.anvil_running {
+                                    "● anvil running"
+                                } else {
+                                    ""
+                                }
+                            ))
+                            .style(if selected {
+                                Style::default().fg(Color::Yellow).bold()
+                            } else {
+                                Style::default()
+                            }),
+                        );
+                        if selected {
+                            content.push(
+                                Line::from(format!("    {}", row.project.path.display()))
+                                    .style(Style::default().fg(Color::Gray)),
+                            );
                         }
-                        1 => {
-                            // Continue (overwrite)
-                            self.state = AppState::Installing(InstallStep::CloningRepo);
-                            self.status_message =
-                                format!("Installing project '{}'...", self.project_name);
+                    }
+                }
+                content.push(Line::from(""));
+                content.push(Line::from(
+                    "↑↓: select · Enter: open · t: test · h: health · u: upgrade · r: refresh · Esc: back",
+                ));
+
+                let block = Block::default().borders(Borders::ALL).title("Projects");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::ChoosingNetwork => {
+                let mut content = Vec::new();
+                for (i, network) in self.networks.iter().enumerate() {
+                    let selected = i == self.network_selected;
+                    let line = Line::from(format!(
+                        "{} {:<16} {:<18} chain id {:<10}{}",
+                        if selected { "▶" } else { " " },
+                        network.name,
+                        network.chain.label(),
+                        network.chain.chain_id(),
+                        if self
+                            .network
+                            .as_ref()
+                            .is_some_and(|n| n.name == network.name)
+                        {
+                            " (current)"
+                        } else {
+                            ""
+                        }
+                    ));
+                    content.push(if selected {
+                        line.style(Style::default().fg(Color::Yellow).bold())
+                    } else {
+                        line
+                    });
+                }
+                if let Some(network) = self.networks.get(self.network_selected) {
+                    content.push(Line::from(""));
+                    content.push(Line::from(format!(
+                        "RPC              {}",
+                        network
+                            .rpc_url
+                            .as_deref()
+                            .unwrap_or("from Settings, else the chain's public endpoint")
+                    )));
+                    content.push(Line::from(format!(
+                        "Wallet           {}",
+                        network
+                            .wallet
+                            .as_deref()
+                            .unwrap_or("none; chosen when deploying")
+                    )));
+                    content.push(Line::from(format!(
+                        "Verifier router  {}",
+                        network.verifier_router().unwrap_or_else(|| String::from(
+                            "none; deploy scripts bring their own verifier"
+                        ))
+                    )));
+                }
+                content.push(Line::from(""));
+                content.push(
+                    Line::from(if self.project_root().join(networks::FILE_NAME).exists() {
+                        format!("From {} in the project.", networks::FILE_NAME)
+                    } else {
+                        format!(
+                            "The defaults; add {} to the project to define your own.",
+                            networks::FILE_NAME
+                        )
+                    })
+                    .style(Style::default().fg(Color::Gray)),
+                );
+                content.push(Line::from(""));
+                content.push(Line::from("↑↓: select · Enter: continue · Esc: back"));
+
+                let block = Block::default().borders(Borders::ALL).title("Network");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::ChoosingVersions => {
+                let mut content = Vec::new();
+                if let Some(form) = &self.version_form {
+                    content.push(Line::from(
+                        "The risc0-ethereum version each crate is taken from:",
+                    ));
+                    content.push(Line::from(""));
+                    for (index, krate) in form.release.crates.iter().enumerate() {
+                        let selected = index == form.field;
+                        let (version, hint, fits) = match form.tag(index) {
+                            Some(tag) => {
+                                let (fits, hint) = compat::tag_hint(form.release, tag);
+                                (tag.to_string(), hint, fits)
+                            }
+                            None => (
+                                String::from("branch head"),
+                                format!("latest of {}", form.release.branch),
+                                true,
+                            ),
+                        };
+                        let line = Line::from(format!(
+                            "{} {:<26} ◀ {:<12} ▶  {}{}",
+                            if selected { "▶" } else { " " },
+                            krate.name,
+                            version,
+                            if fits { "" } else { "⚠ " },
+                            hint
+                        ));
+                        content.push(match (selected, fits) {
+                            (true, _) => line.style(Style::default().fg(Color::Yellow).bold()),
+                            (false, false) => line.style(Style::default().fg(Color::Red)),
+                            (false, true) => line,
+                        });
+                    }
+                    content.push(Line::from(""));
+                    if form.mixed() {
+                        content.push(
+                            Line::from(
+                                "⚠ The crates come from different revisions; cargo builds risc0-ethereum once for each, which may not resolve.",
+                            )
+                            .style(Style::default().fg(Color::Yellow)),
+                        );
+                    }
+                    content.push(
+                        Line::from(
+                            "Tags pin an exact release; the branch head picks up fixes as they land. The contracts in lib/risc0-ethereum stay on the branch.",
+                        )
+                        .style(Style::default().fg(Color::Gray)),
+                    );
+                    content.push(Line::from(""));
+                    content.push(Line::from(
+                        "↑↓: crate · ←/→: version · a: same for all · Enter: continue · Esc: branch heads",
+                    ));
+                }
+
+                let block = Block::default().borders(Borders::ALL).title("Versions");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::EnteringTemplateRepo => {
+                let form = &self.repo_form;
+                let field = |index: usize, label: &str, value: &str| {
+                    let line = Line::from(format!(
+                        "{} {:<8} {}{}",
+                        if form.field == index { ">" } else { " " },
+                        label,
+                        value,
+                        if form.field == index { "█" } else { "" }
+                    ));
+                    if form.field == index {
+                        line.style(Style::default().fg(Color::Yellow))
+                    } else {
+                        line
+                    }
+                };
+                let content = vec![
+                    field(0, "Git URL", &form.url),
+                    field(1, "Branch", &form.branch),
+                    Line::from(""),
+                    Line::from(
+                        "The repository needs risc0-ethereum's layout: the templates under examples/, the crates they use by path. Those crates are then taken from it too. Leave the URL empty for risc0-ethereum.",
+                    )
+                    .style(Style::default().fg(Color::Gray)),
+                    Line::from(""),
+                    Line::from("Tab: next field · Enter: use · Esc: cancel"),
+                ];
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title("Template Repository");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::SelectingTemplate => {
+                let mut content = Vec::new();
+                for (i, (_, template)) in self.template_choices.iter().enumerate() {
+                    let selected = i == self.template_selected;
+                    let line = Line::from(format!(
+                        "{} {:<14} {:<12} {}{}",
+                        if selected { "▶" } else { " " },
+                        template.name,
+                        template.branch,
+                        template.description,
+                        if i == self.template_choice {
+                            " (current)"
+                        } else {
+                            ""
+                        }
+                    ));
+                    content.push(if selected {
+                        line.style(Style::default().fg(Color::Yellow).bold())
+                    } else {
+                        line
+                    });
+                }
+                content.push(Line::from(""));
+                content.push(
+                    Line::from(
+                        "Only releases the installed RISC0 toolchain builds are listed, newest first.",
+                    )
+                    .style(Style::default().fg(Color::Gray)),
+                );
+                content.push(Line::from(""));
+                content.push(Line::from("↑↓: select · Enter: use · Esc: back"));
+
+                let block = Block::default().borders(Borders::ALL).title("Templates");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::ChoosingExamples => {
+                let mut content = Vec::new();
+                for (i, example) in self.combinable_examples().iter().enumerate() {
+                    let selected = i == self.example_selected;
+                    let picked = self.summary.examples.iter().any(|e| e == example);
+                    let line = Line::from(format!(
+                        "{} [{}] {}",
+                        if selected { "▶" } else { " " },
+                        if picked { "x" } else { " " },
+                        example
+                    ));
+                    content.push(if selected {
+                        line.style(Style::default().fg(Color::Yellow).bold())
+                    } else {
+                        line
+                    });
+                }
+                content.push(Line::from(""));
+                content.push(
+                    Line::from(
+                        "Each example gets a directory of its own in one Cargo workspace, and they share lib/. Picking none uses the template alone.",
+                    )
+                    .style(Style::default().fg(Color::Gray)),
+                );
+                content.push(Line::from(""));
+                content.push(Line::from(
+                    "↑↓: select · Space: pick · Enter: done · Esc: back",
+                ));
+
+                let block = Block::default().borders(Borders::ALL).title("Examples");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::ChoosingChain => {
+                let mut content = Vec::new();
+                for (i, chain) in ChainPreset::ALL.iter().enumerate() {
+                    let selected = i == self.chain_selected;
+                    let line = Line::from(format!(
+                        "{} {:<18} chain id {:<10}{}",
+                        if selected { "▶" } else { " " },
+                        chain.label(),
+                        chain.chain_id(),
+                        if *chain == self.chain() {
+                            " (current)"
+                        } else {
+                            ""
+                        }
+                    ));
+                    content.push(if selected {
+                        line.style(Style::default().fg(Color::Yellow).bold())
+                    } else {
+                        line
+                    });
+                }
+                let chain = ChainPreset::ALL[self.chain_selected];
+                content.push(Line::from(""));
+                content.push(Line::from(format!(
+                    "Default RPC      {}",
+                    chain.default_rpc()
+                )));
+                content.push(Line::from(format!(
+                    "Explorer         {}",
+                    chain.explorer().unwrap_or("none")
+                )));
+                content.push(Line::from(format!(
+                    "Verifier router  {}",
+                    chain
+                        .verifier_router()
+                        .unwrap_or("none; deploy scripts bring their own verifier")
+                )));
+                content.push(Line::from(""));
+                content.push(
+                    Line::from(
+                        "Passed to scripts as CHAIN_ID, EXPLORER_URL and VERIFIER_ROUTER_ADDRESS unless you export them. An RPC provider from Settings or ETH_RPC_URL overrides the default RPC.",
+                    )
+                    .style(Style::default().fg(Color::Gray)),
+                );
+                content.push(Line::from(""));
+                content.push(Line::from("↑↓: select · Enter: use this chain · Esc: back"));
+
+                let block = Block::default().borders(Borders::ALL).title("Chain");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::AnvilControls => {
+                let mut content = Vec::new();
+                if let Some(panel) = &self.anvil_panel {
+                    content.push(match &panel.latest {
+                        Ok((number, timestamp)) => Line::from(format!(
+                            "Block {} · {}",
+                            number,
+                            chrono::DateTime::from_timestamp(*timestamp as i64, 0)
+                                .map(|time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                                .unwrap_or_else(|| timestamp.to_string())
+                        ))
+                        .bold(),
+                        Err(e) => {
+                            Line::from(format!("✗ {}", e)).style(Style::default().fg(Color::Red))
+                        }
+                    });
+                    content.push(Line::from(""));
+                    let fields = [
+                        ("Mine blocks", &panel.blocks, "blocks, mined right away"),
+                        (
+                            "Increase time",
+                            &panel.seconds,
+                            "seconds, then a block is mined",
+                        ),
+                        (
+                            "Interval mining",
+                            &panel.interval,
+                            "seconds per block, 0 = every tx",
+                        ),
+                        (
+                            "Impersonate",
+                            &panel.address,
+                            "address; Enter again to stop",
+                        ),
+                    ];
+                    for (i, (label, value, hint)) in fields.iter().enumerate() {
+                        let focused = panel.field == i;
+                        let line = Line::from(format!(
+                            "{} {:<16} {:<44} {}",
+                            if focused { ">" } else { " " },
+                            label,
+                            value,
+                            hint
+                        ));
+                        content.push(if focused {
+                            line.style(Style::default().fg(Color::Yellow))
+                        } else {
+                            line
+                        });
+                    }
+                    if !panel.impersonating.is_empty() {
+                        content.push(Line::from(""));
+                        content.push(Line::from(format!(
+                            "Impersonating: {}",
+                            panel.impersonating.join(", ")
+                        )));
+                    }
+                    match &panel.last {
+                        Some(Ok(done)) => {
+                            content.push(Line::from(""));
+                            content.push(
+                                Line::from(format!("✓ {}", done))
+                                    .style(Style::default().fg(Color::Green)),
+                            );
+                        }
+                        Some(Err(e)) => {
+                            content.push(Line::from(""));
+                            content.push(
+                                Line::from(format!("✗ {}", e))
+                                    .style(Style::default().fg(Color::Red)),
+                            );
+                        }
+                        None => {}
+                    }
+                }
+                content.push(Line::from(""));
+                content.push(Line::from("Tab: next field · Enter: apply · Esc: back"));
+
+                let block = Block::default().borders(Borders::ALL).title(format!(
+                    "Anvil at {}",
+                    self.anvil_panel
+                        .as_ref()
+                        .map_or("", |panel| panel.chain.rpc_url())
+                ));
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::Format => {
+                let mode = if self.format_check {
+                    "Check only: report files that need formatting, change nothing"
+                } else {
+                    "Format: run cargo fmt --all and forge fmt on the project"
+                };
+                let mut content = vec![
+                    Line::from(format!("Mode: ◀ {} ▶", mode)).style(Style::default().bold()),
+                    Line::from(""),
+                ];
+                if let Some(report) = &self.format_report {
+                    content.push(
+                        Line::from(match (report.check, report.files.len()) {
+                            (_, 0) if report.errors.is_empty() => {
+                                String::from("✓ Everything is formatted")
+                            }
+                            (true, n) => format!("{} file(s) need formatting", n),
+                            (false, n) => format!("{} file(s) reformatted", n),
+                        })
+                        .bold(),
+                    );
+                    for (tool, file) in &report.files {
+                        content.push(
+                            Line::from(format!("  {:<10} {}", tool.label(), file.display())).style(
+                                Style::default().fg(if report.check {
+                                    Color::Yellow
+                                } else {
+                                    Color::Green
+                                }),
+                            ),
+                        );
+                    }
+                    for error in &report.errors {
+                        content.push(
+                            Line::from(format!("✗ {}", error))
+                                .style(Style::default().fg(Color::Red)),
+                        );
+                    }
+                    content.push(Line::from(""));
+                }
+                content.push(Line::from("←→: mode · Enter: run · Esc: back"));
+
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("Format '{}'", self.project_name));
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::Benchmark => {
+                let form = &self.benchmark;
+                let bonsai = self.bonsai_key().is_some() && !self.offline;
+                let field = |index: usize, label: &str, value: String| {
+                    let line = Line::from(format!(
+                        "{} {:<22} {}",
+                        if form.field == index { ">" } else { " " },
+                        label,
+                        value
+                    ));
+                    if form.field == index {
+                        line.style(Style::default().fg(Color::Yellow))
+                    } else {
+                        line
+                    }
+                };
+                let mut content = vec![
+                    field(0, "Runs per backend", format!("◀ {} ▶", form.runs)),
+                    Line::from(""),
+                ];
+                for (i, backend) in benchmark::Backend::ALL.iter().enumerate() {
+                    let value = match backend {
+                        benchmark::Backend::Bonsai if !bonsai => String::from("[ ] (no API key)"),
+                        _ if form.backends[i] => String::from("[x]"),
+                        _ => String::from("[ ]"),
+                    };
+                    content.push(field(i + 1, backend.label(), value));
+                }
+                content.push(
+                    Line::from("  Each run is the whole end-to-end script against a local Anvil.")
+                        .style(Style::default().fg(Color::Gray)),
+                );
+                content.push(Line::from(""));
+
+                let summaries = benchmark::summarize(&form.results);
+                if !summaries.is_empty() {
+                    content.push(Line::from(benchmark::Summary::header()).bold());
+                    for summary in &summaries {
+                        let line = Line::from(summary.row());
+                        content.push(if summary.passed < summary.runs {
+                            line.style(Style::default().fg(Color::Red))
+                        } else {
+                            line
+                        });
+                    }
+                    if let Some(fastest) = summaries
+                        .iter()
+                        .filter(|s| s.backend != benchmark::Backend::Dev)
+                        .filter_map(|s| s.wall.map(|(mean, _, _)| (mean, s.backend)))
+                        .min_by_key(|(mean, _)| *mean)
+                    {
+                        content.push(Line::from(""));
+                        content.push(
+                            Line::from(format!(
+                                "Fastest real prover: {} ({:.1}s per run)",
+                                fastest.1.label(),
+                                fastest.0.as_secs_f64()
+                            ))
+                            .style(Style::default().fg(Color::Green)),
+                        );
+                    }
+                    content.push(Line::from(""));
+                }
+                content.push(Line::from(
+                    "Tab: next field · ←→: runs · Space: toggle backend · Enter: run · Esc: back",
+                ));
+
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title("Proving Benchmark");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::BoundlessRequest | AppState::SubmittingBoundlessRequest => {
+                let form = &self.boundless_form;
+                let field = |index: usize, label: &str, value: String| {
+                    let line = Line::from(format!(
+                        "{} {:<8} {}",
+                        if form.field == index { ">" } else { " " },
+                        label,
+                        value
+                    ));
+                    if form.field == index {
+                        line.style(Style::default().fg(Color::Yellow))
+                    } else {
+                        line
+                    }
+                };
+                let guest = match self.guest_images.get(form.guest) {
+                    Some(image) => format!(
+                        "◀ {} ▶{}",
+                        image.name,
+                        if image.elf_path.is_none() {
+                            " (no ELF)"
+                        } else {
+                            ""
+                        }
+                    ),
+                    None => String::from("no guests built yet"),
+                };
+                let content = vec![
+                    field(0, "Guest", guest),
+                    field(1, "Input", form.input.clone()),
+                    Line::from("  A file with the bytes the guest reads from stdin")
+                        .style(Style::default().fg(Color::Gray)),
+                    Line::from(""),
+                    Line::from(
+                        "Submits with the boundless CLI, which reads BOUNDLESS_MARKET_ADDRESS, RPC_URL and PRIVATE_KEY",
+                    )
+                    .style(Style::default().fg(Color::Gray)),
+                    Line::from(""),
+                    Line::from("Tab: next field · ←→: guest · Enter: submit · Esc: back"),
+                ];
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(
+                            Block::default()
+                                .borders(Borders::ALL)
+                                .title("Boundless Proof Request"),
+                        )
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::InspectingReceipt => {
+                let form = &self.inspect_form;
+                let field = |index: usize, label: &str, value: String| {
+                    let line = Line::from(format!(
+                        "{} {:<14} {}",
+                        if form.field == index { ">" } else { " " },
+                        label,
+                        value
+                    ));
+                    if form.field == index {
+                        line.style(Style::default().fg(Color::Yellow))
+                    } else {
+                        line
+                    }
+                };
+                let mut content = vec![
+                    field(0, "File", form.path.clone()),
+                    Line::from("  A receipt saved as JSON, or a journal as hex or raw bytes")
+                        .style(Style::default().fg(Color::Gray)),
+                    field(
+                        1,
+                        "Journal type",
+                        format!("◀ {} ▶", form.journal_type().unwrap_or("raw bytes (hex)")),
+                    ),
+                    field(2, "Verifier", form.verifier.clone()),
+                    Line::from(format!(
+                        "  Checks the seal with verify() at {}; leave empty to skip",
+                        self.rpc_url()
+                    ))
+                    .style(Style::default().fg(Color::Gray)),
+                    Line::from(""),
+                ];
+                match &form.result {
+                    Some(Ok(inspection)) => {
+                        content.push(Line::from(format!("From a {}", inspection.source)).bold());
+                        for check in &inspection.checks {
+                            match &check.outcome {
+                                Ok(detail) => content.push(
+                                    Line::from(format!("✓ {:<16} {}", check.check, detail))
+                                        .style(Style::default().fg(Color::Green)),
+                                ),
+                                Err(problem) => {
+                                    content.push(
+                                        Line::from(format!(
+                                            "✗ {:<16} {}",
+                                            check.check, problem.detail
+                                        ))
+                                        .style(Style::default().fg(Color::Red)),
+                                    );
+                                    content.push(
+                                        Line::from(format!("  {:<16} → {}", "", problem.fix))
+                                            .style(Style::default().fg(Color::Yellow)),
+                                    );
+                                }
+                            }
                         }
-                        2 => {
-                            // Exit
-                            return Ok(true);
+                        content.push(Line::from(""));
+                        content.push(Line::from("Committed values").bold());
+                        let decoded = match (form.journal_type(), &form.structs) {
+                            (Some(name), Some(structs)) => structs
+                                .decode(name, &inspection.journal)
+                                .map_err(|e| e.to_string()),
+                            _ => Ok(vec![format!(
+                                "0x{}",
+                                alloy::hex::encode(&inspection.journal)
+                            )]),
+                        };
+                        match decoded {
+                            Ok(lines) => content
+                                .extend(lines.into_iter().map(|l| Line::from(format!("  {}", l)))),
+                            Err(e) => content.push(
+                                Line::from(format!("  ✗ {}", e))
+                                    .style(Style::default().fg(Color::Red)),
+                            ),
                         }
-                        _ => {}
                     }
+                    Some(Err(e)) => content.push(
+                        Line::from(format!("✗ {}", e)).style(Style::default().fg(Color::Red)),
+                    ),
+                    None => {}
                 }
-                KeyCode::Up => {
-                    self.confirm_menu_item = self.confirm_menu_item.saturating_sub(1);
-                }
-                KeyCode::Down => {
-                    self.confirm_menu_item = (self.confirm_menu_item + 1).min(2);
-                }
-                KeyCode::Esc => return Ok(true),
-                _ => {}
-            },
-            AppState::Success => match key.code {
-                KeyCode::Enter => {
-                    self.state = AppState::TestMenu;
-                    self.status_message = String::from("Select test to run:");
-                    self.command_output.clear();
+                content.push(Line::from(""));
+                content.push(Line::from(
+                    "Tab: next field · ←→: journal type · Enter: load · Esc: back",
+                ));
+
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title("Receipt Inspector");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::GuestImages => {
+                let mut content = Vec::new();
+                if self.guest_images.is_empty() {
+                    content.push(Line::from(
+                        "No guests found. The ImageID.sol library is generated by cargo build.",
+                    ));
                 }
-                KeyCode::Esc => return Ok(true),
-                _ => {}
-            },
-            AppState::EnteringProjectName => match key.code {
-                KeyCode::Enter => {
-                    if !self.project_name.is_empty() {
-                        if Path::new(&self.project_name).exists() {
-                            self.state = AppState::ConfirmOverwrite;
-                            self.status_message = String::from("Directory exists. Overwrite?");
-                        } else {
-                            self.state = AppState::Installing(InstallStep::CloningRepo);
-                            self.status_message =
-                                format!("Installing project '{}'...", self.project_name);
-                        }
+                for image in &self.guest_images {
+                    content.push(Line::from(image.name.clone()).bold());
+                    content.push(Line::from(format!("  Image ID   {}", image.image_id)));
+                    content.push(Line::from(format!("  Solidity   {}", image.id_constant)));
+                    if let Some(constant) = &image.elf_constant {
+                        content.push(Line::from(format!("             {}", constant)));
                     }
+                    content.push(Line::from(match (&image.elf_path, image.elf_size) {
+                        (Some(path), Some(size)) => format!(
+                            "  ELF        {} ({})",
+                            path.display(),
+                            resources::format_bytes(size)
+                        ),
+                        (Some(path), None) => format!("  ELF        {} (missing)", path.display()),
+                        (None, _) => String::from("  ELF        not listed in Elf.sol"),
+                    }));
+                    content.push(Line::from(""));
                 }
-                KeyCode::Char(c) => {
-                    self.project_name.push(c);
+                content.push(Line::from("r: refresh · Esc: back"));
+
+                let block = Block::default().borders(Borders::ALL).title("Guest Images");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::TestHistory => {
+                let view = &self.history;
+                let mut content = Vec::new();
+                if view.runs.is_empty() {
+                    content.push(Line::from(
+                        "No recorded runs yet. End-to-end tests and queue runs are recorded under artifacts/.",
+                    ));
                 }
-                KeyCode::Backspace => {
-                    self.project_name.pop();
+                for (i, run) in view.runs.iter().enumerate() {
+                    let line = Line::from(format!(
+                        "{} [{}] {}  {:<10} {} {:>8.1}s  {}",
+                        if i == view.selected { "▶" } else { " " },
+                        if view.marked.contains(&i) { "x" } else { " " },
+                        run.id,
+                        run.kind,
+                        if run.passed { "✓" } else { "✗" },
+                        run.seconds(),
+                        run.environment
+                            .get("prover")
+                            .map(String::as_str)
+                            .unwrap_or_default()
+                    ));
+                    content.push(match run.passed {
+                        _ if i == view.selected => line.bold(),
+                        false => line.style(Style::default().fg(Color::Red)),
+                        true => line,
+                    });
                 }
-                KeyCode::Esc => return Ok(true),
-                _ => {}
-            },
-            AppState::TestMenu => match key.code {
-                KeyCode::Enter => {
-                    match self.selected_menu_item {
-                        0 => {
-                            // Run end-to-end test
-                            self.state = AppState::EnteringBonsaiKey;
-                            self.status_message = String::from("Please enter your Bonsai API key");
-                            self.bonsai_api_key.clear();
-                        }
-                        1 => {
-                            // Exit
-                            return Ok(true);
-                        }
-                        _ => {}
+                content.push(Line::from(""));
+                if let Some((older, newer)) = view.pair() {
+                    let mut lines = history::compare(older, newer).into_iter();
+                    if let Some(header) = lines.next() {
+                        content.push(Line::from(header).bold());
                     }
+                    content.extend(lines.map(Line::from));
+                    content.push(Line::from(""));
                 }
-                KeyCode::Up => {
-                    self.selected_menu_item = self.selected_menu_item.saturating_sub(1);
+                content.push(Line::from(
+                    "↑↓: select · Space: pick two runs to compare · Esc: back",
+                ));
+
+                let block = Block::default().borders(Borders::ALL).title("Test History");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::Queue => {
+                let mut content = vec![
+                    Line::from(format!(
+                        "Add: ◀ {} ▶",
+                        queue::Action::ALL[self.queue_action].label()
+                    ))
+                    .style(Style::default().fg(Color::Yellow)),
+                    Line::from(""),
+                ];
+                if self.queue.entries.is_empty() {
+                    content.push(Line::from(
+                        "Nothing queued. Press a to add the action above, or p for build → forge build → e2e (dev mode) → forge test.",
+                    ));
                 }
-                KeyCode::Down => {
-                    self.selected_menu_item = (self.selected_menu_item + 1).min(1);
+                for (i, entry) in self.queue.entries.iter().enumerate() {
+                    let line = Line::from(format!(
+                        "{} {}",
+                        if i == self.queue.selected { "▶" } else { " " },
+                        entry.describe()
+                    ));
+                    content.push(match entry.status {
+                        _ if i == self.queue.selected => line.bold(),
+                        TaskStatus::Done => line.style(Style::default().fg(Color::Green)),
+                        TaskStatus::Failed => line.style(Style::default().fg(Color::Red)),
+                        _ => line,
+                    });
                 }
-                KeyCode::Esc => return Ok(true),
-                _ => {}
-            },
-            AppState::EnteringBonsaiKey => {
-                match key.code {
-                    KeyCode::Enter if !self.bonsai_api_key.is_empty() => {
-                        self.state = AppState::Testing(E2ETestStep::PreparingEnvironment);
-                        self.status_message = String::from("Starting end-to-end test...");
-                        self.test_env = Some(TestEnvironment {
-                            eth_rpc_url: String::from("http://localhost:8545"),
-                            eth_wallet_address: String::from("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"),
-                            eth_wallet_private_key: String::from("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"),
-                            bonsai_api_key: self.bonsai_api_key.clone(),
-                            bonsai_api_url: String::from("https://api.bonsai.xyz"),
-                            anvil_process: None,
-                        });
+                content.push(Line::from(""));
+                content.push(Line::from(
+                    "←/→: choose · a: add · p: add preset · ↑↓: select · u/d: move · x: remove · c: clear finished · Enter: run · Esc: back",
+                ));
+
+                let block = Block::default().borders(Borders::ALL).title("Task Queue");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::TransactionBuilder => {
+                let form = &self.tx_form;
+                let field = |index: usize, label: &str, value: String| {
+                    let line = Line::from(format!(
+                        "{} {:<20} {}",
+                        if form.field == index { ">" } else { " " },
+                        label,
+                        value
+                    ));
+                    if form.field == index {
+                        line.style(Style::default().fg(Color::Yellow))
+                    } else {
+                        line
                     }
-                    KeyCode::Char(c) => {
-                        self.bonsai_api_key.push(c);
+                };
+                let mut content = Vec::new();
+                if let (Some(contract), Some(function)) =
+                    (form.selected_contract(), form.selected_function())
+                {
+                    let view = transaction::is_view(function);
+                    content.push(field(
+                        0,
+                        "Contract",
+                        format!("◀ {} at {} ▶", contract.name, contract.address),
+                    ));
+                    content.push(field(
+                        1,
+                        "Function",
+                        format!(
+                            "◀ {} ▶{}",
+                            function.signature(),
+                            if view { " (view)" } else { "" }
+                        ),
+                    ));
+                    for (i, label) in transaction::param_labels(function).iter().enumerate() {
+                        content.push(field(2 + i, label, form.args[i].clone()));
                     }
-                    KeyCode::Backspace => {
-                        self.bonsai_api_key.pop();
+                    let args = form.args.len();
+                    if transaction::is_payable(function) {
+                        content.push(field(2 + args, "Value (ether)", form.value.clone()));
                     }
-                    KeyCode::Esc => {
-                        self.state = AppState::TestMenu;
-                        self.status_message = String::from("Test cancelled");
+                    if !view {
+                        let kind = form.kind();
+                        content.push(field(3 + args, "Signer", format!("◀ {} ▶", kind.label())));
+                        if let Some(label) = kind.detail_label() {
+                            let value = if kind == SignerKind::PrivateKey {
+                                redact::mask_input(&form.detail)
+                            } else {
+                                form.detail.clone()
+                            };
+                            content.push(field(4 + args, label, value));
+                        }
+                        if kind.needs_password() {
+                            content.push(field(
+                                5 + args,
+                                "Password",
+                                "•".repeat(form.password.chars().count()),
+                            ));
+                        }
                     }
-                    _ => {}
+
+                    content.push(Line::from(""));
+                    match &form.preview {
+                        None => {}
+                        Some(Err(e)) => content.push(
+                            Line::from(format!("✗ {}", e)).style(Style::default().fg(Color::Red)),
+                        ),
+                        Some(Ok(preview)) => {
+                            content.push(Line::from(format!("Calldata  {}", preview.calldata)));
+                            content.push(Line::from(match &preview.gas {
+                                Ok(gas) => format!("Gas       ~{}", gas),
+                                Err(e) => format!("Gas       ✗ {}", e),
+                            }));
+                            if let Some(result) = &preview.result {
+                                content.push(
+                                    Line::from(match result {
+                                        Ok(result) => format!("Returns   {}", result),
+                                        Err(e) => format!("Returns   ✗ {}", e),
+                                    })
+                                    .style(Style::default().fg(Color::Green)),
+                                );
+                            }
+                        }
+                    }
+                    content.push(Line::from(""));
+                    let enter = match &form.preview {
+                        Some(Ok(_)) if !view => "Enter: send",
+                        _ if view => "Enter: call",
+                        _ => "Enter: preview",
+                    };
+                    content.push(Line::from(format!(
+                        "Tab: next field · ←/→: change choice · {} · Esc: back",
+                        enter
+                    )));
                 }
+
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("Transaction on {}", form.rpc_url));
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
             }
-            _ => {}
-        }
+            AppState::Events => {
+                let mut content = Vec::new();
+                if let Some(log) = &self.event_log {
+                    content.push(Line::from(format!(
+                        "Following {} on {}",
+                        log.watched
+                            .iter()
+                            .map(|w| format!("{} ({})", w.name, w.address))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        log.rpc_url()
+                    )));
+                    if let Some(e) = &log.error {
+                        content.push(
+                            Line::from(format!("✗ {}", e)).style(Style::default().fg(Color::Red)),
+                        );
+                    }
+                    content.push(Line::from(""));
+                    content.push(
+                        Line::from(format!(
+                            "{:>8}  {:<16} {:<20} {}",
+                            "Block", "Contract", "Event", "Arguments"
+                        ))
+                        .bold(),
+                    );
+                    if log.rows.is_empty() {
+                        content.push(Line::from("No events yet"));
+                    }
+                    let area = Block::default().borders(Borders::ALL).inner(chunks[1]);
+                    let room = (area.height as usize).saturating_sub(content.len() + 2);
+                    let end = log.rows.len().saturating_sub(self.events_scroll);
+                    let start = end.saturating_sub(room);
+                    content.extend(
+                        log.rows[start..end]
+                            .iter()
+                            .map(|row| Line::from(row.describe())),
+                    );
+                }
+                content.push(Line::from(""));
+                content.push(Line::from(
+                    "↑↓: scroll · r: reload on the current RPC · Esc: back",
+                ));
 
-        // Handle scrolling for output
-        match key.code {
-            KeyCode::PageUp => {
-                if self.output_scroll > 0 {
-                    self.output_scroll = self.output_scroll.saturating_sub(1);
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title("Contract Events");
+                frame.render_widget(Paragraph::new(content).block(block), chunks[1]);
+            }
+            AppState::Abi => {
+                let mut content = Vec::new();
+                let mut selected_row = 0;
+                let mut index = 0;
+                for contract in &self.abi_contracts {
+                    content.push(
+                        Line::from(format!(
+                            "{}  {}{}",
+                            contract.name,
+                            contract.source.display(),
+                            if contract.is_dependency() {
+                                " (dependency)"
+                            } else {
+                                ""
+                            }
+                        ))
+                        .bold(),
+                    );
+                    for entry in &contract.entries {
+                        let selected = index == self.abi_selected;
+                        if selected {
+                            selected_row = content.len();
+                        }
+                        let line = Line::from(format!(
+                            "{} {:<8} {:<10}  {}",
+                            if selected { "▶" } else { " " },
+                            entry.kind.label(),
+                            // Event topics are 32 bytes; the full value is copied
+                            if entry.selector.len() > 10 {
+                                format!("{}…", &entry.selector[..9])
+                            } else {
+                                entry.selector.clone()
+                            },
+                            entry.signature
+                        ))
+                        .style(Style::default().fg(match entry.kind {
+                            abi::Kind::Function => Color::White,
+                            abi::Kind::Event => Color::Cyan,
+                            abi::Kind::Error => Color::Red,
+                        }));
+                        content.push(if selected { line.bold() } else { line });
+                        index += 1;
+                    }
                 }
+                let area = Block::default().borders(Borders::ALL).inner(chunks[1]);
+                let list_height = (area.height as usize).saturating_sub(2);
+                // Keep the selection in view
+                let offset = selected_row.saturating_sub(list_height.saturating_sub(1));
+                let mut lines: Vec<Line> =
+                    content.into_iter().skip(offset).take(list_height).collect();
+                lines.push(Line::from(""));
+                lines.push(Line::from(
+                    "↑↓: select · Enter/c: copy selector · s: copy signature · r: reload · Esc: back",
+                ));
+
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title("Contract ABIs");
+                frame.render_widget(Paragraph::new(lines).block(block), chunks[1]);
+            }
+            AppState::Scaffolding(kind) => {
+                let content = vec![
+                    Line::from(format!("Name ({}):", kind.name_hint())),
+                    Line::from(format!("{}_", self.scaffold_name))
+                        .style(Style::default().fg(Color::Yellow)),
+                    Line::from(""),
+                    Line::from("Enter: generate · Esc: back"),
+                ];
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("Add {}", kind.label()));
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
             }
-            KeyCode::PageDown => {
-                if !self.command_output.is_empty() {
-                    self.output_scroll = self.output_scroll.saturating_add(1);
+            AppState::FetchingTemplate | AppState::TemplateDrift => {
+                let mut content = Vec::new();
+                match &self.drift {
+                    Some(view) if matches!(self.state, AppState::TemplateDrift) => {
+                        if view.files.is_empty() {
+                            content.push(Line::from(
+                                "Nothing to pull: every template file matches the project.",
+                            ));
+                        }
+                        for (i, file) in view.files.iter().enumerate() {
+                            let line = Line::from(format!(
+                                "{} [{}] {} {}",
+                                if i == view.selected { "▶" } else { " " },
+                                if view.marked[i] { "x" } else { " " },
+                                if file.new { "new     " } else { "changed " },
+                                file.path.display()
+                            ));
+                            content.push(if i == view.selected {
+                                line.style(Style::default().fg(Color::Yellow).bold())
+                            } else {
+                                line
+                            });
+                        }
+                        content.push(Line::from(""));
+                        content.push(Line::from(
+                            "Your own sources (apps/src, contracts/src, methods/guest/src, ...) aren't compared.",
+                        ));
+                    }
+                    _ => content.push(Line::from("Fetching the template...")),
                 }
+                content.push(Line::from(""));
+                content.push(Line::from(
+                    "↑↓: select · Enter: show diff · Space: mark · p: pull marked · r: refetch · Esc: back",
+                ));
+
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title("Compare with Template");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
             }
-            _ => {}
-        }
+            AppState::CheckingContracts | AppState::ContractChecks => {
+                let mut content = Vec::new();
+                let line = |finding: &contract_checks::Finding| {
+                    let (mark, color) = match finding.verdict {
+                        contract_checks::Verdict::Ok => ("✓", Color::Green),
+                        contract_checks::Verdict::Near => ("⚠", Color::Yellow),
+                        contract_checks::Verdict::Over => ("✗", Color::Red),
+                    };
+                    Line::from(format!(
+                        "{} {:<36} {}",
+                        mark, finding.subject, finding.detail
+                    ))
+                    .style(Style::default().fg(color))
+                };
+                match &self.contract_report {
+                    None if matches!(self.state, AppState::CheckingContracts) => {
+                        content.push(Line::from("Checking..."))
+                    }
+                    None => content.push(Line::from("No results; see the output pane")),
+                    Some(report) => {
+                        content.push(Line::from("Contract sizes").bold());
+                        content.extend(report.sizes.iter().map(line));
+                        if report.sizes.is_empty() {
+                            content.push(Line::from("  No contracts measured"));
+                        }
+                        content.push(Line::from(""));
+                        content.push(
+                            Line::from(format!(
+                                "Costliest tests against a block gas limit of {} ({})",
+                                report.block_gas_limit, report.limit_source
+                            ))
+                            .bold(),
+                        );
+                        content.extend(report.gas.iter().map(line));
+                        if report.gas.is_empty() {
+                            content.push(Line::from("  No test gas reported"));
+                        }
+                    }
+                }
+                content.push(Line::from(""));
+                content.push(
+                    Line::from(
+                        "Contracts over 24,576 bytes can't be deployed, and a transaction over \
+                         the block gas limit, such as verifying a proof, can't be mined.",
+                    )
+                    .style(Style::default().fg(Color::Gray)),
+                );
+                content.push(Line::from("r: run again · Esc: back"));
 
-        Ok(false)
-    }
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title("Check Contracts");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::CheckingHealth | AppState::Health => {
+                let mut content = Vec::new();
+                if matches!(self.state, AppState::CheckingHealth) {
+                    content.push(Line::from("Checking..."));
+                }
+                for finding in &self.health {
+                    match &finding.outcome {
+                        Ok(detail) => content.push(
+                            Line::from(format!("✓ {:<20} {}", finding.check, detail))
+                                .style(Style::default().fg(Color::Green)),
+                        ),
+                        Err(problem) => {
+                            content.push(
+                                Line::from(format!("✗ {:<20} {}", finding.check, problem.detail))
+                                    .style(Style::default().fg(Color::Red)),
+                            );
+                            content.push(
+                                Line::from(format!("  {:<20} → {}", "", problem.fix))
+                                    .style(Style::default().fg(Color::Yellow)),
+                            );
+                        }
+                    }
+                }
+                content.push(Line::from(""));
+                content.push(Line::from("r: run again · Esc: back"));
 
-    pub fn run(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
-        loop {
-            if self.pending_redraw {
-                terminal.draw(|frame| self.ui(frame))?;
-                self.pending_redraw = false;
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("Health of '{}'", self.project_name));
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
             }
+            AppState::BonsaiAccount => {
+                let mut content = Vec::new();
+                if let Some(panel) = &self.bonsai_panel {
+                    content.push(Line::from("API").bold());
+                    content.push(match &panel.availability {
+                        Ok(availability) => Line::from(format!(
+                            "  ✓ Available ({} ms), serving zkVM {}",
+                            availability.latency.as_millis(),
+                            availability.versions.join(", ")
+                        ))
+                        .style(Style::default().fg(Color::Green)),
+                        Err(e) => Line::from(format!("  ✗ Unavailable: {}", e))
+                            .style(Style::default().fg(Color::Red)),
+                    });
+                    content.push(Line::from(""));
 
-            // Check for events with a shorter timeout
-            if event::poll(std::time::Duration::from_millis(16))? {
-                // ~60fps
-                if let Event::Key(key) = event::read()? {
-                    if self.handle_key_event(key)? {
-                        return Ok(());
+                    content.push(Line::from("Quota").bold());
+                    match &panel.quotas {
+                        Ok(quotas) => {
+                            let remaining = quotas.cycle_budget - quotas.cycle_usage;
+                            content.push(Line::from(format!(
+                                "  Cycles used {} of {} ({} remaining)",
+                                quotas.cycle_usage, quotas.cycle_budget, remaining
+                            )));
+                            content.push(Line::from(format!(
+                                "  Concurrent proofs: {} · Executor cycle limit: {}",
+                                quotas.concurrent_proofs, quotas.exec_cycle_limit
+                            )));
+                        }
+                        Err(e) => content.push(
+                            Line::from(format!("  ✗ {}", e)).style(Style::default().fg(Color::Red)),
+                        ),
+                    }
+                    content.push(Line::from(""));
+
+                    content.push(Line::from("Recent sessions").bold());
+                    if panel.sessions.is_empty() {
+                        content.push(Line::from("  None seen in this session's output yet"));
+                    }
+                    for (id, status) in &panel.sessions {
+                        content.push(Line::from(match status {
+                            Ok(status) => {
+                                let mut line = format!("  {} {}", id, status.status);
+                                if let Some(state) = &status.state {
+                                    line.push_str(&format!(" ({})", state));
+                                }
+                                if let Some(elapsed) = status.elapsed_time {
+                                    line.push_str(&format!(", {:.0}s", elapsed));
+                                }
+                                if let Some(error) = &status.error_msg {
+                                    line.push_str(&format!(": {}", error));
+                                }
+                                line
+                            }
+                            Err(e) => format!("  {} ✗ {}", id, e),
+                        }));
+                    }
+                }
+                content.push(Line::from(""));
+                content.push(Line::from("r: refresh · Esc: back"));
+
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title("Bonsai Account");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::TemplatePrompts => {
+                if let Some(CargoTemplate {
+                    template: Some(template),
+                    values,
+                    field,
+                    ..
+                }) = &self.cargo_template
+                {
+                    let mut content = Vec::new();
+                    for (i, (placeholder, value)) in
+                        template.placeholders.iter().zip(values).enumerate()
+                    {
+                        let selected = i == *field;
+                        let shown = match placeholder.kind {
+                            cargo_generate::Kind::Text if selected && self.cursor_shown() => {
+                                format!("{}█", value)
+                            }
+                            cargo_generate::Kind::Text => value.clone(),
+                            _ => format!("◀ {} ▶", value),
+                        };
+                        let line = Line::from(format!(
+                            "{} {}: {}",
+                            if selected { ">" } else { " " },
+                            placeholder.prompt,
+                            shown
+                        ));
+                        content.push(if selected {
+                            line.style(Style::default().fg(Color::Yellow))
+                        } else {
+                            line
+                        });
                     }
+                    content.push(Line::from(""));
+                    content.push(
+                        Line::from(
+                            "Tab/↑↓: next value · ←/→: change choice · Enter: generate · Esc: back",
+                        )
+                        .style(Style::default().fg(Color::Gray)),
+                    );
+                    let block = Block::default()
+                        .borders(Borders::ALL)
+                        .title(format!("{} (cargo-generate)", template.name));
+                    frame.render_widget(Paragraph::new(content).block(block), chunks[1]);
                 }
             }
+            AppState::Settings => {
+                let form = &self.settings_form;
+                let field = |index: usize, label: &str, value: String| {
+                    let line = Line::from(format!(
+                        "{} {:<14} {}",
+                        if form.field == index { ">" } else { " " },
+                        label,
+                        value
+                    ));
+                    if form.field == index {
+                        line.style(Style::default().fg(Color::Yellow))
+                    } else {
+                        line
+                    }
+                };
+                let hint = |text: String| Line::from(text).style(Style::default().fg(Color::Gray));
+                let mut content = vec![
+                    field(0, "Proxy", form.proxy.clone()),
+                    hint(String::from(
+                        "  For HTTP requests and git, e.g. http://proxy.example.com:3128",
+                    )),
+                ];
+                if form.proxy.trim().is_empty() {
+                    content.push(hint(
+                        http::proxy()
+                            .map(|proxy| format!("  Empty: using {} from the environment", proxy))
+                            .unwrap_or_else(|| String::from("  Empty: no proxy")),
+                    ));
+                }
+                let provider = form.rpc_provider();
+                content.push(Line::from(""));
+                content.push(field(
+                    1,
+                    "RPC provider",
+                    format!("◀ {} ▶", provider.label()),
+                ));
+                if provider != RpcProvider::Local {
+                    content.push(field(
+                        2,
+                        "Network",
+                        format!("◀ {} ▶", form.rpc_network().label()),
+                    ));
+                }
+                if provider.needs_api_key() {
+                    content.push(field(3, "API key", redact::mask_input(&form.rpc_api_key)));
+                }
+                let masked_key = redact::mask_input(&form.rpc_api_key);
+                if let Some(url) = rpc_presets::url(provider, form.rpc_network(), &masked_key) {
+                    content.push(hint(format!("  ETH_RPC_URL: {}", url)));
+                }
+                if std::env::var("ETH_RPC_URL").is_ok_and(|url| !url.is_empty()) {
+                    content.push(hint(String::from(
+                        "  ETH_RPC_URL is set in the environment and takes precedence",
+                    )));
+                }
+                content.push(Line::from(""));
+                content.push(field(
+                    4,
+                    "Submodules",
+                    format!(
+                        "◀ {} ▶",
+                        if form.full_submodule_history {
+                            "Full history"
+                        } else {
+                            "Shallow (--depth 1)"
+                        }
+                    ),
+                ));
+                content.push(hint(String::from(if form.full_submodule_history {
+                    "  Slower and larger; lets you check out other tags and browse history in lib/"
+                } else {
+                    "  Only the latest commit of each Forge dependency; much faster to fetch"
+                })));
+                content.push(Line::from(""));
+                content.push(field(5, "Cargo mirror", form.cargo_mirror.clone()));
+                content.push(hint(String::from(
+                    "  Registry index for new projects' .cargo/config.toml, e.g. sparse+https://rsproxy.cn/index/",
+                )));
+                content.push(field(
+                    6,
+                    "Git fetch",
+                    format!(
+                        "◀ {} ▶",
+                        if form.cargo_git_fetch_with_cli {
+                            "git CLI"
+                        } else {
+                            "cargo built-in"
+                        }
+                    ),
+                ));
+                content.push(hint(String::from(
+                    "  The git CLI honours your git proxy and credential config for git dependencies",
+                )));
+                content.push(Line::from(""));
+                content.push(field(
+                    7,
+                    "Dev mode",
+                    format!("◀ {} ▶", if form.dev_mode { "On" } else { "Off" }),
+                ));
+                content.push(hint(format!(
+                    "  RISC0_DEV_MODE=1 for everything run in '{}': fast, but the proofs are fake",
+                    self.project_name
+                )));
+                content.push(Line::from(""));
+                content.push(field(
+                    8,
+                    "Tool versions",
+                    format!("◀ {} ▶", form.tool_versions().label()),
+                ));
+                content.push(hint(String::from(
+                    "  Pins the Rust and Foundry versions new projects are created with",
+                )));
+                content.push(Line::from(""));
+                content.push(field(
+                    9,
+                    "Motion",
+                    format!(
+                        "◀ {} ▶",
+                        if form.reduced_motion {
+                            "Reduced"
+                        } else {
+                            "Full"
+                        }
+                    ),
+                ));
+                content.push(hint(String::from(
+                    "  Reduced: a steady cursor and still progress icons instead of spinners",
+                )));
+                content.push(Line::from(""));
+                content.push(field(
+                    10,
+                    "Log level",
+                    format!("◀ {} ▶", form.log_level().label()),
+                ));
+                content.push(field(11, "Per crate", form.log_overrides.clone()));
+                let overrides = form.log_overrides.trim().trim_matches(',');
+                content.push(hint(format!(
+                    "  RUST_LOG={}{}{} for cargo and the host, e.g. risc0_zkvm=warn,risc0_steel=debug",
+                    form.log_level().label(),
+                    if overrides.is_empty() { "" } else { "," },
+                    overrides
+                )));
+                if std::env::var_os("RUST_LOG").is_some() {
+                    content.push(hint(String::from(
+                        "  RUST_LOG is set in the environment and takes precedence",
+                    )));
+                }
+                content.push(Line::from(""));
+                content.push(field(
+                    12,
+                    "Progress",
+                    format!("◀ {} ▶", form.progress_view.label()),
+                ));
+                content.push(hint(String::from(
+                    "  Compact: one line per install and test step, and only pass/fail messages (F7)",
+                )));
+                content.push(Line::from(""));
+                content.push(field(
+                    13,
+                    "Template cache",
+                    format!(
+                        "◀ {} ▶",
+                        if form.skip_template_refresh {
+                            "Use as it is"
+                        } else {
+                            "Refresh with git fetch"
+                        }
+                    ),
+                ));
+                content.push(hint(format!(
+                    "  New projects are cloned from {} once it holds the template",
+                    paths::template_cache_dir().display()
+                )));
+                content.push(Line::from(""));
+                content.push(Line::from(format!(
+                    "Saved to {}",
+                    paths::settings_file().display()
+                )));
+                content.push(Line::from(
+                    "Tab: next field · ←/→: change choice · Enter: save · Esc: cancel",
+                ));
 
-            match &self.state {
-                AppState::CheckingDependencies => {
-                    if !self.rust_installed {
-                        self.rust_installed = self.check_rust();
+                let block = Block::default().borders(Borders::ALL).title("Settings");
+                frame.render_widget(Paragraph::new(content).block(block), chunks[1]);
+            }
+            AppState::DeployedContracts => {
+                let mut content = Vec::new();
+                if let Some(view) = &self.deployed {
+                    let width = view
+                        .contracts
+                        .iter()
+                        .map(|contract| contract.name.len())
+                        .max()
+                        .unwrap_or_default()
+                        .max(8);
+                    content.push(
+                        Line::from(format!(
+                            "  {:<width$}  {:<42}  Transaction",
+                            "Contract", "Address"
+                        ))
+                        .bold(),
+                    );
+                    for contract in &view.contracts {
+                        content.push(Line::from(format!(
+                            "  {:<width$}  {:<42}  {}",
+                            contract.name, contract.address, contract.tx_hash
+                        )));
                     }
-                    if !self.foundry_installed {
-                        self.foundry_installed = self.check_foundry();
+                    content.push(Line::from(""));
+                    content.push(
+                        Line::from(format!(
+                            "Recorded for chain {} in {}, and as {} in {}.",
+                            view.chain_id,
+                            address_book::FILE_NAME,
+                            view.contracts
+                                .iter()
+                                .map(|contract| address_book::env_var(&contract.name))
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                            address_book::ENV_FILE
+                        ))
+                        .style(Style::default().fg(Color::Gray)),
+                    );
+                }
+                content.push(Line::from("Enter/Esc: back"));
+
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title("Deployed Contracts");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::ConfirmingDeploy => {
+                let mut content = Vec::new();
+                if let Some(preview) = &self.deploy_preview {
+                    let creations: Vec<_> = preview
+                        .transactions
+                        .iter()
+                        .filter(|tx| tx.is_creation())
+                        .collect();
+                    content.push(Line::from(format!(
+                        "The deploy script would create {} contract(s):",
+                        creations.len()
+                    )));
+                    for tx in &creations {
+                        content.push(Line::from(format!(
+                            "  {:<28} {:>12} gas",
+                            tx.contract.as_deref().unwrap_or("unknown contract"),
+                            tx.gas.map(|gas| gas.to_string()).unwrap_or_default()
+                        )));
                     }
-                    if self.risc0_version.is_none() {
-                        self.check_risc0();
+                    let calls = preview.transactions.len() - creations.len();
+                    if calls > 0 {
+                        content.push(Line::from(format!(
+                            "  and send {} other transaction(s)",
+                            calls
+                        )));
                     }
+                    content.push(Line::from(""));
+                    content.push(Line::from(format!("Gas limit       {}", preview.gas())));
+                    content.push(Line::from(format!(
+                        "Gas price       {}",
+                        chain::format_gwei(preview.gas_price)
+                    )));
+                    content.push(
+                        Line::from(format!(
+                            "Cost at most    {}",
+                            chain::format_ether(preview.cost())
+                        ))
+                        .style(Style::default().fg(Color::Yellow).bold()),
+                    );
+                }
+                content.push(Line::from(""));
+                content.push(
+                    Line::from(
+                        "Forge's gas limits are estimates with headroom; the deployment usually \
+                         costs less.",
+                    )
+                    .style(Style::default().fg(Color::Gray)),
+                );
+                content.push(Line::from("Enter: broadcast · Esc: back to the form"));
 
-                    if self.rust_installed && self.foundry_installed && self.risc0_version.is_some()
-                    {
-                        self.state = AppState::EnteringProjectName;
-                        self.status_message =
-                            String::from("Enter project name (press Enter when done):");
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title("Confirm Deployment");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::Scripts => {
+                let mut content = Vec::new();
+                for (i, script) in self.scripts.iter().enumerate() {
+                    let selected = i == self.script_selected;
+                    let flags = match self.script_forms.get(script) {
+                        Some(form) if form.verify => "  --broadcast --verify",
+                        Some(form) if form.broadcast => "  --broadcast",
+                        _ => "",
+                    };
+                    let line = Line::from(format!(
+                        "{} {}{}",
+                        if selected { "▶" } else { " " },
+                        script.display(),
+                        flags
+                    ));
+                    content.push(if selected {
+                        line.style(Style::default().fg(Color::Yellow).bold())
+                    } else {
+                        line
+                    });
+                }
+                content.push(Line::from(""));
+                content.push(
+                    Line::from(
+                        "Each script keeps its own flags; the last run's transactions are read \
+                         from broadcast/ afterwards.",
+                    )
+                    .style(Style::default().fg(Color::Gray)),
+                );
+                content.push(Line::from("↑↓: select · Enter: set flags · Esc: back"));
+
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title("Forge Scripts");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: false }),
+                    chunks[1],
+                );
+            }
+            AppState::ScriptSetup => {
+                let script = self.scripts.get(self.script_selected);
+                let default = ScriptForm::default();
+                let form = script
+                    .and_then(|script| self.script_forms.get(script))
+                    .unwrap_or(&default);
+                let field = |index: usize, label: &str, value: String| {
+                    let line = Line::from(format!(
+                        "{} {:<14} {}",
+                        if form.field == index { ">" } else { " " },
+                        label,
+                        value
+                    ));
+                    if form.field == index {
+                        line.style(Style::default().fg(Color::Yellow))
+                    } else {
+                        line
                     }
+                };
+                let check = |on: bool| String::from(if on { "[x]" } else { "[ ]" });
+                let mut content = vec![
+                    field(0, "--rpc-url", form.rpc_url.clone()),
+                    field(1, "--broadcast", check(form.broadcast)),
+                ];
+                if form.broadcast {
+                    content.push(field(2, "--verify", check(form.verify)));
                 }
-                AppState::Installing(step) => {
-                    let result = match step {
-                        InstallStep::CloningRepo => match self.clone_repository(terminal) {
-                            Ok(_) => {
-                                self.state = AppState::Installing(InstallStep::SettingUpSparse);
-                                Ok(())
-                            }
-                            Err(e) => Err(e),
-                        },
-                        InstallStep::SettingUpSparse => {
-                            match self.setup_sparse_checkout(terminal) {
-                                Ok(_) => {
-                                    self.state = AppState::Installing(InstallStep::MovingFiles);
-                                    Ok(())
-                                }
-                                Err(e) => Err(e),
-                            }
-                        }
-                        InstallStep::MovingFiles => match self.move_files() {
-                            Ok(_) => {
-                                self.state =
-                                    AppState::Installing(InstallStep::UpdatingDependencies);
-                                Ok(())
-                            }
-                            Err(e) => Err(e),
-                        },
-                        InstallStep::UpdatingDependencies => match self.update_dependencies() {
-                            Ok(_) => {
-                                self.state = AppState::Installing(InstallStep::SettingUpForge);
-                                Ok(())
-                            }
-                            Err(e) => Err(e),
-                        },
-                        InstallStep::SettingUpForge => match self.setup_forge(terminal) {
-                            Ok(_) => {
-                                self.state = AppState::Success;
-                                self.status_message = format!(
-                                    "✓ Project '{}' created successfully!",
-                                    self.project_name
-                                );
-                                Ok(())
-                            }
-                            Err(e) => Err(e),
-                        },
-                    };
+                content.push(Line::from(""));
+                content.push(
+                    Line::from(if form.broadcast {
+                        "Transactions are signed and sent. --verify uses ETHERSCAN_API_KEY."
+                    } else {
+                        "A dry run: forge simulates the transactions without sending them."
+                    })
+                    .style(Style::default().fg(Color::Gray)),
+                );
+                content.push(Line::from(
+                    "Tab: next field · Space: toggle · Enter: run · Esc: back",
+                ));
 
-                    if let Err(e) = result {
-                        self.status_message = format!("Error: {}", e);
-                        self.handle_error()?;
+                let title = format!(
+                    "forge script {}",
+                    script.map(|s| s.display().to_string()).unwrap_or_default()
+                );
+                let block = Block::default().borders(Borders::ALL).title(title);
+                frame.render_widget(Paragraph::new(content).block(block), chunks[1]);
+            }
+            AppState::DeploySetup => {
+                let form = &self.deploy_form;
+                let field = |index: usize, label: &str, value: String| {
+                    let line = Line::from(format!(
+                        "{} {:<14} {}",
+                        if form.field == index { ">" } else { " " },
+                        label,
+                        value
+                    ));
+                    if form.field == index {
+                        line.style(Style::default().fg(Color::Yellow))
+                    } else {
+                        line
                     }
+                };
+                let kind = form.kind();
+                let mut content = vec![
+                    field(0, "RPC URL", form.rpc_url.clone()),
+                    field(1, "Signer", format!("◀ {} ▶", kind.label())),
+                ];
+                if let Some(label) = kind.detail_label() {
+                    let value = if kind == SignerKind::PrivateKey {
+                        redact::mask_input(&form.detail)
+                    } else {
+                        form.detail.clone()
+                    };
+                    content.push(field(2, label, value));
                 }
-                AppState::Success => {
-                    // Remove the automatic state transition on key press
-                    // The transition will now be handled in handle_key_event
+                if kind.needs_password() {
+                    content.push(field(
+                        3,
+                        "Password",
+                        "•".repeat(form.password.chars().count()),
+                    ));
                 }
-                AppState::Testing(_) => {
-                    if let Err(e) = self.handle_test_step(terminal) {
-                        self.add_output(format!("Error: {}", e));
-                        self.cleanup_test();
-                        self.state = AppState::TestMenu;
+                let verifier = form.verifier();
+                content.push(field(4, "Verify on", format!("◀ {} ▶", verifier.label())));
+                if let Some(label) = verifier.detail_label() {
+                    let value = if verifier == VerifierKind::Etherscan {
+                        redact::mask_input(&form.verifier_detail)
+                    } else {
+                        form.verifier_detail.clone()
+                    };
+                    content.push(field(5, label, value));
+                }
+                if kind == SignerKind::CastAccount {
+                    let accounts = signer::cast_accounts();
+                    if !accounts.is_empty() {
+                        content.push(Line::from(format!("  Available: {}", accounts.join(", "))));
                     }
                 }
-                AppState::Finished => break,
-                _ => {}
-            }
-
-            // Always draw at least once per loop
-            terminal.draw(|frame| self.ui(frame))?;
-        }
-        Ok(())
-    }
-
-    /// Renders the user interface.
-    ///
-    /// This is where you add new widgets. See the following resources for more information:
-    /// - <https://docs.rs/ratatui/latest/ratatui/widgets/index.html>
-    /// - <https://github.com/ratatui/ratatui/tree/master/examples>
-    fn ui(&self, frame: &mut Frame) {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(1), // Status line
-                Constraint::Min(3),    // Main content
-                Constraint::Length(1), // Help text
-            ])
-            .split(frame.area());
-
-        // Render status line
-        let status = Paragraph::new(Line::from(vec![self.status_message.clone().bold()]));
-        frame.render_widget(status, chunks[0]);
-
-        match &self.state {
-            AppState::EnteringBonsaiKey => {
-                let cursor_blink = (std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_millis()
-                    / 500)
-                    % 2
-                    == 0;
+                if matches!(kind, SignerKind::Ledger | SignerKind::Trezor) {
+                    content.push(Line::from(
+                        "  Connect and unlock the device; forge will ask it to sign.",
+                    ));
+                }
+                content.push(Line::from(""));
+                content.push(Line::from(
+                    "Tab: next field · ←/→: change choice · Enter: deploy · Esc: cancel",
+                ));
 
-                let input_text = format!(
-                    "Bonsai API Key: {}{}",
-                    self.bonsai_api_key,
-                    if cursor_blink { "█" } else { " " }
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title("Deploy Contracts");
+                frame.render_widget(Paragraph::new(content).block(block), chunks[1]);
+            }
+            AppState::UnlockingCredentials => {
+                let content = vec![
+                    Line::from(format!(
+                        "Credentials are stored encrypted in {}",
+                        paths::credentials_file().display()
+                    )),
+                    Line::from(""),
+                    Line::from(format!(
+                        "Passphrase: {}█",
+                        "•".repeat(self.credentials_passphrase.chars().count())
+                    ))
+                    .style(Style::default().fg(Color::Yellow)),
+                    Line::from(""),
+                    Line::from("Press Enter to unlock, Esc to cancel"),
+                ];
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title("Unlock Credentials");
+                frame.render_widget(
+                    Paragraph::new(content)
+                        .block(block)
+                        .wrap(Wrap { trim: true }),
+                    chunks[1],
                 );
+            }
+            AppState::Credentials => {
+                let entries = self
+                    .credentials
+                    .as_ref()
+                    .map(|c| c.entries.as_slice())
+                    .unwrap_or_default();
+                let mut content: Vec<Line> = entries
+                    .iter()
+                    .enumerate()
+                    .map(|(i, entry)| {
+                        let line = Line::from(format!(
+                            "{} {:<18} {:<20} {}",
+                            if i == self.selected_credential {
+                                ">"
+                            } else {
+                                " "
+                            },
+                            entry.kind.label(),
+                            entry.name,
+                            redact::mask_input(&entry.secret)
+                        ));
+                        if i == self.selected_credential {
+                            line.style(Style::default().fg(Color::Yellow))
+                        } else {
+                            line
+                        }
+                    })
+                    .collect();
+                if content.is_empty() {
+                    content.push(Line::from("No credentials stored yet."));
+                }
+                content.push(Line::from(""));
+                content.push(Line::from(
+                    "a: add · g: generate test wallet · d: delete · ↑/↓: select · Esc: back",
+                ));
 
+                let block = Block::default().borders(Borders::ALL).title("Credentials");
+                frame.render_widget(Paragraph::new(content).block(block), chunks[1]);
+            }
+            AppState::TestWallet => {
+                if let Some(wallet) = &self.test_wallet {
+                    let mut content = vec![
+                        Line::from(format!("Address: {}", wallet.address))
+                            .style(Style::default().fg(Color::Yellow)),
+                        Line::from(""),
+                        Line::from(format!(
+                            "Stored as the test wallet '{}'. Deployments, forge scripts and \
+                             transactions on testnets sign with it unless a network names \
+                             another wallet; mainnet keeps using your deployment key.",
+                            wallet.credential_name()
+                        )),
+                        Line::from("Use it for testnets only: never send it real funds."),
+                        Line::from(""),
+                        Line::from("Fund it from a faucet:"),
+                    ];
+                    for chain in ChainPreset::ALL {
+                        for faucet in chain.faucets() {
+                            content.push(Line::from(format!("  {:<14} {}", chain.label(), faucet)));
+                        }
+                    }
+                    content.push(Line::from(""));
+                    content.push(Line::from("c: copy address · Esc: back"));
+                    let block = Block::default().borders(Borders::ALL).title("Test Wallet");
+                    frame.render_widget(
+                        Paragraph::new(content)
+                            .block(block)
+                            .wrap(Wrap { trim: true }),
+                        chunks[1],
+                    );
+                }
+            }
+            AppState::EditingCredential => {
+                let form = &self.credential_form;
+                let field = |index: usize, label: &str, value: String| {
+                    let line = Line::from(format!(
+                        "{} {:<8} {}",
+                        if form.field == index { ">" } else { " " },
+                        label,
+                        value
+                    ));
+                    if form.field == index {
+                        line.style(Style::default().fg(Color::Yellow))
+                    } else {
+                        line
+                    }
+                };
                 let content = vec![
-                    Line::from(
-                        "Please enter your Bonsai API key to proceed with the end-to-end test.",
+                    field(
+                        0,
+                        "Kind",
+                        format!("◀ {} ▶", CredentialKind::ALL[form.kind].label()),
                     ),
-                    Line::from("This key is required to authenticate with the Bonsai service."),
+                    field(1, "Name", form.name.clone()),
+                    field(2, "Secret", redact::mask_input(&form.secret)),
                     Line::from(""),
-                    Line::from(input_text).style(Style::default().fg(Color::Yellow)),
-                    Line::from(""),
-                    Line::from("Press Enter to continue, Esc to cancel"),
+                    Line::from("Tab: next field · ←/→: change kind · Enter: save · Esc: cancel"),
                 ];
-
-                let input_block = Block::default()
+                let block = Block::default()
                     .borders(Borders::ALL)
-                    .title("Bonsai API Key Input");
-
-                let input = Paragraph::new(content)
-                    .block(input_block)
-                    .wrap(Wrap { trim: true });
-
-                frame.render_widget(input, chunks[1]);
+                    .title("Add Credential");
+                frame.render_widget(Paragraph::new(content).block(block), chunks[1]);
             }
             _ => {
-                let area = frame.area();
-
-                let main_block = Block::default()
+                let mut main_block = Block::default()
                     .title("Steel App Creator")
                     .borders(Borders::ALL);
+                let mut processes = supervisor::list()
+                    .iter()
+                    .map(|c| {
+                        let mut entry = format!(
+                            "{} (pid {}) {} {}s",
+                            c.name,
+                            c.pid,
+                            if c.running { "running" } else { "exited" },
+                            c.uptime.as_secs()
+                        );
+                        if let Some(usage) = self.resources.usage_for(c.pid) {
+                            entry.push_str(&format!(", {}", usage.describe()));
+                        }
+                        entry
+                    })
+                    .collect::<Vec<_>>();
+                if let Some(monitor) = self.chain_monitor.as_ref().filter(|m| m.error.is_none()) {
+                    processes.push(monitor.describe());
+                }
+                if !processes.is_empty() {
+                    main_block = main_block.title_bottom(
+                        Line::from(format!(" {} ", processes.join(" · ")))
+                            .style(Style::default().fg(Color::DarkGray))
+                            .left_aligned(),
+                    );
+                }
+                if let Some(monitor) = self.chain_monitor.as_ref().filter(|m| m.error.is_some()) {
+                    main_block = main_block.title_bottom(
+                        Line::from(format!(" {} ", monitor.describe()))
+                            .style(Style::default().fg(Color::White).bg(Color::Red).bold())
+                            .left_aligned(),
+                    );
+                }
+                if let Some(silent) = self.stuck_for {
+                    main_block = main_block.title_bottom(
+                        Line::from(format!(
+                            " ⚠ No output for {}s, step appears stuck: [w]ait / [k]ill & retry / [a]bort ",
+                            silent.as_secs()
+                        ))
+                        .style(Style::default().fg(Color::Black).bg(Color::Yellow).bold())
+                        .centered(),
+                    );
+                }
+                if supervisor::dev_mode() || supervisor::dev_mode_inherited() {
+                    main_block = main_block.title_bottom(
+                        Line::from(if supervisor::dev_mode() {
+                            " ⚠ DEV MODE: proofs are fake "
+                        } else {
+                            " ⚠ RISC0_DEV_MODE is set in the environment: proofs are fake "
+                        })
+                        .style(Style::default().fg(Color::White).bg(Color::Red).bold())
+                        .right_aligned(),
+                    );
+                }
+                if self.offline {
+                    main_block = main_block.title_bottom(
+                        Line::from(" Offline ")
+                            .style(Style::default().fg(Color::Black).bg(Color::Gray))
+                            .right_aligned(),
+                    );
+                }
+                if let Some(notice) = &self.update_notice {
+                    main_block = main_block.title_bottom(
+                        Line::from(format!(" {} ", notice))
+                            .style(Style::default().fg(Color::Yellow).bold())
+                            .right_aligned(),
+                    );
+                }
 
                 let inner_area = main_block.inner(area);
                 frame.render_widget(main_block, area);
 
+                let step_rows = self.step_rows();
                 // Modify the layout constraints when in Success state
                 let chunks = match self.state {
                     AppState::Success => Layout::default()
@@ -1019,7 +9987,10 @@ impl App {
                             Constraint::Ratio(1, 2), // Command output gets the other half
                         ])
                         .split(inner_area),
-                    AppState::TestMenu | AppState::ConfirmOverwrite => {
+                    AppState::TestMenu
+                    | AppState::ConfirmOverwrite
+                    | AppState::Linting
+                    | AppState::Lint => {
                         Layout::default() // Add ConfirmOverwrite here
                             .direction(Direction::Vertical)
                             .margin(1)
@@ -1037,25 +10008,54 @@ impl App {
                         .constraints([
                             Constraint::Length(1), // Status message
                             Constraint::Length(1), // Input field
-                            Constraint::Length(3), // Progress/menu area
+                            Constraint::Length(match &self.boundless {
+                                _ if self.batch.is_some() => {
+                                    2 + self
+                                        .batch
+                                        .as_ref()
+                                        .map_or(0, |b| b.entries.len().min(BATCH_ROWS) as u16)
+                                }
+                                _ if !step_rows.is_empty() => step_rows.len() as u16,
+                                _ if !self.tasks.is_empty() => 2 + self.tasks.len() as u16,
+                                _ if matches!(self.state, AppState::RunningQueue) => {
+                                    2 + self.queue.entries.len() as u16
+                                }
+                                _ if self
+                                    .guest_build
+                                    .as_ref()
+                                    .is_some_and(|b| !b.steps.is_empty()) =>
+                                {
+                                    2 + self.guest_build.as_ref().map_or(0, |b| b.steps.len())
+                                        as u16
+                                }
+                                _ if matches!(self.state, AppState::EnteringProjectName)
+                                    && !self.recent_projects.is_empty() =>
+                                {
+                                    2 + self.recent_projects.len().min(projects::SHOWN) as u16
+                                        + if self.template_choices.is_empty() {
+                                            0
+                                        } else {
+                                            2
+                                        }
+                                        + self.enclosing_repo.is_some() as u16
+                                }
+                                Some(tracker) if self.tracking_boundless() => {
+                                    3 + tracker.requests.len().max(1) as u16
+                                }
+                                _ => 3,
+                            }), // Progress/menu area
                             Constraint::Min(0),    // Command output
                         ])
                         .split(inner_area),
                 };
 
                 // Render status message
-                let status = Paragraph::new(self.status_message.clone());
+                let status = Paragraph::new(self.status_message());
                 frame.render_widget(status, chunks[0]);
 
                 // Render input field when in EnteringProjectName state
                 if let AppState::EnteringProjectName = self.state {
-                    let cursor_blink = (std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_millis()
-                        / 500)
-                        % 2
-                        == 0;
+                    let cursor_blink = self.cursor_shown();
 
                     let input_text = format!(
                         "{}{}",
@@ -1074,9 +10074,135 @@ impl App {
                     frame.render_widget(input, chunks[1]);
                 }
 
+                // Template and recent projects on the welcome screen
+                if matches!(self.state, AppState::EnteringProjectName) {
+                    let mut lines = Vec::new();
+                    if let Some(cargo_template) = &self.cargo_template {
+                        lines.push(Line::from(format!(
+                            "Template: {} (cargo-generate)  ·  F1: docs  ·  F4: learn mode {}",
+                            cargo_template.source,
+                            if self.learn { "on" } else { "off" }
+                        )));
+                        lines.push(Line::from(""));
+                    } else if let Some((release, template)) =
+                        self.template_choices.get(self.template_choice)
+                    {
+                        let template = if self.summary.examples.is_empty() {
+                            format!("Template: {}", template.name)
+                        } else {
+                            format!("Workspace: {}", self.summary.examples.join(" + "))
+                        };
+                        lines.push(Line::from(format!(
+                            "{}@{} (RISC0 {}.x){}{}  ·  F9: repository  ·  F1: docs  ·  F4: learn mode {}",
+                            template,
+                            self.summary.branch,
+                            release.toolchain.0,
+                            if self.template_choices.len() > 1 {
+                                "  ←/→ to change, F8: list"
+                            } else {
+                                ""
+                            },
+                            if release.examples.len() > 1 {
+                                "  ·  F5: combine examples"
+                            } else {
+                                ""
+                            },
+                            if self.learn { "on" } else { "off" }
+                        )));
+                        if self.custom_repository {
+                            lines.push(
+                                Line::from(format!("From {}", self.summary.repository))
+                                    .style(Style::default().fg(Color::Gray)),
+                            );
+                        }
+                        lines.push(Line::from(""));
+                    }
+                    if let Some(repo) = &self.enclosing_repo {
+                        lines.insert(
+                            lines.len().saturating_sub(1),
+                            Line::from(if self.in_repo {
+                                format!(
+                                    "Adding to the git repository at {}; the name can be a path, e.g. packages/steel-app  ·  Tab: own repository",
+                                    repo.display()
+                                )
+                            } else {
+                                String::from("Own git repository  ·  Tab: add to the enclosing one instead")
+                            })
+                            .style(Style::default().fg(Color::Gray)),
+                        );
+                    }
+                    if !self.recent_projects.is_empty() {
+                        lines.push(
+                            Line::from(
+                                "Recent projects (↓ to select, Enter to open the toolbox, F3 for all):",
+                            )
+                            .style(Style::default().fg(Color::Gray)),
+                        );
+                        lines.push(Line::from(""));
+                    }
+                    for (i, project) in self
+                        .recent_projects
+                        .iter()
+                        .take(projects::SHOWN)
+                        .enumerate()
+                    {
+                        let selected = self.selected_recent == Some(i);
+                        lines.push(
+                            Line::from(format!(
+                                "{} {:<20} {}@{}  {}  {}",
+                                if selected { "▶" } else { " " },
+                                project.name(),
+                                project.template,
+                                project.branch,
+                                project.date(),
+                                project.path.display()
+                            ))
+                            .style(if selected {
+                                Style::default().fg(Color::Yellow).bold()
+                            } else {
+                                Style::default()
+                            }),
+                        );
+                    }
+                    frame.render_widget(Paragraph::new(lines), chunks[2]);
+                }
+
+                if let (AppState::RestoringSession, Some(previous)) =
+                    (&self.state, &self.previous_session)
+                {
+                    let saved = chrono::DateTime::parse_from_rfc3339(&previous.saved_at)
+                        .map(|at| {
+                            at.with_timezone(&chrono::Local)
+                                .format("%Y-%m-%d %H:%M")
+                                .to_string()
+                        })
+                        .unwrap_or_default();
+                    let lines = vec![
+                        Line::from(format!("{}  (left {})", previous.describe(), saved))
+                            .style(Style::default().fg(Color::Yellow)),
+                        Line::from(""),
+                        Line::from("Enter: restore · n: start a new project · Esc: exit")
+                            .style(Style::default().fg(Color::Gray)),
+                    ];
+                    frame.render_widget(Paragraph::new(lines), chunks[2]);
+                }
+
+                // Latest line of the plugin install, whose cargo output is hidden below -v
+                if let AppState::InstallingCargoRisczero = self.state {
+                    let latest = self
+                        .command_output
+                        .last()
+                        .map(|line| line.text.trim())
+                        .unwrap_or_default();
+                    frame.render_widget(
+                        Paragraph::new(latest.to_string()).style(Style::default().fg(Color::Gray)),
+                        chunks[2],
+                    );
+                }
+
                 // Show dependency status
                 if let AppState::CheckingDependencies = self.state {
-                    let deps_status = vec![
+                    let deps_status = [
                         format!("Rust: {}", if self.rust_installed { "✓" } else { "..." }),
                         format!(
                             "Foundry: {}",
@@ -1084,7 +10210,13 @@ impl App {
                         ),
                         format!(
                             "RISC0: {}",
-                            self.risc0_version.as_ref().map_or("...", |v| v)
+                            match &self.risc0_version {
+                                Some(version) => version.as_str(),
+                                None if self.cargo_risczero_missing => {
+                                    "cargo-risczero missing (i: install with cargo)"
+                                }
+                                None => "...",
+                            }
                         ),
                     ]
                     .join("\n");
@@ -1095,40 +10227,278 @@ impl App {
 
                 // Show installation progress when installing
                 if let AppState::Installing(step) = &self.state {
-                    let (progress, details) = match step {
-                        InstallStep::CloningRepo => (
-                            "Step 1/5: Downloading Template",
-                            format!("• Downloading RISC0 Ethereum template into '{}'\n• Using release-1.3 branch", self.project_name)
-                        ),
-                        InstallStep::SettingUpSparse => (
-                            "Step 2/5: Extracting ERC20 Counter Example",
-                            "• Configuring repository for minimal download\n• Extracting ERC20 counter example code".to_string()
-                        ),
-                        InstallStep::MovingFiles => (
-                            "Step 3/5: Setting Up Project Structure",
-                            "• Moving files to root directory\n• Creating standard project layout".to_string()
-                        ),
-                        InstallStep::UpdatingDependencies => (
-                            "Step 4/5: Configuring Dependencies",
-                            "• Updating Rust package dependencies\n• Setting up RISC0 and Ethereum integrations".to_string()
-                        ),
-                        InstallStep::SettingUpForge => (
-                            "Step 5/5: Installing Forge Components",
-                            "• Setting up Foundry development environment\n• Installing OpenZeppelin contracts\n• Configuring RISC0 Ethereum components".to_string()
-                        ),
+                    let details = match step {
+                        InstallStep::CloningRepo if self.custom_repository => format!("• Downloading the template from {} into '{}'\n• Using {} branch", self.summary.repository, self.project_name, self.summary.branch),
+                        InstallStep::CloningRepo => format!("• Downloading RISC0 Ethereum template into '{}'\n• Using {} branch", self.project_name, self.summary.branch),
+                        InstallStep::SettingUpSparse => format!("• Configuring repository for minimal download\n• Extracting {}", self.sparse_paths().join(", ")),
+                        InstallStep::MovingFiles => "• Moving files to root directory\n• Creating standard project layout".to_string(),
+                        InstallStep::UpdatingDependencies => "• Updating Rust package dependencies\n• Setting up RISC0 and Ethereum integrations".to_string(),
+                        InstallStep::SettingUpForge => "• Setting up Foundry development environment\n• Installing OpenZeppelin contracts\n• Configuring RISC0 Ethereum components".to_string(),
                     };
 
-                    let progress_text = vec![
+                    let progress = format!(
+                        "Step {}/{}: {}",
+                        step.number(),
+                        InstallStep::ALL.len(),
+                        step.title()
+                    );
+                    let mut progress_text = vec![
                         Line::from(progress).style(Style::default().fg(Color::Blue).bold()),
                         Line::from(""),
-                        Line::from(details),
                     ];
+                    if self.tasks.is_empty() {
+                        progress_text.push(Line::from(details));
+                        if self.transfer.seen() {
+                            progress_text.push(Line::from(""));
+                            progress_text.push(
+                                Line::from(format!("⇣ {}", self.transfer.describe()))
+                                    .style(Style::default().fg(Color::Gray)),
+                            );
+                        }
+                    } else {
+                        for task in &self.tasks {
+                            progress_text.push(Line::from(format!(
+                                "{} {:<24} {}",
+                                task.status.icon(),
+                                task.label,
+                                task.last_line
+                            )));
+                        }
+                    }
 
                     let progress_widget = Paragraph::new(progress_text)
                         .block(Block::default().borders(Borders::NONE));
                     frame.render_widget(progress_widget, chunks[2]);
                 }
 
+                // Boundless requests of the running test or submitted from here
+                if let (true, Some(tracker)) = (self.tracking_boundless(), &self.boundless) {
+                    let mut lines =
+                        vec![Line::from(format!("Boundless market {}", tracker.market()))
+                            .style(Style::default().fg(Color::Blue).bold())];
+                    if let Some(error) = &tracker.last_error {
+                        lines.push(
+                            Line::from(format!("⚠ {}", error))
+                                .style(Style::default().fg(Color::Yellow)),
+                        );
+                    }
+                    if tracker.requests.is_empty() {
+                        lines.push(
+                            Line::from("Waiting for a proof request to be submitted...")
+                                .style(Style::default().fg(Color::Gray)),
+                        );
+                    }
+                    for request in &tracker.requests {
+                        lines.push(Line::from(request.describe()));
+                    }
+                    frame.render_widget(Paragraph::new(lines), chunks[2]);
+                }
+
+                // Result of the last rebuild while watching
+                if let (AppState::Watching, Some(session)) = (&self.state, &self.watch) {
+                    let header = match &session.last {
+                        Some((true, at)) => Line::from(format!("✓ Build passed at {}", at))
+                            .style(Style::default().fg(Color::Black).bg(Color::Green).bold()),
+                        Some((false, at)) => Line::from(format!("✗ Build failed at {}", at))
+                            .style(Style::default().fg(Color::White).bg(Color::Red).bold()),
+                        None => Line::from("Waiting for a change...")
+                            .style(Style::default().fg(Color::Gray)),
+                    };
+                    let lines = vec![
+                        header,
+                        Line::from(format!(
+                            "{} rebuild(s){} · Esc: stop watching",
+                            session.rebuilds,
+                            session
+                                .watcher
+                                .last_change
+                                .as_ref()
+                                .map(|path| format!(", last change {}", path.display()))
+                                .unwrap_or_default()
+                        ))
+                        .style(Style::default().fg(Color::Gray)),
+                    ];
+                    frame.render_widget(Paragraph::new(lines), chunks[2]);
+                }
+
+                // Clippy's diagnostics, grouped by file
+                if let (AppState::Linting | AppState::Lint, Some(view)) = (&self.state, &self.lint)
+                {
+                    let linting = matches!(self.state, AppState::Linting);
+                    let mut lines = vec![Line::from(format!(
+                        "{} error(s), {} warning(s){}",
+                        view.count(lint::Level::Error),
+                        view.count(lint::Level::Warning),
+                        if linting {
+                            " so far"
+                        } else {
+                            " · ↑↓: select · Enter: show in output · r: run again · Esc: back"
+                        }
+                    ))
+                    .style(Style::default().fg(Color::Gray))];
+                    let mut selected_row = 0;
+                    let mut file = None;
+                    for (i, (diagnostic, _)) in view.diagnostics.iter().enumerate() {
+                        if file != Some(&diagnostic.file) {
+                            file = Some(&diagnostic.file);
+                            lines.push(
+                                Line::from(diagnostic.file.as_ref().map_or_else(
+                                    || String::from("(no file)"),
+                                    |path| path.display().to_string(),
+                                ))
+                                .bold(),
+                            );
+                        }
+                        let selected = !linting && i == view.selected;
+                        if selected {
+                            selected_row = lines.len();
+                        }
+                        let line = Line::from(format!(
+                            "{} {:<7} {:>5}  {}{}",
+                            if selected { "▶" } else { " " },
+                            diagnostic.level.label(),
+                            diagnostic.line,
+                            diagnostic.message,
+                            diagnostic
+                                .code
+                                .as_ref()
+                                .map(|code| format!(" [{}]", code))
+                                .unwrap_or_default()
+                        ))
+                        .style(Style::default().fg(
+                            match diagnostic.level {
+                                lint::Level::Error => Color::Red,
+                                lint::Level::Warning => Color::Yellow,
+                                lint::Level::Note => Color::Cyan,
+                            },
+                        ));
+                        lines.push(if selected { line.bold() } else { line });
+                    }
+                    // Keep the selection in view
+                    let offset =
+                        selected_row.saturating_sub((chunks[2].height as usize).saturating_sub(1));
+                    frame
+                        .render_widget(Paragraph::new(lines).scroll((offset as u16, 0)), chunks[2]);
+                }
+
+                // Queue progress
+                if let AppState::RunningQueue = self.state {
+                    let mut lines = vec![Line::from(format!(
+                        "Task queue · {} left · Ctrl+C: stop",
+                        self.queue.pending()
+                    ))
+                    .style(Style::default().fg(Color::Blue).bold())];
+                    lines.extend(
+                        self.queue
+                            .entries
+                            .iter()
+                            .map(|entry| Line::from(entry.describe())),
+                    );
+                    frame.render_widget(Paragraph::new(lines), chunks[2]);
+                }
+
+                // Benchmark progress
+                if let AppState::Benchmarking = self.state {
+                    let total = self.benchmark.selected().len() as u32 * self.benchmark.runs;
+                    let lines = vec![
+                        Line::from(match self.benchmark.current {
+                            Some((backend, run)) => format!(
+                                "Benchmarking {}: run {} of {}",
+                                backend.label(),
+                                run,
+                                self.benchmark.runs
+                            ),
+                            None => String::from("Building before benchmarking..."),
+                        })
+                        .style(Style::default().fg(Color::Blue).bold()),
+                        Line::from(format!(
+                            "{} of {} run(s) done · Ctrl+C: stop",
+                            self.benchmark.results.len(),
+                            total
+                        ))
+                        .style(Style::default().fg(Color::Gray)),
+                    ];
+                    frame.render_widget(Paragraph::new(lines), chunks[2]);
+                }
+
+                // Guests of the running test, once built
+                if let (AppState::Testing(_), None, false) =
+                    (&self.state, &self.boundless, self.guest_images.is_empty())
+                {
+                    let mut lines =
+                        vec![Line::from("Guests").style(Style::default().fg(Color::Blue).bold())];
+                    for image in &self.guest_images {
+                        lines.push(Line::from(image.describe()));
+                    }
+                    frame.render_widget(Paragraph::new(lines), chunks[2]);
+                }
+
+                // Guest build phases of the running cargo build, over whatever else is there
+                if let Some(progress) = self.guest_build.as_ref().filter(|b| !b.steps.is_empty()) {
+                    let mut lines =
+                        vec![Line::from("Guest build")
+                            .style(Style::default().fg(Color::Blue).bold())];
+                    for step in &progress.steps {
+                        lines.push(Line::from(step.describe(self.animate())));
+                    }
+                    frame.render_widget(Clear, chunks[2]);
+                    frame.render_widget(Paragraph::new(lines), chunks[2]);
+                }
+
+                // Every project of a batch, in place of the install steps
+                if let (AppState::Installing(_) | AppState::Batch, Some(batch)) =
+                    (&self.state, &self.batch)
+                {
+                    let header = match &self.state {
+                        AppState::Installing(step) => format!(
+                            "Batch · {} of {} done · {}: step {}/{}, {}",
+                            batch.count(TaskStatus::Done) + batch.count(TaskStatus::Failed),
+                            batch.entries.len(),
+                            self.project_name,
+                            step.number(),
+                            InstallStep::ALL.len(),
+                            step.title()
+                        ),
+                        _ => String::from("Batch"),
+                    };
+                    let mut lines =
+                        vec![Line::from(header).style(Style::default().fg(Color::Blue).bold())];
+                    // Keep the project being created in view
+                    let running = batch
+                        .entries
+                        .iter()
+                        .position(|entry| entry.status == TaskStatus::Running)
+                        .unwrap_or(0);
+                    let offset = running.saturating_sub(BATCH_ROWS - 1);
+                    for entry in batch.entries.iter().skip(offset).take(BATCH_ROWS) {
+                        let line = Line::from(entry.describe());
+                        lines.push(match entry.status {
+                            TaskStatus::Running => line.bold(),
+                            TaskStatus::Failed => line.style(Style::default().fg(Color::Red)),
+                            _ => line,
+                        });
+                    }
+                    frame.render_widget(Clear, chunks[2]);
+                    frame.render_widget(Paragraph::new(lines), chunks[2]);
+                }
+
+                // The compact view's one line per step, in place of all of the above
+                if !step_rows.is_empty() && self.batch.is_none() {
+                    let lines: Vec<Line> = step_rows
+                        .iter()
+                        .map(|(status, title)| {
+                            let line = Line::from(format!("{} {}", status.icon(), title));
+                            match status {
+                                TaskStatus::Running => line.bold(),
+                                TaskStatus::Pending => line.style(Style::default().fg(Color::Gray)),
+                                _ => line,
+                            }
+                        })
+                        .collect();
+                    frame.render_widget(Clear, chunks[2]);
+                    frame.render_widget(Paragraph::new(lines), chunks[2]);
+                }
+
                 // Add confirmation dialog display
                 if let AppState::ConfirmOverwrite = self.state {
                     let confirm_text = vec![
@@ -1201,18 +10571,46 @@ impl App {
                     frame.render_widget(success, chunks[2]);
                 }
 
-                // Show command output
-                if !self.command_output.is_empty() {
+                // A failed command takes over the output pane with its report
+                if let Some(error) = &self.last_error {
+                    let mut report = error.report();
+                    if let Some(log) = &self.failed_log {
+                        report.push(format!("Full log: {}", log.display()));
+                    }
+                    let mut lines: Vec<Line> = report.into_iter().map(Line::from).collect();
+                    lines[0] = lines[0]
+                        .clone()
+                        .style(Style::default().fg(Color::Red).bold());
+                    let report = Paragraph::new(lines)
+                        .block(
+                            Block::default()
+                                .title("Command Failed")
+                                .borders(Borders::ALL)
+                                .border_style(Style::default().fg(Color::Red)),
+                        )
+                        .wrap(Wrap { trim: false });
+                    frame.render_widget(report, chunks[3]);
+                } else if !self.command_output.is_empty() {
                     let output_text = self
-                        .command_output
-                        .iter()
-                        .map(|line| Line::from(line.as_str()))
+                        .visible_output()
+                        .map(|line| Line::from(line.display(self.timestamps).into_owned()))
                         .collect::<Vec<_>>();
+                    let visible_lines = output_text.len();
 
                     let output = Paragraph::new(output_text)
                         .block(
                             Block::default()
-                                .title("Command Output")
+                                .title(match self.settings.progress_view {
+                                    ProgressView::Detailed => format!(
+                                        "Command Output ({}, F2 to change · {}, F6 · F7 for compact)",
+                                        self.verbosity.label(),
+                                        self.timestamps.label()
+                                    ),
+                                    ProgressView::Compact => format!(
+                                        "Command Output (compact, F7 for detailed · {}, F6)",
+                                        self.timestamps.label()
+                                    ),
+                                })
                                 .borders(Borders::ALL),
                         )
                         .wrap(Wrap { trim: true })
@@ -1232,7 +10630,7 @@ impl App {
                             }),
                         );
                     }
-                    if (self.output_scroll as usize) < self.command_output.len().saturating_sub(1) {
+                    if (self.output_scroll as usize) < visible_lines.saturating_sub(1) {
                         frame.render_widget(
                             Paragraph::new("↓ More below (PgUp/PgDn to scroll)")
                                 .alignment(Alignment::Center)
@@ -1246,34 +10644,49 @@ impl App {
                 }
 
                 if let AppState::TestMenu = self.state {
-                    let menu_text = vec![
+                    let mut menu_text = vec![
                         Line::from("End-to-End Test Menu").style(Style::default().bold()),
                         Line::from(""),
                         Line::from("Use ↑↓ arrows to select, Enter to confirm:")
                             .style(Style::default().fg(Color::Gray)),
-                        Line::from(""),
-                        Line::from(if self.selected_menu_item == 0 {
-                            "▶ 🔧 Run end-to-end test with Anvil"
-                        } else {
-                            "  🔧 Run end-to-end test with Anvil"
-                        })
-                        .style(if self.selected_menu_item == 0 {
-                            Style::default().fg(Color::Yellow).bold()
-                        } else {
-                            Style::default()
-                        }),
-                        Line::from(""),
-                        Line::from(if self.selected_menu_item == 1 {
-                            "▶ 🚪 Exit"
-                        } else {
-                            "  🚪 Exit"
-                        })
-                        .style(if self.selected_menu_item == 1 {
-                            Style::default().fg(Color::Yellow).bold()
-                        } else {
-                            Style::default()
-                        }),
                     ];
+                    let mut presets = vec![Span::from("Logging for the next test: ")];
+                    for (i, preset) in LogPreset::ALL.iter().enumerate() {
+                        if i > 0 {
+                            presets.push(Span::from(" · "));
+                        }
+                        let text = format!("{} {}", i + 1, preset.label());
+                        presets.push(if self.log_preset == Some(*preset) {
+                            Span::styled(text, Style::default().fg(Color::Yellow).bold())
+                        } else {
+                            Span::from(text)
+                        });
+                    }
+                    menu_text.push(Line::from(presets).style(Style::default().fg(Color::Gray)));
+                    if let Some(preset) = self.log_preset {
+                        menu_text.push(
+                            Line::from(format!("  {}", preset.description()))
+                                .style(Style::default().fg(Color::Gray)),
+                        );
+                    }
+                    for (i, item) in ToolboxItem::ALL.iter().enumerate() {
+                        let selected = i == self.selected_menu_item;
+                        let unavailable = self.offline && item.needs_network();
+                        menu_text.push(Line::from(""));
+                        menu_text.push(
+                            Line::from(format!(
+                                "{} {}{}",
+                                if selected { "▶" } else { " " },
+                                item.label(),
+                                if unavailable { " (needs network)" } else { "" }
+                            ))
+                            .style(match (selected, unavailable) {
+                                (_, true) => Style::default().fg(Color::DarkGray),
+                                (true, false) => Style::default().fg(Color::Yellow).bold(),
+                                (false, false) => Style::default(),
+                            }),
+                        );
+                    }
 
                     let menu =
                         Paragraph::new(menu_text).block(Block::default().borders(Borders::NONE));
@@ -1281,5 +10694,71 @@ impl App {
                 }
             }
         }
+
+        if let Some((docs_area, page)) = docs_area {
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Blue))
+                .title(" Docs (F1 to hide) ");
+            frame.render_widget(
+                Paragraph::new(docs::render(page))
+                    .block(block)
+                    .wrap(Wrap { trim: false }),
+                docs_area,
+            );
+        }
+
+        if let Some(lesson) = self.lesson().filter(|_| self.learn) {
+            self.render_lesson(frame, area, lesson);
+        }
+
+        if let Some(palette) = &self.palette {
+            self.render_palette(frame, area, palette);
+        }
+    }
+
+    /// The command palette in a box at the top of `area`, over whatever is there.
+    fn render_palette(&self, frame: &mut Frame, area: Rect, palette: &Palette) {
+        let matches = palette.matches();
+        let width = area.width.min(60);
+        let height = (matches.len() as u16 + 4).clamp(5, area.height.saturating_sub(2).max(5));
+        let rows = height.saturating_sub(4).max(1) as usize;
+        let skip = palette.selected.saturating_sub(rows - 1);
+        let mut lines = vec![
+            Line::from(format!(
+                "> {}{}",
+                palette.query,
+                if self.cursor_shown() { "▏" } else { " " }
+            )),
+            Line::from(""),
+        ];
+        if matches.is_empty() {
+            lines.push(Line::from("No matching action").style(Style::default().fg(Color::Gray)));
+        }
+        for (i, action) in matches.iter().enumerate().skip(skip).take(rows) {
+            let selected = i == palette.selected;
+            let line = Line::from(format!(
+                "{} {}",
+                if selected { "▶" } else { " " },
+                action.label()
+            ));
+            lines.push(if selected {
+                line.style(Style::default().fg(Color::Yellow).bold())
+            } else {
+                line
+            });
+        }
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + 1.min(area.height.saturating_sub(height)),
+            width,
+            height: height.min(area.height),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" Commands (Esc to close) ");
+        frame.render_widget(Clear, popup);
+        frame.render_widget(Paragraph::new(lines).block(block), popup);
     }
 }
@@ -0,0 +1,128 @@
+//! Known gotchas on Apple Silicon Macs: toolchains installed for Intel that only run under
+//! Rosetta, and Docker missing for reproducible guest builds. Each comes with the fix for that
+//! machine instead of a generic failure.
+
+use crate::{health::Finding, tools};
+use std::{path::PathBuf, process::Command};
+
+/// An arm64 Mac, whether or not this binary itself runs under Rosetta.
+pub fn detected() -> bool {
+    cfg!(target_os = "macos") && (cfg!(target_arch = "aarch64") || translated())
+}
+
+/// Whether this process is an Intel binary translated by Rosetta.
+fn translated() -> bool {
+    tools::version("sysctl", &["-n", "sysctl.proc_translated"]).is_ok_and(|v| v == "1")
+}
+
+/// How to install `tool` natively, for the dependency check's failure message.
+pub fn install_hint(tool: &str) -> Option<&'static str> {
+    if !detected() {
+        return None;
+    }
+    match tool {
+        "rust" => Some(
+            "Rust not found. From a native (not Rosetta) terminal run: curl --proto '=https' -sSf https://sh.rustup.rs | sh",
+        ),
+        "foundry" => Some(
+            "Foundry not found. From a native (not Rosetta) terminal run: curl -L https://foundry.paradigm.xyz | bash && foundryup",
+        ),
+        "risc0" => Some(
+            "RISC Zero not found. From a native (not Rosetta) terminal run: curl -L https://risczero.com/install | bash && rzup install",
+        ),
+        _ => None,
+    }
+}
+
+/// `program` as found on `PATH`.
+fn locate(program: &str) -> Option<PathBuf> {
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(program))
+        .find(|path| path.is_file())
+}
+
+/// Whether `program` only has an Intel slice, going by what `file` says about it.
+fn intel_only(program: &str) -> Option<bool> {
+    let path = locate(program)?;
+    let output = Command::new("file").arg("-b").arg(&path).output().ok()?;
+    let kind = String::from_utf8_lossy(&output.stdout);
+    Some(kind.contains("x86_64") && !kind.contains("arm64"))
+}
+
+/// Every check, when on Apple Silicon; nothing elsewhere.
+pub fn checks() -> Vec<Finding> {
+    if !detected() {
+        return Vec::new();
+    }
+    let mut findings = Vec::new();
+
+    if translated() {
+        findings.push(Finding::problem(
+            "Rosetta",
+            "steel-tui is an Intel build running under Rosetta, so everything it starts is too",
+            "Install the aarch64-apple-darwin build of steel-tui",
+        ));
+    }
+
+    const RUST: &str = "Rust toolchain";
+    if let Ok(output) = Command::new("rustc").arg("-vV").output() {
+        let info = String::from_utf8_lossy(&output.stdout);
+        if info.contains("host: x86_64-apple-darwin") {
+            findings.push(Finding::problem(
+                RUST,
+                "The default Rust toolchain is x86_64 and runs under Rosetta, which makes guest builds slow",
+                "Run `rustup set default-host aarch64-apple-darwin` and `rustup toolchain install stable`",
+            ));
+        } else {
+            findings.push(Finding::ok(RUST, "Native arm64 toolchain"));
+        }
+    }
+
+    for (check, programs, fix) in [
+        (
+            "Foundry",
+            &["forge", "anvil", "cast"][..],
+            "Run `foundryup` from a native (not Rosetta) terminal",
+        ),
+        (
+            "RISC Zero",
+            &["cargo-risczero", "r0vm"][..],
+            "Run `rzup install` from a native (not Rosetta) terminal",
+        ),
+    ] {
+        let intel: Vec<&str> = programs
+            .iter()
+            .copied()
+            .filter(|program| intel_only(program) == Some(true))
+            .collect();
+        if intel.is_empty() {
+            findings.push(Finding::ok(check, "No Intel-only binaries"));
+        } else {
+            findings.push(Finding::problem(
+                check,
+                format!("{} only run under Rosetta", intel.join(", ")),
+                fix,
+            ));
+        }
+    }
+
+    // `cargo risczero build` and RISC0_USE_DOCKER build guests in a container, so every machine
+    // gets the same image IDs
+    const DOCKER: &str = "Docker";
+    match Command::new("docker").arg("info").output() {
+        Ok(output) if output.status.success() => {
+            findings.push(Finding::ok(DOCKER, "Running, for reproducible guest builds"))
+        }
+        Ok(_) => findings.push(Finding::problem(
+            DOCKER,
+            "Docker is installed but not running; reproducible guest builds need it",
+            "Start Docker Desktop, or run `colima start`",
+        )),
+        Err(_) => findings.push(Finding::problem(
+            DOCKER,
+            "Docker isn't installed; reproducible guest builds, which give the same image IDs on every machine, need it",
+            "Install Docker Desktop for Apple Silicon, or `brew install colima docker && colima start`",
+        )),
+    }
+    findings
+}
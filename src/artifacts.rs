@@ -0,0 +1,285 @@
+//! Keeps what a test run leaves behind in `artifacts/<timestamp>/` inside the project: the
+//! captured output, anvil's log, forge's broadcast files and any receipts the host saved, plus a
+//! `run.json` record the test history compares runs by.
+
+use color_eyre::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::OnceLock,
+    time::{Duration, Instant, SystemTime},
+};
+
+pub const DIR: &str = "artifacts";
+
+const RECORD: &str = "run.json";
+
+/// Directories that never hold anything a run produced worth keeping.
+const SKIPPED_DIRS: &[&str] = &[".git", "target", "lib", "node_modules", "out", "cache", DIR];
+
+pub struct Run {
+    pub dir: PathBuf,
+    started: SystemTime,
+    /// When the host started proving and, once it had a receipt, how long that took.
+    proving: Option<(Instant, Option<Duration>)>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Step {
+    pub name: String,
+    pub seconds: f64,
+    pub passed: bool,
+}
+
+/// What the history knows about a run.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Record {
+    /// The run's directory name, its start time.
+    #[serde(skip)]
+    pub id: String,
+    /// `end-to-end` or `queue`.
+    pub kind: String,
+    pub passed: bool,
+    pub steps: Vec<Step>,
+    pub proving_seconds: Option<f64>,
+    pub cycles: Option<u64>,
+    /// Gas used by the transactions forge broadcast during the run.
+    pub gas_used: Option<u64>,
+    /// Prover settings and tool versions the run used.
+    pub environment: BTreeMap<String, String>,
+}
+
+impl Record {
+    pub fn seconds(&self) -> f64 {
+        self.steps.iter().map(|step| step.seconds).sum()
+    }
+}
+
+impl Run {
+    /// Makes the run's directory under the project at `root`, and keeps it out of git.
+    pub fn start(root: &Path) -> Result<Self> {
+        let dir = root
+            .join(DIR)
+            .join(chrono::Local::now().format("%Y%m%d-%H%M%S").to_string());
+        fs::create_dir_all(&dir)?;
+        ignore(root)?;
+        // File times come from a coarser clock than `SystemTime::now`, so compare against one
+        let started = fs::metadata(&dir)?.modified()?;
+        Ok(Run {
+            dir,
+            started,
+            proving: None,
+        })
+    }
+
+    /// Stdout and stderr for a background process, both appended to `name` in the run's
+    /// directory. Discards the output if the file can't be made.
+    pub fn log(&self, name: &str) -> (Stdio, Stdio) {
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.dir.join(name));
+        match file.and_then(|file| Ok((file.try_clone()?, file))) {
+            Ok((stdout, stderr)) => (stdout.into(), stderr.into()),
+            Err(_) => (Stdio::null(), Stdio::null()),
+        }
+    }
+
+    /// Times proving from the host's output: from its first line about proving to the first
+    /// one about a receipt or seal after it.
+    pub fn observe(&mut self, line: &str) {
+        static STARTED: OnceLock<Regex> = OnceLock::new();
+        static FINISHED: OnceLock<Regex> = OnceLock::new();
+        match self.proving {
+            None if STARTED
+                .get_or_init(|| {
+                    Regex::new(r"(?i)\b(proving|generating (a )?proof)\b").expect("valid regex")
+                })
+                .is_match(line) =>
+            {
+                self.proving = Some((Instant::now(), None));
+            }
+            Some((started, None))
+                if FINISHED
+                    .get_or_init(|| Regex::new(r"(?i)\b(receipt|seal)\b").expect("valid regex"))
+                    .is_match(line) =>
+            {
+                self.proving = Some((started, Some(started.elapsed())));
+            }
+            _ => {}
+        }
+    }
+
+    /// Copies in what the run wrote to the project, saves its output and writes its record.
+    /// Returns one line per kind of artifact kept.
+    pub fn collect(
+        &self,
+        root: &Path,
+        output: &[String],
+        mut record: Record,
+    ) -> Result<Vec<String>> {
+        let mut kept = Vec::new();
+        fs::write(self.dir.join("output.log"), output.join("\n") + "\n")?;
+        kept.push(String::from("output.log"));
+        if self.dir.join("anvil.log").exists() {
+            kept.push(String::from("anvil.log"));
+        }
+
+        let mut broadcasts = Vec::new();
+        self.changed(root, Path::new("broadcast"), &mut |_| true, &mut broadcasts);
+        for relative in &broadcasts {
+            copy(&root.join(relative), &self.dir.join(relative))?;
+        }
+        record.gas_used = broadcasts
+            .iter()
+            .filter(|relative| relative.extension().is_some_and(|ext| ext == "json"))
+            // run-latest.json repeats the newest timestamped run
+            .filter(|relative| {
+                relative
+                    .file_name()
+                    .is_some_and(|name| name != "run-latest.json")
+            })
+            .filter_map(|relative| gas_used(&root.join(relative)))
+            .reduce(|a, b| a + b);
+        if !broadcasts.is_empty() {
+            kept.push(format!("{} broadcast file(s)", broadcasts.len()));
+        }
+
+        let mut receipts = Vec::new();
+        self.changed(root, Path::new(""), &mut is_receipt, &mut receipts);
+        for relative in &receipts {
+            if let Some(name) = relative.file_name() {
+                copy(&root.join(relative), &self.dir.join("receipts").join(name))?;
+            }
+        }
+        if !receipts.is_empty() {
+            kept.push(format!("{} receipt(s)", receipts.len()));
+        }
+
+        record.proving_seconds = self
+            .proving
+            .and_then(|(_, took)| took)
+            .map(|took| took.as_secs_f64());
+        fs::write(
+            self.dir.join(RECORD),
+            serde_json::to_string_pretty(&record)? + "\n",
+        )?;
+        Ok(kept)
+    }
+
+    /// Files under `relative` written since the run started that `keep` accepts, relative to
+    /// `root`.
+    fn changed(
+        &self,
+        root: &Path,
+        relative: &Path,
+        keep: &mut dyn FnMut(&Path) -> bool,
+        files: &mut Vec<PathBuf>,
+    ) {
+        let Ok(entries) = fs::read_dir(root.join(relative)) else {
+            return;
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let name = entry.file_name();
+            let path = relative.join(&name);
+            if entry.path().is_dir() {
+                if !SKIPPED_DIRS.iter().any(|dir| name == *dir) {
+                    self.changed(root, &path, keep, files);
+                }
+            } else if keep(&path)
+                && entry
+                    .metadata()
+                    .and_then(|meta| meta.modified())
+                    .is_ok_and(|modified| modified >= self.started)
+            {
+                files.push(path);
+            }
+        }
+    }
+}
+
+/// Recorded runs of the project at `root`, newest first.
+pub fn history(root: &Path) -> Vec<Record> {
+    let Ok(entries) = fs::read_dir(root.join(DIR)) else {
+        return Vec::new();
+    };
+    let mut records: Vec<Record> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let content = fs::read_to_string(entry.path().join(RECORD)).ok()?;
+            let mut record: Record = serde_json::from_str(&content).ok()?;
+            record.id = entry.file_name().to_string_lossy().to_string();
+            Some(record)
+        })
+        .collect();
+    records.sort_by(|a, b| b.id.cmp(&a.id));
+    records
+}
+
+/// Total gas of the transaction receipts in a forge broadcast file.
+fn gas_used(file: &Path) -> Option<u64> {
+    #[derive(Deserialize)]
+    struct Broadcast {
+        receipts: Vec<Receipt>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Receipt {
+        gas_used: String,
+    }
+    let broadcast: Broadcast = serde_json::from_str(&fs::read_to_string(file).ok()?).ok()?;
+    broadcast
+        .receipts
+        .iter()
+        .map(|receipt| {
+            let gas = &receipt.gas_used;
+            match gas.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16).ok(),
+                None => gas.parse().ok(),
+            }
+        })
+        .sum()
+}
+
+/// Receipts and journals as hosts tend to save them, e.g. `receipt.json` or `proof.seal`.
+fn is_receipt(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    name.contains("receipt")
+        || path
+            .extension()
+            .is_some_and(|ext| ext == "seal" || ext == "journal")
+}
+
+fn copy(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::copy(from, to)?;
+    Ok(())
+}
+
+/// Adds `artifacts/` to the project's `.gitignore` if it isn't there yet.
+fn ignore(root: &Path) -> Result<()> {
+    let gitignore = root.join(".gitignore");
+    let ignored = fs::read_to_string(&gitignore).unwrap_or_default();
+    if ignored
+        .lines()
+        .any(|line| line.trim().trim_matches('/') == DIR)
+    {
+        return Ok(());
+    }
+    let separator = if ignored.is_empty() || ignored.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    fs::write(&gitignore, format!("{}{}{}/\n", ignored, separator, DIR))?;
+    Ok(())
+}
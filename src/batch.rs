@@ -0,0 +1,178 @@
+//! Several projects created in one run from a manifest, e.g. a scaffold for every seat of a
+//! workshop:
+//!
+//! ```toml
+//! [[project]]
+//! name = "team-1"
+//! template = "erc20-counter"   # optional, the default template
+//! branch = "release-2.1"       # optional, the newest release with the template
+//! destination = "workshop"     # optional, relative to the working directory
+//! ```
+
+use crate::tasks::TaskStatus;
+use color_eyre::{eyre::eyre, Result};
+use serde::Deserialize;
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+#[derive(Deserialize)]
+struct File {
+    #[serde(default, rename = "project")]
+    projects: Vec<Project>,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Project {
+    pub name: String,
+    pub template: Option<String>,
+    pub branch: Option<String>,
+    pub destination: Option<PathBuf>,
+}
+
+pub struct Entry {
+    pub project: Project,
+    pub status: TaskStatus,
+    pub started: Option<Instant>,
+    pub took: Option<Duration>,
+    /// Why it failed, shown on its row.
+    pub error: Option<String>,
+}
+
+impl Entry {
+    pub fn describe(&self) -> String {
+        let dir = match &self.project.destination {
+            Some(dir) => dir.join(&self.project.name),
+            None => PathBuf::from(&self.project.name),
+        };
+        let mut line = format!("{} {:<32}", self.status.icon(), dir.display());
+        if let Some(took) = self.took {
+            line.push_str(&format!(" {:.1}s", took.as_secs_f64()));
+        }
+        if let Some(error) = &self.error {
+            line.push_str(&format!("  {}", error));
+        }
+        line
+    }
+}
+
+pub struct Batch {
+    pub entries: Vec<Entry>,
+    /// What destinations are relative to: the directory the app was launched from.
+    pub base_dir: PathBuf,
+}
+
+impl Batch {
+    pub fn load(path: &Path, base_dir: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| eyre!("Could not read {}: {}", path.display(), e))?;
+        let file: File = toml::from_str(&content)
+            .map_err(|e| eyre!("Invalid batch manifest {}: {}", path.display(), e))?;
+        if file.projects.is_empty() {
+            return Err(eyre!("{} lists no [[project]]", path.display()));
+        }
+        for (i, project) in file.projects.iter().enumerate() {
+            if project.name.trim().is_empty() {
+                return Err(eyre!("Project {} in {} has no name", i + 1, path.display()));
+            }
+            // The directory is replaced with --overwrite, so it can't be anything but a child
+            if !matches!(
+                Path::new(&project.name).components().collect::<Vec<_>>()[..],
+                [Component::Normal(_)]
+            ) {
+                return Err(eyre!(
+                    "'{}' in {} isn't a plain directory name",
+                    project.name,
+                    path.display()
+                ));
+            }
+            if let Some(destination) = &project.destination {
+                if !destination
+                    .components()
+                    .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+                {
+                    return Err(eyre!(
+                        "The destination of '{}' in {} must stay inside the working directory",
+                        project.name,
+                        path.display()
+                    ));
+                }
+            }
+            let dir = project.destination.clone().unwrap_or_default();
+            if file.projects[..i].iter().any(|other| {
+                other.destination.clone().unwrap_or_default() == dir && other.name == project.name
+            }) {
+                return Err(eyre!(
+                    "'{}' is listed twice in {}",
+                    dir.join(&project.name).display(),
+                    path.display()
+                ));
+            }
+        }
+        Ok(Self {
+            entries: file
+                .projects
+                .into_iter()
+                .map(|project| Entry {
+                    project,
+                    status: TaskStatus::Pending,
+                    started: None,
+                    took: None,
+                    error: None,
+                })
+                .collect(),
+            base_dir: base_dir.to_path_buf(),
+        })
+    }
+
+    /// Index of the next project to create.
+    pub fn next_pending(&self) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|entry| entry.status == TaskStatus::Pending)
+    }
+
+    /// Marks the project at `index` as being created.
+    pub fn start(&mut self, index: usize) -> &Project {
+        let entry = &mut self.entries[index];
+        entry.status = TaskStatus::Running;
+        entry.started = Some(Instant::now());
+        &entry.project
+    }
+
+    /// Records how creating the running project went.
+    pub fn finish(&mut self, error: Option<String>) {
+        let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.status == TaskStatus::Running)
+        else {
+            return;
+        };
+        entry.status = if error.is_some() {
+            TaskStatus::Failed
+        } else {
+            TaskStatus::Done
+        };
+        entry.took = entry.started.map(|started| started.elapsed());
+        entry.error = error;
+    }
+
+    /// Where the project is created.
+    pub fn dir(&self, project: &Project) -> PathBuf {
+        match &project.destination {
+            Some(dir) => self.base_dir.join(dir),
+            None => self.base_dir.clone(),
+        }
+    }
+
+    pub fn count(&self, status: TaskStatus) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.status == status)
+            .count()
+    }
+}
@@ -0,0 +1,148 @@
+//! Runs the end-to-end flow repeatedly under each prover backend and compares wall time and the
+//! cycle counts the zkVM logs, to help decide where to prove.
+
+use regex::Regex;
+use std::time::Duration;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Dev,
+    Local,
+    Bonsai,
+}
+
+impl Backend {
+    pub const ALL: &'static [Backend] = &[Backend::Dev, Backend::Local, Backend::Bonsai];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Backend::Dev => "Dev mode (no proof)",
+            Backend::Local => "Local prover",
+            Backend::Bonsai => "Bonsai",
+        }
+    }
+
+    /// Variables selecting this backend. Each sets all of them so nothing leaks in from our
+    /// own environment.
+    pub fn vars(self, bonsai_api_key: &str) -> Vec<(&'static str, String)> {
+        match self {
+            Backend::Dev => vec![
+                ("RISC0_DEV_MODE", String::from("1")),
+                ("RISC0_PROVER", String::from("local")),
+            ],
+            Backend::Local => vec![
+                ("RISC0_DEV_MODE", String::from("0")),
+                ("RISC0_PROVER", String::from("local")),
+            ],
+            Backend::Bonsai => vec![
+                ("RISC0_DEV_MODE", String::from("0")),
+                ("RISC0_PROVER", String::from("bonsai")),
+                ("BONSAI_API_KEY", bonsai_api_key.to_string()),
+                (
+                    "BONSAI_API_URL",
+                    String::from(crate::bonsai::DEFAULT_API_URL),
+                ),
+            ],
+        }
+    }
+}
+
+pub struct Run {
+    pub backend: Backend,
+    pub wall: Duration,
+    pub passed: bool,
+    pub cycles: Option<u64>,
+    pub user_cycles: Option<u64>,
+}
+
+/// Total and user cycles of the last proof in a run's output. The local executor logs
+/// `N total cycles`; Bonsai's session stats use `total_cycles: N`.
+pub fn cycles(lines: &[String]) -> (Option<u64>, Option<u64>) {
+    let find = |patterns: &[&str]| {
+        let patterns: Vec<Regex> = patterns
+            .iter()
+            .map(|p| Regex::new(p).expect("valid regex"))
+            .collect();
+        lines.iter().rev().find_map(|line| {
+            patterns.iter().find_map(|pattern| {
+                let caps = pattern.captures(line)?;
+                caps[1].replace(',', "").parse().ok()
+            })
+        })
+    };
+    (
+        find(&[r"(\d[\d,]*) total cycles", r"total_cycles\W+(\d+)"]),
+        find(&[r"(\d[\d,]*) user cycles", r"user_cycles\W+(\d+)"]),
+    )
+}
+
+/// One row of the comparison table.
+pub struct Summary {
+    pub backend: Backend,
+    pub runs: usize,
+    pub passed: usize,
+    /// Mean, fastest and slowest of the runs that passed.
+    pub wall: Option<(Duration, Duration, Duration)>,
+    pub cycles: Option<u64>,
+    pub user_cycles: Option<u64>,
+}
+
+impl Summary {
+    pub fn header() -> String {
+        format!(
+            "{:<20} {:>9} {:>8} {:>8} {:>8} {:>14} {:>14}",
+            "Backend", "Passed", "Mean", "Min", "Max", "Total cycles", "User cycles"
+        )
+    }
+
+    pub fn row(&self) -> String {
+        let secs = |d: Duration| format!("{:.1}s", d.as_secs_f64());
+        let (mean, min, max) = match self.wall {
+            Some((mean, min, max)) => (secs(mean), secs(min), secs(max)),
+            None => (String::from("—"), String::from("—"), String::from("—")),
+        };
+        let count = |c: Option<u64>| c.map_or(String::from("—"), |c| c.to_string());
+        format!(
+            "{:<20} {:>4}/{:<4} {:>8} {:>8} {:>8} {:>14} {:>14}",
+            self.backend.label(),
+            self.passed,
+            self.runs,
+            mean,
+            min,
+            max,
+            count(self.cycles),
+            count(self.user_cycles)
+        )
+    }
+}
+
+/// Per-backend summaries, in `Backend::ALL` order, of the backends that ran.
+pub fn summarize(runs: &[Run]) -> Vec<Summary> {
+    Backend::ALL
+        .iter()
+        .filter_map(|&backend| {
+            let runs: Vec<&Run> = runs.iter().filter(|r| r.backend == backend).collect();
+            if runs.is_empty() {
+                return None;
+            }
+            let passed: Vec<&&Run> = runs.iter().filter(|r| r.passed).collect();
+            let wall = (!passed.is_empty()).then(|| {
+                let total: Duration = passed.iter().map(|r| r.wall).sum();
+                (
+                    total / passed.len() as u32,
+                    passed.iter().map(|r| r.wall).min().unwrap_or_default(),
+                    passed.iter().map(|r| r.wall).max().unwrap_or_default(),
+                )
+            });
+            Some(Summary {
+                backend,
+                runs: runs.len(),
+                passed: passed.len(),
+                wall,
+                // The guest runs the same input every time, so any run's count stands for all
+                cycles: passed.iter().find_map(|r| r.cycles),
+                user_cycles: passed.iter().find_map(|r| r.user_cycles),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,109 @@
+//! Read-only Bonsai API calls for the account panel: availability, quotas, and session status.
+
+use crate::http;
+use color_eyre::Result;
+use regex::Regex;
+use serde::{de::DeserializeOwned, Deserialize};
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_API_URL: &str = "https://api.bonsai.xyz";
+
+pub struct Client {
+    api_url: String,
+    api_key: String,
+    /// Bonsai rejects requests that don't name a zkVM version it serves.
+    risc0_version: String,
+}
+
+#[derive(Deserialize)]
+pub struct Quotas {
+    pub exec_cycle_limit: i64,
+    pub concurrent_proofs: i64,
+    pub cycle_budget: i64,
+    pub cycle_usage: i64,
+}
+
+#[derive(Deserialize)]
+struct Versions {
+    risc0_zkvm: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SessionStatus {
+    /// RUNNING, SUCCEEDED, FAILED, TIMED_OUT, or ABORTED.
+    pub status: String,
+    pub state: Option<String>,
+    pub error_msg: Option<String>,
+    pub elapsed_time: Option<f64>,
+}
+
+/// How the API answered a version request.
+pub struct Availability {
+    pub versions: Vec<String>,
+    pub latency: Duration,
+}
+
+impl Client {
+    pub fn new(api_url: &str, api_key: &str, risc0_version: &str) -> Self {
+        Self {
+            api_url: api_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+            risc0_version: risc0_version.to_string(),
+        }
+    }
+
+    fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        Ok(http::agent()
+            .get(&format!("{}{}", self.api_url, path))
+            .header("Accept", "application/json")
+            .header("x-api-key", &self.api_key)
+            .header("x-risc0-version", &self.risc0_version)
+            .call()?
+            .body_mut()
+            .read_json()?)
+    }
+
+    pub fn availability(&self) -> Result<Availability> {
+        let started = Instant::now();
+        let versions: Versions = self.get("/version")?;
+        Ok(Availability {
+            versions: versions.risc0_zkvm,
+            latency: started.elapsed(),
+        })
+    }
+
+    pub fn quotas(&self) -> Result<Quotas> {
+        self.get("/user/quotas")
+    }
+
+    pub fn session_status(&self, session_id: &str) -> Result<SessionStatus> {
+        self.get(&format!("/sessions/status/{}", session_id))
+    }
+}
+
+/// Session IDs mentioned in command output, most recent first, at most `limit`.
+pub fn session_ids<'a>(
+    lines: impl DoubleEndedIterator<Item = &'a str>,
+    limit: usize,
+) -> Vec<String> {
+    let uuid =
+        Regex::new(r"[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}")
+            .expect("valid regex");
+
+    let mut ids = Vec::new();
+    for line in lines.rev() {
+        if !line.to_lowercase().contains("session") {
+            continue;
+        }
+        for id in uuid.find_iter(line) {
+            if !ids.iter().any(|known| known == id.as_str()) {
+                ids.push(id.as_str().to_string());
+            }
+        }
+        if ids.len() >= limit {
+            ids.truncate(limit);
+            break;
+        }
+    }
+    ids
+}
@@ -0,0 +1,294 @@
+//! Submits proof requests to the Boundless market and tracks them, whether we or the
+//! end-to-end test's host submitted them. Request IDs are picked out of the output, then their
+//! state is read from the market contract.
+
+use crate::chain::Chain;
+use alloy::primitives::{keccak256, Address, B256, U256};
+use color_eyre::{eyre::eyre, Result};
+use regex::Regex;
+use std::{
+    path::Path,
+    process::Command,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+/// How often the market is asked about open requests.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// How far back to look for lock and fulfillment events.
+const LOG_LOOKBACK_BLOCKS: u64 = 10_000;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RequestStatus {
+    /// Submitted and waiting for a prover to lock it.
+    Submitted,
+    Locked,
+    Fulfilled,
+}
+
+impl RequestStatus {
+    pub fn icon(self) -> &'static str {
+        match self {
+            RequestStatus::Submitted => "○",
+            RequestStatus::Locked => "◐",
+            RequestStatus::Fulfilled => "✓",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RequestStatus::Submitted => "submitted",
+            RequestStatus::Locked => "locked",
+            RequestStatus::Fulfilled => "fulfilled",
+        }
+    }
+}
+
+pub struct TrackedRequest {
+    pub id: U256,
+    pub status: RequestStatus,
+    pub prover: Option<Address>,
+    pub seen: Instant,
+    /// Time from first sighting to fulfillment.
+    pub fulfilled_after: Option<Duration>,
+}
+
+impl TrackedRequest {
+    /// One row of the progress panel.
+    pub fn describe(&self) -> String {
+        let id = format!("{:#x}", self.id);
+        let short_id = if id.len() > 14 {
+            format!("{}…{}", &id[..8], &id[id.len() - 4..])
+        } else {
+            id
+        };
+        let mut row = format!(
+            "{} {:<14} {:<10}",
+            self.status.icon(),
+            short_id,
+            self.status.label()
+        );
+        if let Some(prover) = self.prover {
+            row.push_str(&format!(" prover {}", prover));
+        }
+        if let Some(after) = self.fulfilled_after {
+            row.push_str(&format!(" in {}s", after.as_secs()));
+        }
+        row
+    }
+}
+
+pub struct Tracker {
+    market: String,
+    rpc_url: String,
+    chain: Option<Chain>,
+    pub requests: Vec<TrackedRequest>,
+    last_poll: Option<Instant>,
+    /// Why the last poll failed, if it did.
+    pub last_error: Option<String>,
+}
+
+impl Tracker {
+    /// Tracks requests on the market at `BOUNDLESS_MARKET_ADDRESS`, read through `RPC_URL`
+    /// (the variable the Boundless SDK uses) or `default_rpc`. `None` without a market address.
+    pub fn from_env(default_rpc: &str) -> Option<Self> {
+        let market = std::env::var("BOUNDLESS_MARKET_ADDRESS")
+            .ok()
+            .filter(|m| !m.is_empty())?;
+        let rpc_url = std::env::var("RPC_URL")
+            .ok()
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| default_rpc.to_string());
+        Some(Self {
+            market,
+            rpc_url,
+            chain: None,
+            requests: Vec::new(),
+            last_poll: None,
+            last_error: None,
+        })
+    }
+
+    pub fn market(&self) -> &str {
+        &self.market
+    }
+
+    /// Starts tracking any request ID mentioned in a line of output. Returns the new ID.
+    pub fn observe(&mut self, line: &str) -> Option<U256> {
+        let id: U256 = request_id_regex()
+            .captures(line)?
+            .get(1)?
+            .as_str()
+            .parse()
+            .ok()?;
+        if self.requests.iter().any(|r| r.id == id) {
+            return None;
+        }
+        self.requests.push(TrackedRequest {
+            id,
+            status: RequestStatus::Submitted,
+            prover: None,
+            seen: Instant::now(),
+            fulfilled_after: None,
+        });
+        // Ask about it right away
+        self.last_poll = None;
+        Some(id)
+    }
+
+    /// Refreshes open requests at most every [`POLL_INTERVAL`]. Returns a message for each
+    /// request whose status changed.
+    pub fn poll(&mut self) -> Vec<String> {
+        let open = self
+            .requests
+            .iter()
+            .any(|r| r.status != RequestStatus::Fulfilled);
+        if !open || self.last_poll.is_some_and(|t| t.elapsed() < POLL_INTERVAL) {
+            return Vec::new();
+        }
+        self.last_poll = Some(Instant::now());
+
+        match self.refresh() {
+            Ok(changes) => {
+                self.last_error = None;
+                changes
+            }
+            Err(e) => {
+                self.last_error = Some(e.to_string());
+                Vec::new()
+            }
+        }
+    }
+
+    fn refresh(&mut self) -> Result<Vec<String>> {
+        if self.chain.is_none() {
+            self.chain = Some(Chain::connect(&self.rpc_url)?);
+        }
+        let chain = self.chain.as_ref().ok_or_else(|| eyre!("Not connected"))?;
+
+        let mut changes = Vec::new();
+        for request in &mut self.requests {
+            if request.status == RequestStatus::Fulfilled {
+                continue;
+            }
+            let status = if market_flag(chain, &self.market, "requestIsFulfilled", request.id)? {
+                RequestStatus::Fulfilled
+            } else if market_flag(chain, &self.market, "requestIsLocked", request.id)? {
+                RequestStatus::Locked
+            } else {
+                RequestStatus::Submitted
+            };
+            if status == request.status {
+                continue;
+            }
+
+            request.status = status;
+            if request.prover.is_none() {
+                request.prover = assigned_prover(chain, &self.market, request.id)?;
+            }
+            if status == RequestStatus::Fulfilled {
+                request.fulfilled_after = Some(request.seen.elapsed());
+            }
+            let mut message = format!("Boundless request {:#x} {}", request.id, status.label());
+            if let Some(prover) = request.prover {
+                message.push_str(&format!(" by prover {}", prover));
+            }
+            changes.push(message);
+        }
+        Ok(changes)
+    }
+}
+
+fn request_id_regex() -> &'static Regex {
+    static REQUEST_ID: OnceLock<Regex> = OnceLock::new();
+    REQUEST_ID.get_or_init(|| {
+        Regex::new(r"(?i)request(?:\s+id)?[\s:=#]*(0x[0-9a-f]{1,64})\b").expect("valid regex")
+    })
+}
+
+/// Submits a request to prove `program` with the bytes in `input`, through the `boundless`
+/// CLI. It reads the market, RPC URL and key from the same `BOUNDLESS_*`, `RPC_URL` and
+/// `PRIVATE_KEY` variables the SDK does, and prints the request ID [`Tracker::observe`] picks up.
+pub fn submit_command(program: &Path, input: &Path) -> Command {
+    let mut command = Command::new("boundless");
+    command
+        .args(["request", "submit-offer", "--program"])
+        .arg(program)
+        .arg("--input-file")
+        .arg(input);
+    command
+}
+
+/// Calls one of the market's `(uint256) returns (bool)` views.
+fn market_flag(chain: &Chain, market: &str, function: &str, id: U256) -> Result<bool> {
+    let selector = keccak256(format!("{}(uint256)", function));
+    let mut calldata = selector[..4].to_vec();
+    calldata.extend_from_slice(&id.to_be_bytes::<32>());
+    let result = chain.call(market, calldata)?;
+    Ok(result.last().is_some_and(|b| *b != 0))
+}
+
+/// `ProofRequest` as the market's events encode it.
+const PROOF_REQUEST: &str = "(uint256,(bytes32,(uint8,bytes),(address,uint96),bytes4),string,(uint8,bytes),(uint256,uint256,uint64,uint32,uint32,uint32,uint256))";
+/// `Fulfillment` as the market's events encode it.
+const FULFILLMENT: &str = "(uint256,bytes32,bytes32,bytes,bytes)";
+
+/// Where an event names the prover.
+enum ProverField {
+    /// The first data word, for `RequestLocked`.
+    FirstWord,
+    /// The second indexed topic, for `RequestFulfilled` and `ProofDelivered`.
+    Topic,
+}
+
+/// Market events that name the prover of a request, by topic. Older markets locked with just
+/// the prover, hence two `RequestLocked` signatures.
+fn prover_events() -> &'static [(B256, ProverField)] {
+    static EVENTS: OnceLock<Vec<(B256, ProverField)>> = OnceLock::new();
+    EVENTS.get_or_init(|| {
+        vec![
+            (
+                keccak256(format!(
+                    "RequestLocked(uint256,address,{},bytes)",
+                    PROOF_REQUEST
+                )),
+                ProverField::FirstWord,
+            ),
+            (
+                keccak256("RequestLocked(uint256,address)"),
+                ProverField::FirstWord,
+            ),
+            (
+                keccak256(format!("RequestFulfilled(uint256,address,{})", FULFILLMENT)),
+                ProverField::Topic,
+            ),
+            (
+                keccak256(format!("ProofDelivered(uint256,address,{})", FULFILLMENT)),
+                ProverField::Topic,
+            ),
+        ]
+    })
+}
+
+/// The prover that locked or fulfilled a request, from the market's events for that ID.
+/// Events of a market version we don't know are skipped, leaving the prover unknown.
+fn assigned_prover(chain: &Chain, market: &str, id: U256) -> Result<Option<Address>> {
+    let logs = chain.logs(market, B256::from(id), LOG_LOOKBACK_BLOCKS)?;
+    for log in logs.iter().rev() {
+        let topics = log.topics();
+        let Some((_, field)) = topics
+            .first()
+            .and_then(|topic| prover_events().iter().find(|(event, _)| event == topic))
+        else {
+            continue;
+        };
+        let data = &log.data().data;
+        let prover = match field {
+            ProverField::FirstWord if data.len() >= 32 => Address::from_slice(&data[12..32]),
+            ProverField::Topic if topics.len() >= 3 => Address::from_word(topics[2]),
+            _ => continue,
+        };
+        return Ok(Some(prover));
+    }
+    Ok(None)
+}
@@ -0,0 +1,137 @@
+//! Reads the `broadcast/` artifacts forge writes after `forge script --broadcast`.
+
+use color_eyre::{eyre::eyre, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// A contract created by a broadcast script.
+#[derive(Clone)]
+pub struct Deployed {
+    pub name: String,
+    pub address: String,
+    pub tx_hash: String,
+}
+
+#[derive(Deserialize)]
+struct Run {
+    transactions: Vec<Transaction>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Transaction {
+    hash: Option<String>,
+    transaction_type: String,
+    contract_name: Option<String>,
+    contract_address: Option<String>,
+    function: Option<String>,
+    transaction: Option<Request>,
+}
+
+/// The request forge sent, or would send.
+#[derive(Deserialize)]
+struct Request {
+    /// Hex gas limit.
+    gas: Option<String>,
+}
+
+/// A transaction a script sent, or would have sent without `--broadcast`.
+pub struct Sent {
+    pub kind: String,
+    pub contract: Option<String>,
+    pub address: Option<String>,
+    pub function: Option<String>,
+    pub hash: Option<String>,
+    /// Gas limit forge estimated for it.
+    pub gas: Option<u64>,
+}
+
+impl Sent {
+    pub fn is_creation(&self) -> bool {
+        self.kind == "CREATE" || self.kind == "CREATE2"
+    }
+
+    /// e.g. `CREATE Counter at 0x5FbD…` or `CALL Counter.increment(uint256)`.
+    pub fn describe(&self) -> String {
+        let contract = self.contract.as_deref().unwrap_or("unknown contract");
+        let mut line = match (&self.function, &self.address) {
+            (Some(function), _) => format!("{} {}.{}", self.kind, contract, function),
+            (None, Some(address)) => format!("{} {} at {}", self.kind, contract, address),
+            (None, None) => format!("{} {}", self.kind, contract),
+        };
+        if let Some(hash) = &self.hash {
+            line.push_str(&format!(" ({})", hash));
+        }
+        line
+    }
+}
+
+/// `broadcast/<script file>/<chain id>/run-latest.json` under the project root.
+pub fn latest_run_file(root: &Path, script: &Path, chain_id: u64) -> PathBuf {
+    let script_name = script.file_name().unwrap_or(script.as_os_str());
+    root.join("broadcast")
+        .join(script_name)
+        .join(chain_id.to_string())
+        .join("run-latest.json")
+}
+
+/// Where forge records a run of `script` on `chain_id`: `run-latest.json` when it was
+/// broadcast, else the same under `dry-run/`.
+pub fn run_file(root: &Path, script: &Path, chain_id: u64, broadcast: bool) -> PathBuf {
+    let file = latest_run_file(root, script, chain_id);
+    if broadcast {
+        return file;
+    }
+    let dir = file.parent().unwrap_or(root);
+    dir.join("dry-run").join("run-latest.json")
+}
+
+/// The transactions of the latest run of `script` on `chain_id`.
+pub fn transactions(
+    root: &Path,
+    script: &Path,
+    chain_id: u64,
+    broadcast: bool,
+) -> Result<Vec<Sent>> {
+    let file = run_file(root, script, chain_id, broadcast);
+    let content = std::fs::read_to_string(&file)
+        .map_err(|e| eyre!("Could not read {}: {}", file.display(), e))?;
+    let run: Run = serde_json::from_str(&content)?;
+
+    Ok(run
+        .transactions
+        .into_iter()
+        .map(|tx| Sent {
+            kind: tx.transaction_type,
+            contract: tx.contract_name,
+            address: tx.contract_address,
+            function: tx.function,
+            hash: tx.hash.filter(|hash| !hash.is_empty()),
+            gas: tx
+                .transaction
+                .and_then(|request| request.gas)
+                .and_then(|gas| u64::from_str_radix(gas.trim_start_matches("0x"), 16).ok()),
+        })
+        .collect())
+}
+
+/// Contracts created by the latest broadcast of `script` on `chain_id`.
+pub fn deployed_contracts(root: &Path, script: &Path, chain_id: u64) -> Result<Vec<Deployed>> {
+    let file = latest_run_file(root, script, chain_id);
+    let content = std::fs::read_to_string(&file)
+        .map_err(|e| eyre!("Could not read {}: {}", file.display(), e))?;
+    let run: Run = serde_json::from_str(&content)?;
+
+    Ok(run
+        .transactions
+        .into_iter()
+        .filter(|tx| tx.transaction_type == "CREATE" || tx.transaction_type == "CREATE2")
+        .filter_map(|tx| {
+            Some(Deployed {
+                name: tx.contract_name?,
+                address: tx.contract_address?,
+                tx_hash: tx.hash.unwrap_or_default(),
+            })
+        })
+        .collect())
+}
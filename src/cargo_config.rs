@@ -0,0 +1,77 @@
+//! Writes `.cargo/config.toml` into a generated project so its builds fetch crates through a
+//! registry mirror, for places where crates.io itself is slow to reach.
+
+use color_eyre::{eyre::eyre, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use toml::{Table, Value};
+
+/// The name the mirror is registered under in `[source]`.
+const SOURCE: &str = "mirror";
+
+/// Checks a mirror is an index URL cargo accepts, e.g. `sparse+https://rsproxy.cn/index/`.
+pub fn validate_mirror(mirror: &str) -> Result<()> {
+    const SCHEMES: &[&str] = &[
+        "sparse+https://",
+        "sparse+http://",
+        "https://",
+        "http://",
+        "git://",
+        "ssh://",
+        "file://",
+    ];
+    if SCHEMES.iter().any(|scheme| mirror.starts_with(scheme)) {
+        Ok(())
+    } else {
+        Err(eyre!(
+            "Mirror '{}' should be an index URL, e.g. sparse+https://rsproxy.cn/index/",
+            mirror
+        ))
+    }
+}
+
+/// Adds the mirror and `git-fetch-with-cli` to the project's cargo config, keeping anything the
+/// template already put there. Returns the file written, if there was anything to write.
+pub fn write(
+    root: &Path,
+    mirror: Option<&str>,
+    git_fetch_with_cli: bool,
+) -> Result<Option<PathBuf>> {
+    if mirror.is_none() && !git_fetch_with_cli {
+        return Ok(None);
+    }
+    let file = root.join(".cargo").join("config.toml");
+    let mut config = match fs::read_to_string(&file) {
+        Ok(content) => content
+            .parse::<Table>()
+            .map_err(|e| eyre!("Invalid cargo config in {}: {}", file.display(), e))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Table::new(),
+        Err(e) => return Err(eyre!("Could not read {}: {}", file.display(), e)),
+    };
+
+    if let Some(mirror) = mirror {
+        let source = table(&mut config, "source");
+        table(source, "crates-io").insert(String::from("replace-with"), Value::from(SOURCE));
+        table(source, SOURCE).insert(String::from("registry"), Value::from(mirror));
+    }
+    if git_fetch_with_cli {
+        table(&mut config, "net").insert(String::from("git-fetch-with-cli"), Value::from(true));
+    }
+
+    fs::create_dir_all(root.join(".cargo"))?;
+    fs::write(&file, toml::to_string_pretty(&config)?)?;
+    Ok(Some(file))
+}
+
+/// The table under `key`, created if missing or replaced if something else is there.
+fn table<'a>(parent: &'a mut Table, key: &str) -> &'a mut Table {
+    let value = parent
+        .entry(key)
+        .or_insert_with(|| Value::Table(Table::new()));
+    if !value.is_table() {
+        *value = Value::Table(Table::new());
+    }
+    value.as_table_mut().expect("just made a table")
+}
@@ -0,0 +1,365 @@
+//! Templates made for cargo-generate: a directory of files with Liquid placeholders such as
+//! `{{project-name}}` or `{{crate_name | upper_camel_case}}`, and a `cargo-generate.toml`
+//! declaring the values to ask for. Placeholders and their filters are rendered in file contents
+//! and names; Liquid tags (`{% if %}` and the like) are not, and are reported instead.
+
+use color_eyre::{eyre::eyre, Result};
+use regex::{Captures, Regex};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::OnceLock,
+};
+
+pub const CONFIG_FILE: &str = "cargo-generate.toml";
+
+/// Never copied into the project.
+const SKIPPED: &[&str] = &[".git", CONFIG_FILE, "cargo-generate.toml.liquid"];
+
+#[derive(Deserialize, Default)]
+struct Config {
+    #[serde(default)]
+    template: TemplateSection,
+    #[serde(default)]
+    placeholders: BTreeMap<String, PlaceholderSpec>,
+}
+
+#[derive(Deserialize, Default)]
+struct TemplateSection {
+    /// Paths left out of the project.
+    #[serde(default)]
+    ignore: Vec<String>,
+    /// Paths copied without rendering.
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct PlaceholderSpec {
+    #[serde(rename = "type", default = "string_type")]
+    kind: String,
+    prompt: Option<String>,
+    #[serde(default)]
+    choices: Vec<String>,
+    default: Option<toml::Value>,
+    regex: Option<String>,
+}
+
+fn string_type() -> String {
+    String::from("string")
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub enum Kind {
+    Text,
+    Bool,
+    Choice(Vec<String>),
+}
+
+#[derive(Clone)]
+pub struct Placeholder {
+    pub name: String,
+    pub prompt: String,
+    pub kind: Kind,
+    /// What the field starts with.
+    pub default: String,
+    regex: Option<Regex>,
+}
+
+impl Placeholder {
+    fn parse(name: &str, spec: PlaceholderSpec) -> Result<Self> {
+        let kind = match spec.kind.as_str() {
+            "bool" => Kind::Bool,
+            "string" if !spec.choices.is_empty() => Kind::Choice(spec.choices),
+            "string" => Kind::Text,
+            other => return Err(eyre!("placeholder '{}' has unknown type '{}'", name, other)),
+        };
+        let default = match spec.default {
+            Some(toml::Value::String(value)) => value,
+            Some(value) => value.to_string(),
+            None => match &kind {
+                Kind::Bool => String::from("false"),
+                Kind::Choice(choices) => choices[0].clone(),
+                Kind::Text => String::new(),
+            },
+        };
+        let regex = spec
+            .regex
+            .map(|pattern| {
+                Regex::new(&format!("^(?:{})$", pattern))
+                    .map_err(|e| eyre!("placeholder '{}' has an invalid regex: {}", name, e))
+            })
+            .transpose()?;
+        Ok(Self {
+            name: name.to_string(),
+            prompt: spec.prompt.unwrap_or_else(|| name.to_string()),
+            kind,
+            default,
+            regex,
+        })
+    }
+
+    /// Why `value` can't be used, if it can't.
+    pub fn check(&self, value: &str) -> Option<String> {
+        match &self.regex {
+            Some(regex) if !regex.is_match(value) => Some(format!(
+                "'{}' doesn't match the pattern {}",
+                value,
+                regex
+                    .as_str()
+                    .trim_start_matches("^(?:")
+                    .trim_end_matches(")$")
+            )),
+            _ => None,
+        }
+    }
+}
+
+pub struct Template {
+    /// The directory's name, e.g. `steel-starter`.
+    pub name: String,
+    pub dir: PathBuf,
+    pub placeholders: Vec<Placeholder>,
+    ignore: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl Template {
+    /// The template in `dir`. A template without a `cargo-generate.toml` only gets the built-in
+    /// values.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let config = match fs::read_to_string(dir.join(CONFIG_FILE)) {
+            Ok(content) => toml::from_str::<Config>(&content)
+                .map_err(|e| eyre!("Invalid {} in the template: {}", CONFIG_FILE, e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Config::default(),
+            Err(e) => return Err(e.into()),
+        };
+        let placeholders = config
+            .placeholders
+            .into_iter()
+            .map(|(name, spec)| Placeholder::parse(&name, spec))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            name: dir
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            dir: dir.to_path_buf(),
+            placeholders,
+            ignore: config.template.ignore,
+            exclude: config.template.exclude,
+        })
+    }
+
+    /// Writes the rendered template to `dest`, returning one line per thing worth reporting.
+    pub fn generate(&self, dest: &Path, values: &BTreeMap<String, String>) -> Result<Vec<String>> {
+        let mut report = Vec::new();
+        let mut written = 0;
+        let mut with_tags = Vec::new();
+        for source in files(&self.dir)? {
+            let relative = source.strip_prefix(&self.dir)?;
+            let key = relative.to_string_lossy().replace('\\', "/");
+            if matches_any(&self.ignore, &key) {
+                continue;
+            }
+            let rendered_path = render(&key, values);
+            let target = dest.join(
+                rendered_path
+                    .strip_suffix(".liquid")
+                    .unwrap_or(&rendered_path),
+            );
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let bytes = fs::read(&source)?;
+            match String::from_utf8(bytes) {
+                Ok(text) if !matches_any(&self.exclude, &key) => {
+                    if text.contains("{%") {
+                        with_tags.push(key);
+                    }
+                    fs::write(&target, render(&text, values))?;
+                }
+                Ok(text) => fs::write(&target, text)?,
+                Err(e) => fs::write(&target, e.into_bytes())?,
+            }
+            copy_permissions(&source, &target);
+            written += 1;
+        }
+        report.push(format!(
+            "Wrote {} files from the {} template",
+            written, self.name
+        ));
+        for file in with_tags {
+            report.push(format!(
+                "⚠ {} uses Liquid tags, which were copied as they are",
+                file
+            ));
+        }
+        Ok(report)
+    }
+}
+
+/// The values cargo-generate always provides, for a project called `project_name`.
+pub fn builtins(project_name: &str) -> BTreeMap<String, String> {
+    let name = project_name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(project_name);
+    BTreeMap::from([
+        (String::from("project-name"), kebab_case(name)),
+        (String::from("crate_name"), snake_case(name)),
+        (String::from("crate_type"), String::from("bin")),
+        (String::from("authors"), authors()),
+        (
+            String::from("os-arch"),
+            format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH),
+        ),
+        (String::from("is_init"), String::from("false")),
+        (String::from("within_cargo_project"), String::from("false")),
+    ])
+}
+
+/// `Name <email>` from git's configuration, as far as it's set.
+fn authors() -> String {
+    let get = |key: &str| {
+        Command::new("git")
+            .args(["config", key])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    match (get("user.name"), get("user.email")) {
+        (Some(name), Some(email)) => format!("{} <{}>", name, email),
+        (Some(name), None) => name,
+        (None, Some(email)) => format!("<{}>", email),
+        (None, None) => String::new(),
+    }
+}
+
+fn placeholder_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"\{\{-?\s*([A-Za-z_][\w-]*)\s*((?:\|\s*\w+\s*)*)-?\}\}").unwrap()
+    })
+}
+
+/// Fills in `{{name}}` and `{{name | filter}}` from `values`. Unknown names are left alone.
+fn render(text: &str, values: &BTreeMap<String, String>) -> String {
+    placeholder_regex()
+        .replace_all(text, |caps: &Captures| {
+            let Some(value) = values.get(&caps[1]) else {
+                return caps[0].to_string();
+            };
+            caps[2]
+                .split('|')
+                .map(str::trim)
+                .filter(|filter| !filter.is_empty())
+                .fold(value.clone(), |value, filter| apply(filter, &value))
+        })
+        .to_string()
+}
+
+fn apply(filter: &str, value: &str) -> String {
+    match filter {
+        "snake_case" => snake_case(value),
+        "kebab_case" => kebab_case(value),
+        "shouty_snake_case" => snake_case(value).to_uppercase(),
+        "upper_camel_case" | "pascal_case" => {
+            words(value).iter().map(|word| capitalize(word)).collect()
+        }
+        "lower_camel_case" => {
+            let pascal: String = words(value).iter().map(|word| capitalize(word)).collect();
+            let mut chars = pascal.chars();
+            chars
+                .next()
+                .map(|first| first.to_lowercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+        "upcase" | "upper" => value.to_uppercase(),
+        "downcase" | "lower" => value.to_lowercase(),
+        "capitalize" => capitalize(value),
+        _ => value.to_string(),
+    }
+}
+
+/// Lowercase words of `value`, split at punctuation and where the case changes.
+fn words(value: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in value.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_lowercase() || c.is_numeric();
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn snake_case(value: &str) -> String {
+    words(value).join("_")
+}
+
+fn kebab_case(value: &str) -> String {
+    words(value).join("-")
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Whether `path`, relative to the template and with `/` separators, is `pattern` or inside it.
+fn matches_any(patterns: &[String], path: &str) -> bool {
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.trim_matches('/');
+        path == pattern || path.starts_with(&format!("{}/", pattern))
+    })
+}
+
+/// Every file below `dir`, skipping git's own and the template's configuration.
+fn files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut found = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path
+            .file_name()
+            .is_some_and(|name| SKIPPED.iter().any(|s| name == *s))
+        {
+            continue;
+        }
+        if path.is_dir() {
+            found.extend(files(&path)?);
+        } else {
+            found.push(path);
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+/// Keeps scripts executable.
+fn copy_permissions(source: &Path, target: &Path) {
+    if let Ok(metadata) = fs::metadata(source) {
+        let _ = fs::set_permissions(target, metadata.permissions());
+    }
+}
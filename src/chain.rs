@@ -0,0 +1,450 @@
+//! JSON-RPC access to the chain the project talks to (anvil or a remote node), through alloy
+//! providers instead of shelling out to curl or cast.
+
+use alloy::{
+    consensus::{SignableTransaction, TxEip1559, TxEnvelope},
+    eips::eip2718::Encodable2718,
+    primitives::{Address, Bytes, Signature, TxHash, TxKind, B256, U256},
+    providers::{DynProvider, Provider, ProviderBuilder},
+    rpc::types::{BlockNumberOrTag, Filter, Log, TransactionReceipt, TransactionRequest},
+};
+use color_eyre::{eyre::eyre, Result};
+use k256::ecdsa::SigningKey;
+use std::{future::Future, sync::mpsc, thread, time::Duration};
+
+/// Requests against a local or healthy node finish well within this.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often [`Monitor`] asks the chain where it's at.
+const MONITOR_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Where the chain is at, e.g. for "anvil at block 42, chain id 31337".
+#[derive(Clone, Copy)]
+pub struct ChainStatus {
+    pub chain_id: u64,
+    pub block_number: u64,
+    /// In wei.
+    pub gas_price: u128,
+}
+
+/// A blocking client for one RPC endpoint. Owns a small runtime so the rest of the app can
+/// stay synchronous.
+pub struct Chain {
+    runtime: tokio::runtime::Runtime,
+    provider: DynProvider,
+    rpc_url: String,
+}
+
+impl Chain {
+    pub fn connect(rpc_url: &str) -> Result<Self> {
+        let url = rpc_url
+            .parse()
+            .map_err(|e| eyre!("Invalid RPC URL '{}': {}", rpc_url, e))?;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let provider = ProviderBuilder::new().connect_http(url).erased();
+        Ok(Self {
+            runtime,
+            provider,
+            rpc_url: rpc_url.to_string(),
+        })
+    }
+
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    fn block_on<T, E: std::fmt::Display>(
+        &self,
+        request: impl Future<Output = std::result::Result<T, E>>,
+    ) -> Result<T> {
+        self.runtime.block_on(async {
+            match tokio::time::timeout(REQUEST_TIMEOUT, request).await {
+                Ok(result) => {
+                    result.map_err(|e| eyre!("RPC request to {} failed: {}", self.rpc_url, e))
+                }
+                Err(_) => Err(eyre!(
+                    "RPC request to {} timed out after {}s",
+                    self.rpc_url,
+                    REQUEST_TIMEOUT.as_secs()
+                )),
+            }
+        })
+    }
+
+    pub fn block_number(&self) -> Result<u64> {
+        self.block_on(self.provider.get_block_number())
+    }
+
+    pub fn chain_id(&self) -> Result<u64> {
+        self.block_on(self.provider.get_chain_id())
+    }
+
+    /// Number and timestamp of the latest block.
+    pub fn latest_block(&self) -> Result<(u64, u64)> {
+        let block = self
+            .block_on(async {
+                self.provider
+                    .get_block_by_number(BlockNumberOrTag::Latest)
+                    .await
+            })?
+            .ok_or_else(|| eyre!("{} has no latest block", self.rpc_url))?;
+        Ok((block.header.number, block.header.timestamp))
+    }
+
+    /// Gas limit of the latest block, the most one transaction can use.
+    pub fn block_gas_limit(&self) -> Result<u64> {
+        let block = self
+            .block_on(async {
+                self.provider
+                    .get_block_by_number(BlockNumberOrTag::Latest)
+                    .await
+            })?
+            .ok_or_else(|| eyre!("{} has no latest block", self.rpc_url))?;
+        Ok(block.header.gas_limit)
+    }
+
+    pub fn status(&self) -> Result<ChainStatus> {
+        Ok(ChainStatus {
+            chain_id: self.chain_id()?,
+            block_number: self.block_number()?,
+            gas_price: self.block_on(self.provider.get_gas_price())?,
+        })
+    }
+
+    /// Balance in wei.
+    pub fn balance(&self, address: &str) -> Result<U256> {
+        let address: Address = address
+            .parse()
+            .map_err(|e| eyre!("Invalid address '{}': {}", address, e))?;
+        self.block_on(async { self.provider.get_balance(address).await })
+    }
+
+    /// The receipt of a mined transaction, or `None` while it's pending or unknown.
+    pub fn receipt(&self, tx_hash: &str) -> Result<Option<TransactionReceipt>> {
+        let hash: TxHash = tx_hash
+            .parse()
+            .map_err(|e| eyre!("Invalid transaction hash '{}': {}", tx_hash, e))?;
+        self.block_on(async { self.provider.get_transaction_receipt(hash).await })
+    }
+
+    /// Read-only `eth_call` of `calldata` against the contract at `to`.
+    pub fn call(&self, to: &str, calldata: Vec<u8>) -> Result<Bytes> {
+        let to: Address = to
+            .parse()
+            .map_err(|e| eyre!("Invalid address '{}': {}", to, e))?;
+        let request = TransactionRequest::default()
+            .to(to)
+            .input(Bytes::from(calldata).into());
+        self.block_on(async { self.provider.call(request).await })
+    }
+
+    /// Logs emitted by `address` in the last `lookback` blocks whose first indexed topic is
+    /// `topic1`, oldest first.
+    pub fn logs(&self, address: &str, topic1: B256, lookback: u64) -> Result<Vec<Log>> {
+        let address: Address = address
+            .parse()
+            .map_err(|e| eyre!("Invalid address '{}': {}", address, e))?;
+        let from_block = self.block_number()?.saturating_sub(lookback);
+        let filter = Filter::new()
+            .address(address)
+            .topic1(topic1)
+            .from_block(from_block);
+        self.block_on(async { self.provider.get_logs(&filter).await })
+    }
+
+    /// Gas a call is expected to use, simulated from `from` when given.
+    pub fn estimate_gas(
+        &self,
+        from: Option<Address>,
+        to: Address,
+        calldata: Bytes,
+        value: U256,
+    ) -> Result<u64> {
+        let mut request = TransactionRequest::default()
+            .to(to)
+            .value(value)
+            .input(calldata.into());
+        request.from = from;
+        self.block_on(async { self.provider.estimate_gas(request).await })
+    }
+
+    /// Read-only `eth_call` from `from`, for functions that check the caller.
+    pub fn call_from(
+        &self,
+        from: Option<Address>,
+        to: Address,
+        calldata: Bytes,
+        value: U256,
+    ) -> Result<Bytes> {
+        let mut request = TransactionRequest::default()
+            .to(to)
+            .value(value)
+            .input(calldata.into());
+        request.from = from;
+        self.block_on(async { self.provider.call(request).await })
+    }
+
+    /// Signs an EIP-1559 call with `private_key` and broadcasts it, returning its hash without
+    /// waiting for it to be mined.
+    pub fn send_signed(
+        &self,
+        private_key: &str,
+        to: Address,
+        calldata: Bytes,
+        value: U256,
+        gas_limit: u64,
+    ) -> Result<TxHash> {
+        let key = signing_key(private_key)?;
+        let from = Address::from_private_key(&key);
+        let nonce =
+            self.block_on(async { self.provider.get_transaction_count(from).pending().await })?;
+        let fees = self.block_on(self.provider.estimate_eip1559_fees())?;
+        let tx = TxEip1559 {
+            chain_id: self.chain_id()?,
+            nonce,
+            gas_limit,
+            max_fee_per_gas: fees.max_fee_per_gas,
+            max_priority_fee_per_gas: fees.max_priority_fee_per_gas,
+            to: TxKind::Call(to),
+            value,
+            input: calldata,
+            ..Default::default()
+        };
+        let signature: Signature = key
+            .sign_prehash_recoverable(tx.signature_hash().as_slice())
+            .map_err(|e| eyre!("Could not sign the transaction: {}", e))?
+            .into();
+        let raw = TxEnvelope::from(tx.into_signed(signature)).encoded_2718();
+        let pending = self.block_on(self.provider.send_raw_transaction(&raw))?;
+        Ok(*pending.tx_hash())
+    }
+
+    /// Every log emitted by `addresses` from `from_block` through `to_block`, oldest first.
+    pub fn contract_logs(
+        &self,
+        addresses: Vec<Address>,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<Log>> {
+        let filter = Filter::new()
+            .address(addresses)
+            .from_block(from_block)
+            .to_block(to_block);
+        self.block_on(async { self.provider.get_logs(&filter).await })
+    }
+}
+
+/// The account a hex private key controls.
+pub fn address_of(private_key: &str) -> Result<Address> {
+    Ok(Address::from_private_key(&signing_key(private_key)?))
+}
+
+fn signing_key(private_key: &str) -> Result<SigningKey> {
+    let bytes = alloy::hex::decode(private_key.trim())
+        .map_err(|e| eyre!("Private key is not valid hex: {}", e))?;
+    SigningKey::from_slice(&bytes).map_err(|e| eyre!("Invalid private key: {}", e))
+}
+
+/// Anvil's state-management calls, which other nodes reject.
+impl Chain {
+    /// Takes an in-memory snapshot that `revert` can return to. Returns its ID.
+    pub fn snapshot(&self) -> Result<U256> {
+        self.block_on(
+            self.provider
+                .raw_request::<_, U256>("evm_snapshot".into(), [(); 0]),
+        )
+    }
+
+    /// Returns to a snapshot, which is used up. Returns whether it existed.
+    pub fn revert(&self, id: U256) -> Result<bool> {
+        self.block_on(
+            self.provider
+                .raw_request::<_, bool>("evm_revert".into(), (id,)),
+        )
+    }
+
+    /// The whole chain state, which `load_state` can put into any anvil.
+    pub fn dump_state(&self) -> Result<Bytes> {
+        self.block_on(
+            self.provider
+                .raw_request::<_, Bytes>("anvil_dumpState".into(), [(); 0]),
+        )
+    }
+
+    /// Merges a dumped state into the chain, overwriting what it touches.
+    pub fn load_state(&self, state: Bytes) -> Result<bool> {
+        self.block_on(
+            self.provider
+                .raw_request::<_, bool>("anvil_loadState".into(), (state,)),
+        )
+    }
+
+    /// Mines `blocks` blocks right away.
+    pub fn mine(&self, blocks: u64) -> Result<()> {
+        self.block_on(
+            self.provider
+                .raw_request::<_, ()>("anvil_mine".into(), (U256::from(blocks),)),
+        )
+    }
+
+    /// Moves the clock forward for the next block. Returns the total offset in seconds.
+    pub fn increase_time(&self, seconds: u64) -> Result<i64> {
+        self.block_on(
+            self.provider
+                .raw_request::<_, i64>("evm_increaseTime".into(), (U256::from(seconds),)),
+        )
+    }
+
+    /// Mines a block every `seconds`, or on every transaction when `seconds` is 0.
+    pub fn set_interval_mining(&self, seconds: u64) -> Result<()> {
+        if seconds == 0 {
+            return self.block_on(
+                self.provider
+                    .raw_request::<_, ()>("evm_setAutomine".into(), (true,)),
+            );
+        }
+        self.block_on(
+            self.provider
+                .raw_request::<_, ()>("evm_setIntervalMining".into(), (seconds,)),
+        )
+    }
+
+    /// Lets transactions be sent from `address` without its key, or stops that.
+    pub fn impersonate(&self, address: &str, enable: bool) -> Result<()> {
+        let address: Address = address
+            .parse()
+            .map_err(|e| eyre!("Invalid address '{}': {}", address, e))?;
+        let method = if enable {
+            "anvil_impersonateAccount"
+        } else {
+            "anvil_stopImpersonatingAccount"
+        };
+        self.block_on(
+            self.provider
+                .raw_request::<_, ()>(method.into(), (address,)),
+        )
+    }
+
+    /// Whether there's a contract at `address`.
+    pub fn has_code(&self, address: &str) -> Result<bool> {
+        let address: Address = address
+            .parse()
+            .map_err(|e| eyre!("Invalid address '{}': {}", address, e))?;
+        let code = self.block_on(async { self.provider.get_code_at(address).await })?;
+        Ok(!code.is_empty())
+    }
+}
+
+impl ChainStatus {
+    pub fn describe(&self, name: &str) -> String {
+        format!(
+            "{} at block {}, chain id {}, gas {}",
+            name,
+            self.block_number,
+            self.chain_id,
+            format_gwei(self.gas_price)
+        )
+    }
+}
+
+/// Keeps a chain's status current from a background thread, so a node that dies mid-run
+/// shows up without waiting for the next step to fail.
+pub struct Monitor {
+    name: String,
+    rpc_url: String,
+    updates: mpsc::Receiver<std::result::Result<ChainStatus, String>>,
+    /// Last status the node reported, kept when it stops answering.
+    pub last: ChainStatus,
+    /// Why the latest check failed, while the node isn't answering.
+    pub error: Option<String>,
+}
+
+impl Monitor {
+    /// Starts polling `rpc_url`, which just reported `status`. The thread ends once the
+    /// monitor is dropped.
+    pub fn start(rpc_url: &str, name: &str, status: ChainStatus) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let url = rpc_url.to_string();
+        thread::spawn(move || {
+            let chain = Chain::connect(&url);
+            loop {
+                thread::sleep(MONITOR_INTERVAL);
+                let status = match &chain {
+                    Ok(chain) => chain.status().map_err(|e| e.to_string()),
+                    Err(e) => Err(e.to_string()),
+                };
+                if tx.send(status).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            name: name.to_string(),
+            rpc_url: rpc_url.to_string(),
+            updates: rx,
+            last: status,
+            error: None,
+        }
+    }
+
+    /// Takes in the latest checks. Returns the error if the node just stopped answering, or the
+    /// polling thread died.
+    pub fn poll(&mut self) -> Option<String> {
+        let mut lost = None;
+        loop {
+            let (update, stopped) = match self.updates.try_recv() {
+                Ok(update) => (update, false),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    (Err(String::from("the monitor stopped")), true)
+                }
+            };
+            match update {
+                Ok(status) => {
+                    self.last = status;
+                    self.error = None;
+                }
+                Err(e) => {
+                    if self.error.is_none() {
+                        lost = Some(e.clone());
+                    }
+                    self.error = Some(e);
+                }
+            }
+            if stopped {
+                break;
+            }
+        }
+        lost
+    }
+
+    /// For the status bar, e.g. "anvil at block 42, chain id 31337, gas 1.00 gwei".
+    pub fn describe(&self) -> String {
+        match &self.error {
+            None => self.last.describe(&self.name),
+            Some(_) => format!(
+                "⚠ {} at {} not answering (last seen at block {})",
+                self.name, self.rpc_url, self.last.block_number
+            ),
+        }
+    }
+}
+
+/// Formats a wei amount as gwei with two decimals.
+pub fn format_gwei(wei: u128) -> String {
+    format!(
+        "{}.{:02} gwei",
+        wei / 1_000_000_000,
+        wei % 1_000_000_000 / 10_000_000
+    )
+}
+
+/// Formats a wei amount as ether with four decimals.
+pub fn format_ether(wei: U256) -> String {
+    let wei_per_ether = U256::from(10u64).pow(U256::from(18u64));
+    let whole = wei / wei_per_ether;
+    let fraction = (wei % wei_per_ether) / U256::from(10u64).pow(U256::from(14u64));
+    format!("{}.{:04} ETH", whole, fraction.to::<u64>())
+}
@@ -0,0 +1,133 @@
+//! Chains the test and deploy flows can target, with the details users would otherwise look up
+//! and export by hand: chain ID, a default RPC, the block explorer, and RISC Zero's verifier
+//! router deployment.
+
+use crate::{
+    dev_keys,
+    rpc_presets::{self, Network, RpcProvider},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChainPreset {
+    #[default]
+    Anvil,
+    Sepolia,
+    Holesky,
+    BaseSepolia,
+    OpSepolia,
+    Mainnet,
+}
+
+impl ChainPreset {
+    pub const ALL: &'static [ChainPreset] = &[
+        ChainPreset::Anvil,
+        ChainPreset::Sepolia,
+        ChainPreset::Holesky,
+        ChainPreset::BaseSepolia,
+        ChainPreset::OpSepolia,
+        ChainPreset::Mainnet,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ChainPreset::Anvil => "Anvil (local)",
+            ChainPreset::Sepolia => "Sepolia",
+            ChainPreset::Holesky => "Holesky",
+            ChainPreset::BaseSepolia => "Base Sepolia",
+            ChainPreset::OpSepolia => "OP Sepolia",
+            ChainPreset::Mainnet => "Ethereum mainnet",
+        }
+    }
+
+    pub fn chain_id(self) -> u64 {
+        match self {
+            ChainPreset::Anvil => 31337,
+            ChainPreset::Sepolia => 11155111,
+            ChainPreset::Holesky => 17000,
+            ChainPreset::BaseSepolia => 84532,
+            ChainPreset::OpSepolia => 11155420,
+            ChainPreset::Mainnet => 1,
+        }
+    }
+
+    /// The RPC provider network for this chain; `None` for anvil.
+    pub fn network(self) -> Option<Network> {
+        match self {
+            ChainPreset::Anvil => None,
+            ChainPreset::Sepolia => Some(Network::Sepolia),
+            ChainPreset::Holesky => Some(Network::Holesky),
+            ChainPreset::BaseSepolia => Some(Network::BaseSepolia),
+            ChainPreset::OpSepolia => Some(Network::OpSepolia),
+            ChainPreset::Mainnet => Some(Network::Mainnet),
+        }
+    }
+
+    /// Used when no RPC provider is configured: anvil, or the chain's public endpoint.
+    pub fn default_rpc(self) -> String {
+        self.network()
+            .and_then(|network| rpc_presets::url(RpcProvider::Public, network, ""))
+            .unwrap_or_else(|| dev_keys::LOCAL_RPC_URL.to_string())
+    }
+
+    pub fn explorer(self) -> Option<&'static str> {
+        match self {
+            ChainPreset::Anvil => None,
+            ChainPreset::Sepolia => Some("https://sepolia.etherscan.io"),
+            ChainPreset::Holesky => Some("https://holesky.etherscan.io"),
+            ChainPreset::BaseSepolia => Some("https://sepolia.basescan.org"),
+            ChainPreset::OpSepolia => Some("https://sepolia-optimism.etherscan.io"),
+            ChainPreset::Mainnet => Some("https://etherscan.io"),
+        }
+    }
+
+    /// Whether the chain's ETH is worthless, so a generated test wallet may sign there.
+    pub fn is_testnet(self) -> bool {
+        self != ChainPreset::Mainnet
+    }
+
+    /// Where to get test ETH for free; none for anvil, whose accounts are funded, or mainnet.
+    pub fn faucets(self) -> &'static [&'static str] {
+        match self {
+            ChainPreset::Anvil | ChainPreset::Mainnet => &[],
+            ChainPreset::Sepolia => &[
+                "https://cloud.google.com/application/web3/faucet/ethereum/sepolia",
+                "https://www.alchemy.com/faucets/ethereum-sepolia",
+            ],
+            ChainPreset::Holesky => {
+                &["https://cloud.google.com/application/web3/faucet/ethereum/holesky"]
+            }
+            ChainPreset::BaseSepolia => &[
+                "https://portal.cdp.coinbase.com/products/faucet",
+                "https://www.alchemy.com/faucets/base-sepolia",
+            ],
+            ChainPreset::OpSepolia => &["https://console.optimism.io/faucet"],
+        }
+    }
+
+    /// RISC Zero's `RiscZeroVerifierRouter`. Anvil has none; the deploy scripts bring their own
+    /// verifier there.
+    pub fn verifier_router(self) -> Option<&'static str> {
+        match self {
+            ChainPreset::Anvil => None,
+            ChainPreset::Sepolia => Some("0x925d8331ddc0a1F0d96E68CF073DFE1d92b69187"),
+            ChainPreset::Holesky => Some("0xf70aBAb028Eb6F4100A24B203E113D94E87DE93C"),
+            ChainPreset::BaseSepolia => Some("0x0b144E07A0826182B6b59788c34b32Bfa86Fb711"),
+            ChainPreset::OpSepolia => Some("0xB369b4dd27FBfb59921d3A4a3D23AC2fc32FB908"),
+            ChainPreset::Mainnet => Some("0x8EaB2D97Dfce405A1692a21b3ff3A172d593D319"),
+        }
+    }
+
+    /// Variables passed to the test script and deploy scripts.
+    pub fn vars(self) -> Vec<(&'static str, String)> {
+        let mut vars = vec![("CHAIN_ID", self.chain_id().to_string())];
+        if let Some(explorer) = self.explorer() {
+            vars.push(("EXPLORER_URL", explorer.to_string()));
+        }
+        if let Some(router) = self.verifier_router() {
+            vars.push(("VERIFIER_ROUTER_ADDRESS", router.to_string()));
+        }
+        vars
+    }
+}
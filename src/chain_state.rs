@@ -0,0 +1,103 @@
+//! Anvil state saved after a local deployment, so later runs can restore the deployed
+//! contracts in one call instead of deploying them again.
+
+use crate::{
+    address_book,
+    broadcast::Deployed,
+    chain::Chain,
+    formatting::{Snapshot, Tool},
+    paths, platform,
+};
+use alloy::primitives::Bytes;
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{fs, path::Path, path::PathBuf};
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedContract {
+    pub name: String,
+    pub address: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedState {
+    /// RFC 3339 time it was saved.
+    pub saved: String,
+    /// Digest of the project's Solidity sources at the time; any change makes the state stale.
+    sources: String,
+    pub contracts: Vec<SavedContract>,
+    /// `anvil_dumpState` output.
+    state: Bytes,
+}
+
+/// Where the state for the project at `root` is kept.
+fn file(root: &Path) -> PathBuf {
+    let root = platform::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let key = Sha256::digest(root.to_string_lossy().as_bytes());
+    paths::anvil_state_dir().join(format!("{}.json", &alloy::hex::encode(key)[..16]))
+}
+
+fn sources(root: &Path) -> String {
+    alloy::hex::encode(Snapshot::take(root, Tool::Forge).digest())
+}
+
+/// Dumps the chain's state along with the contracts just deployed to it.
+pub fn save(root: &Path, chain: &Chain, contracts: &[Deployed]) -> Result<()> {
+    let saved = SavedState {
+        saved: chrono::Utc::now().to_rfc3339(),
+        sources: sources(root),
+        contracts: contracts
+            .iter()
+            .map(|contract| SavedContract {
+                name: contract.name.clone(),
+                address: contract.address.clone(),
+            })
+            .collect(),
+        state: chain.dump_state()?,
+    };
+    fs::create_dir_all(paths::anvil_state_dir())?;
+    fs::write(file(root), serde_json::to_string(&saved)?)?;
+    Ok(())
+}
+
+/// The saved state, unless there's none or the contracts have changed since.
+pub fn load(root: &Path) -> Option<SavedState> {
+    let content = fs::read_to_string(file(root)).ok()?;
+    let saved: SavedState = serde_json::from_str(&content).ok()?;
+    (saved.sources == sources(root)).then_some(saved)
+}
+
+impl SavedState {
+    /// Loads the state into `chain` and checks every saved contract is there. On failure the
+    /// chain is put back the way it was.
+    pub fn restore(&self, chain: &Chain) -> Result<()> {
+        let before = chain.snapshot()?;
+        let restored = chain.load_state(self.state.clone()).and_then(|_| {
+            match self
+                .contracts
+                .iter()
+                .find(|contract| !chain.has_code(&contract.address).unwrap_or(false))
+            {
+                Some(missing) => Err(eyre!(
+                    "{} isn't at {} after loading the state",
+                    missing.name,
+                    missing.address
+                )),
+                None => Ok(()),
+            }
+        });
+        if restored.is_err() {
+            let _ = chain.revert(before);
+        }
+        restored
+    }
+}
+
+impl SavedContract {
+    /// The variable the contract's address is passed to the test script in, e.g.
+    /// `COUNTER_ADDRESS` for `Counter`.
+    pub fn env_var(&self) -> String {
+        address_book::env_var(&self.name)
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{paths, platform};
+use color_eyre::Result;
+use std::{fs, path::Path};
+
+/// Remember a spawned child so `clean` can find it if the TUI dies without cleaning up.
+pub fn record_pid(pid: u32) -> Result<()> {
+    let mut pids = recorded_pids();
+    if !pids.contains(&pid) {
+        pids.push(pid);
+    }
+    write_pids(&pids)
+}
+
+/// Drop a PID once the child has been reaped normally.
+pub fn forget_pid(pid: u32) -> Result<()> {
+    let pids: Vec<u32> = recorded_pids().into_iter().filter(|p| *p != pid).collect();
+    write_pids(&pids)
+}
+
+pub fn recorded_pids() -> Vec<u32> {
+    fs::read_to_string(paths::pid_file())
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| line.trim().parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn write_pids(pids: &[u32]) -> Result<()> {
+    let file = paths::pid_file();
+    if pids.is_empty() {
+        if file.exists() {
+            fs::remove_file(file)?;
+        }
+        return Ok(());
+    }
+
+    fs::create_dir_all(paths::state_dir())?;
+    let content: String = pids.iter().map(|pid| format!("{}\n", pid)).collect();
+    fs::write(file, content)?;
+    Ok(())
+}
+
+fn remove_path(path: &Path, removed: &mut Vec<String>) -> Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)?;
+    } else if path.exists() {
+        fs::remove_file(path)?;
+    } else {
+        return Ok(());
+    }
+    removed.push(path.display().to_string());
+    Ok(())
+}
+
+/// Entry point for `steel-tui clean`.
+pub fn run(clear_cache: bool) -> Result<()> {
+    let mut removed = Vec::new();
+
+    // Only touch processes we started ourselves, and only if the PID still belongs to anvil
+    for pid in recorded_pids() {
+        match platform::process_name(pid) {
+            Some(name) if name.contains("anvil") => {
+                if platform::terminate_tree(pid) || platform::terminate(pid) {
+                    removed.push(format!("process {} ({})", pid, name));
+                } else {
+                    println!("✗ Failed to terminate process {} ({})", pid, name);
+                }
+            }
+            Some(name) => {
+                println!("Skipping PID {}: now belongs to '{}', not anvil", pid, name);
+            }
+            None => {}
+        }
+    }
+    remove_path(&paths::pid_file(), &mut removed)?;
+    remove_path(&paths::temp_dir(), &mut removed)?;
+
+    if clear_cache {
+        remove_path(&paths::template_cache_dir(), &mut removed)?;
+    }
+
+    if removed.is_empty() {
+        println!("Nothing to clean.");
+    } else {
+        println!("Removed:");
+        for item in &removed {
+            println!("  ✓ {}", item);
+        }
+    }
+
+    Ok(())
+}
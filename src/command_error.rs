@@ -0,0 +1,103 @@
+use crate::{
+    advice::{self, Advice},
+    redact,
+};
+use std::{fmt, process::Command, time::Duration};
+
+/// Number of trailing output lines kept for error reports.
+pub const TAIL_LINES: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailureReason {
+    /// Exited unsuccessfully. `None` if terminated by a signal.
+    Exited(Option<i32>),
+    TimedOut,
+    Aborted,
+}
+
+/// A spawned command that did not succeed, with enough context to diagnose it.
+#[derive(Debug, Clone)]
+pub struct CommandError {
+    pub program: String,
+    pub args: Vec<String>,
+    pub reason: FailureReason,
+    pub duration: Duration,
+    /// The last lines the command printed, stdout and stderr interleaved.
+    pub output_tail: Vec<String>,
+    /// What to do about it, if the output shows a known failure.
+    pub advice: Option<&'static Advice>,
+}
+
+impl CommandError {
+    pub fn new(
+        command: &Command,
+        reason: FailureReason,
+        duration: Duration,
+        output: &[String],
+    ) -> Self {
+        Self {
+            program: command.get_program().to_string_lossy().to_string(),
+            args: command
+                .get_args()
+                .map(|arg| redact::redact(&arg.to_string_lossy()))
+                .collect(),
+            reason,
+            duration,
+            output_tail: output[output.len().saturating_sub(TAIL_LINES)..]
+                .iter()
+                .map(|line| redact::redact(line))
+                .collect(),
+            advice: advice::diagnose(output),
+        }
+    }
+
+    pub fn command_line(&self) -> String {
+        std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Multi-line report for the error screen and CI output.
+    pub fn report(&self) -> Vec<String> {
+        let mut lines = vec![
+            self.to_string(),
+            format!("Command: {}", self.command_line()),
+        ];
+        if let Some(advice) = self.advice {
+            lines.push(format!("Likely cause: {}", advice.problem));
+            lines.push(format!("Try: {}", advice.fix));
+        }
+        if !self.output_tail.is_empty() {
+            lines.push(String::from("Last output:"));
+            lines.extend(self.output_tail.iter().map(|line| format!("  {}", line)));
+        }
+        lines
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.duration.as_secs_f64();
+        match &self.reason {
+            FailureReason::Exited(Some(code)) => write!(
+                f,
+                "'{}' exited with code {} after {:.1}s",
+                self.program, code, secs
+            ),
+            FailureReason::Exited(None) => write!(
+                f,
+                "'{}' was terminated by a signal after {:.1}s",
+                self.program, secs
+            ),
+            FailureReason::TimedOut => {
+                write!(f, "'{}' timed out after {:.1}s", self.program, secs)
+            }
+            FailureReason::Aborted => {
+                write!(f, "'{}' was aborted after {:.1}s", self.program, secs)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
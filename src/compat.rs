@@ -0,0 +1,193 @@
+//! Which risc0-ethereum releases build with which RISC Zero toolchain, and how each release's
+//! crates are pulled into a generated project.
+
+use crate::manifest;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// A risc0-ethereum crate the template depends on through a path into the repository.
+pub struct Crate {
+    pub name: &'static str,
+    /// Features the host side (`apps/`) enables.
+    pub host_features: &'static [&'static str],
+}
+
+pub struct Release {
+    pub branch: &'static str,
+    /// The `cargo risczero` major and minor version the release is built against.
+    pub toolchain: (u64, u64),
+    /// Examples that can be combined into one multi-example workspace.
+    pub examples: &'static [&'static str],
+    pub crates: &'static [Crate],
+}
+
+/// The crates with rules of their own, unchanged so far across releases; a release that renames
+/// one or its features gets its own list. Other crates of the repository a template uses are
+/// found in its manifests by [`rewrite_repository_paths`].
+const CRATES: &[Crate] = &[
+    Crate {
+        name: "risc0-build-ethereum",
+        host_features: &[],
+    },
+    Crate {
+        name: "risc0-ethereum-contracts",
+        host_features: &[],
+    },
+    Crate {
+        name: "risc0-steel",
+        host_features: &["host"],
+    },
+];
+
+/// The same in every release so far.
+const EXAMPLES: &[&str] = &["erc20-counter", "erc20", "token-stats", "governance"];
+
+/// Newest first.
+pub const RELEASES: &[Release] = &[
+    Release {
+        branch: "release-2.1",
+        toolchain: (2, 1),
+        examples: EXAMPLES,
+        crates: CRATES,
+    },
+    Release {
+        branch: "release-2.0",
+        toolchain: (2, 0),
+        examples: EXAMPLES,
+        crates: CRATES,
+    },
+    Release {
+        branch: "release-1.3",
+        toolchain: (1, 2),
+        examples: EXAMPLES,
+        crates: CRATES,
+    },
+];
+
+/// The manifest line pointing `krate` at `branch` of `repository`, or at `tag` if one was picked.
+pub fn dependency(
+    krate: &Crate,
+    host: bool,
+    repository: &str,
+    branch: &str,
+    tag: Option<&str>,
+) -> String {
+    let features = if host && !krate.host_features.is_empty() {
+        format!(
+            ", features = [{}]",
+            krate
+                .host_features
+                .iter()
+                .map(|feature| format!("\"{}\"", feature))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    } else {
+        String::new()
+    };
+    let reference = match tag {
+        Some(tag) => format!("tag = \"{}\"", tag),
+        None => format!("branch = \"{}\"", branch),
+    };
+    format!(
+        "{} = {{ git = \"{}\", {}{} }}",
+        krate.name, repository, reference, features
+    )
+}
+
+/// Points every remaining dependency on a crate of the repository, such as
+/// `risc0-op-steel = { path = "../../crates/op-steel" }`, at `branch` or `tag` of `repository`,
+/// keeping its other keys. Covers crates a release adds or renames without a rule here. Only
+/// paths that climb out of the project count, `depth` being how far below its root the
+/// manifest is. Returns the new content and the crates rewritten.
+pub fn rewrite_repository_paths(
+    content: &str,
+    depth: usize,
+    repository: &str,
+    branch: &str,
+    tag: Option<&str>,
+) -> (String, Vec<String>) {
+    static PATH: OnceLock<Regex> = OnceLock::new();
+    let path = PATH.get_or_init(|| {
+        Regex::new(
+            r#"(?m)^([ \t]*([A-Za-z0-9_-]+)\s*=\s*\{[^}\n]*?)path\s*=\s*"((?:\.\./)+)crates/[^"]*""#,
+        )
+        .expect("valid regex")
+    });
+    let reference = match tag {
+        Some(tag) => format!("tag = \"{}\"", tag),
+        None => format!("branch = \"{}\"", branch),
+    };
+    let mut rewritten = Vec::new();
+    let content = path
+        .replace_all(content, |caps: &regex::Captures| {
+            if caps[3].len() / 3 <= depth {
+                return caps[0].to_string();
+            }
+            rewritten.push(caps[2].to_string());
+            format!("{}git = \"{}\", {}", &caps[1], repository, reference)
+        })
+        .to_string();
+    (content, rewritten)
+}
+
+/// Whether a risc0-ethereum tag was cut from `release`'s branch, and a hint on how it fits.
+pub fn tag_hint(release: &Release, tag: &str) -> (bool, String) {
+    let Some((major, minor, _)) = manifest::tag_version(tag) else {
+        return (false, String::from("not a release tag"));
+    };
+    let cut_from =
+        |release: &&Release| manifest::release_version(release.branch) == Some((major, minor));
+    if cut_from(&release) {
+        return (true, format!("from {}", release.branch));
+    }
+    match RELEASES.iter().find(cut_from) {
+        Some(other) => (
+            false,
+            format!(
+                "from {}, built for RISC0 {}.{}.x; the template is for {}.{}.x",
+                other.branch,
+                other.toolchain.0,
+                other.toolchain.1,
+                release.toolchain.0,
+                release.toolchain.1
+            ),
+        ),
+        None => (
+            false,
+            String::from("not in the support matrix; it may not build"),
+        ),
+    }
+}
+
+/// The version in `cargo risczero --version` output, e.g. `cargo-risczero 1.2.5`.
+pub fn toolchain_version(output: &str) -> Option<(u64, u64, u64)> {
+    let version = output.split_whitespace().last()?;
+    let mut parts = version
+        .split(['.', '-'])
+        .map(|part| part.parse::<u64>().ok());
+    Some((parts.next()??, parts.next()??, parts.next()??))
+}
+
+/// Releases a toolchain can build, newest first: those made for its major version and no
+/// newer minor version than it.
+pub fn compatible((major, minor, _): (u64, u64, u64)) -> Vec<&'static Release> {
+    RELEASES
+        .iter()
+        .filter(|release| release.toolchain.0 == major && release.toolchain.1 <= minor)
+        .collect()
+}
+
+/// Toolchain versions with a matching release, e.g. `2.1, 2.0, 1.2`.
+pub fn supported() -> String {
+    RELEASES
+        .iter()
+        .map(|release| format!("{}.{}", release.toolchain.0, release.toolchain.1))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The release behind a branch, if it's one we know.
+pub fn release(branch: &str) -> Option<&'static Release> {
+    RELEASES.iter().find(|release| release.branch == branch)
+}
@@ -0,0 +1,173 @@
+//! Limits contracts otherwise only run into when deploying: EIP-170's 24KB cap on runtime code,
+//! EIP-3860's cap on init code, and transactions, such as verifying a proof, that need more gas
+//! than a block holds.
+
+use regex::Regex;
+
+/// EIP-170.
+pub const RUNTIME_LIMIT: u64 = 24_576;
+/// EIP-3860.
+pub const INITCODE_LIMIT: u64 = 49_152;
+/// Ethereum's block gas limit, for when the chain can't be asked.
+pub const DEFAULT_BLOCK_GAS_LIMIT: u64 = 30_000_000;
+
+/// Share of a limit, in percent, from which it counts as close.
+const NEAR_PERCENT: u64 = 90;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verdict {
+    Ok,
+    Near,
+    Over,
+}
+
+impl Verdict {
+    fn of(used: u64, limit: u64) -> Self {
+        if used > limit {
+            Verdict::Over
+        } else if used * 100 >= limit * NEAR_PERCENT {
+            Verdict::Near
+        } else {
+            Verdict::Ok
+        }
+    }
+}
+
+pub struct Finding {
+    pub subject: String,
+    pub detail: String,
+    pub verdict: Verdict,
+}
+
+/// A contract's code sizes in bytes, from `forge build --sizes`.
+pub struct Size {
+    pub contract: String,
+    pub runtime: u64,
+    pub initcode: Option<u64>,
+}
+
+/// Reads the table `forge build --sizes` prints. Newer forge has runtime and init code sizes
+/// in bytes; older forge has only the runtime size, in kB.
+pub fn sizes(lines: &[String]) -> Vec<Size> {
+    let mut runtime_column = None;
+    let mut initcode_column = None;
+    let mut kilobytes = false;
+    let mut sizes = Vec::new();
+    for line in lines {
+        let line = line.trim();
+        if !line.starts_with('|') {
+            continue;
+        }
+        let cells: Vec<&str> = line.trim_matches('|').split('|').map(str::trim).collect();
+        if cells.first().is_some_and(|cell| *cell == "Contract") {
+            runtime_column = cells
+                .iter()
+                .position(|cell| cell.starts_with("Runtime Size") || cell.starts_with("Size"));
+            initcode_column = cells
+                .iter()
+                .position(|cell| cell.starts_with("Initcode Size"));
+            kilobytes = cells.iter().any(|cell| cell.contains("(kB)"));
+            continue;
+        }
+        let parse = |column: Option<usize>| -> Option<u64> {
+            let cell = cells.get(column?)?.replace(',', "");
+            if kilobytes {
+                cell.parse::<f64>()
+                    .ok()
+                    .map(|kb| (kb * 1000.0).round() as u64)
+            } else {
+                cell.parse().ok()
+            }
+        };
+        let (Some(contract), Some(runtime)) = (cells.first(), parse(runtime_column)) else {
+            continue;
+        };
+        sizes.push(Size {
+            contract: contract.to_string(),
+            runtime,
+            initcode: parse(initcode_column),
+        });
+    }
+    sizes
+}
+
+pub fn size_findings(sizes: &[Size]) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = sizes
+        .iter()
+        .map(|size| {
+            let mut verdict = Verdict::of(size.runtime, RUNTIME_LIMIT);
+            let mut detail = format!(
+                "{} of {} bytes of runtime code",
+                size.runtime, RUNTIME_LIMIT
+            );
+            if let Some(initcode) = size.initcode {
+                verdict = verdict.max(Verdict::of(initcode, INITCODE_LIMIT));
+                detail.push_str(&format!(
+                    ", {} of {} of init code",
+                    initcode, INITCODE_LIMIT
+                ));
+            }
+            Finding {
+                subject: size.contract.clone(),
+                detail,
+                verdict,
+            }
+        })
+        .collect();
+    findings.sort_by_key(|finding| std::cmp::Reverse(finding.verdict));
+    findings
+}
+
+/// Gas one test used, from `forge test`'s `[PASS] name() (gas: N)` lines.
+pub struct TestGas {
+    /// e.g. `CounterTest.test_Increment()`.
+    pub test: String,
+    pub gas: u64,
+}
+
+pub fn test_gas(lines: &[String]) -> Vec<TestGas> {
+    let suite = Regex::new(r"^Ran \d+ tests? for \S+:(\S+)").expect("valid regex");
+    let result =
+        Regex::new(r"^\[(?:PASS|FAIL[^\]]*)\] (\S+\(.*?\)) \(gas: (\d+)\)").expect("valid regex");
+    let mut current = String::new();
+    let mut tests = Vec::new();
+    for line in lines {
+        let line = line.trim();
+        if let Some(captures) = suite.captures(line) {
+            current = captures[1].to_string();
+        } else if let Some(captures) = result.captures(line) {
+            let Ok(gas) = captures[2].parse() else {
+                continue;
+            };
+            tests.push(TestGas {
+                test: if current.is_empty() {
+                    captures[1].to_string()
+                } else {
+                    format!("{}.{}", current, &captures[1])
+                },
+                gas,
+            });
+        }
+    }
+    tests
+}
+
+/// The most expensive tests against the block gas limit, the costliest first. A test that
+/// can't fit in a block means the transaction it makes, e.g. verifying a proof, can't either.
+pub fn gas_findings(tests: &[TestGas], block_gas_limit: u64, count: usize) -> Vec<Finding> {
+    let mut tests: Vec<&TestGas> = tests.iter().collect();
+    tests.sort_by_key(|test| std::cmp::Reverse(test.gas));
+    tests
+        .into_iter()
+        .take(count)
+        .map(|test| Finding {
+            subject: test.test.clone(),
+            detail: format!(
+                "{} gas, {}% of the block gas limit",
+                test.gas,
+                test.gas * 100 / block_gas_limit.max(1)
+            ),
+            verdict: Verdict::of(test.gas, block_gas_limit),
+        })
+        .collect()
+}
@@ -0,0 +1,122 @@
+//! Crash reports. When a panic or fatal error ends the app, what it was doing is written to a
+//! file the user can attach to a bug report, instead of scrolling past under the alternate
+//! screen.
+
+use crate::{paths, redact, tools};
+use color_eyre::Result;
+use std::{
+    collections::VecDeque,
+    fs,
+    path::PathBuf,
+    sync::{Mutex, OnceLock},
+};
+
+/// Lines of recent output kept for the report.
+const OUTPUT_TAIL: usize = 100;
+
+struct Context {
+    state: String,
+    output: VecDeque<String>,
+}
+
+/// Kept up to date by the app, because a panic hook can't reach the `App` itself.
+static CONTEXT: Mutex<Context> = Mutex::new(Context {
+    state: String::new(),
+    output: VecDeque::new(),
+});
+
+/// Tool versions, looked up once in the background so a panic hook never has to run them.
+static TOOLS: OnceLock<Vec<(&'static str, Result<String, String>)>> = OnceLock::new();
+
+/// Starts looking up the tool versions reports list.
+pub fn collect_tools() {
+    std::thread::spawn(|| TOOLS.get_or_init(tools::versions));
+}
+
+fn context() -> std::sync::MutexGuard<'static, Context> {
+    CONTEXT.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Records the screen or step the app is on.
+pub fn set_state(state: String) {
+    context().state = state;
+}
+
+/// Records a line of output, keeping only the last [`OUTPUT_TAIL`].
+pub fn record_output(line: &str) {
+    let mut context = context();
+    if context.output.len() == OUTPUT_TAIL {
+        context.output.pop_front();
+    }
+    context.output.push_back(line.to_string());
+}
+
+/// Writes a report for `kind` ("panic" or "error") and returns its path. Everything in it
+/// goes through [`redact::redact`]. A panic report lists the tools only if they were already
+/// looked up, since it's written from the panic hook.
+pub fn write_report(kind: &str, message: &str, backtrace: &str) -> Result<PathBuf> {
+    let (state, output) = {
+        let context = context();
+        (
+            context.state.clone(),
+            context.output.iter().cloned().collect::<Vec<_>>(),
+        )
+    };
+
+    let mut report = vec![
+        format!("steel-tui {} report", kind),
+        format!("Time: {}", chrono::Utc::now().to_rfc3339()),
+        format!("Version: {}", env!("CARGO_PKG_VERSION")),
+        format!(
+            "OS: {} {} ({})",
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            std::env::consts::FAMILY
+        ),
+        format!(
+            "State: {}",
+            if state.is_empty() { "unknown" } else { &state }
+        ),
+        String::new(),
+        String::from("Tools:"),
+    ];
+    let tools = match TOOLS.get() {
+        Some(tools) => Some(tools),
+        None if kind == "panic" => None,
+        None => Some(TOOLS.get_or_init(tools::versions)),
+    };
+    match tools {
+        Some(tools) => {
+            for (name, version) in tools {
+                report.push(format!(
+                    "  {}: {}",
+                    name,
+                    version.clone().unwrap_or_else(|e| e)
+                ));
+            }
+        }
+        None => report.push(String::from("  not looked up yet")),
+    }
+    report.push(String::new());
+    report.push(format!(
+        "{}:",
+        if kind == "panic" { "Panic" } else { "Error" }
+    ));
+    report.push(message.to_string());
+    report.push(String::new());
+    report.push(String::from("Backtrace:"));
+    report.push(backtrace.to_string());
+    report.push(String::new());
+    report.push(format!("Recent output (last {} lines):", output.len()));
+    report.extend(output);
+
+    let dir = paths::crash_report_dir();
+    fs::create_dir_all(&dir)?;
+    let file = dir.join(format!(
+        "{}-{}.txt",
+        kind,
+        chrono::Utc::now().format("%Y%m%dT%H%M%SZ")
+    ));
+    fs::write(&file, redact::redact(&report.join("\n")))?;
+    Ok(file)
+}
@@ -0,0 +1,121 @@
+//! Local credentials store: the Bonsai API key, deployment keys, generated test wallets, and RPC
+//! provider keys, kept in an age-encrypted file unlocked with a passphrase.
+
+use crate::{paths, platform, redact};
+use age::secrecy::SecretString;
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialKind {
+    BonsaiApiKey,
+    DeploymentKey,
+    RpcProviderKey,
+    /// A key generated by steel-tui, only ever offered on testnets.
+    TestWallet,
+}
+
+impl CredentialKind {
+    pub const ALL: &'static [CredentialKind] = &[
+        CredentialKind::BonsaiApiKey,
+        CredentialKind::DeploymentKey,
+        CredentialKind::RpcProviderKey,
+        CredentialKind::TestWallet,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CredentialKind::BonsaiApiKey => "Bonsai API key",
+            CredentialKind::DeploymentKey => "Deployment key",
+            CredentialKind::RpcProviderKey => "RPC provider key",
+            CredentialKind::TestWallet => "Test wallet key",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Credential {
+    pub kind: CredentialKind,
+    /// Distinguishes several keys of the same kind, e.g. `sepolia-deployer` or `alchemy`.
+    pub name: String,
+    pub secret: String,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct Credentials {
+    pub entries: Vec<Credential>,
+}
+
+impl Credentials {
+    pub fn exists() -> bool {
+        paths::credentials_file().exists()
+    }
+
+    /// Decrypts the store, or returns an empty one if none has been created yet. Every loaded
+    /// secret is registered for redaction.
+    pub fn load(passphrase: &str) -> Result<Self> {
+        let file = paths::credentials_file();
+        if !file.exists() {
+            return Ok(Self::default());
+        }
+
+        let identity = age::scrypt::Identity::new(SecretString::from(passphrase.to_string()));
+        let plaintext = age::decrypt(&identity, &fs::read(&file)?)
+            .map_err(|e| eyre!("Could not unlock {}: {}", file.display(), e))?;
+        let credentials: Self = serde_json::from_slice(&plaintext)?;
+        for entry in &credentials.entries {
+            redact::register(&entry.secret);
+        }
+        Ok(credentials)
+    }
+
+    pub fn save(&self, passphrase: &str) -> Result<()> {
+        let recipient = age::scrypt::Recipient::new(SecretString::from(passphrase.to_string()));
+        let encrypted = age::encrypt(&recipient, &serde_json::to_vec(self)?)?;
+
+        fs::create_dir_all(paths::config_dir())?;
+        let file = paths::credentials_file();
+        // Write then rename, so an interrupted save can't leave a truncated store behind
+        let staged = file.with_extension("age.new");
+        fs::write(&staged, encrypted)?;
+        platform::restrict_to_owner(&staged)?;
+        fs::rename(staged, file)?;
+        Ok(())
+    }
+
+    pub fn get(&self, kind: CredentialKind) -> Option<&Credential> {
+        self.entries.iter().find(|entry| entry.kind == kind)
+    }
+
+    /// Adds a credential, replacing any existing one with the same kind and name.
+    pub fn set(&mut self, kind: CredentialKind, name: &str, secret: &str) {
+        redact::register(secret);
+        self.entries
+            .retain(|entry| !(entry.kind == kind && entry.name == name));
+        self.entries.push(Credential {
+            kind,
+            name: name.to_string(),
+            secret: secret.to_string(),
+        });
+    }
+
+    /// Moves the named credential ahead of the others of its kind, so [`Credentials::get`]
+    /// returns it.
+    pub fn prefer(&mut self, kind: CredentialKind, name: &str) {
+        if let Some(index) = self
+            .entries
+            .iter()
+            .position(|entry| entry.kind == kind && entry.name == name)
+        {
+            let entry = self.entries.remove(index);
+            self.entries.insert(0, entry);
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.entries.len() {
+            self.entries.remove(index);
+        }
+    }
+}
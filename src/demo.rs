@@ -0,0 +1,51 @@
+//! `--demo`: rendering that records cleanly with asciinema or vhs. Frames come at a steady,
+//! capped rate, the cursor doesn't blink and spinners stand still, so two recordings of the same
+//! session look the same. Each screen can also be held for a moment before moving on.
+
+use std::{
+    cell::Cell,
+    time::{Duration, Instant},
+};
+
+/// The shortest time between two frames, ten a second.
+const FRAME: Duration = Duration::from_millis(100);
+
+pub struct Demo {
+    /// How long to hold each screen after it changes.
+    step_delay: Duration,
+    last_frame: Cell<Option<Instant>>,
+    last_state: String,
+}
+
+impl Demo {
+    pub fn new(step_delay: Duration) -> Self {
+        Demo {
+            step_delay,
+            last_frame: Cell::new(None),
+            last_state: String::new(),
+        }
+    }
+
+    /// Whether enough time has passed to draw again, counting this as the new frame if so.
+    pub fn frame_due(&self) -> bool {
+        let now = Instant::now();
+        if self
+            .last_frame
+            .get()
+            .is_some_and(|last| now.duration_since(last) < FRAME)
+        {
+            return false;
+        }
+        self.last_frame.set(Some(now));
+        true
+    }
+
+    /// How long to hold the screen, if the app moved to `state` since last asked.
+    pub fn entered(&mut self, state: &str) -> Option<Duration> {
+        if self.last_state == state {
+            return None;
+        }
+        self.last_state = state.to_string();
+        Some(self.step_delay).filter(|delay| !delay.is_zero())
+    }
+}
@@ -0,0 +1,49 @@
+//! Well-known development keys, and the check that keeps them off public networks.
+
+/// Private keys of anvil's (and hardhat's) default accounts, derived from the public
+/// `test test ... junk` mnemonic. Anyone can sweep funds sent to them on a real chain.
+pub const ANVIL_PRIVATE_KEYS: [&str; 10] = [
+    "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+    "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+    "0x5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a",
+    "0x7c852118294e51e653712a81e05800f419141751be58f605c371e15141b007a6",
+    "0x47e179ec197488593b187f80a00eb0da91f1b9d0b13f8733639f19c30a34926a",
+    "0x8b3a350cf5c34c9194ca85829a2df0ec3153be0318b5e2d3348e872092edffba",
+    "0x92db14e403b83dfe3df233f83dfa3a0d7096f21ca9b0d6d6b8d88b2b4ec1564e",
+    "0x4bbbf85ce3377467afe5d46f804f221813b2bb87f24d81f60f1fcdbf7cbf4356",
+    "0xdbda1821b80551c9d65939329250298aa3472ba22feea921c0cf5d620ea67b97",
+    "0x2a871d0798f97d79848a013d4936a73bf4cc922c825d33c1cf7073dff6d409c6",
+];
+
+/// Address of the first anvil account, matching `ANVIL_PRIVATE_KEYS[0]`.
+pub const ANVIL_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+/// RPC endpoint anvil listens on by default.
+pub const LOCAL_RPC_URL: &str = "http://localhost:8545";
+
+pub fn is_dev_key(key: &str) -> bool {
+    let key = key.trim();
+    let key = key.strip_prefix("0x").unwrap_or(key);
+    ANVIL_PRIVATE_KEYS
+        .iter()
+        .any(|known| known[2..].eq_ignore_ascii_case(key))
+}
+
+/// Whether `url` points at this machine.
+pub fn is_local_rpc(url: &str) -> bool {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?']).next().unwrap_or_default();
+    let authority = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = match authority.strip_prefix('[') {
+        // IPv6 literal, e.g. [::1]:8545
+        Some(v6) => v6.split(']').next().unwrap_or_default(),
+        None => authority.split(':').next().unwrap_or_default(),
+    };
+
+    host.eq_ignore_ascii_case("localhost")
+        || host.starts_with("127.")
+        || host == "::1"
+        || host == "0.0.0.0"
+}
@@ -0,0 +1,119 @@
+//! Short docs for the side panel, one page per screen, written in markdown and embedded in the
+//! binary.
+
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+pub const DEPENDENCIES: &str = include_str!("docs/dependencies.md");
+pub const TEMPLATE: &str = include_str!("docs/template.md");
+pub const FORGE: &str = include_str!("docs/forge.md");
+pub const BONSAI: &str = include_str!("docs/bonsai.md");
+pub const DEPLOY: &str = include_str!("docs/deploy.md");
+pub const TOOLBOX: &str = include_str!("docs/toolbox.md");
+pub const SETTINGS: &str = include_str!("docs/settings.md");
+
+/// Renders the markdown the pages use: headings, paragraphs, lists, emphasis, inline code,
+/// code blocks and links. Anything else is shown as plain text.
+pub fn render(markdown: &str) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut line: Vec<Span<'static>> = Vec::new();
+    let mut styles = vec![Style::default()];
+    let mut in_code_block = false;
+
+    fn flush(lines: &mut Vec<Line<'static>>, line: &mut Vec<Span<'static>>) {
+        if !line.is_empty() {
+            lines.push(Line::from(std::mem::take(line)));
+        }
+    }
+    /// Separates blocks with one empty line.
+    fn gap(lines: &mut Vec<Line<'static>>) {
+        if lines.last().is_some_and(|line| !line.spans.is_empty()) {
+            lines.push(Line::default());
+        }
+    }
+    fn push_style(styles: &mut Vec<Style>, style: Style) {
+        let current = *styles.last().expect("base style");
+        styles.push(current.patch(style));
+    }
+
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::Heading { .. }) => {
+                gap(&mut lines);
+                push_style(
+                    &mut styles,
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                );
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                styles.pop();
+                flush(&mut lines, &mut line);
+                gap(&mut lines);
+            }
+            Event::End(TagEnd::Paragraph) => {
+                flush(&mut lines, &mut line);
+                gap(&mut lines);
+            }
+            Event::Start(Tag::Item) => {
+                flush(&mut lines, &mut line);
+                line.push(Span::raw("• "));
+            }
+            Event::End(TagEnd::Item) => flush(&mut lines, &mut line),
+            Event::End(TagEnd::List(_)) => gap(&mut lines),
+            Event::Start(Tag::Strong) => {
+                push_style(&mut styles, Style::default().add_modifier(Modifier::BOLD))
+            }
+            Event::Start(Tag::Emphasis) => {
+                push_style(&mut styles, Style::default().add_modifier(Modifier::ITALIC))
+            }
+            Event::Start(Tag::Link { .. }) => push_style(
+                &mut styles,
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::UNDERLINED),
+            ),
+            Event::End(TagEnd::Strong | TagEnd::Emphasis | TagEnd::Link) => {
+                styles.pop();
+            }
+            Event::Start(Tag::CodeBlock(_)) => {
+                flush(&mut lines, &mut line);
+                in_code_block = true;
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                in_code_block = false;
+                gap(&mut lines);
+            }
+            Event::Text(text) if in_code_block => {
+                for code in text.lines() {
+                    lines.push(Line::styled(
+                        format!("  {}", code),
+                        Style::default().fg(Color::Gray),
+                    ));
+                }
+            }
+            Event::Text(text) => {
+                line.push(Span::styled(
+                    text.into_string(),
+                    *styles.last().expect("base style"),
+                ));
+            }
+            Event::Code(code) => line.push(Span::styled(
+                code.into_string(),
+                Style::default().fg(Color::Yellow),
+            )),
+            Event::SoftBreak => line.push(Span::raw(" ")),
+            Event::HardBreak => flush(&mut lines, &mut line),
+            _ => {}
+        }
+    }
+    flush(&mut lines, &mut line);
+    while lines.last().is_some_and(|line| line.spans.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
@@ -0,0 +1,101 @@
+//! Compares a generated project with the current version of the template it came from, so
+//! upstream changes can be reviewed and pulled in file by file.
+
+use crate::http;
+use color_eyre::{eyre::eyre, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Where users write their own code, plus build output and tool state. Never compared.
+const IGNORED: &[&str] = &[
+    ".git",
+    "target",
+    "lib",
+    "out",
+    "cache",
+    "broadcast",
+    "apps/src",
+    "contracts/src",
+    "contracts/test",
+    "methods/guest/src",
+    "tests",
+    crate::project_file::FILE_NAME,
+];
+
+/// A template file that differs from the project's copy.
+pub struct FileDrift {
+    /// Relative to both roots.
+    pub path: PathBuf,
+    /// The template has it but the project doesn't.
+    pub new: bool,
+}
+
+/// Template files that are missing from `project` or differ from its copy, sorted by path.
+/// Files only the project has are left out: they're the user's.
+pub fn compare(project: &Path, template: &Path) -> Result<Vec<FileDrift>> {
+    let mut drift = Vec::new();
+    for path in template_files(template, Path::new(""))? {
+        let ours = project.join(&path);
+        if !ours.exists() {
+            drift.push(FileDrift { path, new: true });
+        } else if fs::read(&ours)? != fs::read(template.join(&path))? {
+            drift.push(FileDrift { path, new: false });
+        }
+    }
+    drift.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(drift)
+}
+
+fn template_files(root: &Path, relative: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(root.join(relative))? {
+        let path = relative.join(entry?.file_name());
+        if IGNORED.iter().any(|ignored| path == Path::new(ignored)) {
+            continue;
+        }
+        if root.join(&path).is_dir() {
+            files.extend(template_files(root, &path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// Unified diff from the project's copy of `path` to the template's, as `git diff` prints it.
+pub fn diff(project: &Path, template: &Path, path: &Path) -> Result<Vec<String>> {
+    let ours = project.join(path);
+    let ours = if ours.exists() {
+        ours
+    } else {
+        PathBuf::from("/dev/null")
+    };
+    let output = http::git()
+        .args(["diff", "--no-index", "--no-color", "--"])
+        .arg(ours)
+        .arg(template.join(path))
+        .output()?;
+    // `--no-index` exits with 1 when the files differ
+    if output.status.code().is_some_and(|code| code > 1) {
+        return Err(eyre!(
+            "git diff failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
+/// Replaces the project's copy of `path` with the template's.
+pub fn pull(project: &Path, template: &Path, path: &Path) -> Result<()> {
+    let target = project.join(path);
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::copy(template.join(path), target)?;
+    Ok(())
+}
@@ -0,0 +1,181 @@
+//! Follows the logs of the project's deployed contracts and decodes them with the ABIs forge
+//! compiled, so the effect of a test run or deployment can be checked on-chain.
+
+use crate::{abi, chain::Chain, journal};
+use alloy::{dyn_abi::EventExt, json_abi::Event, primitives::Address, rpc::types::Log};
+use color_eyre::{eyre::eyre, Result};
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How far back the first poll looks. Public RPCs cap the block range of `eth_getLogs`.
+const LOOKBACK: u64 = 5_000;
+
+/// A deployed contract whose logs are followed.
+pub struct Watched {
+    pub name: String,
+    pub address: Address,
+    events: Vec<Event>,
+}
+
+/// One decoded log.
+pub struct Row {
+    pub block: u64,
+    pub contract: String,
+    pub event: String,
+    /// `name=value` pairs in declaration order.
+    pub args: String,
+}
+
+impl Row {
+    pub fn describe(&self) -> String {
+        format!(
+            "{:>8}  {:<16} {:<20} {}",
+            self.block, self.contract, self.event, self.args
+        )
+    }
+}
+
+pub struct EventLog {
+    chain: Chain,
+    pub watched: Vec<Watched>,
+    pub rows: Vec<Row>,
+    /// First block the next poll asks for.
+    next_block: Option<u64>,
+    last_poll: Option<Instant>,
+    /// Why the last poll failed, e.g. the test's anvil has been stopped.
+    pub error: Option<String>,
+}
+
+impl EventLog {
+    /// Follows `contracts` (name and address) on `rpc_url`, decoding with the ABIs in `out/`.
+    pub fn new(root: &Path, rpc_url: &str, contracts: &[(String, String)]) -> Result<Self> {
+        if contracts.is_empty() {
+            return Err(eyre!(
+                "No deployed contracts found: deploy or run the end-to-end test first"
+            ));
+        }
+        let compiled = abi::load(root).unwrap_or_default();
+        let watched = contracts
+            .iter()
+            .map(|(name, address)| {
+                let address = address
+                    .parse()
+                    .map_err(|e| eyre!("Invalid address '{}' for {}: {}", address, name, e))?;
+                let events = compiled
+                    .iter()
+                    .find(|contract| contract.name == *name)
+                    .map(|contract| contract.abi.events().cloned().collect())
+                    .unwrap_or_default();
+                Ok(Watched {
+                    name: name.clone(),
+                    address,
+                    events,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            chain: Chain::connect(rpc_url)?,
+            watched,
+            rows: Vec::new(),
+            next_block: None,
+            last_poll: None,
+            error: None,
+        })
+    }
+
+    pub fn rpc_url(&self) -> &str {
+        self.chain.rpc_url()
+    }
+
+    /// Fetches logs since the last poll, at most every [`POLL_INTERVAL`]. Returns the number of
+    /// new rows, or `None` when it was too early to ask.
+    pub fn poll(&mut self) -> Option<usize> {
+        if self.last_poll.is_some_and(|t| t.elapsed() < POLL_INTERVAL) {
+            return None;
+        }
+        self.last_poll = Some(Instant::now());
+        let before = self.rows.len();
+        match self.fetch() {
+            Ok(()) => self.error = None,
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        Some(self.rows.len() - before)
+    }
+
+    fn fetch(&mut self) -> Result<()> {
+        let latest = self.chain.block_number()?;
+        let from = self
+            .next_block
+            .unwrap_or_else(|| latest.saturating_sub(LOOKBACK));
+        if from > latest {
+            return Ok(());
+        }
+        let addresses = self.watched.iter().map(|w| w.address).collect();
+        let logs = self.chain.contract_logs(addresses, from, latest)?;
+        let rows: Vec<Row> = logs.iter().map(|log| self.decode(log)).collect();
+        self.rows.extend(rows);
+        self.next_block = Some(latest + 1);
+        Ok(())
+    }
+
+    fn decode(&self, log: &Log) -> Row {
+        let watched = self.watched.iter().find(|w| w.address == log.address());
+        let contract = watched.map_or_else(|| log.address().to_string(), |w| w.name.clone());
+        let topics = log.topics();
+        let event = watched.and_then(|w| {
+            w.events
+                .iter()
+                .find(|event| !event.anonymous && topics.first() == Some(&event.selector()))
+        });
+        let decoded = event.and_then(|event| {
+            let decoded = event.decode_log(log.data()).ok()?;
+            Some((event, decoded))
+        });
+        let (event, args) = match decoded {
+            Some((event, decoded)) => {
+                let mut indexed = decoded.indexed.iter();
+                let mut body = decoded.body.iter();
+                let args = event
+                    .inputs
+                    .iter()
+                    .map(|input| {
+                        let value = if input.indexed {
+                            indexed.next()
+                        } else {
+                            body.next()
+                        };
+                        let value = value.map(journal::format_value).unwrap_or_default();
+                        if input.name.is_empty() {
+                            value
+                        } else {
+                            format!("{}={}", input.name, value)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                (event.name.clone(), args)
+            }
+            None => (
+                topics.first().map_or_else(
+                    || String::from("(anonymous)"),
+                    |t| format!("{:.10}…", t.to_string()),
+                ),
+                format!(
+                    "{} topic(s), {} bytes of data (not in the compiled ABI)",
+                    topics.len(),
+                    log.data().data.len()
+                ),
+            ),
+        };
+        Row {
+            block: log.block_number.unwrap_or_default(),
+            contract,
+            event,
+            args,
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use std::process::ExitCode;
+
+/// How a run ended, mapped to a distinct process exit code so wrappers and CI can branch on it.
+///
+/// | Code | Meaning                                   |
+/// |------|-------------------------------------------|
+/// | 0    | Success                                   |
+/// | 1    | Internal error (unexpected failure)       |
+/// | 2    | Invalid usage or missing required input   |
+/// | 3    | A required dependency is missing          |
+/// | 4    | An install step failed                    |
+/// | 5    | A test run failed                         |
+/// | 130  | Aborted by the user                       |
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Success,
+    InternalError,
+    /// Input was required but can't be prompted for (CI mode).
+    MissingInput,
+    DependencyMissing,
+    InstallFailed,
+    TestFailed,
+    UserAbort,
+}
+
+impl Outcome {
+    pub fn code(self) -> u8 {
+        match self {
+            Outcome::Success => 0,
+            Outcome::InternalError => 1,
+            Outcome::MissingInput => 2,
+            Outcome::DependencyMissing => 3,
+            Outcome::InstallFailed => 4,
+            Outcome::TestFailed => 5,
+            Outcome::UserAbort => 130,
+        }
+    }
+}
+
+impl From<Outcome> for ExitCode {
+    fn from(outcome: Outcome) -> Self {
+        ExitCode::from(outcome.code())
+    }
+}
@@ -0,0 +1,144 @@
+//! Runs `cargo fmt` and `forge fmt` over a project and works out which files they reformatted,
+//! or in check mode which files they would.
+
+use crate::platform;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Build output, dependencies, and tool state; neither formatter touches them.
+const SKIPPED_DIRS: &[&str] = &[
+    ".git",
+    "target",
+    "lib",
+    "out",
+    "cache",
+    "broadcast",
+    "node_modules",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    Cargo,
+    Forge,
+}
+
+impl Tool {
+    pub const ALL: &'static [Tool] = &[Tool::Cargo, Tool::Forge];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Tool::Cargo => "cargo fmt",
+            Tool::Forge => "forge fmt",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Tool::Cargo => "rs",
+            Tool::Forge => "sol",
+        }
+    }
+
+    /// The formatter, listing files it would change instead of changing them in check mode.
+    pub fn command(self, check: bool) -> Command {
+        match self {
+            Tool::Cargo => {
+                let mut command = Command::new("cargo");
+                command.args(["fmt", "--all", "--"]);
+                if check {
+                    command.args(["--check", "--files-with-diff"]);
+                }
+                command
+            }
+            Tool::Forge => {
+                let mut command = Command::new("forge");
+                command.arg("fmt").args(check.then_some("--check"));
+                command
+            }
+        }
+    }
+}
+
+/// Digests of the files a formatter covers, by path relative to the root.
+pub struct Snapshot(BTreeMap<PathBuf, [u8; 32]>);
+
+impl Snapshot {
+    pub fn take(root: &Path, tool: Tool) -> Self {
+        let mut files = BTreeMap::new();
+        collect(root, Path::new(""), tool.extension(), &mut files);
+        Self(files)
+    }
+
+    /// One digest over every file, to tell whether any of them changed.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        for (path, digest) in &self.0 {
+            hasher.update(path.to_string_lossy().as_bytes());
+            hasher.update(digest);
+        }
+        hasher.finalize().into()
+    }
+
+    /// Files whose content differs in `after`, sorted.
+    pub fn changed(&self, after: &Snapshot) -> Vec<PathBuf> {
+        after
+            .0
+            .iter()
+            .filter(|(path, digest)| self.0.get(*path) != Some(digest))
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+}
+
+fn collect(root: &Path, relative: &Path, extension: &str, files: &mut BTreeMap<PathBuf, [u8; 32]>) {
+    let Ok(entries) = fs::read_dir(root.join(relative)) else {
+        return;
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let name = entry.file_name();
+        let path = relative.join(&name);
+        if entry.path().is_dir() {
+            if !SKIPPED_DIRS.iter().any(|dir| name == *dir) {
+                collect(root, &path, extension, files);
+            }
+        } else if path.extension().is_some_and(|ext| ext == extension) {
+            if let Ok(content) = fs::read(entry.path()) {
+                files.insert(path, Sha256::digest(&content).into());
+            }
+        }
+    }
+}
+
+/// Files a check run reported, relative to the root where possible. rustfmt lists one path
+/// per line; forge prints `Diff in <path>:` above each diff.
+pub fn violations(root: &Path, tool: Tool, lines: &[String]) -> Vec<PathBuf> {
+    // rustfmt prints absolute paths
+    let canonical = platform::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let mut files: Vec<PathBuf> = lines
+        .iter()
+        .filter_map(|line| {
+            let line = line.trim();
+            let path = match tool {
+                Tool::Cargo => line,
+                Tool::Forge => line.strip_prefix("Diff in ")?.trim_end_matches(':'),
+            };
+            let path = Path::new(path);
+            if path.extension()? != tool.extension() {
+                return None;
+            }
+            let relative = path
+                .strip_prefix(&canonical)
+                .or_else(|_| path.strip_prefix(root))
+                .unwrap_or(path);
+            Some(relative.to_path_buf())
+        })
+        .collect();
+    files.sort();
+    files.dedup();
+    files
+}
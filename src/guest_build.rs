@@ -0,0 +1,220 @@
+//! Follows a `cargo build` through risc0-build's guest build so its phases can be shown as they
+//! happen. risc0-build writes the guest's progress to the terminal directly rather than through
+//! cargo, so it's pointed at a log file with `RISC0_GUEST_LOGFILE` and that file is tailed.
+
+use crate::paths;
+use std::{
+    fs,
+    io::{Read, Seek, SeekFrom},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+const SPINNER: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Lines that show the guest is being built in Docker and the builder image is being set up.
+const DOCKER: &[&str] = &[
+    "Docker context:",
+    "Building ELF binaries in",
+    "Unable to find image",
+    "Pulling from",
+    "Pull complete",
+    "docker pull",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    DockerPull,
+    Compile,
+    ImageId,
+}
+
+impl Phase {
+    pub fn label(self) -> &'static str {
+        match self {
+            Phase::DockerPull => "Pulling builder image",
+            Phase::Compile => "Compiling guest",
+            Phase::ImageId => "Computing image IDs",
+        }
+    }
+}
+
+pub struct Step {
+    pub phase: Phase,
+    started: Instant,
+    took: Option<Duration>,
+    /// e.g. the crate being compiled.
+    detail: String,
+}
+
+impl Step {
+    /// One row of the progress panel. Without `animate`, a running step shows a still icon in
+    /// place of the spinner.
+    pub fn describe(&self, animate: bool) -> String {
+        let (icon, elapsed) = match self.took {
+            Some(took) => ("✓", took),
+            None if !animate => ("…", self.started.elapsed()),
+            None => {
+                let elapsed = self.started.elapsed();
+                let frame = (elapsed.as_millis() / 100) as usize % SPINNER.len();
+                (SPINNER[frame], elapsed)
+            }
+        };
+        format!(
+            "{} {:<22} {:>4}s  {}",
+            icon,
+            self.phase.label(),
+            elapsed.as_secs(),
+            self.detail
+        )
+        .trim_end()
+        .to_string()
+    }
+}
+
+pub struct Progress {
+    pub steps: Vec<Step>,
+    log: PathBuf,
+    read: u64,
+    /// Unterminated tail of the log, completed by the next read.
+    partial: String,
+    image_ids: usize,
+}
+
+impl Progress {
+    pub fn start() -> Self {
+        let log = paths::temp_dir().join("guest-build.log");
+        let _ = fs::create_dir_all(paths::temp_dir());
+        let _ = fs::write(&log, "");
+        Self {
+            steps: Vec::new(),
+            log,
+            read: 0,
+            partial: String::new(),
+            image_ids: 0,
+        }
+    }
+
+    /// Environment for the `cargo build` being followed.
+    pub fn env(&self) -> (&'static str, PathBuf) {
+        ("RISC0_GUEST_LOGFILE", self.log.clone())
+    }
+
+    pub fn running(&self) -> bool {
+        self.steps.last().is_some_and(|step| step.took.is_none())
+    }
+
+    fn current(&self) -> Option<Phase> {
+        self.steps
+            .last()
+            .filter(|step| step.took.is_none())
+            .map(|step| step.phase)
+    }
+
+    fn close(&mut self) {
+        if let Some(step) = self.steps.last_mut().filter(|step| step.took.is_none()) {
+            step.took = Some(step.started.elapsed());
+        }
+    }
+
+    fn enter(&mut self, phase: Phase, detail: impl Into<String>) {
+        if self.current() != Some(phase) {
+            self.close();
+            self.steps.push(Step {
+                phase,
+                started: Instant::now(),
+                took: None,
+                detail: String::new(),
+            });
+        }
+        if let Some(step) = self.steps.last_mut() {
+            step.detail = detail.into();
+        }
+    }
+
+    /// New lines of the guest build log, after updating the phases from them.
+    pub fn poll(&mut self) -> Vec<String> {
+        let Ok(mut file) = fs::File::open(&self.log) else {
+            return Vec::new();
+        };
+        let mut new = String::new();
+        if file.seek(SeekFrom::Start(self.read)).is_err() || file.read_to_string(&mut new).is_err()
+        {
+            return Vec::new();
+        }
+        self.read += new.len() as u64;
+        self.partial.push_str(&new);
+        let Some(end) = self.partial.rfind('\n') else {
+            return Vec::new();
+        };
+        let complete: String = self.partial.drain(..=end).collect();
+        let lines: Vec<String> = complete.lines().map(str::to_string).collect();
+        for line in &lines {
+            self.observe_guest(line);
+        }
+        lines
+    }
+
+    /// A line of the guest build, with or without the `<package>: ` prefix risc0-build adds.
+    fn observe_guest(&mut self, line: &str) {
+        let message = match line.split_once(": ") {
+            Some((package, rest)) if !package.contains(' ') => rest.trim(),
+            _ => line.trim(),
+        };
+        if DOCKER.iter().any(|pattern| message.contains(pattern)) {
+            self.enter(Phase::DockerPull, "");
+        } else if message.starts_with("Starting build for") {
+            self.enter(Phase::Compile, "");
+        } else if let Some(krate) = message.strip_prefix("Compiling ") {
+            let krate = krate.split_whitespace().next().unwrap_or_default();
+            self.enter(Phase::Compile, krate);
+        } else if message.starts_with("Finished ") && self.current() == Some(Phase::Compile) {
+            self.enter(Phase::ImageId, "");
+        } else if message.starts_with("ImageID:") {
+            self.image_ids += 1;
+            let detail = format!("{} computed", self.image_ids);
+            self.enter(Phase::ImageId, detail);
+        }
+    }
+
+    /// A line of cargo's own output. The host crates that depend on the guests only start once
+    /// the image IDs are in, so any of them moving on ends the guest build.
+    pub fn observe(&mut self, line: &str) {
+        if let Some(message) = line
+            .strip_prefix("warning: ")
+            .and_then(|rest| rest.split_once(": "))
+            .filter(|(package, _)| package.contains('@') && !package.contains(' '))
+            .map(|(_, message)| message)
+        {
+            self.observe_guest(message);
+            return;
+        }
+        let trimmed = line.trim_start();
+        if self.current() == Some(Phase::ImageId)
+            && (trimmed.starts_with("Compiling ") || trimmed.starts_with("Finished "))
+        {
+            self.close();
+        }
+    }
+
+    /// Closes the running step and returns a one-line summary, if the guest build was seen.
+    pub fn finish(&mut self) -> Option<String> {
+        self.close();
+        let _ = fs::remove_file(&self.log);
+        if self.steps.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "Guest build: {}",
+            self.steps
+                .iter()
+                .map(|step| format!(
+                    "{} {}s",
+                    step.phase.label(),
+                    step.took.unwrap_or_default().as_secs()
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    }
+}
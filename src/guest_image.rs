@@ -0,0 +1,119 @@
+//! Image IDs and ELFs of the project's guests, read from the `ImageID.sol` and `Elf.sol`
+//! libraries risc0-build-ethereum generates during `cargo build`.
+
+use crate::resources;
+use regex::Regex;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Directories that never hold the generated libraries.
+const SKIPPED_DIRS: &[&str] = &[".git", "target", "lib", "out", "cache", "broadcast"];
+
+/// One guest as the contracts see it.
+pub struct GuestImage {
+    /// Guest name as it appears in the constants, e.g. `BALANCE_OF`.
+    pub name: String,
+    pub image_id: String,
+    /// Solidity expression for the image ID, e.g. `ImageID.BALANCE_OF_ID`.
+    pub id_constant: String,
+    /// Solidity expression for the ELF path, if `Elf.sol` was generated too.
+    pub elf_constant: Option<String>,
+    pub elf_path: Option<PathBuf>,
+    pub elf_size: Option<u64>,
+}
+
+impl GuestImage {
+    pub fn describe(&self) -> String {
+        let elf = match self.elf_size {
+            Some(size) => resources::format_bytes(size),
+            None => String::from("ELF not found"),
+        };
+        format!(
+            "{}: image ID {} ({}), {}",
+            self.name, self.image_id, self.id_constant, elf
+        )
+    }
+}
+
+fn find(dir: &Path, file_name: &str, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        if path.is_dir() {
+            let skipped = path
+                .file_name()
+                .is_some_and(|name| SKIPPED_DIRS.iter().any(|s| name == *s));
+            if !skipped {
+                find(&path, file_name, found);
+            }
+        } else if path.file_name().is_some_and(|name| name == file_name) {
+            found.push(path);
+        }
+    }
+}
+
+/// `(library, constant prefix, value)` for every constant ending in `suffix`.
+fn constants(file: &Path, kind: &str, suffix: &str, value: &str) -> Vec<(String, String, String)> {
+    let Ok(content) = fs::read_to_string(file) else {
+        return Vec::new();
+    };
+    let library = Regex::new(r"library\s+(\w+)")
+        .expect("valid regex")
+        .captures(&content)
+        .map(|caps| caps[1].to_string())
+        .unwrap_or_default();
+    let constant = Regex::new(&format!(
+        r"{}\s+(?:public\s+|internal\s+)?constant\s+(\w+){}\s*=\s*{}",
+        kind, suffix, value
+    ))
+    .expect("valid regex");
+    constant
+        .captures_iter(&content)
+        .map(|caps| (library.clone(), caps[1].to_string(), caps[2].to_string()))
+        .collect()
+}
+
+/// Every guest in the project's generated `ImageID.sol`, with its ELF if `Elf.sol` names one.
+/// Empty until the project has been built.
+pub fn read(root: &Path) -> Vec<GuestImage> {
+    let mut id_files = Vec::new();
+    find(root, "ImageID.sol", &mut id_files);
+    let mut elf_files = Vec::new();
+    find(root, "Elf.sol", &mut elf_files);
+
+    let elves: Vec<(String, String, String)> = elf_files
+        .iter()
+        .flat_map(|file| constants(file, "string", "_PATH", r#""([^"]+)""#))
+        .collect();
+
+    id_files
+        .iter()
+        .flat_map(|file| {
+            constants(
+                file,
+                "bytes32",
+                "_ID",
+                r"bytes32\(\s*(0x[0-9a-fA-F]{64})\s*\)",
+            )
+        })
+        .map(|(library, name, image_id)| {
+            let elf = elves.iter().find(|(_, elf_name, _)| *elf_name == name);
+            let elf_path = elf.map(|(_, _, path)| PathBuf::from(path));
+            GuestImage {
+                id_constant: format!("{}.{}_ID", library, name),
+                elf_constant: elf.map(|(library, name, _)| format!("{}.{}_PATH", library, name)),
+                elf_size: elf_path
+                    .as_ref()
+                    .and_then(|path| fs::metadata(path).ok())
+                    .map(|metadata| metadata.len()),
+                elf_path,
+                name,
+                image_id,
+            }
+        })
+        .collect()
+}
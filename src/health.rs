@@ -0,0 +1,210 @@
+//! Checks that an existing project is still in a state that builds: its pinned risc0-ethereum
+//! release matches the installed toolchain, submodules are there, and the forge configuration
+//! the setup step wrote hasn't been broken.
+
+use crate::{apple_silicon, http, manifest, projects, tools};
+use std::{fs, path::Path};
+
+/// The result of one check.
+pub struct Finding {
+    pub check: &'static str,
+    pub outcome: Result<String, Problem>,
+}
+
+/// What's wrong and how to fix it.
+pub struct Problem {
+    pub detail: String,
+    pub fix: String,
+}
+
+impl Finding {
+    pub fn ok(check: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            check,
+            outcome: Ok(detail.into()),
+        }
+    }
+
+    pub fn problem(check: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            check,
+            outcome: Err(Problem {
+                detail: detail.into(),
+                fix: fix.into(),
+            }),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.outcome.is_ok()
+    }
+}
+
+/// Every check that doesn't need a build. `cargo check` is run separately so its output can be
+/// streamed.
+pub fn check(root: &Path) -> Vec<Finding> {
+    let mut findings = vec![toolchain(root), submodules(root), foundry_toml(root)];
+    findings.extend(remappings(root));
+    findings.extend(apple_silicon::checks());
+    findings
+}
+
+/// `release-X.Y` of risc0-ethereum is built against RISC Zero X.Y.
+fn toolchain(root: &Path) -> Finding {
+    const CHECK: &str = "RISC Zero toolchain";
+    let Some(branch) = projects::pinned_branch(root) else {
+        return Finding::problem(
+            CHECK,
+            "No risc0-ethereum dependency found in the project's manifests",
+            "Check that Cargo.toml still depends on risc0-ethereum by branch",
+        );
+    };
+    let Some((major, minor)) = manifest::release_version(&branch) else {
+        return Finding::ok(
+            CHECK,
+            format!("Pinned to '{}', which isn't a release branch", branch),
+        );
+    };
+    let installed = match tools::version("cargo", &["risczero", "--version"]) {
+        Ok(version) => version,
+        Err(e) => {
+            return Finding::problem(
+                CHECK,
+                format!("cargo-risczero is {}", e),
+                format!("Run `rzup install cargo-risczero {}.{}`", major, minor),
+            )
+        }
+    };
+    let wanted = format!("{}.{}.", major, minor);
+    if installed
+        .split_whitespace()
+        .any(|word| word.starts_with(&wanted))
+    {
+        Finding::ok(CHECK, format!("{} matches {}", installed, branch))
+    } else {
+        Finding::problem(
+            CHECK,
+            format!("{} is pinned but {} is installed", branch, installed),
+            format!(
+                "Run `rzup install cargo-risczero {}.{}`, or `steel-tui upgrade` to move the project to a release matching the toolchain",
+                major, minor
+            ),
+        )
+    }
+}
+
+/// `git submodule status` marks uninitialized submodules with `-` and ones checked out at a
+/// different commit than recorded with `+`.
+fn submodules(root: &Path) -> Finding {
+    const CHECK: &str = "Submodules";
+    const FIX: &str = "Run `git submodule update --init --recursive`";
+    if !root.join(".gitmodules").exists() {
+        return Finding::ok(CHECK, "The project has no submodules");
+    }
+    let output = match http::git()
+        .args(["submodule", "status", "--recursive"])
+        .current_dir(root)
+        .output()
+    {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            return Finding::problem(
+                CHECK,
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+                FIX,
+            )
+        }
+        Err(e) => return Finding::problem(CHECK, format!("Could not run git: {}", e), FIX),
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let path_of = |line: &str| {
+        line.split_whitespace()
+            .nth(1)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let missing: Vec<String> = stdout
+        .lines()
+        .filter(|line| line.starts_with('-'))
+        .map(path_of)
+        .collect();
+    let moved: Vec<String> = stdout
+        .lines()
+        .filter(|line| line.starts_with('+'))
+        .map(path_of)
+        .collect();
+    if !missing.is_empty() {
+        Finding::problem(
+            CHECK,
+            format!("Not initialized: {}", missing.join(", ")),
+            FIX,
+        )
+    } else if !moved.is_empty() {
+        Finding::problem(
+            CHECK,
+            format!("Checked out at a different commit: {}", moved.join(", ")),
+            "Run `git submodule update --recursive`, or commit the new commits if intended",
+        )
+    } else {
+        Finding::ok(CHECK, format!("{} initialized", stdout.lines().count()))
+    }
+}
+
+/// The settings the setup step writes so forge only looks inside the project.
+fn foundry_toml(root: &Path) -> Finding {
+    const CHECK: &str = "foundry.toml";
+    let Ok(content) = fs::read_to_string(root.join("foundry.toml")) else {
+        return Finding::problem(
+            CHECK,
+            "foundry.toml is missing",
+            "Restore it with `git checkout -- foundry.toml`",
+        );
+    };
+    if content.contains("../../") {
+        return Finding::problem(
+            CHECK,
+            "Still points outside the project (../../)",
+            "Set `libs = [\"lib\"]` under [profile.default]",
+        );
+    }
+    if !content.contains("auto_detect_remappings = false") {
+        return Finding::problem(
+            CHECK,
+            "Remapping auto-detection is on, so remappings.txt may be ignored",
+            "Add `auto_detect_remappings = false` under [profile.default]",
+        );
+    }
+    Finding::ok(CHECK, "Intact")
+}
+
+/// Every remapping has to point at a directory that exists.
+fn remappings(root: &Path) -> Vec<Finding> {
+    const CHECK: &str = "remappings.txt";
+    let Ok(content) = fs::read_to_string(root.join("remappings.txt")) else {
+        return vec![Finding::problem(
+            CHECK,
+            "remappings.txt is missing",
+            "Restore it with `git checkout -- remappings.txt`",
+        )];
+    };
+
+    let broken: Vec<Finding> = content
+        .lines()
+        .filter_map(|line| line.trim().split_once('='))
+        .filter(|(_, target)| !root.join(target).exists())
+        .map(|(prefix, target)| {
+            let fix = if target.starts_with("lib/") {
+                String::from("Run `git submodule update --init --recursive`")
+            } else {
+                format!("Point '{}' at an existing directory", prefix)
+            };
+            Finding::problem(CHECK, format!("{} → {} doesn't exist", prefix, target), fix)
+        })
+        .collect();
+    if broken.is_empty() {
+        vec![Finding::ok(CHECK, "Every remapping resolves")]
+    } else {
+        broken
+    }
+}
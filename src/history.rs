@@ -0,0 +1,87 @@
+//! Side-by-side comparison of two recorded test runs, for judging a prover backend or an
+//! upgrade by what changed between runs.
+
+use crate::artifacts::Record;
+
+/// `+1.2s`, `-0.4s`, or `—` when either side is missing.
+fn delta(a: Option<f64>, b: Option<f64>, unit: &str, precision: usize) -> String {
+    match (a, b) {
+        (Some(a), Some(b)) => format!("{:+.*}{}", precision, b - a, unit),
+        _ => String::from("—"),
+    }
+}
+
+fn value(v: Option<f64>, unit: &str, precision: usize) -> String {
+    v.map_or(String::from("—"), |v| {
+        format!("{:.*}{}", precision, v, unit)
+    })
+}
+
+fn row(label: &str, a: Option<f64>, b: Option<f64>, unit: &str, precision: usize) -> String {
+    format!(
+        "{:<24} {:>16} {:>16} {:>12}",
+        label,
+        value(a, unit, precision),
+        value(b, unit, precision),
+        delta(a, b, unit, precision)
+    )
+}
+
+/// The comparison table from run `a` to run `b`, then how their environments differ.
+pub fn compare(a: &Record, b: &Record) -> Vec<String> {
+    let mut lines = vec![format!(
+        "{:<24} {:>16} {:>16} {:>12}",
+        "", a.id, b.id, "Change"
+    )];
+
+    // Steps in the order they ran, with any only the second run had at the end
+    let mut names: Vec<&str> = a.steps.iter().map(|step| step.name.as_str()).collect();
+    for step in &b.steps {
+        if !names.contains(&step.name.as_str()) {
+            names.push(&step.name);
+        }
+    }
+    let seconds = |record: &Record, name: &str| {
+        record
+            .steps
+            .iter()
+            .find(|step| step.name == name)
+            .map(|step| step.seconds)
+    };
+    for name in names {
+        lines.push(row(name, seconds(a, name), seconds(b, name), "s", 1));
+    }
+    lines.push(row("Total", Some(a.seconds()), Some(b.seconds()), "s", 1));
+    lines.push(String::new());
+
+    lines.push(row("Proving", a.proving_seconds, b.proving_seconds, "s", 1));
+    let count = |v: Option<u64>| v.map(|v| v as f64);
+    lines.push(row("Cycles", count(a.cycles), count(b.cycles), "", 0));
+    lines.push(row("Gas used", count(a.gas_used), count(b.gas_used), "", 0));
+    lines.push(String::new());
+
+    let mut keys: Vec<&String> = a.environment.keys().chain(b.environment.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    let changed: Vec<String> = keys
+        .into_iter()
+        .filter(|key| a.environment.get(*key) != b.environment.get(*key))
+        .map(|key| {
+            let side = |record: &Record| {
+                record
+                    .environment
+                    .get(key)
+                    .cloned()
+                    .unwrap_or_else(|| String::from("(unset)"))
+            };
+            format!("  {}: {} → {}", key, side(a), side(b))
+        })
+        .collect();
+    if changed.is_empty() {
+        lines.push(String::from("Environment: the same"));
+    } else {
+        lines.push(String::from("Environment:"));
+        lines.extend(changed);
+    }
+    lines
+}
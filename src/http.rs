@@ -0,0 +1,132 @@
+use crate::redact;
+use color_eyre::{eyre::eyre, Result};
+use serde::de::DeserializeOwned;
+use std::{process::Command, sync::Mutex, time::Duration};
+use ureq::Proxy;
+
+/// Upper bound for downloaded files such as release binaries.
+const MAX_DOWNLOAD_BYTES: u64 = 200 * 1024 * 1024;
+
+/// Proxy chosen in settings. Without one, the usual proxy environment variables apply.
+static PROXY: Mutex<Option<String>> = Mutex::new(None);
+
+/// Environment variables consulted for a proxy, in the order ureq checks them.
+const PROXY_VARS: &[&str] = &[
+    "ALL_PROXY",
+    "all_proxy",
+    "HTTPS_PROXY",
+    "https_proxy",
+    "HTTP_PROXY",
+    "http_proxy",
+];
+
+pub fn set_proxy(proxy: Option<String>) {
+    let proxy = proxy.filter(|p| !p.trim().is_empty());
+    // The URL ends up in git command lines, which failure reports show
+    if let Some(password) = proxy
+        .as_deref()
+        .and_then(|url| Proxy::new(url).ok())
+        .and_then(|p| p.password().map(str::to_string))
+    {
+        redact::register(&password);
+    }
+    *PROXY.lock().unwrap_or_else(|e| e.into_inner()) = proxy;
+}
+
+/// The proxy in effect: the configured one, else the first proxy environment variable set.
+pub fn proxy() -> Option<String> {
+    let configured = PROXY.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    configured.or_else(|| {
+        PROXY_VARS
+            .iter()
+            .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+    })
+}
+
+/// Checks that `url` is something ureq can use as a proxy.
+pub fn validate_proxy(url: &str) -> Result<()> {
+    Proxy::new(url.trim()).map_err(|e| eyre!("Invalid proxy '{}': {}", url.trim(), e))?;
+    Ok(())
+}
+
+/// The configured proxy, still honoring `NO_PROXY` like ureq does for environment proxies.
+fn configured_proxy() -> Option<Proxy> {
+    let url = PROXY.lock().unwrap_or_else(|e| e.into_inner()).clone()?;
+    let proxy = Proxy::new(&url).ok()?;
+    let mut builder = Proxy::builder(proxy.protocol())
+        .host(proxy.host())
+        .port(proxy.port());
+    if let Some(username) = proxy.username() {
+        builder = builder.username(username);
+    }
+    if let Some(password) = proxy.password() {
+        builder = builder.password(password);
+    }
+    let no_proxy = std::env::var("NO_PROXY")
+        .or_else(|_| std::env::var("no_proxy"))
+        .unwrap_or_default();
+    for expr in no_proxy.split(',') {
+        builder = builder.no_proxy(expr.trim());
+    }
+    builder.build().ok()
+}
+
+/// Shared HTTP agent: identifies the tool and never waits on a host indefinitely.
+pub fn agent() -> ureq::Agent {
+    let mut config = ureq::Agent::config_builder()
+        .user_agent(concat!("steel-tui/", env!("CARGO_PKG_VERSION")))
+        .timeout_global(Some(Duration::from_secs(60)))
+        // Unreachable hosts fail here instead of using up the whole request budget
+        .timeout_connect(Some(Duration::from_secs(10)));
+    // ureq reads HTTP_PROXY, HTTPS_PROXY and NO_PROXY itself when nothing is configured
+    if let Some(proxy) = configured_proxy() {
+        config = config.proxy(Some(proxy));
+    }
+    config.build().into()
+}
+
+/// `git` with the proxy passed through as `-c http.proxy=`. git still honors `NO_PROXY`.
+pub fn git() -> Command {
+    let mut command = Command::new("git");
+    if let Some(proxy) = proxy() {
+        command.arg("-c").arg(format!("http.proxy={}", proxy));
+    }
+    command
+}
+
+pub fn get_json<T: DeserializeOwned>(url: &str) -> Result<T> {
+    Ok(agent()
+        .get(url)
+        .header("Accept", "application/json")
+        .call()?
+        .body_mut()
+        .read_json()?)
+}
+
+pub fn get_text(url: &str) -> Result<String> {
+    Ok(agent().get(url).call()?.body_mut().read_to_string()?)
+}
+
+/// Downloads `url`, telling `progress` how many bytes have arrived and how many the server
+/// said to expect.
+pub fn download(url: &str, progress: &mut dyn FnMut(u64, Option<u64>)) -> Result<Vec<u8>> {
+    use std::io::Read;
+
+    let mut response = agent().get(url).call()?;
+    let total = response.body().content_length();
+    let mut reader = response
+        .body_mut()
+        .with_config()
+        .limit(MAX_DOWNLOAD_BYTES)
+        .reader();
+    let mut bytes = Vec::new();
+    let mut chunk = [0u8; 64 * 1024];
+    loop {
+        let read = reader.read(&mut chunk)?;
+        if read == 0 {
+            return Ok(bytes);
+        }
+        bytes.extend_from_slice(&chunk[..read]);
+        progress(bytes.len() as u64, total);
+    }
+}
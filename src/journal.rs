@@ -0,0 +1,211 @@
+//! Decodes ABI-encoded journals with the struct definitions in the project's Solidity sources,
+//! so committed values show up by field name.
+
+use alloy::dyn_abi::{DynSolType, DynSolValue};
+use color_eyre::{eyre::eyre, Result};
+use regex::Regex;
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// Build output and tool state; `lib` is searched for definitions but its structs aren't offered.
+const SKIPPED_DIRS: &[&str] = &[
+    ".git",
+    "target",
+    "out",
+    "cache",
+    "broadcast",
+    "node_modules",
+];
+
+/// The Solidity structs a project can see, by name.
+pub struct Structs {
+    definitions: BTreeMap<String, Vec<(String, String)>>,
+    /// Structs declared in the project itself rather than its dependencies, sorted.
+    pub own: Vec<String>,
+}
+
+enum AbiType {
+    Elementary(DynSolType),
+    Struct(Vec<(String, AbiType)>),
+    Array(Box<AbiType>, Option<usize>),
+}
+
+impl AbiType {
+    fn to_dyn(&self) -> DynSolType {
+        match self {
+            AbiType::Elementary(ty) => ty.clone(),
+            AbiType::Struct(fields) => {
+                DynSolType::Tuple(fields.iter().map(|(_, ty)| ty.to_dyn()).collect())
+            }
+            AbiType::Array(inner, None) => DynSolType::Array(Box::new(inner.to_dyn())),
+            AbiType::Array(inner, Some(len)) => {
+                DynSolType::FixedArray(Box::new(inner.to_dyn()), *len)
+            }
+        }
+    }
+}
+
+fn collect(dir: &Path, in_lib: bool, structs: &mut Structs) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let definition = Regex::new(r"struct\s+(\w+)\s*\{([^}]*)\}").expect("valid regex");
+    let field = Regex::new(r"^\s*([\w.]+(?:\[\d*\])*)\s+(\w+)\s*$").expect("valid regex");
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() {
+            if !SKIPPED_DIRS.contains(&name.as_str()) {
+                collect(&path, in_lib || name == "lib", structs);
+            }
+            continue;
+        }
+        if !name.ends_with(".sol") {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        for caps in definition.captures_iter(&content) {
+            let fields = caps[2]
+                .split(';')
+                .map(|line| {
+                    // Drop comments so they don't end up in field declarations
+                    line.lines()
+                        .map(|l| l.split("//").next().unwrap_or_default())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .filter_map(|decl| {
+                    let caps = field.captures(decl.trim())?;
+                    Some((caps[2].to_string(), caps[1].to_string()))
+                })
+                .collect();
+            let struct_name = caps[1].to_string();
+            if !in_lib && !structs.own.contains(&struct_name) {
+                structs.own.push(struct_name.clone());
+            }
+            // The project's own definition wins over a dependency's of the same name
+            if !in_lib || !structs.definitions.contains_key(&struct_name) {
+                structs.definitions.insert(struct_name, fields);
+            }
+        }
+    }
+}
+
+impl Structs {
+    /// Every struct declared under `root`, including in `lib/`.
+    pub fn load(root: &Path) -> Self {
+        let mut structs = Structs {
+            definitions: BTreeMap::new(),
+            own: Vec::new(),
+        };
+        collect(root, false, &mut structs);
+        structs.own.sort();
+        structs
+    }
+
+    fn resolve(&self, ty: &str, depth: usize) -> Result<AbiType> {
+        if depth > 16 {
+            return Err(eyre!("'{}' nests too deeply", ty));
+        }
+        if let Some(open) = ty.rfind('[') {
+            let len = ty[open + 1..].trim_end_matches(']');
+            let inner = self.resolve(&ty[..open], depth + 1)?;
+            let len = if len.is_empty() {
+                None
+            } else {
+                Some(len.parse()?)
+            };
+            return Ok(AbiType::Array(Box::new(inner), len));
+        }
+        // Structs are often referred to through their library, e.g. `Steel.Commitment`
+        let name = ty.rsplit('.').next().unwrap_or(ty);
+        if let Some(fields) = self.definitions.get(name) {
+            let fields = fields
+                .iter()
+                .map(|(field, ty)| Ok((field.clone(), self.resolve(ty, depth + 1)?)))
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(AbiType::Struct(fields));
+        }
+        DynSolType::parse(ty).map(AbiType::Elementary).map_err(|_| {
+            eyre!(
+                "Unknown type '{}' (enums and interfaces aren't supported)",
+                ty
+            )
+        })
+    }
+
+    /// Decodes `data` as `abi.encode` of the struct `name`, one `path = value` line per field.
+    pub fn decode(&self, name: &str, data: &[u8]) -> Result<Vec<String>> {
+        let ty = self.resolve(name, 0)?;
+        let dyn_ty = ty.to_dyn();
+        // `abi.encode(value)` of a struct with dynamic fields starts with an offset; without
+        // any it's the same as the bare tuple
+        let value = dyn_ty
+            .abi_decode(data)
+            .or_else(|_| dyn_ty.abi_decode_params(data))
+            .map_err(|e| eyre!("Journal isn't an encoded {}: {}", name, e))?;
+        let mut lines = Vec::new();
+        render(&ty, &value, "", &mut lines);
+        Ok(lines)
+    }
+}
+
+fn render(ty: &AbiType, value: &DynSolValue, path: &str, lines: &mut Vec<String>) {
+    let child = |field: &str| {
+        if path.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}.{}", path, field)
+        }
+    };
+    match (ty, value) {
+        (AbiType::Struct(fields), DynSolValue::Tuple(values)) => {
+            for ((field, ty), value) in fields.iter().zip(values) {
+                render(ty, value, &child(field), lines);
+            }
+        }
+        (
+            AbiType::Array(inner, _),
+            DynSolValue::Array(values) | DynSolValue::FixedArray(values),
+        ) => {
+            if values.is_empty() {
+                lines.push(format!("{} = []", path));
+            }
+            for (i, value) in values.iter().enumerate() {
+                render(inner, value, &format!("{}[{}]", path, i), lines);
+            }
+        }
+        (_, value) => lines.push(format!("{} = {}", path, format_value(value))),
+    }
+}
+
+/// A value as it would be written in Solidity, e.g. a checksummed address or `[1, 2]`.
+pub fn format_value(value: &DynSolValue) -> String {
+    match value {
+        DynSolValue::Bool(b) => b.to_string(),
+        DynSolValue::Int(i, _) => i.to_string(),
+        DynSolValue::Uint(u, _) => u.to_string(),
+        DynSolValue::FixedBytes(word, size) => format!("0x{}", alloy::hex::encode(&word[..*size])),
+        DynSolValue::Address(address) => address.to_checksum(None),
+        DynSolValue::Bytes(bytes) => format!("0x{}", alloy::hex::encode(bytes)),
+        DynSolValue::String(s) => format!("{:?}", s),
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => format!(
+            "[{}]",
+            values
+                .iter()
+                .map(format_value)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        DynSolValue::Tuple(values) => format!(
+            "({})",
+            values
+                .iter()
+                .map(format_value)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        other => format!("{:?}", other),
+    }
+}
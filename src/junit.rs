@@ -0,0 +1,144 @@
+//! JUnit-style XML reports of test runs, written with `--junit` so CI dashboards can show each
+//! step as a test case.
+
+use crate::{command_error::CommandError, redact};
+use color_eyre::{Report, Result};
+use std::{fmt::Write as _, fs, path::Path, time::Duration};
+
+pub struct Case {
+    pub name: String,
+    pub duration: Duration,
+    /// What went wrong, first line first.
+    pub failure: Option<Vec<String>>,
+}
+
+/// The steps of one run, in the order they ran.
+pub struct Suite {
+    name: String,
+    timestamp: chrono::DateTime<chrono::Utc>,
+    cases: Vec<Case>,
+}
+
+impl Suite {
+    pub fn new(name: &str) -> Self {
+        Suite {
+            name: name.to_string(),
+            timestamp: chrono::Utc::now(),
+            cases: Vec::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn cases(&self) -> &[Case] {
+        &self.cases
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cases.is_empty()
+    }
+
+    pub fn pass(&mut self, name: &str, duration: Duration) {
+        self.cases.push(Case {
+            name: name.to_string(),
+            duration,
+            failure: None,
+        });
+    }
+
+    /// Records a failed step, with the command and its last output when a command failed.
+    pub fn fail(&mut self, name: &str, duration: Duration, error: &Report) {
+        let lines = match error.downcast_ref::<CommandError>() {
+            Some(error) => error.report(),
+            None => vec![error.to_string()],
+        };
+        self.cases.push(Case {
+            name: name.to_string(),
+            duration,
+            failure: Some(lines),
+        });
+    }
+
+    /// Records a step that failed without an error to go with it, e.g. a missing tool.
+    pub fn fail_with(&mut self, name: &str, duration: Duration, message: &str) {
+        self.cases.push(Case {
+            name: name.to_string(),
+            duration,
+            failure: Some(vec![message.to_string()]),
+        });
+    }
+
+    pub fn to_xml(&self) -> String {
+        let failures = self.cases.iter().filter(|c| c.failure.is_some()).count();
+        let time: f64 = self.cases.iter().map(|c| c.duration.as_secs_f64()).sum();
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"steel-tui\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+            self.cases.len(),
+            failures,
+            time
+        );
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\" time=\"{:.3}\" timestamp=\"{}\">",
+            escape(&self.name),
+            self.cases.len(),
+            failures,
+            time,
+            self.timestamp.format("%Y-%m-%dT%H:%M:%S")
+        );
+        for case in &self.cases {
+            let _ = write!(
+                xml,
+                "    <testcase name=\"{}\" classname=\"steel-tui.{}\" time=\"{:.3}\"",
+                escape(&case.name),
+                escape(&self.name),
+                case.duration.as_secs_f64()
+            );
+            match &case.failure {
+                None => xml.push_str("/>\n"),
+                Some(lines) => {
+                    let message = lines.first().map(String::as_str).unwrap_or_default();
+                    let _ = writeln!(
+                        xml,
+                        ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>",
+                        escape(&redact::redact(message)),
+                        escape(&redact::redact(&lines.join("\n")))
+                    );
+                }
+            }
+        }
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_xml())?;
+        Ok(())
+    }
+}
+
+/// Escapes text for an attribute or element, dropping control characters XML can't hold
+/// (command output often carries ANSI escapes).
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
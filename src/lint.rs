@@ -0,0 +1,89 @@
+//! Picks clippy's diagnostics out of `cargo clippy --message-format=json` so they can be listed
+//! by file and each one found again in the output pane.
+
+use serde_json::Value;
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Level {
+    fn parse(level: &str) -> Self {
+        match level {
+            "error" | "error: internal compiler error" => Level::Error,
+            "warning" => Level::Warning,
+            _ => Level::Note,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warning => "warning",
+            Level::Note => "note",
+        }
+    }
+}
+
+pub struct Diagnostic {
+    pub level: Level,
+    pub message: String,
+    /// e.g. `clippy::needless_return`.
+    pub code: Option<String>,
+    /// Where the primary span is, relative to the workspace.
+    pub file: Option<PathBuf>,
+    pub line: usize,
+    /// The diagnostic as cargo would have printed it.
+    pub rendered: String,
+}
+
+/// What a line of cargo's output turned out to be.
+pub enum Message {
+    Diagnostic(Diagnostic),
+    /// Another JSON message (artifacts, build script output); nothing to show.
+    Other,
+    /// Not JSON, e.g. `Checking foo` on stderr.
+    Text,
+}
+
+pub fn parse(line: &str) -> Message {
+    if !line.starts_with('{') {
+        return Message::Text;
+    }
+    let Ok(message) = serde_json::from_str::<Value>(line) else {
+        return Message::Text;
+    };
+    if message["reason"] != "compiler-message" {
+        return Message::Other;
+    }
+    let diagnostic = &message["message"];
+    let level = Level::parse(diagnostic["level"].as_str().unwrap_or_default());
+    let text = diagnostic["message"].as_str().unwrap_or_default();
+    // The closing "N warnings emitted" summary has no span; the list shows its own counts
+    let spans = diagnostic["spans"].as_array().cloned().unwrap_or_default();
+    if spans.is_empty() && (text.ends_with("emitted") || text.starts_with("aborting due to")) {
+        return Message::Other;
+    }
+    let primary = spans
+        .iter()
+        .find(|span| span["is_primary"] == true)
+        .or(spans.first());
+    Message::Diagnostic(Diagnostic {
+        level,
+        message: text.to_string(),
+        code: diagnostic["code"]["code"].as_str().map(str::to_string),
+        file: primary.and_then(|span| span["file_name"].as_str().map(PathBuf::from)),
+        line: primary
+            .and_then(|span| span["line_start"].as_u64())
+            .unwrap_or_default() as usize,
+        rendered: diagnostic["rendered"]
+            .as_str()
+            .unwrap_or(text)
+            .trim_end()
+            .to_string(),
+    })
+}
@@ -0,0 +1,55 @@
+//! Logging for common debugging situations, so the next test run can be made verbose in the
+//! right places with one key instead of a hand-written `RUST_LOG` filter.
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LogPreset {
+    SteelCommitment,
+    ProverVerbose,
+    RpcTracing,
+}
+
+impl LogPreset {
+    pub const ALL: &'static [LogPreset] = &[
+        LogPreset::SteelCommitment,
+        LogPreset::ProverVerbose,
+        LogPreset::RpcTracing,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LogPreset::SteelCommitment => "Steel commitment debugging",
+            LogPreset::ProverVerbose => "Prover verbose",
+            LogPreset::RpcTracing => "RPC tracing",
+        }
+    }
+
+    /// What it shows, for the toolbox.
+    pub fn description(self) -> &'static str {
+        match self {
+            LogPreset::SteelCommitment => {
+                "Steel's preflight, the block it commits to and the commitment the contract checks"
+            }
+            LogPreset::ProverVerbose => "Segments, cycle counts and proving times, with backtraces",
+            LogPreset::RpcTracing => "Every JSON-RPC request the host makes and its response",
+        }
+    }
+
+    /// Environment for the test script; `RUST_LOG` replaces the one from Settings.
+    pub fn vars(self) -> Vec<(&'static str, &'static str)> {
+        match self {
+            LogPreset::SteelCommitment => vec![(
+                "RUST_LOG",
+                "info,risc0_steel=trace,risc0_ethereum_contracts=debug",
+            )],
+            LogPreset::ProverVerbose => vec![
+                ("RUST_LOG", "info,risc0_zkvm=debug,risc0_steel=debug"),
+                ("RISC0_INFO", "1"),
+                ("RUST_BACKTRACE", "1"),
+            ],
+            LogPreset::RpcTracing => vec![(
+                "RUST_LOG",
+                "info,risc0_steel=debug,alloy_rpc_client=debug,alloy_transport_http=trace",
+            )],
+        }
+    }
+}
@@ -1,35 +1,379 @@
-use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-    ExecutableCommand,
-};
+use clap::{Parser, Subcommand};
 use ratatui::prelude::*;
-use std::io::stdout;
+use std::{io::stdout, path::PathBuf, process::ExitCode};
 
 pub use app::App;
+use batch::Batch;
+use color_eyre::eyre::eyre;
+use exit_code::Outcome;
+use output::Verbosity;
+use terminal_guard::TerminalGuard;
+use timeouts::Timeouts;
 
+pub mod abi;
+pub mod address_book;
+pub mod advice;
 pub mod app;
+pub mod apple_silicon;
+pub mod artifacts;
+pub mod batch;
+pub mod benchmark;
+pub mod bonsai;
+pub mod boundless;
+pub mod broadcast;
+pub mod cargo_config;
+pub mod cargo_generate;
+pub mod chain;
+pub mod chain_presets;
+pub mod chain_state;
+pub mod clean;
+pub mod command_error;
+pub mod compat;
+pub mod contract_checks;
+pub mod crash;
+pub mod credentials;
+pub mod demo;
+pub mod dev_keys;
+pub mod docs;
+pub mod drift;
+pub mod events;
+pub mod exit_code;
+pub mod formatting;
+pub mod guest_build;
+pub mod guest_image;
+pub mod health;
+pub mod history;
+pub mod http;
+pub mod journal;
+pub mod junit;
+pub mod lint;
+pub mod log_presets;
+pub mod manifest;
+pub mod monorepo;
+pub mod network;
+pub mod networks;
+pub mod nix;
+pub mod output;
+pub mod palette;
+pub mod paths;
+pub mod platform;
+pub mod project_file;
+pub mod projects;
+pub mod prover;
+pub mod queue;
+pub mod receipt;
+pub mod redact;
+pub mod replay;
+pub mod resources;
+pub mod rpc_presets;
+pub mod scaffold;
+pub mod scripts;
+pub mod self_update;
+pub mod session;
+pub mod settings;
+pub mod shutdown;
+pub mod signals;
+pub mod signer;
+pub mod solc;
+pub mod step_logs;
+pub mod summary;
+pub mod supervisor;
+pub mod tasks;
+pub mod template_cache;
+pub mod templates;
+pub mod terminal_guard;
+pub mod test_wallet;
+pub mod timeouts;
+pub mod tool_versions;
+pub mod tools;
+pub mod transaction;
+pub mod transfer;
+pub mod tutorial;
+pub mod upgrade;
+pub mod verify;
+pub mod watch;
+pub mod workspace;
 
-fn main() -> color_eyre::Result<()> {
-    color_eyre::install()?;
-    
-    // Setup terminal
-    enable_raw_mode()?;
-    stdout()
-        .execute(EnterAlternateScreen)?
-        .execute(EnableMouseCapture)?;
-    
+/// Scaffold and test RISC Zero Steel applications.
+#[derive(Parser)]
+#[command(name = "steel-tui", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
+    /// Write a JSON summary of the created project to PATH (or stdout if omitted)
+    #[arg(
+        long,
+        value_name = "PATH",
+        num_args = 0..=1,
+        default_missing_value = "-"
+    )]
+    output_json: Option<PathBuf>,
+
+    /// Only show step results, hiding child process output
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Show more child process output (-v for full cargo output, -vv for git progress too)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Run without prompts, colors, or the alternate screen (implied by CI=true)
+    #[arg(long, global = true)]
+    ci: bool,
+
+    /// Write a JUnit XML report of each test run (and of a --ci install) to PATH
+    #[arg(long, value_name = "PATH", env = "STEEL_TUI_JUNIT")]
+    junit: Option<PathBuf>,
+
+    /// Render for recordings: a capped frame rate, a steady cursor and still spinners
+    #[arg(long)]
+    demo: bool,
+
+    /// In --demo, hold each new screen for this many milliseconds
+    #[arg(long, value_name = "MS", requires = "demo")]
+    demo_step_delay: Option<u64>,
+
+    /// Play back the key presses in a script at PATH, as written by --record
+    #[arg(long, value_name = "PATH")]
+    replay: Option<PathBuf>,
+
+    /// Write the keys pressed, with the pauses between them, to a script at PATH
+    #[arg(long, value_name = "PATH")]
+    record: Option<PathBuf>,
+
+    /// Name of the project directory to create
+    #[arg(long, value_name = "NAME")]
+    name: Option<String>,
+
+    /// Create the project from a cargo-generate template: a git URL or a local directory
+    #[arg(long, value_name = "SOURCE", conflicts_with = "batch")]
+    template: Option<String>,
+
+    /// Create every project listed in the manifest at PATH, one after another
+    #[arg(long, value_name = "PATH", conflicts_with = "name")]
+    batch: Option<PathBuf>,
+
+    /// Add the project to the git repository around the working directory, e.g. as a package of
+    /// a monorepo, instead of making it a repository of its own. --name may then be a path
+    #[arg(long)]
+    in_repo: bool,
+
+    /// Replace the project directory if it already exists
+    #[arg(long)]
+    overwrite: bool,
+
+    /// Warn that a step appears stuck after this many seconds without output
+    #[arg(long, value_name = "SECS")]
+    stuck_after: Option<u64>,
+
+    /// Hard time limit for a program, e.g. `--timeout git=1200` (repeatable)
+    #[arg(long, value_name = "PROGRAM=SECS")]
+    timeout: Vec<String>,
+
+    /// Maximum number of independent install commands to run at once
+    #[arg(long, value_name = "N", default_value_t = 3)]
+    jobs: usize,
+
+    /// Work without the network: clone from the template cache and skip online features.
+    /// Turned on automatically when GitHub can't be reached
+    #[arg(long, env = "STEEL_TUI_OFFLINE")]
+    offline: bool,
+
+    /// Don't check for a newer steel-tui release on startup
+    #[arg(long, env = "STEEL_TUI_NO_UPDATE_CHECK")]
+    no_update_check: bool,
+}
+
+impl Cli {
+    fn ci(&self) -> bool {
+        self.ci || std::env::var("CI").is_ok_and(|v| v.eq_ignore_ascii_case("true") || v == "1")
+    }
+
+    /// `--offline`, or the network turned out to be unreachable.
+    fn offline(&self) -> bool {
+        if self.offline {
+            return true;
+        }
+        let reachable = network::is_reachable();
+        if !reachable {
+            eprintln!("Network unreachable; running in offline mode.");
+        }
+        !reachable
+    }
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Terminate leftover anvil processes and remove temporary state
+    Clean {
+        /// Also clear the template cache
+        #[arg(long)]
+        cache: bool,
+    },
+    /// Migrate an existing project to a newer risc0-ethereum release
+    Upgrade {
+        /// Path to the project
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Release branch to migrate to (defaults to the latest release)
+        #[arg(long, value_name = "BRANCH")]
+        to: Option<String>,
+        /// Apply the changes without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Download and install the latest steel-tui release
+    SelfUpdate,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    if let Err(e) = shutdown::install_hooks(cli.ci()) {
+        eprintln!("Error: {:?}", e);
+        return Outcome::InternalError.into();
+    }
+
+    let settings = match settings::Settings::load() {
+        Ok(settings) => settings,
+        Err(e) => {
+            eprintln!("Warning: {}; using defaults", e);
+            settings::Settings::default()
+        }
+    };
+    settings.apply();
+
+    let result = match &cli.command {
+        Some(Commands::Clean { cache }) => clean::run(*cache).map(|_| Outcome::Success),
+        Some(Commands::Upgrade { path, to, yes }) => upgrade::run(path, to.clone(), *yes, cli.ci()),
+        Some(Commands::SelfUpdate) => self_update::run().map(|_| Outcome::Success),
+        None if cli.ci() => run_ci(&cli, settings),
+        None => run_tui(&cli, settings),
+    };
+
+    match result {
+        Ok(outcome) => outcome.into(),
+        Err(e) => {
+            shutdown::report_error(&e, cli.command.is_none());
+            Outcome::InternalError.into()
+        }
+    }
+}
+
+fn new_app(cli: &Cli, settings: settings::Settings) -> color_eyre::Result<App> {
+    let mut timeouts = Timeouts::default();
+    if let Some(secs) = cli.stuck_after {
+        timeouts.stuck_after = std::time::Duration::from_secs(secs);
+    }
+    for spec in &cli.timeout {
+        timeouts.set_override(spec).map_err(|e| eyre!(e))?;
+    }
+
+    let enclosing_repo = std::env::current_dir()
+        .ok()
+        .and_then(|dir| monorepo::enclosing_repo(&dir));
+    if cli.in_repo && enclosing_repo.is_none() {
+        return Err(eyre!(
+            "--in-repo: the working directory isn't inside a git repository"
+        ));
+    }
+
+    let batch = match &cli.batch {
+        Some(path) => Some(Batch::load(path, &std::env::current_dir()?)?),
+        None => None,
+    };
+
+    Ok(App::new()
+        .with_batch(batch)
+        .with_cargo_template(cli.template.clone())
+        .with_verbosity(Verbosity::from_flags(cli.quiet, cli.verbose))
+        .with_project_name(cli.name.clone())
+        .with_overwrite(cli.overwrite)
+        .with_enclosing_repo(enclosing_repo, cli.in_repo)
+        .with_timeouts(timeouts)
+        .with_parallelism(cli.jobs)
+        .with_junit(cli.junit.clone())
+        .with_settings(settings))
+}
+
+fn run_ci(cli: &Cli, settings: settings::Settings) -> color_eyre::Result<Outcome> {
+    signals::install()?;
+    let offline = cli.offline();
+    let mut app = new_app(cli, settings)?.with_ci(true).with_offline(offline);
+    let result = app.run_headless();
+    supervisor::terminate_all();
+
+    if let (Some(path), Some(summary)) = (&cli.output_json, app.summary()) {
+        summary.write(path)?;
+    }
+    result
+}
+
+fn run_tui(cli: &Cli, settings: settings::Settings) -> color_eyre::Result<Outcome> {
+    signals::install()?;
+    let offline = cli.offline();
+    let replay = cli
+        .replay
+        .as_deref()
+        .map(replay::Script::load)
+        .transpose()?;
+    let recorder = cli
+        .record
+        .as_deref()
+        .map(replay::Recorder::create)
+        .transpose()?;
+
+    // Setup terminal; restored when the guard drops, on every path out of here
+    let guard = TerminalGuard::enter()?;
     let backend = CrosstermBackend::new(stdout());
     let mut terminal = Terminal::new(backend)?;
 
     // Create and run app
-    let result = App::new().run(&mut terminal);
-
-    // Restore terminal
-    disable_raw_mode()?;
-    stdout()
-        .execute(LeaveAlternateScreen)?
-        .execute(DisableMouseCapture)?;
-    
+    // `--name` asks for a new project, so only look for an existing one without it
+    let existing = match (&cli.name, &cli.batch, std::env::current_dir()) {
+        (None, None, Ok(dir)) => projects::detect(&dir),
+        _ => None,
+    };
+    // A scripted run starts the same way every time
+    let previous = match (&existing, &replay) {
+        (None, None) if cli.name.is_none() && cli.batch.is_none() => session::load(),
+        _ => None,
+    };
+    let mut app = new_app(cli, settings)?
+        .with_offline(offline)
+        .with_update_check(!cli.no_update_check)
+        .with_existing_project(existing)
+        .with_demo(cli.demo.then(|| {
+            demo::Demo::new(std::time::Duration::from_millis(
+                cli.demo_step_delay.unwrap_or_default(),
+            ))
+        }))
+        .with_replay(replay)
+        .with_recorder(recorder)
+        .with_previous_session(previous);
+    let result = app.run(&mut terminal);
+    let stopped = supervisor::terminate_all();
+    if let Some(session) = app.session() {
+        let _ = session::save(&session);
+    }
+
+    // Restore terminal before printing anything
+    drop(guard);
+
+    if let Ok(Outcome::InstallFailed) = result {
+        eprintln!("{}", app.status_message());
+    }
+    if signals::interrupted() {
+        for line in app.interrupted_report() {
+            eprintln!("{}", line);
+        }
+        if stopped > 0 {
+            eprintln!("Stopped {} child process(es).", stopped);
+        }
+    }
+    if let (Some(path), Some(summary)) = (&cli.output_json, app.summary()) {
+        summary.write(path)?;
+    }
     result
 }
@@ -0,0 +1,100 @@
+use crate::http;
+use color_eyre::{eyre::eyre, Result};
+use regex::Regex;
+use std::{fs, path::Path, path::PathBuf, sync::OnceLock};
+
+pub const RISC0_ETHEREUM_REPO: &str = "https://github.com/risc0/risc0-ethereum";
+
+/// Whether `url` is risc0-ethereum's own repository, with or without `.git`.
+pub fn is_risc0_ethereum(url: &str) -> bool {
+    url.trim_end_matches('/').trim_end_matches(".git") == RISC0_ETHEREUM_REPO
+}
+
+/// Directories that never contain manifests we own.
+const SKIPPED_DIRS: &[&str] = &["target", "lib", ".git"];
+
+/// Recursively collects every `Cargo.toml` below `dir`, skipping build output and submodules.
+pub fn find_cargo_toml_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut cargo_files = Vec::new();
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_dir() {
+            let skipped = path
+                .file_name()
+                .is_some_and(|name| SKIPPED_DIRS.iter().any(|s| name == *s));
+            if !skipped {
+                cargo_files.extend(find_cargo_toml_files(&path)?);
+            }
+        } else if path.file_name().is_some_and(|name| name == "Cargo.toml") {
+            cargo_files.push(path);
+        }
+    }
+
+    Ok(cargo_files)
+}
+
+/// Matches a risc0-ethereum git dependency pinned with `branch = "…"` or `tag = "…"`.
+fn pin_regex() -> &'static Regex {
+    static PIN: OnceLock<Regex> = OnceLock::new();
+    PIN.get_or_init(|| {
+        Regex::new(r#"(git\s*=\s*"https://github\.com/risc0/risc0-ethereum(?:\.git)?"\s*,\s*)(branch|tag)\s*=\s*"([^"]+)""#)
+            .unwrap()
+    })
+}
+
+/// Returns the risc0-ethereum branch a manifest's git dependencies are pinned to. A `vX.Y.Z`
+/// tag is reported as the `release-X.Y` branch it was cut from.
+pub fn detect_branch(content: &str) -> Option<String> {
+    let caps = pin_regex().captures(content)?;
+    if &caps[2] == "branch" {
+        return Some(caps[3].to_string());
+    }
+    tag_version(&caps[3]).map(|(major, minor, _)| format!("release-{}.{}", major, minor))
+}
+
+/// Re-points every risc0-ethereum git dependency at `branch`, replacing tag pins.
+pub fn rewrite_branch(content: &str, branch: &str) -> String {
+    pin_regex()
+        .replace_all(content, |caps: &regex::Captures| {
+            format!("{}branch = \"{}\"", &caps[1], branch)
+        })
+        .to_string()
+}
+
+/// Parses `release-X.Y` into a sortable version tuple.
+pub fn release_version(branch: &str) -> Option<(u64, u64)> {
+    let (major, minor) = branch.strip_prefix("release-")?.split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+/// Parses a `vX.Y.Z` tag; pre-release tags aren't offered and give `None`.
+pub fn tag_version(tag: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = tag
+        .strip_prefix('v')?
+        .split('.')
+        .map(|part| part.parse::<u64>().ok());
+    let version = (parts.next()??, parts.next()??, parts.next()??);
+    parts.next().is_none().then_some(version)
+}
+
+/// Release tags published in risc0-ethereum, newest first.
+pub fn release_tags() -> Result<Vec<String>> {
+    let output = http::git()
+        .args(["ls-remote", "--tags", RISC0_ETHEREUM_REPO, "v*"])
+        .output()?;
+    if !output.status.success() {
+        return Err(eyre!("Failed to list risc0-ethereum tags"));
+    }
+
+    // Annotated tags are listed twice, the second time as `vX.Y.Z^{}`, which doesn't parse
+    let mut tags: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.rsplit_once("refs/tags/").map(|(_, t)| t.to_string()))
+        .filter(|tag| tag_version(tag).is_some())
+        .collect();
+    tags.sort_by_key(|tag| std::cmp::Reverse(tag_version(tag)));
+    Ok(tags)
+}
@@ -0,0 +1,97 @@
+//! Scaffolding into a subdirectory of an existing git repository, e.g. as a package of a
+//! monorepo, rather than as a repository of its own. The Forge dependencies become submodules of
+//! the enclosing repository under the project's own `lib/`, so the template's remappings and
+//! foundry paths, which are relative to the project, stay as they are.
+
+use crate::{http, platform};
+use color_eyre::Result;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The top of the git repository `dir` is in, if any.
+pub fn enclosing_repo(dir: &Path) -> Option<PathBuf> {
+    let output = http::git()
+        .args(["rev-parse", "--show-toplevel"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    platform::canonicalize(Path::new(String::from_utf8_lossy(&output.stdout).trim())).ok()
+}
+
+/// `project` relative to `base`, with `/` separators as Cargo and git expect.
+pub fn relative(base: &Path, project: &Path) -> Option<String> {
+    let relative = project.strip_prefix(base).ok()?;
+    Some(
+        relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+    )
+}
+
+/// Keeps a Cargo workspace of the enclosing repository from claiming the project, which is a
+/// workspace of its own, by adding it to the workspace's `exclude`. Returns the manifest changed.
+pub fn exclude_from_workspace(repo: &Path, project: &Path) -> Result<Option<PathBuf>> {
+    let project = platform::canonicalize(project)?;
+    let Some(manifest) = project
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(repo))
+        .map(|dir| dir.join("Cargo.toml"))
+        .find(|manifest| fs::read_to_string(manifest).is_ok_and(|content| has_workspace(&content)))
+    else {
+        return Ok(None);
+    };
+    let Some(path) = manifest
+        .parent()
+        .and_then(|workspace| relative(workspace, &project))
+    else {
+        return Ok(None);
+    };
+    let content = fs::read_to_string(&manifest)?;
+    let quoted = format!("\"{}\"", path);
+    if content.contains(&quoted) {
+        return Ok(None);
+    }
+
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+    let start = lines
+        .iter()
+        .position(|line| line.trim() == "[workspace]")
+        .unwrap_or_default();
+    let end = lines
+        .iter()
+        .skip(start + 1)
+        .position(|line| line.trim_start().starts_with('['))
+        .map_or(lines.len(), |i| start + 1 + i);
+    match (start + 1..end).find(|&i| lines[i].trim_start().starts_with("exclude")) {
+        Some(i) => match lines[i].find('[') {
+            // `exclude = [` opens the list; the new entry goes first
+            Some(bracket) => {
+                let (head, tail) = lines[i].split_at(bracket + 1);
+                let separator = if tail.trim_start().starts_with(']') {
+                    ""
+                } else if tail.trim().is_empty() {
+                    ","
+                } else {
+                    ", "
+                };
+                lines[i] = format!("{}{}{}{}", head, quoted, separator, tail);
+            }
+            None => return Ok(None),
+        },
+        None => lines.insert(start + 1, format!("exclude = [{}]", quoted)),
+    }
+    fs::write(&manifest, lines.join("\n") + "\n")?;
+    Ok(Some(manifest))
+}
+
+fn has_workspace(content: &str) -> bool {
+    content.lines().any(|line| line.trim() == "[workspace]")
+}
@@ -0,0 +1,127 @@
+//! The networks a project is tested and deployed on, e.g. local anvil, Sepolia and Base Sepolia
+//! at once, each with its own RPC, wallet and verifier, in `networks.toml` at the project root.
+//! The test and deploy screens start by picking one.
+
+use crate::{chain_presets::ChainPreset, dev_keys, signer::SignerKind};
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+pub const FILE_NAME: &str = "networks.toml";
+
+const HEADER: &str = "\
+# Networks steel-tui offers when testing and deploying this project.
+#
+# chain            anvil, sepolia, holesky, base-sepolia, op-sepolia or mainnet; the defaults
+#                  for the fields left out
+# rpc_url          where to connect; left out, the RPC provider from Settings, else the
+#                  chain's public endpoint
+# wallet           dev (anvil's first account), env:VAR (a private key in an environment
+#                  variable), keystore:PATH, cast:ACCOUNT, ledger or trezor. Never a key itself.
+# verifier_router  RISC Zero's verifier router; left out, the chain's
+
+";
+
+#[derive(Serialize, Deserialize)]
+struct File {
+    #[serde(default, rename = "network")]
+    networks: Vec<Network>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Network {
+    pub name: String,
+    #[serde(default)]
+    pub chain: ChainPreset,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verifier_router: Option<String>,
+}
+
+impl Network {
+    fn preset(name: &str, chain: ChainPreset, wallet: Option<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            chain,
+            rpc_url: None,
+            wallet: wallet.map(str::to_string),
+            verifier_router: None,
+        }
+    }
+
+    /// The verifier router configured for the network, else the chain's.
+    pub fn verifier_router(&self) -> Option<String> {
+        self.verifier_router
+            .clone()
+            .or_else(|| self.chain.verifier_router().map(str::to_string))
+    }
+
+    /// The wallet as a signer kind and the detail that kind needs, with `env:` keys read from
+    /// the environment.
+    pub fn wallet(&self) -> Result<Option<(SignerKind, String)>> {
+        let Some(wallet) = self.wallet.as_deref().map(str::trim) else {
+            return Ok(None);
+        };
+        let (kind, detail) = wallet.split_once(':').unwrap_or((wallet, ""));
+        let detail = detail.trim().to_string();
+        Ok(Some(match kind {
+            "dev" => (
+                SignerKind::PrivateKey,
+                dev_keys::ANVIL_PRIVATE_KEYS[0].to_string(),
+            ),
+            "env" => {
+                let key = std::env::var(&detail).map_err(|_| {
+                    eyre!("{}'s wallet is in {}, which is not set", self.name, detail)
+                })?;
+                (SignerKind::PrivateKey, key)
+            }
+            "keystore" => (SignerKind::Keystore, detail),
+            "cast" => (SignerKind::CastAccount, detail),
+            "ledger" => (SignerKind::Ledger, detail),
+            "trezor" => (SignerKind::Trezor, detail),
+            other => {
+                return Err(eyre!(
+                    "{}'s wallet '{}' isn't one of dev, env:, keystore:, cast:, ledger or trezor",
+                    self.name,
+                    other
+                ))
+            }
+        }))
+    }
+}
+
+/// Local anvil, Sepolia and Base Sepolia, for projects without a `networks.toml`.
+pub fn defaults() -> Vec<Network> {
+    vec![
+        Network::preset("local", ChainPreset::Anvil, Some("dev")),
+        Network::preset("sepolia", ChainPreset::Sepolia, None),
+        Network::preset("base-sepolia", ChainPreset::BaseSepolia, None),
+    ]
+}
+
+/// The project's networks, or the defaults if it doesn't define any.
+pub fn load(root: &Path) -> Result<Vec<Network>> {
+    let Ok(content) = fs::read_to_string(root.join(FILE_NAME)) else {
+        return Ok(defaults());
+    };
+    let file: File = toml::from_str(&content).map_err(|e| eyre!("Invalid {}: {}", FILE_NAME, e))?;
+    if file.networks.is_empty() {
+        return Err(eyre!("{} defines no [[network]]", FILE_NAME));
+    }
+    Ok(file.networks)
+}
+
+/// Writes the defaults into a new project, commented, to edit from there.
+pub fn write_defaults(root: &Path) -> Result<()> {
+    let file = File {
+        networks: defaults(),
+    };
+    fs::write(
+        root.join(FILE_NAME),
+        format!("{}{}", HEADER, toml::to_string(&file)?),
+    )?;
+    Ok(())
+}
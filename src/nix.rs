@@ -0,0 +1,126 @@
+//! `flake.nix` for a generated project: a dev shell with pinned Rust, Foundry and the
+//! cargo-risczero version its risc0-ethereum release builds with.
+
+use crate::{compat, projects, tools};
+use color_eyre::{eyre::eyre, Result};
+use std::{fs, path::Path};
+
+pub const FILE_NAME: &str = "flake.nix";
+
+/// Where the shell installs cargo-risczero, which nixpkgs doesn't package.
+const TOOLS_DIR: &str = ".nix-tools";
+
+const FLAKE: &str = r#"{
+  description = "Development shell for __NAME__";
+
+  inputs = {
+    nixpkgs.url = "github:NixOS/nixpkgs/nixos-unstable";
+    flake-utils.url = "github:numtide/flake-utils";
+    rust-overlay = {
+      url = "github:oxalica/rust-overlay";
+      inputs.nixpkgs.follows = "nixpkgs";
+    };
+    foundry = {
+      url = "github:shazow/foundry.nix/monthly";
+      inputs.nixpkgs.follows = "nixpkgs";
+    };
+  };
+
+  outputs = { nixpkgs, flake-utils, rust-overlay, foundry, ... }:
+    flake-utils.lib.eachDefaultSystem (system:
+      let
+        pkgs = import nixpkgs {
+          inherit system;
+          overlays = [ (import rust-overlay) foundry.overlay ];
+        };
+        rust = __RUST__;
+      in {
+        devShells.default = pkgs.mkShell {
+          packages = [ rust pkgs.foundry-bin pkgs.git pkgs.pkg-config pkgs.openssl ];
+          shellHook = ''
+            # cargo-risczero isn't in nixpkgs; keep the version this project needs in the project
+            export CARGO_INSTALL_ROOT="$PWD/__TOOLS_DIR__"
+            export PATH="$CARGO_INSTALL_ROOT/bin:$PATH"
+            if ! cargo risczero --version 2>/dev/null | grep -q " __TOOLCHAIN__\."; then
+              cargo install --locked cargo-risczero --version "~__TOOLCHAIN__"
+            fi
+          '';
+        };
+      });
+}
+"#;
+
+/// Writes the flake into the project at `root`, refusing to replace one that's there.
+/// Returns what it pinned, one line each.
+pub fn generate(root: &Path, name: &str) -> Result<Vec<String>> {
+    let file = root.join(FILE_NAME);
+    if file.exists() {
+        return Err(eyre!(
+            "{} already exists; remove it to generate a new one",
+            FILE_NAME
+        ));
+    }
+
+    let (rust, rust_pin) = if root.join("rust-toolchain.toml").exists() {
+        (
+            String::from("pkgs.rust-bin.fromRustupToolchainFile ./rust-toolchain.toml"),
+            String::from("Rust from rust-toolchain.toml"),
+        )
+    } else {
+        // Pin whatever builds the project today
+        let version = tools::version("rustc", &["--version"])
+            .ok()
+            .and_then(|v| v.split_whitespace().nth(1).map(str::to_string))
+            .ok_or_else(|| eyre!("Could not tell which Rust version to pin"))?;
+        (
+            format!("pkgs.rust-bin.stable.\"{}\".default", version),
+            format!("Rust {}", version),
+        )
+    };
+
+    let branch = projects::pinned_branch(root)
+        .ok_or_else(|| eyre!("Could not tell which risc0-ethereum release the project uses"))?;
+    let (major, minor) = compat::release(&branch)
+        .map(|release| release.toolchain)
+        .ok_or_else(|| eyre!("No known RISC Zero toolchain for {}", branch))?;
+    let toolchain = format!("{}.{}", major, minor);
+
+    let content = FLAKE
+        .replace("__NAME__", &name.replace(['"', '\\'], ""))
+        .replace("__RUST__", &rust)
+        .replace("__TOOLS_DIR__", TOOLS_DIR)
+        .replace("__TOOLCHAIN__", &toolchain);
+    fs::write(&file, content)?;
+
+    let mut report = vec![
+        format!("Wrote {}", FILE_NAME),
+        format!("  {}", rust_pin),
+        String::from("  Foundry from foundry.nix, pinned by flake.lock"),
+        format!("  cargo-risczero ~{} for {}", toolchain, branch),
+    ];
+
+    // The installed tools are per machine
+    let gitignore = root.join(".gitignore");
+    let ignored = fs::read_to_string(&gitignore).unwrap_or_default();
+    if !ignored
+        .lines()
+        .any(|line| line.trim_end_matches('/') == TOOLS_DIR)
+    {
+        let separator = if ignored.is_empty() || ignored.ends_with('\n') {
+            ""
+        } else {
+            "\n"
+        };
+        fs::write(
+            &gitignore,
+            format!("{}{}{}/\n", ignored, separator, TOOLS_DIR),
+        )?;
+        report.push(format!("Added {}/ to .gitignore", TOOLS_DIR));
+    }
+    // Flakes only see files git knows about
+    report.push(format!(
+        "Run `git add {}` and then `nix develop` to enter the shell",
+        FILE_NAME
+    ));
+    Ok(report)
+}
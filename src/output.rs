@@ -0,0 +1,169 @@
+use chrono::{DateTime, Local};
+use std::{
+    borrow::Cow,
+    time::{Duration, Instant},
+};
+
+/// How much captured output is shown in the output pane.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Only step results reported by the app itself.
+    Quiet,
+    /// Child process output, minus build and transfer progress chatter.
+    #[default]
+    Normal,
+    /// Adds full cargo output (`Compiling`, `Downloading`, ...).
+    Verbose,
+    /// Everything, including git's transfer progress.
+    VeryVerbose,
+}
+
+impl Verbosity {
+    /// Maps `-q` / `-v` / `-vv` to a level. `-q` wins over any number of `-v`.
+    pub fn from_flags(quiet: bool, verbose: u8) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Verbosity::Quiet,
+            (false, 0) => Verbosity::Normal,
+            (false, 1) => Verbosity::Verbose,
+            (false, _) => Verbosity::VeryVerbose,
+        }
+    }
+
+    /// Cycles to the next level, wrapping around. Used by the runtime toggle.
+    pub fn next(self) -> Self {
+        match self {
+            Verbosity::Quiet => Verbosity::Normal,
+            Verbosity::Normal => Verbosity::Verbose,
+            Verbosity::Verbose => Verbosity::VeryVerbose,
+            Verbosity::VeryVerbose => Verbosity::Quiet,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Verbosity::Quiet => "quiet",
+            Verbosity::Normal => "normal",
+            Verbosity::Verbose => "verbose",
+            Verbosity::VeryVerbose => "very verbose",
+        }
+    }
+}
+
+/// What, if anything, goes in front of each line in the output pane.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Timestamps {
+    #[default]
+    Off,
+    /// Time since the step that printed the line started, e.g. `[+12.4s]`.
+    Relative,
+    /// Local time, e.g. `[14:03:27.512]`, to line up with anvil's log or Bonsai's sessions.
+    WallClock,
+}
+
+impl Timestamps {
+    /// Cycles to the next mode, wrapping around. Used by the runtime toggle.
+    pub fn next(self) -> Self {
+        match self {
+            Timestamps::Off => Timestamps::Relative,
+            Timestamps::Relative => Timestamps::WallClock,
+            Timestamps::WallClock => Timestamps::Off,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Timestamps::Off => "no times",
+            Timestamps::Relative => "step times",
+            Timestamps::WallClock => "clock times",
+        }
+    }
+}
+
+/// A captured line together with the lowest verbosity at which it is shown.
+pub struct OutputLine {
+    pub text: String,
+    pub level: Verbosity,
+    /// When it was captured and how far into its step; unknown for lines restored from an
+    /// earlier session.
+    pub at: Option<(DateTime<Local>, Duration)>,
+}
+
+impl OutputLine {
+    pub fn new(text: String, level: Verbosity, step_started: Instant) -> Self {
+        Self {
+            text,
+            level,
+            at: Some((Local::now(), step_started.elapsed())),
+        }
+    }
+
+    pub fn restored(text: String) -> Self {
+        Self {
+            text,
+            level: Verbosity::Quiet,
+            at: None,
+        }
+    }
+
+    /// The line as the output pane shows it, timestamped as `timestamps` asks.
+    pub fn display(&self, timestamps: Timestamps) -> Cow<'_, str> {
+        match (timestamps, self.at) {
+            (Timestamps::Off, _) | (_, None) => Cow::Borrowed(&self.text),
+            (Timestamps::Relative, Some((_, since))) => {
+                Cow::Owned(format!("[+{:.1}s] {}", since.as_secs_f64(), self.text))
+            }
+            (Timestamps::WallClock, Some((at, _))) => {
+                Cow::Owned(format!("[{}] {}", at.format("%H:%M:%S%.3f"), self.text))
+            }
+        }
+    }
+}
+
+/// Classifies a line of child process output.
+pub fn classify(line: &str) -> Verbosity {
+    const GIT_PROGRESS: &[&str] = &[
+        "remote: ",
+        "Receiving objects",
+        "Resolving deltas",
+        "Counting objects",
+        "Compressing objects",
+        "Enumerating objects",
+        "Unpacking objects",
+        "Updating files",
+    ];
+    const CARGO_PROGRESS: &[&str] = &[
+        "Compiling ",
+        "Checking ",
+        "Downloading ",
+        "Downloaded ",
+        "Fresh ",
+        "Updating ",
+        "Locking ",
+        "Adding ",
+    ];
+
+    let trimmed = line.trim_start();
+    // Concurrent tasks prefix their output with the task's label
+    let trimmed = match trimmed
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once("] "))
+    {
+        Some((label, rest)) if !label.contains(' ') => rest.trim_start(),
+        _ => trimmed,
+    };
+    // Guest builds prefix cargo's output with the package name
+    let unprefixed = match trimmed.split_once(": ") {
+        Some((package, rest)) if !package.contains(' ') => rest.trim_start(),
+        _ => trimmed,
+    };
+    if GIT_PROGRESS.iter().any(|p| trimmed.starts_with(p)) {
+        Verbosity::VeryVerbose
+    } else if CARGO_PROGRESS
+        .iter()
+        .any(|p| trimmed.starts_with(p) || unprefixed.starts_with(p))
+    {
+        Verbosity::Verbose
+    } else {
+        Verbosity::Normal
+    }
+}
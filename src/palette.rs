@@ -0,0 +1,122 @@
+//! The Ctrl+P command palette: every action the app offers in one list, narrowed down by typing
+//! a few letters of its name, so nothing is more than a couple of keys away.
+
+use crate::app::ToolboxItem;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    NewProject,
+    Tool(ToolboxItem),
+    StartAnvil,
+    /// Writes the output pane to a file.
+    ExportOutput,
+}
+
+impl Action {
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::NewProject => "✨ Create a new project",
+            Action::Tool(item) => item.label(),
+            Action::StartAnvil => "⛓️ Start anvil",
+            Action::ExportOutput => "💾 Export the output log",
+        }
+    }
+
+    /// Whether it works on the open project, and so isn't offered before there is one.
+    pub fn needs_project(self) -> bool {
+        match self {
+            Action::Tool(item) => !matches!(
+                item,
+                ToolboxItem::Projects
+                    | ToolboxItem::Credentials
+                    | ToolboxItem::TestWallet
+                    | ToolboxItem::Settings
+                    | ToolboxItem::SelfUpdate
+                    | ToolboxItem::Exit
+            ),
+            Action::StartAnvil => true,
+            Action::NewProject | Action::ExportOutput => false,
+        }
+    }
+}
+
+pub struct Palette {
+    pub query: String,
+    /// Index into [`Palette::matches`].
+    pub selected: usize,
+    actions: Vec<Action>,
+}
+
+impl Palette {
+    pub fn new(project_open: bool) -> Self {
+        let actions = std::iter::once(Action::NewProject)
+            .chain(ToolboxItem::ALL.iter().map(|item| Action::Tool(*item)))
+            .chain([Action::StartAnvil, Action::ExportOutput])
+            .filter(|action| project_open || !action.needs_project())
+            .collect();
+        Self {
+            query: String::new(),
+            selected: 0,
+            actions,
+        }
+    }
+
+    /// The actions the query matches, closest first.
+    pub fn matches(&self) -> Vec<Action> {
+        let mut scored: Vec<(usize, Action)> = self
+            .actions
+            .iter()
+            .filter_map(|action| score(&self.query, action.label()).map(|s| (s, *action)))
+            .collect();
+        scored.sort_by_key(|(score, _)| *score);
+        scored.into_iter().map(|(_, action)| action).collect()
+    }
+
+    pub fn chosen(&self) -> Option<Action> {
+        self.matches().get(self.selected).copied()
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.query.push(c);
+        self.selected = 0;
+    }
+
+    pub fn pop(&mut self) {
+        self.query.pop();
+        self.selected = 0;
+    }
+
+    pub fn up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn down(&mut self) {
+        let count = self.matches().len();
+        self.selected = (self.selected + 1).min(count.saturating_sub(1));
+    }
+}
+
+/// How loosely `label` matches `query`, whose letters it must have in order, ignoring case.
+/// Lower is closer: it counts the characters skipped after the first match, so a query typed
+/// as one word of the label comes before one whose letters are spread across it.
+fn score(query: &str, label: &str) -> Option<usize> {
+    let mut label = label.chars().flat_map(char::to_lowercase);
+    let mut gaps = 0;
+    let mut started = false;
+    for wanted in query.chars().flat_map(char::to_lowercase) {
+        if wanted == ' ' {
+            continue;
+        }
+        loop {
+            let c = label.next()?;
+            if c == wanted {
+                started = true;
+                break;
+            }
+            if started {
+                gaps += 1;
+            }
+        }
+    }
+    Some(gaps)
+}
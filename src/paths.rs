@@ -0,0 +1,90 @@
+use std::path::PathBuf;
+
+const APP_DIR: &str = "steel-tui";
+
+/// Directory for runtime state that should survive between runs (PID files, checkpoints).
+pub fn state_dir() -> PathBuf {
+    dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .unwrap_or_else(std::env::temp_dir)
+        .join(APP_DIR)
+}
+
+/// Directory for data that can be regenerated at any time (template clones).
+pub fn cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join(APP_DIR)
+}
+
+/// Cached template checkouts live here, one directory per template.
+pub fn template_cache_dir() -> PathBuf {
+    cache_dir().join("templates")
+}
+
+/// PIDs of processes this tool has spawned, one per line.
+pub fn pid_file() -> PathBuf {
+    state_dir().join("pids")
+}
+
+/// Scratch files written while a step is in progress.
+pub fn temp_dir() -> PathBuf {
+    state_dir().join("tmp")
+}
+
+/// Directory for user configuration and credentials.
+pub fn config_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join(APP_DIR)
+}
+
+/// Passphrase-encrypted credentials, for platforms without a usable keychain.
+pub fn credentials_file() -> PathBuf {
+    config_dir().join("credentials.age")
+}
+
+/// User preferences edited on the Settings screen.
+pub fn settings_file() -> PathBuf {
+    config_dir().join("settings.toml")
+}
+
+/// Templates the user adds to the bundled ones.
+pub fn templates_file() -> PathBuf {
+    config_dir().join("templates.toml")
+}
+
+/// Crash reports written after a panic or fatal error.
+pub fn crash_report_dir() -> PathBuf {
+    state_dir().join("crash-reports")
+}
+
+/// Projects created with this tool, newest first.
+pub fn projects_file() -> PathBuf {
+    state_dir().join("projects.json")
+}
+
+/// Where the last session left off, offered for restoring on the next launch.
+pub fn session_file() -> PathBuf {
+    state_dir().join("session.json")
+}
+
+/// Anvil states saved after local deployments, one file per project.
+pub fn anvil_state_dir() -> PathBuf {
+    state_dir().join("anvil-states")
+}
+
+/// Fresh template checkouts that projects are compared against.
+pub fn drift_dir() -> PathBuf {
+    cache_dir().join("drift")
+}
+
+/// cargo-generate templates fetched from git, one checkout per repository.
+pub fn cargo_template_dir() -> PathBuf {
+    cache_dir().join("cargo-generate")
+}
+
+/// Each install and test step's output, one directory per session.
+pub fn log_dir() -> PathBuf {
+    state_dir().join("logs")
+}
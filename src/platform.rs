@@ -0,0 +1,220 @@
+//! OS-specific process and file operations, so callers don't shell out to Unix-only tools.
+
+use crate::tools;
+use color_eyre::{eyre::eyre, Result};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// Makes `command` start in its own process group, so it can be terminated with its descendants.
+pub fn new_process_group(command: &mut Command) -> &mut Command {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+        command.creation_flags(CREATE_NEW_PROCESS_GROUP);
+    }
+    command
+}
+
+/// Terminates a process and everything it spawned. Returns whether the signal was delivered.
+///
+/// On Unix this signals the process group led by `pid` (see [`new_process_group`]); on Windows
+/// `taskkill /T` walks the process tree.
+pub fn terminate_tree(pid: u32) -> bool {
+    #[cfg(unix)]
+    let output = Command::new("kill")
+        .args(["-TERM", "--", &format!("-{}", pid)])
+        .output();
+    #[cfg(windows)]
+    let output = Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/T", "/F"])
+        .output();
+
+    output.is_ok_and(|o| o.status.success())
+}
+
+/// Terminates a single process. Used when `pid` may not lead its own group.
+pub fn terminate(pid: u32) -> bool {
+    #[cfg(unix)]
+    let output = Command::new("kill").arg(pid.to_string()).output();
+    #[cfg(windows)]
+    let output = Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/F"])
+        .output();
+
+    output.is_ok_and(|o| o.status.success())
+}
+
+/// Returns the executable name of `pid` if it is still alive.
+pub fn process_name(pid: u32) -> Option<String> {
+    #[cfg(unix)]
+    {
+        let output = Command::new("ps")
+            .args(["-p", &pid.to_string(), "-o", "comm="])
+            .output()
+            .ok()?;
+        let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !name.is_empty()).then_some(name)
+    }
+    #[cfg(windows)]
+    {
+        // CSV rows look like: "anvil.exe","1234","Console","1","12,345 K"
+        let output = Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+            .output()
+            .ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let name = stdout.trim().split(',').next()?.trim_matches('"');
+        (!name.is_empty() && !name.starts_with("INFO:")).then(|| name.to_string())
+    }
+}
+
+/// A command that runs the bash script `script`, e.g. the template's `e2e-test.sh`.
+///
+/// Windows has no bash of its own. Git for Windows ships one, which is looked up next to
+/// `git` so that WSL's `bash.exe`, which would run the script inside Linux, isn't picked up.
+pub fn bash_script(script: &str) -> Result<Command> {
+    #[cfg(windows)]
+    let bash = git_bash().ok_or_else(|| {
+        eyre!(
+            "{} is a bash script. Install Git for Windows, which includes Git Bash, or run steel-tui in WSL",
+            script
+        )
+    })?;
+    #[cfg(not(windows))]
+    let bash = PathBuf::from("bash");
+
+    let mut command = Command::new(bash);
+    command.arg(script);
+    Ok(command)
+}
+
+/// Checks that the Foundry tools an end-to-end script drives are on `PATH`, `anvil` too when
+/// it runs against a local chain, so a missing one stops the flow before anything is built.
+///
+/// On Windows Foundry is easy to miss: foundryup only runs in Git Bash, and one installed in
+/// WSL lives inside Linux, out of steel-tui's sight.
+pub fn require_foundry(local_chain: bool) -> Result<()> {
+    let missing: Vec<&str> = ["forge", "cast", "anvil"]
+        .into_iter()
+        .filter(|tool| *tool != "anvil" || local_chain)
+        .filter(|tool| tools::version(tool, &["--version"]).is_err())
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    #[cfg(windows)]
+    let hint = "Install Foundry by running foundryup in Git Bash, then restart steel-tui so it finds %USERPROFILE%\\.foundry\\bin. A Foundry installed in WSL can't be used from Windows";
+    #[cfg(not(windows))]
+    let hint =
+        "Install Foundry with foundryup: https://book.getfoundry.sh/getting-started/installation";
+    Err(eyre!("{} not found. {}", missing.join(", "), hint))
+}
+
+/// `bin\bash.exe` of the Git for Windows installation `git` comes from.
+#[cfg(windows)]
+fn git_bash() -> Option<PathBuf> {
+    // e.g. C:/Program Files/Git/mingw64/libexec/git-core
+    let output = Command::new("git").arg("--exec-path").output().ok()?;
+    let exec_path = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    exec_path
+        .ancestors()
+        .map(|dir| dir.join("bin").join("bash.exe"))
+        .find(|bash| bash.is_file())
+}
+
+/// `fs::canonicalize`, without the `\\?\` prefix Windows gives the result. cmd, bash and
+/// some build scripts can't use such paths as a working directory.
+pub fn canonicalize(path: &Path) -> std::io::Result<PathBuf> {
+    let canonical = std::fs::canonicalize(path)?;
+    #[cfg(windows)]
+    {
+        let text = canonical.to_string_lossy();
+        if let Some(rest) = text.strip_prefix(r"\\?\") {
+            // Only plain drive paths; UNC shares and over-long paths need the prefix
+            if rest.as_bytes().get(1) == Some(&b':') && rest.len() < 260 {
+                return Ok(PathBuf::from(rest));
+            }
+        }
+    }
+    Ok(canonical)
+}
+
+/// Marks a file as executable. A no-op on Windows, where scripts aren't gated by a mode bit.
+pub fn make_executable(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut permissions = std::fs::metadata(path)?.permissions();
+        permissions.set_mode(permissions.mode() | 0o111);
+        std::fs::set_permissions(path, permissions)?;
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+    }
+    Ok(())
+}
+
+/// Makes a file readable and writable by its owner only. A no-op on Windows, where files under
+/// the user's profile already are.
+pub fn restrict_to_owner(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+    }
+    Ok(())
+}
+
+/// Puts `text` on the system clipboard through whichever clipboard tool the platform has.
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    let tools: &[&[&str]] = &[&["pbcopy"]];
+    #[cfg(windows)]
+    let tools: &[&[&str]] = &[&["clip"]];
+    #[cfg(not(any(target_os = "macos", windows)))]
+    let tools: &[&[&str]] = &[
+        &["wl-copy"],
+        &["xclip", "-selection", "clipboard"],
+        &["xsel", "--clipboard", "--input"],
+    ];
+
+    for tool in tools {
+        let Ok(mut child) = Command::new(tool[0])
+            .args(&tool[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+    Err(eyre!(
+        "no clipboard tool found (tried {})",
+        tools
+            .iter()
+            .map(|tool| tool[0])
+            .collect::<Vec<_>>()
+            .join(", ")
+    ))
+}
@@ -0,0 +1,98 @@
+//! `.steel-tui.toml`, written into every generated project so later commands know how it was
+//! made instead of guessing from its manifests.
+
+use crate::{chain_presets::ChainPreset, summary::ProjectSummary, tools};
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path};
+
+pub const FILE_NAME: &str = ".steel-tui.toml";
+
+#[derive(Serialize, Deserialize)]
+pub struct ProjectFile {
+    pub project: ProjectInfo,
+    /// Tool versions at creation time, by name.
+    #[serde(default)]
+    pub tools: BTreeMap<String, String>,
+    #[serde(default)]
+    pub options: Options,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ProjectInfo {
+    pub template: String,
+    /// The examples of a multi-example workspace.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<String>,
+    pub repository: String,
+    /// The risc0-ethereum release branch; `upgrade` keeps this current.
+    pub branch: String,
+    /// Template commit the project was created from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+    /// RFC 3339 creation time.
+    pub created: String,
+    pub created_with: String,
+    /// risc0-ethereum crates pinned to a tag; `upgrade` leaves them where they are.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+}
+
+/// Choices made when the project was created, and toggles changed in Settings since.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Options {
+    pub offline: bool,
+    pub parallelism: usize,
+    /// Run everything in the project with `RISC0_DEV_MODE=1`.
+    #[serde(default)]
+    pub dev_mode: bool,
+    /// The chain the project is made for, used instead of the one chosen in the toolbox for
+    /// every project.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain: Option<ChainPreset>,
+}
+
+impl ProjectFile {
+    /// Describes a project that was just created, recording the installed tool versions.
+    pub fn new(summary: &ProjectSummary, options: Options) -> Self {
+        Self {
+            project: ProjectInfo {
+                template: summary.template.clone(),
+                examples: summary.examples.clone(),
+                repository: summary.repository.clone(),
+                branch: summary.branch.clone(),
+                revision: summary.revision.clone(),
+                created: chrono::Utc::now().to_rfc3339(),
+                created_with: format!("steel-tui {}", env!("CARGO_PKG_VERSION")),
+                tags: summary.tags.clone(),
+            },
+            tools: tools::versions()
+                .into_iter()
+                .filter_map(|(name, version)| Some((name.to_string(), version.ok()?)))
+                .collect(),
+            options,
+        }
+    }
+
+    /// The metadata of the project at `root`, or `None` if it has none (e.g. it predates
+    /// this file, or wasn't created by steel-tui).
+    pub fn load(root: &Path) -> Result<Option<Self>> {
+        let file = root.join(FILE_NAME);
+        match fs::read_to_string(&file) {
+            Ok(content) => toml::from_str(&content)
+                .map(Some)
+                .map_err(|e| eyre!("Invalid {}: {}", file.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(eyre!("Could not read {}: {}", file.display(), e)),
+        }
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let content = format!(
+            "# Written by steel-tui. Records how this project was created.\n{}",
+            toml::to_string_pretty(self)?
+        );
+        fs::write(root.join(FILE_NAME), content)?;
+        Ok(())
+    }
+}
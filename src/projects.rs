@@ -0,0 +1,147 @@
+//! Registry of projects created with this tool, offered on the welcome screen so users can go
+//! straight back to a project's test toolbox.
+
+use crate::{manifest, paths, platform, project_file::ProjectFile};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// How many projects the welcome screen lists.
+pub const SHOWN: usize = 5;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RecentProject {
+    pub path: PathBuf,
+    pub template: String,
+    pub branch: String,
+    /// RFC 3339 creation time.
+    pub created: String,
+    #[serde(default)]
+    pub last_test: Option<LastTest>,
+}
+
+/// Outcome of the most recent end-to-end test run against a project.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LastTest {
+    pub passed: bool,
+    /// RFC 3339 time the test finished.
+    pub at: String,
+}
+
+impl RecentProject {
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.display().to_string())
+    }
+
+    /// Creation date without the time, e.g. `2025-03-14`.
+    pub fn date(&self) -> &str {
+        self.created.get(..10).unwrap_or(&self.created)
+    }
+
+    /// The risc0-ethereum branch the project is on now.
+    pub fn pinned_branch(&self) -> Option<String> {
+        pinned_branch(&self.path)
+    }
+}
+
+/// The risc0-ethereum branch the project at `root` is on, from its metadata or, failing that,
+/// its manifests.
+pub fn pinned_branch(root: &Path) -> Option<String> {
+    if let Ok(Some(metadata)) = ProjectFile::load(root) {
+        return Some(metadata.project.branch);
+    }
+    manifest::find_cargo_toml_files(root)
+        .ok()?
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .find_map(|content| manifest::detect_branch(&content))
+}
+
+fn read() -> Vec<RecentProject> {
+    fs::read_to_string(paths::projects_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write(projects: &[RecentProject]) -> Result<()> {
+    fs::create_dir_all(paths::state_dir())?;
+    fs::write(
+        paths::projects_file(),
+        serde_json::to_string_pretty(projects)?,
+    )?;
+    Ok(())
+}
+
+/// Registered projects that still exist, newest first. Entries whose directory is gone are
+/// dropped from the registry.
+pub fn load() -> Vec<RecentProject> {
+    let projects = read();
+    let count = projects.len();
+    let existing: Vec<RecentProject> = projects.into_iter().filter(|p| p.path.is_dir()).collect();
+    if existing.len() != count {
+        let _ = write(&existing);
+    }
+    existing
+}
+
+/// Records the result of a test run, if the project at `path` is registered.
+pub fn record_test(path: &Path, passed: bool) -> Result<()> {
+    let path = platform::canonicalize(path)?;
+    let mut projects = read();
+    let Some(project) = projects.iter_mut().find(|p| p.path == path) else {
+        return Ok(());
+    };
+    project.last_test = Some(LastTest {
+        passed,
+        at: chrono::Utc::now().to_rfc3339(),
+    });
+    write(&projects)
+}
+
+/// The Steel project rooted at `dir`, recognized by its `.steel-tui.toml` or, for projects
+/// created some other way, by the erc20-counter layout.
+pub fn detect(dir: &Path) -> Option<RecentProject> {
+    let path = platform::canonicalize(dir).ok()?;
+    if let Ok(Some(metadata)) = ProjectFile::load(&path) {
+        return Some(RecentProject {
+            path,
+            template: metadata.project.template,
+            branch: metadata.project.branch,
+            created: metadata.project.created,
+            last_test: None,
+        });
+    }
+
+    let layout = ["apps", "contracts", "methods"]
+        .iter()
+        .all(|sub| path.join(sub).is_dir())
+        && path.join("foundry.toml").is_file()
+        && path.join("Cargo.toml").is_file();
+    if !layout {
+        return None;
+    }
+    let mut project = RecentProject {
+        path,
+        template: String::from("erc20-counter"),
+        branch: String::new(),
+        created: chrono::Utc::now().to_rfc3339(),
+        last_test: None,
+    };
+    project.branch = project.pinned_branch().unwrap_or_default();
+    Some(project)
+}
+
+/// Adds a newly created project, replacing any older entry for the same path.
+pub fn record(project: RecentProject) -> Result<()> {
+    let mut projects = read();
+    projects.retain(|p| p.path != project.path);
+    projects.insert(0, project);
+    write(&projects)
+}
@@ -0,0 +1,38 @@
+//! Where the end-to-end test's proofs get made.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProverBackend {
+    #[default]
+    Bonsai,
+    /// The Boundless proving market. The host submits requests itself; we only watch them.
+    Boundless,
+    Local,
+}
+
+impl ProverBackend {
+    pub const ALL: &'static [ProverBackend] = &[
+        ProverBackend::Bonsai,
+        ProverBackend::Boundless,
+        ProverBackend::Local,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProverBackend::Bonsai => "Bonsai",
+            ProverBackend::Boundless => "Boundless market",
+            ProverBackend::Local => "Local prover",
+        }
+    }
+
+    pub fn needs_bonsai_key(self) -> bool {
+        self == ProverBackend::Bonsai
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|b| *b == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
@@ -0,0 +1,136 @@
+//! Actions queued to run one after another, e.g. build, then test in dev mode, then run the
+//! forge tests.
+
+use crate::tasks::TaskStatus;
+use std::time::Duration;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    CargoBuild,
+    ForgeBuild,
+    EndToEndDev,
+    ForgeTest,
+    CargoTest,
+}
+
+impl Action {
+    pub const ALL: &'static [Action] = &[
+        Action::CargoBuild,
+        Action::ForgeBuild,
+        Action::EndToEndDev,
+        Action::ForgeTest,
+        Action::CargoTest,
+    ];
+
+    /// Build everything, then test end to end in dev mode and run the contract tests.
+    pub const PRESET: &'static [Action] = &[
+        Action::CargoBuild,
+        Action::ForgeBuild,
+        Action::EndToEndDev,
+        Action::ForgeTest,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::CargoBuild => "Build guests and host (cargo build)",
+            Action::ForgeBuild => "Compile contracts (forge build)",
+            Action::EndToEndDev => "End-to-end test in dev mode",
+            Action::ForgeTest => "Contract tests (forge test)",
+            Action::CargoTest => "Rust tests in dev mode (cargo test)",
+        }
+    }
+}
+
+pub struct Entry {
+    pub action: Action,
+    pub status: TaskStatus,
+    pub took: Option<Duration>,
+}
+
+impl Entry {
+    pub fn describe(&self) -> String {
+        match self.took {
+            Some(took) => format!(
+                "{} {:<40} {:.1}s",
+                self.status.icon(),
+                self.action.label(),
+                took.as_secs_f64()
+            ),
+            None => format!("{} {}", self.status.icon(), self.action.label()),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Queue {
+    pub entries: Vec<Entry>,
+    pub selected: usize,
+}
+
+impl Queue {
+    pub fn push(&mut self, action: Action) {
+        self.entries.push(Entry {
+            action,
+            status: TaskStatus::Pending,
+            took: None,
+        });
+    }
+
+    /// Index of the next entry to run.
+    pub fn next_pending(&self) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|entry| entry.status == TaskStatus::Pending)
+    }
+
+    pub fn pending(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.status == TaskStatus::Pending)
+            .count()
+    }
+
+    /// Drops the selected entry, unless it has already run.
+    pub fn remove_selected(&mut self) -> bool {
+        if self
+            .entries
+            .get(self.selected)
+            .is_none_or(|entry| entry.status != TaskStatus::Pending)
+        {
+            return false;
+        }
+        self.entries.remove(self.selected);
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+        true
+    }
+
+    /// Swaps the selected entry with its neighbour `up` or down. Only pending entries move,
+    /// so the order of what already ran stays as it happened.
+    pub fn move_selected(&mut self, up: bool) -> bool {
+        let Some(other) = (if up {
+            self.selected.checked_sub(1)
+        } else {
+            Some(self.selected + 1)
+        }) else {
+            return false;
+        };
+        let movable = |i: usize| {
+            self.entries
+                .get(i)
+                .is_some_and(|entry| entry.status == TaskStatus::Pending)
+        };
+        if !movable(self.selected) || !movable(other) {
+            return false;
+        }
+        self.entries.swap(self.selected, other);
+        self.selected = other;
+        true
+    }
+
+    /// Forgets the entries that have run, so the queue can be run again.
+    pub fn clear_finished(&mut self) {
+        self.entries
+            .retain(|entry| entry.status == TaskStatus::Pending);
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+    }
+}
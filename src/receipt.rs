@@ -0,0 +1,262 @@
+//! Loads a receipt or bare journal a host saved and checks what it claims: that the journal is
+//! the one the claim commits to, which image was run, and how it exited. A Groth16 seal is then
+//! verified by calling the verifier contract, as the app's contract would.
+
+use crate::{chain::Chain, guest_image::GuestImage, health::Finding};
+use alloy::{
+    dyn_abi::DynSolValue,
+    primitives::{keccak256, B256},
+};
+use color_eyre::{eyre::eyre, Result};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::{fs, path::Path};
+
+/// What was found in the file.
+pub struct Inspection {
+    /// e.g. "Groth16 receipt" or "journal".
+    pub source: String,
+    pub journal: Vec<u8>,
+    pub checks: Vec<Finding>,
+    /// The image the claim says was run.
+    pub image_id: Option<[u8; 32]>,
+    /// The seal as contracts take it: the verifier selector, then the proof. Only Groth16
+    /// receipts have one.
+    pub seal: Option<Vec<u8>>,
+}
+
+/// Loads `path` as a JSON-serialized receipt, a hex journal, or raw journal bytes, and checks a
+/// receipt's claim against the project's guests.
+pub fn inspect(path: &Path, guests: &[GuestImage]) -> Result<Inspection> {
+    let content = fs::read(path).map_err(|e| eyre!("Could not read {}: {}", path.display(), e))?;
+
+    if let Ok(receipt) = serde_json::from_slice::<Value>(&content) {
+        if let Some(inner) = receipt.get("inner").and_then(Value::as_object) {
+            let journal = bytes(&receipt["journal"]["bytes"])
+                .ok_or_else(|| eyre!("The receipt has no journal"))?;
+            let (kind, inner) = inner
+                .iter()
+                .next()
+                .ok_or_else(|| eyre!("The receipt has no inner receipt"))?;
+            let checks = check_claim(kind, inner, &journal, guests);
+            let image_id = first_claim(inner)
+                .and_then(|claim| claim.get("Value"))
+                .and_then(|claim| image_id(&claim["pre"]));
+            let seal = (kind == "Groth16").then(|| groth16_seal(inner)).flatten();
+            return Ok(Inspection {
+                source: format!("{} receipt", kind),
+                journal,
+                checks,
+                image_id,
+                seal,
+            });
+        }
+    }
+
+    let text = String::from_utf8_lossy(&content);
+    let hex = text.trim().trim_start_matches("0x");
+    let journal = match alloy::hex::decode(hex) {
+        Ok(journal) if !hex.is_empty() => journal,
+        _ => content,
+    };
+    Ok(Inspection {
+        source: String::from("journal"),
+        journal,
+        checks: Vec::new(),
+        image_id: None,
+        seal: None,
+    })
+}
+
+/// Calls `verify(seal, imageId, journalDigest)` on the verifier at `verifier`, which reverts
+/// unless the seal proves the claim.
+pub fn verify_seal(chain: &Chain, verifier: &str, inspection: &Inspection) -> Finding {
+    let (Some(seal), Some(image_id)) = (&inspection.seal, inspection.image_id) else {
+        return Finding::problem(
+            "Seal",
+            format!("A {} can't be verified on-chain", inspection.source),
+            "Compress the receipt to Groth16 to get a seal contracts accept",
+        );
+    };
+    let selector = keccak256("verify(bytes,bytes32,bytes32)");
+    let mut calldata = selector[..4].to_vec();
+    calldata.extend(
+        DynSolValue::Tuple(vec![
+            DynSolValue::Bytes(seal.clone()),
+            DynSolValue::FixedBytes(B256::from(image_id), 32),
+            DynSolValue::FixedBytes(B256::from_slice(&Sha256::digest(&inspection.journal)), 32),
+        ])
+        .abi_encode_params(),
+    );
+    match chain.call(verifier, calldata) {
+        Ok(_) => Finding::ok("Seal", format!("verified by {}", verifier)),
+        Err(e) => Finding::problem(
+            "Seal",
+            format!("{} rejected the seal: {}", verifier, e),
+            "Check the verifier supports the receipt's selector and the journal is unchanged",
+        ),
+    }
+}
+
+/// A JSON array of byte values.
+fn bytes(value: &Value) -> Option<Vec<u8>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|b| b.as_u64().and_then(|b| u8::try_from(b).ok()))
+        .collect()
+}
+
+/// A serialized digest: eight little-endian words.
+fn digest(value: &Value) -> Option<[u8; 32]> {
+    let words = value.as_array()?;
+    if words.len() != 8 {
+        return None;
+    }
+    let mut digest = [0u8; 32];
+    for (chunk, word) in digest.chunks_mut(4).zip(words) {
+        chunk.copy_from_slice(&u32::try_from(word.as_u64()?).ok()?.to_le_bytes());
+    }
+    Some(digest)
+}
+
+/// The zkVM's tagged hash of a struct with digest and word members.
+fn tagged_struct(tag: &str, down: &[[u8; 32]], data: &[u32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(Sha256::digest(tag.as_bytes()));
+    for digest in down {
+        hasher.update(digest);
+    }
+    for word in data {
+        hasher.update(word.to_le_bytes());
+    }
+    hasher.update((down.len() as u16).to_le_bytes());
+    hasher.finalize().into()
+}
+
+/// The encoded seal `risc0-ethereum-contracts` hands to contracts: the first four bytes of the
+/// verifier parameters digest select the verifier behind a router.
+fn groth16_seal(inner: &Value) -> Option<Vec<u8>> {
+    let parameters = digest(&inner["verifier_parameters"])?;
+    let mut seal = parameters[..4].to_vec();
+    seal.extend(bytes(&inner["seal"])?);
+    Some(seal)
+}
+
+/// The claim of the first segment, where the initial state is.
+fn first_claim(inner: &Value) -> Option<&Value> {
+    match inner.get("segments").and_then(Value::as_array) {
+        Some(segments) => segments.first().map(|s| &s["claim"]),
+        None => inner.get("claim"),
+    }
+}
+
+/// The image ID: the digest of the initial system state, which may already be pruned to it.
+fn image_id(pre: &Value) -> Option<[u8; 32]> {
+    if let Some(pruned) = pre.get("Pruned") {
+        return digest(pruned);
+    }
+    let state = pre.get("Value")?;
+    let pc = u32::try_from(state.get("pc")?.as_u64()?).ok()?;
+    let merkle_root = digest(state.get("merkle_root")?)?;
+    Some(tagged_struct("risc0.SystemState", &[merkle_root], &[pc]))
+}
+
+fn check_claim(kind: &str, inner: &Value, journal: &[u8], guests: &[GuestImage]) -> Vec<Finding> {
+    // A composite receipt's claim runs from its first segment to its last
+    let (first, last) = match inner.get("segments").and_then(Value::as_array) {
+        Some(segments) => (
+            segments.first().map(|s| &s["claim"]),
+            segments.last().map(|s| &s["claim"]),
+        ),
+        None => (inner.get("claim"), inner.get("claim")),
+    };
+    let (Some(first), Some(last)) = (
+        first.and_then(|c| c.get("Value")),
+        last.and_then(|c| c.get("Value")),
+    ) else {
+        return vec![Finding::problem(
+            "Claim",
+            format!(
+                "The {} receipt's claim is pruned, so there's nothing to check",
+                kind
+            ),
+            "Save the receipt before compressing it further",
+        )];
+    };
+    let mut checks = Vec::new();
+
+    let journal_digest: [u8; 32] = Sha256::digest(journal).into();
+    let output = &last["output"];
+    let claimed = match (output.get("Value"), output.get("Pruned")) {
+        (Some(Value::Null), _) => Some(Err("The claim has no output")),
+        (Some(output), _) => match (
+            output["journal"].get("Value").and_then(bytes),
+            output["journal"].get("Pruned").and_then(digest),
+        ) {
+            (Some(claimed), _) => Some(Ok(Sha256::digest(&claimed).into())),
+            (None, Some(claimed)) => Some(Ok(claimed)),
+            (None, None) => None,
+        },
+        (None, _) => None,
+    };
+    checks.push(match claimed {
+        Some(Ok(claimed)) if claimed == journal_digest => Finding::ok(
+            "Journal digest",
+            format!("0x{}", alloy::hex::encode(journal_digest)),
+        ),
+        Some(Ok(claimed)) => Finding::problem(
+            "Journal digest",
+            format!(
+                "The journal hashes to 0x{} but the claim commits to 0x{}",
+                alloy::hex::encode(journal_digest),
+                alloy::hex::encode(claimed)
+            ),
+            "The journal was changed after proving; use the one the receipt came with",
+        ),
+        Some(Err(e)) => Finding::problem("Journal digest", e, "Check the guest commits a journal"),
+        None => Finding::problem(
+            "Journal digest",
+            "The claim's output is pruned",
+            "Nothing to compare with; the verifier will recompute it",
+        ),
+    });
+
+    checks.push(match image_id(&first["pre"]) {
+        Some(id) => {
+            let hex = format!("0x{}", alloy::hex::encode(id));
+            match guests
+                .iter()
+                .find(|g| g.image_id.eq_ignore_ascii_case(&hex))
+            {
+                Some(guest) => Finding::ok("Image ID", format!("{} ({})", hex, guest.name)),
+                None if guests.is_empty() => Finding::ok(
+                    "Image ID",
+                    format!("{} (no ImageID.sol to compare with)", hex),
+                ),
+                None => Finding::problem(
+                    "Image ID",
+                    format!("{} isn't any guest in ImageID.sol", hex),
+                    "Rebuild the guest, or verify against the image ID the receipt was proven for",
+                ),
+            }
+        }
+        None => Finding::problem(
+            "Image ID",
+            "Couldn't work out the image ID from the claim",
+            "Check the file is a receipt saved with serde_json",
+        ),
+    });
+
+    checks.push(
+        match last["exit_code"].get("Halted").and_then(Value::as_u64) {
+            Some(0) => Finding::ok("Exit code", "Halted(0)"),
+            _ => Finding::problem(
+                "Exit code",
+                format!("{}", last["exit_code"]),
+                "Only receipts of guests that halted with code 0 verify on-chain",
+            ),
+        },
+    );
+    checks
+}
@@ -0,0 +1,52 @@
+//! Known secret values (private keys, API keys) that must never reach the screen, logs, or
+//! exported files. Anything that renders, logs, or writes text passes it through [`redact`].
+
+use std::sync::{Mutex, MutexGuard};
+
+pub const MASK: &str = "****";
+
+/// Values this short would mask unrelated text, and aren't credible secrets anyway.
+const MIN_SECRET_LEN: usize = 8;
+
+static SECRETS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn secrets() -> MutexGuard<'static, Vec<String>> {
+    // Redaction also runs from the panic hook, so a poisoned lock must still work
+    SECRETS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Remembers a secret so later output containing it is masked.
+pub fn register(secret: &str) {
+    let secret = secret.trim();
+    if secret.len() < MIN_SECRET_LEN {
+        return;
+    }
+    let mut secrets = secrets();
+    if !secrets.iter().any(|s| s == secret) {
+        secrets.push(secret.to_string());
+        // Longest first, so a secret containing another is masked whole
+        secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+    }
+}
+
+/// Replaces every known secret in `text` with [`MASK`].
+pub fn redact(text: &str) -> String {
+    let secrets = secrets();
+    let mut text = text.to_string();
+    for secret in secrets.iter() {
+        if text.contains(secret.as_str()) {
+            text = text.replace(secret.as_str(), MASK);
+        }
+    }
+    text
+}
+
+/// Masks a secret as it's being typed, keeping the last few characters to catch typos.
+pub fn mask_input(input: &str) -> String {
+    let shown = input.chars().count().saturating_sub(4);
+    input
+        .chars()
+        .enumerate()
+        .map(|(i, c)| if i < shown { '•' } else { c })
+        .collect()
+}
@@ -0,0 +1,278 @@
+//! Scripted key presses, for recording reproducible demos and for driving the wizard from start
+//! to finish without anyone at the keyboard. `--record` writes what was pressed, with the pauses
+//! in between, in the same format `--replay` reads:
+//!
+//! ```text
+//! # Comments and blank lines are ignored
+//! pace 80ms            # pause between keys from here on
+//! wait-for project name
+//! type my-app
+//! key Enter
+//! wait 2s
+//! key Down Down Enter
+//! timeout 15m          # how long a wait-for may take from here on
+//! wait-for Tests passed
+//! key ctrl+c
+//! ```
+//!
+//! `wait-for` waits until the status line contains the text, and fails the run if it doesn't
+//! within the timeout.
+
+use color_eyre::{eyre::eyre, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use std::{
+    collections::VecDeque,
+    fs::{self, File},
+    io::Write,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(600);
+
+enum Action {
+    Key(KeyEvent),
+    Wait(Duration),
+    WaitFor(String),
+    Pace(Duration),
+    Timeout(Duration),
+}
+
+pub struct Script {
+    actions: VecDeque<Action>,
+    pace: Duration,
+    timeout: Duration,
+    /// When the wait at the front started.
+    waiting_since: Option<Instant>,
+    /// When the next key may be pressed, after the pace.
+    next_key: Instant,
+}
+
+impl Script {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| eyre!("Could not read {}: {}", path.display(), e))?;
+        Self::parse(&text).map_err(|e| eyre!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut actions = VecDeque::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = match line.split_once(" #") {
+                Some((line, _)) => line,
+                None => line,
+            }
+            .trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let rest = rest.trim();
+            let at = |e: String| eyre!("line {}: {}", number + 1, e);
+            match command {
+                "key" => {
+                    for name in rest.split_whitespace() {
+                        actions.push_back(Action::Key(parse_key(name).map_err(at)?));
+                    }
+                }
+                "type" => actions.extend(
+                    rest.chars()
+                        .map(|c| Action::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))),
+                ),
+                "wait" => actions.push_back(Action::Wait(parse_duration(rest).map_err(at)?)),
+                "wait-for" if !rest.is_empty() => actions.push_back(Action::WaitFor(rest.into())),
+                "pace" => actions.push_back(Action::Pace(parse_duration(rest).map_err(at)?)),
+                "timeout" => actions.push_back(Action::Timeout(parse_duration(rest).map_err(at)?)),
+                _ => return Err(at(format!("unknown command '{}'", line))),
+            }
+        }
+        Ok(Script {
+            actions,
+            pace: Duration::ZERO,
+            timeout: DEFAULT_TIMEOUT,
+            waiting_since: None,
+            next_key: Instant::now(),
+        })
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// The next key, once it's due. `status` is the status line `wait-for` looks at. Fails when
+    /// a `wait-for` times out.
+    pub fn next(&mut self, status: &str) -> Result<Option<KeyEvent>> {
+        loop {
+            let now = Instant::now();
+            match self.actions.front() {
+                None => return Ok(None),
+                Some(Action::Key(key)) => {
+                    if now < self.next_key {
+                        return Ok(None);
+                    }
+                    let key = *key;
+                    self.actions.pop_front();
+                    self.next_key = now + self.pace;
+                    return Ok(Some(key));
+                }
+                Some(Action::Wait(duration)) => {
+                    let since = *self.waiting_since.get_or_insert(now);
+                    if now < since + *duration {
+                        return Ok(None);
+                    }
+                }
+                Some(Action::WaitFor(text)) => {
+                    let since = *self.waiting_since.get_or_insert(now);
+                    if !status.contains(text.as_str()) {
+                        if now.duration_since(since) > self.timeout {
+                            return Err(eyre!(
+                                "Replay timed out after {}s waiting for '{}' (status: '{}')",
+                                self.timeout.as_secs(),
+                                text,
+                                status
+                            ));
+                        }
+                        return Ok(None);
+                    }
+                }
+                Some(Action::Pace(pace)) => self.pace = *pace,
+                Some(Action::Timeout(timeout)) => self.timeout = *timeout,
+            }
+            self.waiting_since = None;
+            self.actions.pop_front();
+        }
+    }
+}
+
+/// Writes the keys pressed during a session as a script, pauses included.
+pub struct Recorder {
+    file: File,
+    last: Instant,
+}
+
+impl Recorder {
+    pub fn create(path: &Path) -> Result<Self> {
+        let mut file =
+            File::create(path).map_err(|e| eyre!("Could not create {}: {}", path.display(), e))?;
+        writeln!(file, "# Recorded by steel-tui; play back with --replay")?;
+        Ok(Recorder {
+            file,
+            last: Instant::now(),
+        })
+    }
+
+    /// Appends a key press and the pause before it. Keys without a name in scripts are left out.
+    pub fn record(&mut self, key: &KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        let Some(name) = key_name(key) else {
+            return;
+        };
+        let pause = self.last.elapsed();
+        self.last = Instant::now();
+        // Pauses shorter than this are typing, not a beat of the demo
+        if pause >= Duration::from_millis(200) {
+            let _ = writeln!(self.file, "wait {}ms", pause.as_millis());
+        }
+        let _ = writeln!(self.file, "key {}", name);
+    }
+}
+
+/// `500ms`, `2s`, `1.5s`, `15m`, or a bare number of milliseconds.
+fn parse_duration(text: &str) -> Result<Duration, String> {
+    let (number, unit) = match text.find(|c: char| c.is_ascii_alphabetic()) {
+        Some(i) => text.split_at(i),
+        None => (text, "ms"),
+    };
+    let number: f64 = number
+        .trim()
+        .parse()
+        .map_err(|_| format!("'{}' isn't a duration, e.g. 500ms or 2s", text))?;
+    let seconds = match unit {
+        "ms" => number / 1000.0,
+        "s" => number,
+        "m" => number * 60.0,
+        _ => return Err(format!("unknown unit in '{}'; use ms, s or m", text)),
+    };
+    Duration::try_from_secs_f64(seconds).map_err(|e| format!("'{}': {}", text, e))
+}
+
+/// `Enter`, `Down`, `F1`, `q`, `Space`, `ctrl+c`, `shift+Tab`, ...
+fn parse_key(name: &str) -> Result<KeyEvent, String> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = name;
+    while let Some((modifier, key)) = rest.split_once('+').filter(|(_, key)| !key.is_empty()) {
+        modifiers |= match modifier.to_lowercase().as_str() {
+            "ctrl" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return Err(format!("unknown modifier in '{}'", name)),
+        };
+        rest = key;
+    }
+    let mut chars = rest.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match rest.to_lowercase().as_str() {
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "space" => KeyCode::Char(' '),
+            other => match other.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                Some(n @ 1..=12) => KeyCode::F(n),
+                _ => return Err(format!("unknown key '{}'", name)),
+            },
+        },
+    };
+    Ok(KeyEvent::new(code, modifiers))
+}
+
+/// The name `parse_key` reads back as `key`.
+fn key_name(key: &KeyEvent) -> Option<String> {
+    let code = match key.code {
+        KeyCode::Char(' ') => String::from("Space"),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        KeyCode::Enter => String::from("Enter"),
+        KeyCode::Esc => String::from("Esc"),
+        KeyCode::Tab => String::from("Tab"),
+        KeyCode::BackTab => String::from("BackTab"),
+        KeyCode::Backspace => String::from("Backspace"),
+        KeyCode::Delete => String::from("Delete"),
+        KeyCode::Up => String::from("Up"),
+        KeyCode::Down => String::from("Down"),
+        KeyCode::Left => String::from("Left"),
+        KeyCode::Right => String::from("Right"),
+        KeyCode::Home => String::from("Home"),
+        KeyCode::End => String::from("End"),
+        KeyCode::PageUp => String::from("PageUp"),
+        KeyCode::PageDown => String::from("PageDown"),
+        _ => return None,
+    };
+    let mut name = String::new();
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        name.push_str("ctrl+");
+    }
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        name.push_str("alt+");
+    }
+    // Shifted characters already say so, and BackTab is its own key
+    if key.modifiers.contains(KeyModifiers::SHIFT) && !matches!(key.code, KeyCode::Char(_)) {
+        name.push_str("shift+");
+    }
+    name.push_str(&code);
+    Some(name)
+}
@@ -0,0 +1,146 @@
+//! CPU and memory sampling of supervised children, so a step that's working hard can be told
+//! apart from one that's stuck.
+
+use crate::supervisor;
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+/// CPU percentages are only meaningful between two refreshes, so sample no faster than this.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Resource usage of a supervised child and everything it spawned.
+pub struct Usage {
+    pub pid: u32,
+    pub name: String,
+    /// Percent of one core; can exceed 100 for parallel builds.
+    pub cpu: f32,
+    /// Resident memory in bytes.
+    pub memory: u64,
+}
+
+#[derive(Default)]
+pub struct Monitor {
+    system: System,
+    last_sample: Option<Instant>,
+    usage: Vec<Usage>,
+}
+
+impl Monitor {
+    /// Refreshes the numbers if the sample interval has passed. Returns whether they changed.
+    pub fn sample(&mut self) -> bool {
+        if self
+            .last_sample
+            .is_some_and(|last| last.elapsed() < SAMPLE_INTERVAL)
+        {
+            return false;
+        }
+        self.last_sample = Some(Instant::now());
+
+        let children = supervisor::list();
+        if children.is_empty() {
+            let had_usage = !self.usage.is_empty();
+            self.usage.clear();
+            return had_usage;
+        }
+
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_cpu().with_memory(),
+        );
+        self.usage = children
+            .into_iter()
+            .filter(|child| child.running)
+            .map(|child| {
+                let (cpu, memory) = self.tree_usage(Pid::from_u32(child.pid));
+                Usage {
+                    pid: child.pid,
+                    name: child.name,
+                    cpu,
+                    memory,
+                }
+            })
+            .collect();
+        true
+    }
+
+    /// Sums usage over `root` and its descendants; cargo's work happens in rustc children.
+    fn tree_usage(&self, root: Pid) -> (f32, u64) {
+        let processes = self.system.processes();
+        let descends_from_root = |mut pid: Pid| {
+            // Bounded walk in case of a parent cycle from PID reuse
+            for _ in 0..64 {
+                if pid == root {
+                    return true;
+                }
+                match processes.get(&pid).and_then(|p| p.parent()) {
+                    Some(parent) => pid = parent,
+                    None => return false,
+                }
+            }
+            false
+        };
+
+        processes
+            .iter()
+            .filter(|(pid, _)| descends_from_root(**pid))
+            .fold((0.0, 0), |(cpu, memory), (_, process)| {
+                (cpu + process.cpu_usage(), memory + process.memory())
+            })
+    }
+
+    pub fn usage_for(&self, pid: u32) -> Option<&Usage> {
+        self.usage.iter().find(|usage| usage.pid == pid)
+    }
+
+    /// One-line summary for CI logs, e.g. `cargo 312% CPU, 1.2 GiB`.
+    pub fn summary(&self) -> Option<String> {
+        if self.usage.is_empty() {
+            return None;
+        }
+        Some(
+            self.usage
+                .iter()
+                .map(|usage| format!("{} {}", usage.name, usage.describe()))
+                .collect::<Vec<_>>()
+                .join(" · "),
+        )
+    }
+}
+
+impl Usage {
+    pub fn describe(&self) -> String {
+        format!("{:.0}% CPU, {}", self.cpu, format_bytes(self.memory))
+    }
+}
+
+/// Working directories of running processes called `name`, whoever started them.
+pub fn working_dirs_of(name: &str) -> Vec<PathBuf> {
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing().with_cwd(UpdateKind::Always),
+    );
+    system
+        .processes()
+        .values()
+        .filter(|process| process.name().to_string_lossy() == name)
+        .filter_map(|process| process.cwd().map(PathBuf::from))
+        .collect()
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const MIB: f64 = 1024.0 * 1024.0;
+    let mib = bytes as f64 / MIB;
+    if mib >= 1024.0 {
+        format!("{:.1} GiB", mib / 1024.0)
+    } else if mib >= 1.0 {
+        format!("{:.0} MiB", mib)
+    } else {
+        format!("{:.0} KiB", bytes as f64 / 1024.0)
+    }
+}
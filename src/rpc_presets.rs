@@ -0,0 +1,117 @@
+//! Built-in RPC endpoints, so users pick a provider and network and only type their API key.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RpcProvider {
+    /// `ETH_RPC_URL`, or anvil on localhost.
+    #[default]
+    Local,
+    Alchemy,
+    Infura,
+    /// Free endpoints from publicnode.com. Rate limited, but need no account.
+    Public,
+}
+
+impl RpcProvider {
+    pub const ALL: &'static [RpcProvider] = &[
+        RpcProvider::Local,
+        RpcProvider::Alchemy,
+        RpcProvider::Infura,
+        RpcProvider::Public,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RpcProvider::Local => "Local (ETH_RPC_URL or anvil)",
+            RpcProvider::Alchemy => "Alchemy",
+            RpcProvider::Infura => "Infura",
+            RpcProvider::Public => "Public endpoint",
+        }
+    }
+
+    pub fn needs_api_key(self) -> bool {
+        matches!(self, RpcProvider::Alchemy | RpcProvider::Infura)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Network {
+    Mainnet,
+    #[default]
+    Sepolia,
+    Holesky,
+    Base,
+    BaseSepolia,
+    OpSepolia,
+}
+
+impl Network {
+    pub const ALL: &'static [Network] = &[
+        Network::Mainnet,
+        Network::Sepolia,
+        Network::Holesky,
+        Network::Base,
+        Network::BaseSepolia,
+        Network::OpSepolia,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Network::Mainnet => "Ethereum mainnet",
+            Network::Sepolia => "Sepolia",
+            Network::Holesky => "Holesky",
+            Network::Base => "Base",
+            Network::BaseSepolia => "Base Sepolia",
+            Network::OpSepolia => "OP Sepolia",
+        }
+    }
+}
+
+/// The full RPC URL for `network` at `provider`. `None` for [`RpcProvider::Local`], or when
+/// the provider needs an API key and none was given.
+pub fn url(provider: RpcProvider, network: Network, api_key: &str) -> Option<String> {
+    let api_key = api_key.trim();
+    if provider.needs_api_key() && api_key.is_empty() {
+        return None;
+    }
+    let url = match provider {
+        RpcProvider::Local => return None,
+        RpcProvider::Alchemy => {
+            let host = match network {
+                Network::Mainnet => "eth-mainnet",
+                Network::Sepolia => "eth-sepolia",
+                Network::Holesky => "eth-holesky",
+                Network::Base => "base-mainnet",
+                Network::BaseSepolia => "base-sepolia",
+                Network::OpSepolia => "opt-sepolia",
+            };
+            format!("https://{}.g.alchemy.com/v2/{}", host, api_key)
+        }
+        RpcProvider::Infura => {
+            let host = match network {
+                Network::Mainnet => "mainnet",
+                Network::Sepolia => "sepolia",
+                Network::Holesky => "holesky",
+                Network::Base => "base-mainnet",
+                Network::BaseSepolia => "base-sepolia",
+                Network::OpSepolia => "optimism-sepolia",
+            };
+            format!("https://{}.infura.io/v3/{}", host, api_key)
+        }
+        RpcProvider::Public => {
+            let host = match network {
+                Network::Mainnet => "ethereum-rpc",
+                Network::Sepolia => "ethereum-sepolia-rpc",
+                Network::Holesky => "ethereum-holesky-rpc",
+                Network::Base => "base-rpc",
+                Network::BaseSepolia => "base-sepolia-rpc",
+                Network::OpSepolia => "optimism-sepolia-rpc",
+            };
+            format!("https://{}.publicnode.com", host)
+        }
+    };
+    Some(url)
+}
@@ -0,0 +1,907 @@
+//! Generates new pieces of a Steel project and wires them into the existing layout, so adding
+//! a guest or a contract doesn't mean hand-editing several manifests.
+
+use crate::chain_presets::ChainPreset;
+use color_eyre::{eyre::eyre, Result};
+use regex::Regex;
+use std::{fs, path::Path};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScaffoldKind {
+    Guest,
+    Contract,
+}
+
+impl ScaffoldKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            ScaffoldKind::Guest => "guest program",
+            ScaffoldKind::Contract => "contract",
+        }
+    }
+
+    /// What the name is for and what shape it has to be.
+    pub fn name_hint(self) -> &'static str {
+        match self {
+            ScaffoldKind::Guest => "snake_case, e.g. `balance_check`",
+            ScaffoldKind::Contract => "PascalCase, e.g. `BalanceCheck`",
+        }
+    }
+
+    /// Generates the piece in the project at `root`, returning one line per file touched.
+    pub fn generate(self, root: &Path, name: &str) -> Result<Vec<String>> {
+        match self {
+            ScaffoldKind::Guest => add_guest(root, name),
+            ScaffoldKind::Contract => add_contract(root, name),
+        }
+    }
+}
+
+/// Used when the existing guest doesn't say which zkVM version it's built against.
+const DEFAULT_RISC0_ZKVM: &str =
+    r#"risc0-zkvm = { version = "1.2", default-features = false, features = ["std"] }"#;
+
+const GUEST_MANIFEST: &str = r#"[package]
+name = "__NAME__"
+version = "0.1.0"
+edition = "2021"
+
+# Built by risc0-build on its own, not as part of the project's workspace
+[workspace]
+
+[dependencies]
+__DEPENDENCIES__
+
+[profile.release]
+lto = "thin"
+"#;
+
+const GUEST_MAIN: &str = r#"use risc0_zkvm::guest::env;
+
+fn main() {
+    // Read the input the host wrote to the executor environment
+    let input: u32 = env::read();
+
+    // TODO: compute whatever __NAME__ should prove
+
+    // Commit the public output to the journal
+    env::commit(&input);
+}
+"#;
+
+const HOST_STUB: &str = r#"//! Proves a run of the __NAME__ guest. Generated by steel-tui: adapt the input and output
+//! types to what the guest reads and commits.
+
+use anyhow::Result;
+use __METHODS__::{__UPPER___ELF, __UPPER___ID};
+use risc0_zkvm::{default_prover, ExecutorEnv};
+
+fn main() -> Result<()> {
+    let input: u32 = 42;
+    let env = ExecutorEnv::builder().write(&input)?.build()?;
+
+    let receipt = default_prover().prove(env, __UPPER___ELF)?.receipt;
+    receipt.verify(__UPPER___ID)?;
+
+    let output: u32 = receipt.journal.decode()?;
+    println!("__NAME__ committed {}", output);
+    Ok(())
+}
+"#;
+
+const EVENT_QUERY_GUEST: &str = r#"//! Proves how many of a token's Transfer events a block has, and how much they moved, by
+//! querying the events with Steel rather than reading contract storage.
+
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::{sol, SolValue};
+use risc0_steel::{
+    ethereum::{EthEvmInput, ETH_SEPOLIA_CHAIN_SPEC},
+    Commitment, Event,
+};
+use risc0_zkvm::guest::env;
+
+sol! {
+    interface IERC20 {
+        event Transfer(address indexed from, address indexed to, uint256 value);
+    }
+}
+
+sol! {
+    struct Journal {
+        Commitment commitment;
+        address tokenContract;
+        uint64 transfers;
+        uint256 transferred;
+    }
+}
+
+fn main() {
+    let input: EthEvmInput = env::read();
+    let token: Address = env::read();
+
+    // Checks the block header and receipts in the input against each other; the events can only
+    // come from that block
+    let env = input.into_env(&ETH_SEPOLIA_CHAIN_SPEC);
+    let logs = Event::new::<IERC20::Transfer>(&env).address(token).query();
+    let transferred = logs
+        .iter()
+        .fold(U256::ZERO, |total, log| total + log.data.value);
+
+    // The commitment names the block, so a contract can check it's one of the chain's
+    let journal = Journal {
+        commitment: env.into_commitment(),
+        tokenContract: token,
+        transfers: logs.len() as u64,
+        transferred,
+    };
+    env::commit_slice(&journal.abi_encode());
+}
+"#;
+
+const EVENT_QUERY_HOST: &str = r#"//! Queries a token's Transfer events in one block and proves them with the transfer_events
+//! guest. Generated by steel-tui.
+//!
+//! ETH_RPC_URL=... TOKEN_CONTRACT=0x... [BLOCK=<number>] cargo run --bin transfer_events
+
+use alloy::{
+    primitives::{Address, U256},
+    sol,
+};
+use anyhow::{Context, Result};
+use __METHODS__::{TRANSFER_EVENTS_ELF, TRANSFER_EVENTS_ID};
+use risc0_steel::{
+    ethereum::{EthEvmEnv, ETH_SEPOLIA_CHAIN_SPEC},
+    Event,
+};
+use risc0_zkvm::{default_prover, ExecutorEnv};
+use url::Url;
+
+sol! {
+    interface IERC20 {
+        event Transfer(address indexed from, address indexed to, uint256 value);
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let rpc_url: Url = std::env::var("ETH_RPC_URL")
+        .context("ETH_RPC_URL is not set")?
+        .parse()?;
+    let token: Address = std::env::var("TOKEN_CONTRACT")
+        .context("TOKEN_CONTRACT is not set")?
+        .parse()?;
+
+    // The latest block by default; the RPC has to serve the receipts of an older one
+    let mut builder = EthEvmEnv::builder()
+        .rpc(rpc_url)
+        .chain_spec(&ETH_SEPOLIA_CHAIN_SPEC);
+    if let Ok(block) = std::env::var("BLOCK") {
+        builder = builder.block_number(block.parse().context("BLOCK is not a number")?);
+    }
+    let mut env = builder.build().await?;
+
+    // Preflight the same query the guest runs, collecting the receipts it needs
+    let logs = Event::preflight::<IERC20::Transfer>(&mut env)
+        .address(token)
+        .query()
+        .await?;
+    let transferred = logs
+        .iter()
+        .fold(U256::ZERO, |total, log| total + log.data.value);
+    println!("{} Transfer event(s) moving {} in total", logs.len(), transferred);
+
+    // The input holds the block header and receipts; the guest commits to that block
+    let input = env.into_input().await?;
+    let env = ExecutorEnv::builder()
+        .write(&input)?
+        .write(&token)?
+        .build()?;
+    let receipt = tokio::task::spawn_blocking(move || {
+        default_prover().prove(env, TRANSFER_EVENTS_ELF)
+    })
+    .await??
+    .receipt;
+    receipt.verify(TRANSFER_EVENTS_ID)?;
+
+    println!(
+        "Proved; the {}-byte journal and the seal can go to a contract that validates the commitment",
+        receipt.journal.bytes.len()
+    );
+    Ok(())
+}
+"#;
+
+const HISTORY_GUEST: &str = r#"//! Proves a token balance at a past block. The input chains that block to a recent one through
+//! beacon block roots, and the journal commits to the recent one, which a contract can validate
+//! for about a day instead of the last 256 blocks.
+
+use alloy_primitives::Address;
+use alloy_sol_types::{sol, SolValue};
+use risc0_steel::{
+    ethereum::{EthEvmInput, ETH_SEPOLIA_CHAIN_SPEC},
+    Commitment, Contract,
+};
+use risc0_zkvm::guest::env;
+
+sol! {
+    interface IERC20 {
+        function balanceOf(address account) external view returns (uint256);
+    }
+}
+
+sol! {
+    struct Journal {
+        Commitment commitment;
+        address tokenContract;
+        address account;
+        uint256 balance;
+    }
+}
+
+fn main() {
+    let input: EthEvmInput = env::read();
+    let token: Address = env::read();
+    let account: Address = env::read();
+
+    // Verifies the state of the past block, and the chain of beacon roots from it to the block
+    // the commitment names
+    let env = input.into_env(&ETH_SEPOLIA_CHAIN_SPEC);
+    let returns = Contract::new(token, &env)
+        .call_builder(&IERC20::balanceOfCall { account })
+        .call();
+
+    let journal = Journal {
+        commitment: env.into_commitment(),
+        tokenContract: token,
+        account,
+        balance: __BALANCE__,
+    };
+    env::commit_slice(&journal.abi_encode());
+}
+"#;
+
+const HISTORY_HOST: &str = r#"//! Reads a token balance at a past block and proves it with the historical_balance guest,
+//! committing to the latest block through the beacon chain. Generated by steel-tui.
+//!
+//! ETH_RPC_URL=... BEACON_API_URL=... TOKEN_CONTRACT=0x... ACCOUNT=0x... EXECUTION_BLOCK=<number> \
+//!     cargo run --bin historical_balance
+
+use alloy::{primitives::Address, sol};
+use anyhow::{Context, Result};
+use __METHODS__::{HISTORICAL_BALANCE_ELF, HISTORICAL_BALANCE_ID};
+use risc0_steel::{
+    ethereum::{EthEvmEnv, ETH_SEPOLIA_CHAIN_SPEC},
+    host::BlockNumberOrTag,
+    Contract,
+};
+use risc0_zkvm::{default_prover, ExecutorEnv};
+use url::Url;
+
+sol! {
+    interface IERC20 {
+        function balanceOf(address account) external view returns (uint256);
+    }
+}
+
+fn var(name: &str) -> Result<String> {
+    std::env::var(name).with_context(|| format!("{} is not set", name))
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let rpc_url: Url = var("ETH_RPC_URL")?.parse()?;
+    // A consensus client's REST API, e.g. a provider's beacon endpoint for the same chain
+    let beacon_api_url: Url = var("BEACON_API_URL")?.parse()?;
+    let token: Address = var("TOKEN_CONTRACT")?.parse()?;
+    let account: Address = var("ACCOUNT")?.parse()?;
+    let execution_block: u64 = var("EXECUTION_BLOCK")?
+        .parse()
+        .context("EXECUTION_BLOCK is not a number")?;
+
+    // State is read at the execution block; the commitment names the latest block, whose
+    // beacon root contracts can look up with EIP-4788 for 8191 slots
+    let mut env = EthEvmEnv::builder()
+        .rpc(rpc_url)
+        .beacon_api(beacon_api_url)
+        .block_number(execution_block)
+        .commitment_block_number_or_tag(BlockNumberOrTag::Latest)
+        .chain_spec(&ETH_SEPOLIA_CHAIN_SPEC)
+        .build()
+        .await?;
+
+    let returns = Contract::preflight(token, &mut env)
+        .call_builder(&IERC20::balanceOfCall { account })
+        .call()
+        .await?;
+    println!("balanceOf at block {}: {:?}", execution_block, returns);
+
+    let input = env.into_input().await?;
+    let env = ExecutorEnv::builder()
+        .write(&input)?
+        .write(&token)?
+        .write(&account)?
+        .build()?;
+    let receipt = tokio::task::spawn_blocking(move || {
+        default_prover().prove(env, HISTORICAL_BALANCE_ELF)
+    })
+    .await??
+    .receipt;
+    receipt.verify(HISTORICAL_BALANCE_ID)?;
+
+    println!(
+        "Proved; Steel.validateCommitment accepts the journal's commitment for about a day"
+    );
+    Ok(())
+}
+"#;
+
+const FORK_TEST: &str = r#"// SPDX-License-Identifier: Apache-2.0
+pragma solidity ^0.8.20;
+
+import {Test} from "forge-std/Test.sol";
+
+/// @notice Checks the deployment the project relies on, against a fork of __LABEL__:
+/// `FOUNDRY_PROFILE=fork forge test`. Skipped on any other chain, e.g. plain `forge test`.
+contract __CONTRACT__ForkTest is Test {
+    uint256 constant CHAIN_ID = __CHAIN_ID__;
+    address constant VERIFIER_ROUTER = __ROUTER__;
+
+    function test_ForksTheChain() public {
+        if (block.chainid != CHAIN_ID) vm.skip(true);
+        assertEq(block.chainid, CHAIN_ID);
+    }
+
+    function test_VerifierRouterIsDeployed() public {
+        if (block.chainid != CHAIN_ID) vm.skip(true);
+        assertGt(VERIFIER_ROUTER.code.length, 0, "no verifier router on the fork");
+    }
+}
+"#;
+
+const OP_STACK_NOTES: &str = r#"# Running on __LABEL__
+
+This project was created from the erc20-counter example for __LABEL__, an OP Stack chain.
+What differs from the Ethereum-oriented example:
+
+- **Chain ID** __CHAIN_ID__. steel-tui tests and deploys on __LABEL__ for this project, passing
+  `CHAIN_ID`, `VERIFIER_ROUTER_ADDRESS` and `EXPLORER_URL` to the scripts. Change it with
+  **Chain** in the toolbox.
+- **Verifier**: RISC Zero's router on __LABEL__ is `__ROUTER__`. Don't reuse the
+  addresses of Ethereum deployments; they differ per chain.
+- **Fork tests**: `FOUNDRY_PROFILE=fork forge test` runs the tests against a fork of the chain,
+  through the `__ALIAS__` endpoint in foundry.toml. Point it at your own provider for anything
+  beyond a few requests.
+- **Steel commitments** name an L2 block. `Steel.validateCommitment` checks block hashes with
+  `blockhash`, which only reaches the last 256 blocks: with 2-second blocks that's under
+  9 minutes, against about 50 on Ethereum. Submit proofs promptly, or commit to an older block
+  through a beacon or history commitment.
+- **Chain spec**: the guest and host use Ethereum's chain spec. To prove OP Stack blocks
+  themselves, switch to `risc0-op-steel` from the same risc0-ethereum release, whose
+  environment knows the L2's block format.
+- **Fees** include the L1 data fee, which `forge script` estimates separately; keep some
+  headroom in the deployer's balance.
+"#;
+
+const CONTRACT: &str = r#"// SPDX-License-Identifier: Apache-2.0
+pragma solidity ^0.8.20;
+
+import {IRiscZeroVerifier} from "risc0/IRiscZeroVerifier.sol";
+
+/// @notice Acts on results proven by the zkVM guest with image ID `imageId`.
+contract __NAME__ {
+    /// @notice RISC Zero verifier contract, e.g. the router deployed on the target chain.
+    IRiscZeroVerifier public immutable verifier;
+    /// @notice Image ID of the only guest whose proofs are accepted.
+    bytes32 public immutable imageId;
+
+    constructor(IRiscZeroVerifier _verifier, bytes32 _imageId) {
+        verifier = _verifier;
+        imageId = _imageId;
+    }
+
+    /// @notice Checks that `seal` proves the guest committed `journal`, then acts on it.
+    function submit(bytes calldata journal, bytes calldata seal) external {
+        verifier.verify(seal, imageId, sha256(journal));
+
+        // TODO: decode the journal and act on it
+    }
+}
+"#;
+
+const DEPLOY_SCRIPT: &str = r#"// SPDX-License-Identifier: Apache-2.0
+pragma solidity ^0.8.20;
+
+import {Script, console2} from "forge-std/Script.sol";
+import {IRiscZeroVerifier} from "risc0/IRiscZeroVerifier.sol";
+import {__NAME__} from "__IMPORT__";
+
+/// @notice Deploys __NAME__ against the verifier at VERIFIER_ADDRESS, accepting proofs of the
+/// guest with image ID IMAGE_ID.
+contract Deploy__NAME__ is Script {
+    function run() external {
+        IRiscZeroVerifier verifier = IRiscZeroVerifier(vm.envAddress("VERIFIER_ADDRESS"));
+        bytes32 imageId = vm.envBytes32("IMAGE_ID");
+
+        // Signers forge drives itself (--account, --ledger) don't set a key
+        uint256 deployerKey = vm.envOr("ETH_WALLET_PRIVATE_KEY", uint256(0));
+        if (deployerKey != 0) {
+            vm.startBroadcast(deployerKey);
+        } else {
+            vm.startBroadcast();
+        }
+        __NAME__ deployed = new __NAME__(verifier, imageId);
+        vm.stopBroadcast();
+
+        console2.log("Deployed __NAME__ to", address(deployed));
+    }
+}
+"#;
+
+const TEST: &str = r#"// SPDX-License-Identifier: Apache-2.0
+pragma solidity ^0.8.20;
+
+import {Test} from "forge-std/Test.sol";
+import {RiscZeroReceipt} from "risc0/IRiscZeroVerifier.sol";
+import {RiscZeroMockVerifier} from "risc0/test/RiscZeroMockVerifier.sol";
+import {__NAME__} from "__IMPORT__";
+
+contract __NAME__Test is Test {
+    bytes32 constant IMAGE_ID = bytes32(uint256(1));
+
+    RiscZeroMockVerifier verifier;
+    __NAME__ target;
+
+    function setUp() public {
+        verifier = new RiscZeroMockVerifier(bytes4(0));
+        target = new __NAME__(verifier, IMAGE_ID);
+    }
+
+    function test_AcceptsProvenJournal() public {
+        bytes memory journal = abi.encode(uint256(42));
+        RiscZeroReceipt memory receipt = verifier.mockProve(IMAGE_ID, sha256(journal));
+        target.submit(journal, receipt.seal);
+    }
+
+    function test_RejectsOtherJournal() public {
+        bytes memory journal = abi.encode(uint256(42));
+        RiscZeroReceipt memory receipt = verifier.mockProve(IMAGE_ID, sha256(journal));
+        vm.expectRevert();
+        target.submit(abi.encode(uint256(43)), receipt.seal);
+    }
+}
+"#;
+
+/// Adds a guest crate under `methods/guest/<name>`, lists it in `methods/Cargo.toml` so
+/// risc0-build embeds it, and stubs a host binary in `apps/src/bin/<name>.rs`.
+pub fn add_guest(root: &Path, name: &str) -> Result<Vec<String>> {
+    // Reuse the existing guest's zkVM dependency so both build against the same version
+    let existing = fs::read_to_string(root.join("methods/guest/Cargo.toml")).unwrap_or_default();
+    let risc0_zkvm =
+        dependency(&existing, "risc0-zkvm").unwrap_or_else(|| DEFAULT_RISC0_ZKVM.to_string());
+    add_guest_with(root, name, &risc0_zkvm, GUEST_MAIN, HOST_STUB)
+}
+
+/// The event-query template: a `transfer_events` guest next to the example's, which proves a
+/// token's Transfer events with Steel, and the host binary that queries and proves them.
+pub fn add_event_query(root: &Path) -> Result<Vec<String>> {
+    add_steel_guest(
+        root,
+        "transfer_events",
+        "unstable-event",
+        EVENT_QUERY_GUEST,
+        EVENT_QUERY_HOST,
+    )
+}
+
+/// The history template: a `historical_balance` guest that reads a token balance at a past
+/// block but commits to a recent one, and the host binary that builds the history input with
+/// the beacon API, so a contract can validate the commitment long after the block it read.
+pub fn add_history(root: &Path) -> Result<Vec<String>> {
+    // The return of a call is wrapped in a struct before alloy 1.0; follow the example's guest
+    let wrapped = fs::read_dir(root.join("methods/guest/src/bin"))
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| fs::read_to_string(entry.ok()?.path()).ok())
+        .any(|source| source.contains("._0"));
+    let balance = if wrapped { "returns._0" } else { "returns" };
+    add_steel_guest(
+        root,
+        "historical_balance",
+        "unstable-history",
+        &HISTORY_GUEST.replace("__BALANCE__", balance),
+        HISTORY_HOST,
+    )
+}
+
+/// Adds a guest using `feature` of risc0-steel, with the example guest's dependencies, and
+/// enables the feature for the host too. Runs once the manifests point at git.
+fn add_steel_guest(
+    root: &Path,
+    name: &str,
+    feature: &str,
+    main: &str,
+    host_stub: &str,
+) -> Result<Vec<String>> {
+    let existing = fs::read_to_string(root.join("methods/guest/Cargo.toml"))
+        .map_err(|e| eyre!("Could not read methods/guest/Cargo.toml: {}", e))?;
+    let mut dependencies = Vec::new();
+    for dependency_name in [
+        "alloy-primitives",
+        "alloy-sol-types",
+        "risc0-steel",
+        "risc0-zkvm",
+    ] {
+        let line = dependency(&existing, dependency_name).ok_or_else(|| {
+            eyre!(
+                "methods/guest/Cargo.toml has no {} dependency to build on",
+                dependency_name
+            )
+        })?;
+        dependencies.push(if dependency_name == "risc0-steel" {
+            with_feature(&line, feature)
+        } else {
+            line
+        });
+    }
+    let mut changes = add_guest_with(root, name, &dependencies.join("\n"), main, host_stub)?;
+
+    let apps_manifest = root.join("apps/Cargo.toml");
+    let apps = fs::read_to_string(&apps_manifest)
+        .map_err(|e| eyre!("Could not read {}: {}", apps_manifest.display(), e))?;
+    let updated = apps
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with("risc0-steel") {
+                with_feature(line, feature)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n";
+    if updated != apps {
+        fs::write(&apps_manifest, updated)?;
+        changes.push(format!(
+            "Enabled {} of risc0-steel in apps/Cargo.toml",
+            feature
+        ));
+    }
+    Ok(changes)
+}
+
+/// The op-stack template: points Forge at `chain` for fork tests, adds a fork test of the
+/// deployment the project relies on there, and notes on what differs from Ethereum.
+pub fn add_op_stack(root: &Path, chain: ChainPreset) -> Result<Vec<String>> {
+    let router = chain
+        .verifier_router()
+        .ok_or_else(|| eyre!("{} has no verifier router", chain.label()))?;
+    let alias = chain.label().to_lowercase().replace(' ', "_");
+    let fill = |template: &str| {
+        template
+            .replace("__LABEL__", chain.label())
+            .replace("__CONTRACT__", &chain.label().replace(' ', ""))
+            .replace("__CHAIN_ID__", &chain.chain_id().to_string())
+            .replace("__ROUTER__", router)
+            .replace("__ALIAS__", &alias)
+    };
+    let mut changes = Vec::new();
+
+    let foundry_toml = root.join("foundry.toml");
+    let mut foundry = fs::read_to_string(&foundry_toml)
+        .map_err(|e| eyre!("Could not read foundry.toml: {}", e))?;
+    let endpoint = format!("{} = \"{}\"", alias, chain.default_rpc());
+    foundry = if foundry.contains("[rpc_endpoints]") {
+        foundry.replacen(
+            "[rpc_endpoints]",
+            &format!("[rpc_endpoints]\n{}", endpoint),
+            1,
+        )
+    } else {
+        format!("{}\n\n[rpc_endpoints]\n{}\n", foundry.trim_end(), endpoint)
+    };
+    if !foundry.contains("[profile.fork]") {
+        foundry = format!(
+            "{}\n\n# FOUNDRY_PROFILE=fork forge test\n[profile.fork]\neth_rpc_url = \"{}\"\n",
+            foundry.trim_end(),
+            alias
+        );
+    }
+    fs::write(&foundry_toml, foundry)?;
+    changes.push(format!(
+        "Added the {} endpoint and a fork profile to foundry.toml",
+        alias
+    ));
+
+    let layout = FoundryLayout::read(root);
+    let test =
+        Path::new(&layout.test).join(format!("{}Fork.t.sol", chain.label().replace(' ', "")));
+    fs::create_dir_all(root.join(&layout.test))?;
+    fs::write(root.join(&test), fill(FORK_TEST))?;
+    changes.push(format!(
+        "Created {} against a fork of {}",
+        test.display(),
+        chain.label()
+    ));
+
+    fs::write(root.join("OP-STACK.md"), fill(OP_STACK_NOTES))?;
+    changes.push(String::from(
+        "Wrote OP-STACK.md on what differs from Ethereum",
+    ));
+    Ok(changes)
+}
+
+fn add_guest_with(
+    root: &Path,
+    name: &str,
+    dependencies: &str,
+    main: &str,
+    host_stub: &str,
+) -> Result<Vec<String>> {
+    if !Regex::new("^[a-z][a-z0-9_]*$")
+        .expect("valid regex")
+        .is_match(name)
+    {
+        return Err(eyre!(
+            "'{}' isn't a valid guest name: use lowercase letters, digits and underscores",
+            name
+        ));
+    }
+    let methods_manifest = root.join("methods").join("Cargo.toml");
+    let methods = fs::read_to_string(&methods_manifest)
+        .map_err(|e| eyre!("Could not read {}: {}", methods_manifest.display(), e))?;
+    let guest_dir = root.join("methods").join("guest").join(name);
+    if guest_dir.exists() {
+        return Err(eyre!("{} already exists", guest_dir.display()));
+    }
+    let mut changes = Vec::new();
+
+    fs::create_dir_all(guest_dir.join("src"))?;
+    fs::write(
+        guest_dir.join("Cargo.toml"),
+        GUEST_MANIFEST
+            .replace("__NAME__", name)
+            .replace("__DEPENDENCIES__", dependencies),
+    )?;
+    fs::write(
+        guest_dir.join("src/main.rs"),
+        main.replace("__NAME__", name),
+    )?;
+    changes.push(format!("Created methods/guest/{}", name));
+
+    fs::write(&methods_manifest, register_method(&methods, name)?)?;
+    changes.push(format!(
+        "Added guest/{} to the methods in methods/Cargo.toml",
+        name
+    ));
+
+    // The generated methods.rs defines the ELF and image ID constants for every guest
+    let lib = root.join("methods/src/lib.rs");
+    let include = r#"include!(concat!(env!("OUT_DIR"), "/methods.rs"));"#;
+    let content = fs::read_to_string(&lib).unwrap_or_default();
+    if !content.contains("/methods.rs\"") {
+        fs::create_dir_all(root.join("methods/src"))?;
+        fs::write(&lib, format!("{}\n{}\n", content.trim_end(), include))?;
+        changes.push(String::from(
+            "Included the generated methods in methods/src/lib.rs",
+        ));
+    }
+
+    let workspace = root.join("Cargo.toml");
+    if let Ok(content) = fs::read_to_string(&workspace) {
+        let excluded = format!("methods/guest/{}", name);
+        let updated = exclude_from_workspace(&content, &excluded);
+        if updated != content {
+            fs::write(&workspace, updated)?;
+            changes.push(format!(
+                "Excluded {} from the workspace in Cargo.toml",
+                excluded
+            ));
+        }
+    }
+
+    let methods_crate = package_name(&methods)
+        .ok_or_else(|| eyre!("methods/Cargo.toml has no package name"))?
+        .replace('-', "_");
+    let host = root.join("apps/src/bin").join(format!("{}.rs", name));
+    if host.exists() {
+        changes.push(format!(
+            "Left apps/src/bin/{}.rs alone; it already exists",
+            name
+        ));
+    } else {
+        fs::create_dir_all(root.join("apps/src/bin"))?;
+        fs::write(
+            &host,
+            host_stub
+                .replace("__METHODS__", &methods_crate)
+                .replace("__UPPER__", &name.to_uppercase())
+                .replace("__NAME__", name),
+        )?;
+        changes.push(format!(
+            "Created apps/src/bin/{}.rs to prove it from the host",
+            name
+        ));
+    }
+    Ok(changes)
+}
+
+/// Adds a contract that accepts proofs through the RISC Zero verifier, with a forge script that
+/// deploys it and a test against the mock verifier. Directories follow foundry.toml.
+pub fn add_contract(root: &Path, name: &str) -> Result<Vec<String>> {
+    if !Regex::new("^[A-Z][A-Za-z0-9]*$")
+        .expect("valid regex")
+        .is_match(name)
+    {
+        return Err(eyre!(
+            "'{}' isn't a valid contract name: start with a capital letter, then letters and digits",
+            name
+        ));
+    }
+    let layout = FoundryLayout::read(root);
+    let contract = Path::new(&layout.src).join(format!("{}.sol", name));
+    let script = Path::new(&layout.script).join(format!("Deploy{}.s.sol", name));
+    let test = Path::new(&layout.test).join(format!("{}.t.sol", name));
+    for path in [&contract, &script, &test] {
+        if root.join(path).exists() {
+            return Err(eyre!("{} already exists", path.display()));
+        }
+    }
+
+    let mut changes = Vec::new();
+    for (path, template, what) in [
+        (&contract, CONTRACT, "the contract"),
+        (&script, DEPLOY_SCRIPT, "a deployment script"),
+        (&test, TEST, "a test against the mock verifier"),
+    ] {
+        let import = relative_import(path, &contract);
+        fs::create_dir_all(root.join(path).parent().unwrap_or(root))?;
+        fs::write(
+            root.join(path),
+            template
+                .replace("__IMPORT__", &import)
+                .replace("__NAME__", name),
+        )?;
+        changes.push(format!("Created {} with {}", path.display(), what));
+    }
+    Ok(changes)
+}
+
+/// Where forge looks for contracts, tests and scripts, from foundry.toml's default profile.
+struct FoundryLayout {
+    src: String,
+    test: String,
+    script: String,
+}
+
+impl FoundryLayout {
+    fn read(root: &Path) -> Self {
+        let profile = fs::read_to_string(root.join("foundry.toml"))
+            .ok()
+            .and_then(|content| content.parse::<toml::Table>().ok())
+            .and_then(|table| table.get("profile")?.get("default")?.as_table().cloned())
+            .unwrap_or_default();
+        let dir = |key: &str, default: &str| {
+            profile
+                .get(key)
+                .and_then(|value| value.as_str())
+                .unwrap_or(default)
+                .trim_end_matches('/')
+                .to_string()
+        };
+        Self {
+            src: dir("src", "src"),
+            test: dir("test", "test"),
+            script: dir("script", "script"),
+        }
+    }
+}
+
+/// Import path of `target` from the file at `from`, both relative to the project root.
+fn relative_import(from: &Path, target: &Path) -> String {
+    let depth = from.parent().map_or(0, |dir| dir.components().count());
+    let up = if depth == 0 {
+        String::from("./")
+    } else {
+        "../".repeat(depth)
+    };
+    format!("{}{}", up, target.display())
+}
+
+/// Adds `guest/<name>` to `methods` under `[package.metadata.risc0]`.
+fn register_method(manifest: &str, name: &str) -> Result<String> {
+    let methods = Regex::new(r#"(?m)^(\s*methods\s*=\s*\[)([^\]]*)\]"#).expect("valid regex");
+    let entry = format!("\"guest/{}\"", name);
+    if let Some(caps) = methods.captures(manifest) {
+        let existing = caps[2].trim().trim_end_matches(',');
+        let list = if existing.is_empty() {
+            entry
+        } else {
+            format!("{}, {}", existing, entry)
+        };
+        return Ok(methods
+            .replace(manifest, |caps: &regex::Captures| {
+                format!("{}{}]", &caps[1], list)
+            })
+            .to_string());
+    }
+    if manifest.contains("[package.metadata.risc0]") {
+        return Err(eyre!(
+            "methods/Cargo.toml has a [package.metadata.risc0] table without a methods list"
+        ));
+    }
+    Ok(format!(
+        "{}\n\n[package.metadata.risc0]\nmethods = [\"guest\", {}]\n",
+        manifest.trim_end(),
+        entry
+    ))
+}
+
+/// Adds `path` to `exclude` under `[workspace]`, creating the key if needed.
+fn exclude_from_workspace(manifest: &str, path: &str) -> String {
+    let entry = format!("\"{}\"", path);
+    if manifest.contains(&entry) {
+        return manifest.to_string();
+    }
+    let exclude = Regex::new(r#"(?m)^(\s*exclude\s*=\s*\[)([^\]]*)\]"#).expect("valid regex");
+    if let Some(caps) = exclude.captures(manifest) {
+        let existing = caps[2].trim().trim_end_matches(',');
+        let list = if existing.is_empty() {
+            entry
+        } else {
+            format!("{}, {}", existing, entry)
+        };
+        return exclude
+            .replace(manifest, |caps: &regex::Captures| {
+                format!("{}{}]", &caps[1], list)
+            })
+            .to_string();
+    }
+    manifest.replacen(
+        "[workspace]",
+        &format!("[workspace]\nexclude = [{}]", entry),
+        1,
+    )
+}
+
+/// The line declaring `name` in a manifest's dependencies, unless it's inherited from a
+/// workspace, which a guest with a workspace of its own can't do.
+fn dependency(manifest: &str, name: &str) -> Option<String> {
+    manifest
+        .lines()
+        .find(|line| {
+            line.trim_start()
+                .strip_prefix(name)
+                .is_some_and(|rest| rest.trim_start().starts_with('='))
+        })
+        .filter(|line| !line.contains("workspace"))
+        .map(|line| line.trim().to_string())
+}
+
+/// `line`, an inline dependency table, with `feature` enabled.
+fn with_feature(line: &str, feature: &str) -> String {
+    let quoted = format!("\"{}\"", feature);
+    if line.contains(&quoted) {
+        return line.to_string();
+    }
+    if let Some(start) = line.find("features = [") {
+        let at = start + "features = [".len();
+        let separator = if line[at..].trim_start().starts_with(']') {
+            ""
+        } else {
+            ", "
+        };
+        return format!("{}{}{}{}", &line[..at], quoted, separator, &line[at..]);
+    }
+    match line.rfind('}') {
+        Some(end) => format!("{}, features = [{}] }}", line[..end].trim_end(), quoted),
+        None => line.to_string(),
+    }
+}
+
+fn package_name(manifest: &str) -> Option<String> {
+    Regex::new(r#"(?m)^name\s*=\s*"([^"]+)""#)
+        .expect("valid regex")
+        .captures(manifest)
+        .map(|caps| caps[1].to_string())
+}
@@ -0,0 +1,32 @@
+//! The project's Forge scripts, every `*.s.sol` under `script/`, which the toolbox runs with
+//! `forge script`.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Scripts under `root/script`, relative to `root` and sorted.
+pub fn discover(root: &Path) -> Vec<PathBuf> {
+    let mut scripts = Vec::new();
+    collect(&root.join("script"), &mut scripts);
+    let mut scripts: Vec<PathBuf> = scripts
+        .into_iter()
+        .filter_map(|path| path.strip_prefix(root).ok().map(Path::to_path_buf))
+        .collect();
+    scripts.sort();
+    scripts
+}
+
+fn collect(dir: &Path, scripts: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+        if path.is_dir() {
+            collect(&path, scripts);
+        } else if path.to_string_lossy().ends_with(".s.sol") {
+            scripts.push(path);
+        }
+    }
+}
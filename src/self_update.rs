@@ -0,0 +1,196 @@
+use crate::http;
+use color_eyre::{eyre::eyre, Result};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{fs, sync::mpsc, thread};
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/sashaaldrick/tui/releases/latest";
+
+/// Release asset listing `<sha256>  <file name>` for every binary.
+const CHECKSUMS_ASSET: &str = "sha256sums.txt";
+
+#[derive(Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    pub assets: Vec<Asset>,
+}
+
+#[derive(Deserialize)]
+pub struct Asset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl Release {
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+
+    /// Whether this release is newer than the running binary.
+    pub fn is_newer(&self) -> bool {
+        parse_version(self.version()) > parse_version(current_version())
+    }
+
+    fn asset(&self, name: &str) -> Result<&Asset> {
+        self.assets
+            .iter()
+            .find(|asset| asset.name == name)
+            .ok_or_else(|| eyre!("Release {} has no asset named '{}'", self.tag_name, name))
+    }
+}
+
+pub fn current_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+/// Parses `X.Y.Z` into a comparable tuple, ignoring pre-release suffixes.
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = version.split(['.', '-']).map(|p| p.parse::<u64>().ok());
+    Some((parts.next()??, parts.next()??, parts.next()??))
+}
+
+pub fn latest_release() -> Result<Release> {
+    http::get_json(LATEST_RELEASE_URL)
+}
+
+/// Name of the release asset built for the running platform.
+pub fn asset_name() -> String {
+    format!(
+        "steel-tui-{}-{}{}",
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        std::env::consts::EXE_SUFFIX
+    )
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Swaps the running executable for `bytes`.
+fn replace_executable(bytes: &[u8]) -> Result<()> {
+    let exe = std::env::current_exe()?;
+    let staged = exe.with_extension("new");
+    fs::write(&staged, bytes)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
+    }
+    #[cfg(windows)]
+    {
+        // A running executable can't be overwritten on Windows, but it can be renamed
+        let old = exe.with_extension("old");
+        let _ = fs::remove_file(&old);
+        fs::rename(&exe, &old)?;
+    }
+
+    fs::rename(&staged, &exe)?;
+    Ok(())
+}
+
+/// What an update running in the background reports.
+pub enum UpdateEvent {
+    Log(String),
+    /// Bytes of the binary downloaded so far, and the total if known.
+    Progress(u64, Option<u64>),
+    /// Whether the binary was replaced, or why the update failed.
+    Done(std::result::Result<bool, String>),
+}
+
+/// Checks for a newer release and installs it. Returns whether the binary was replaced.
+pub fn update(
+    log: &mut dyn FnMut(String),
+    progress: &mut dyn FnMut(u64, Option<u64>),
+) -> Result<bool> {
+    log(format!("Current version: v{}", current_version()));
+    log("Checking for the latest release...".to_string());
+    let release = latest_release()?;
+
+    if !release.is_newer() {
+        log(format!(
+            "✓ Already up to date (latest is v{})",
+            release.version()
+        ));
+        return Ok(false);
+    }
+
+    let name = asset_name();
+    let binary = release.asset(&name)?;
+    let checksums = release.asset(CHECKSUMS_ASSET)?;
+
+    log(format!("Downloading {} from {}...", name, release.tag_name));
+    let bytes = http::download(&binary.browser_download_url, progress)?;
+
+    let expected = http::get_text(&checksums.browser_download_url)?
+        .lines()
+        .find_map(|line| {
+            let (hash, file) = line.split_once(char::is_whitespace)?;
+            (file.trim().trim_start_matches('*') == name).then(|| hash.to_lowercase())
+        })
+        .ok_or_else(|| eyre!("No checksum listed for '{}'", name))?;
+    let actual = sha256_hex(&bytes);
+    if actual != expected {
+        return Err(eyre!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            name,
+            expected,
+            actual
+        ));
+    }
+    log("✓ Checksum verified".to_string());
+
+    replace_executable(&bytes)?;
+    log(format!(
+        "✓ Updated to v{}. Restart steel-tui to use it.",
+        release.version()
+    ));
+    Ok(true)
+}
+
+/// Checks for a newer release on a background thread. The receiver yields a banner message
+/// only if an update is available; network errors are silently ignored.
+pub fn spawn_update_check() -> mpsc::Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        if let Ok(release) = latest_release() {
+            if release.is_newer() {
+                let _ = tx.send(format!(
+                    "v{} available — run `steel-tui self-update`",
+                    release.version()
+                ));
+            }
+        }
+    });
+    rx
+}
+
+/// Runs [`update`] on a background thread. The receiver yields its log and download progress,
+/// then how it ended.
+pub fn spawn_update() -> mpsc::Receiver<UpdateEvent> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let log = tx.clone();
+        let progress = tx.clone();
+        let result = update(
+            &mut |line| {
+                let _ = log.send(UpdateEvent::Log(line));
+            },
+            &mut |done, total| {
+                let _ = progress.send(UpdateEvent::Progress(done, total));
+            },
+        );
+        let _ = tx.send(UpdateEvent::Done(result.map_err(|e| e.to_string())));
+    });
+    rx
+}
+
+/// Entry point for `steel-tui self-update`.
+pub fn run() -> Result<()> {
+    update(&mut |line| println!("{}", line), &mut |_, _| {})?;
+    Ok(())
+}
@@ -0,0 +1,57 @@
+//! Where the last session left off: the project, the screen, the prover settings and the end of
+//! the output, saved on exit so the next launch can offer to pick up from there.
+
+use crate::{app::ToolboxItem, paths, prover::ProverBackend};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+/// How many lines of output are kept.
+pub const OUTPUT_TAIL: usize = 200;
+
+#[derive(Serialize, Deserialize)]
+pub struct Session {
+    /// RFC 3339 time the session ended.
+    pub saved_at: String,
+    pub project: PathBuf,
+    /// The toolbox screen that was open, if it's one that can be opened again safely.
+    pub screen: Option<ToolboxItem>,
+    pub prover: ProverBackend,
+    pub output: Vec<String>,
+}
+
+impl Session {
+    /// e.g. `my-app, Test history`.
+    pub fn describe(&self) -> String {
+        let name = self
+            .project
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.project.display().to_string());
+        match self.screen {
+            Some(screen) => format!("{}, {}", name, screen.label()),
+            None => name,
+        }
+    }
+}
+
+/// The last session, if there is one and its project is still there.
+pub fn load() -> Option<Session> {
+    let content = fs::read_to_string(paths::session_file()).ok()?;
+    let session: Session = serde_json::from_str(&content).ok()?;
+    session.project.is_dir().then_some(session)
+}
+
+pub fn save(session: &Session) -> Result<()> {
+    fs::create_dir_all(paths::state_dir())?;
+    fs::write(
+        paths::session_file(),
+        serde_json::to_string_pretty(session)?,
+    )?;
+    Ok(())
+}
+
+/// Forgets the last session, once it was restored or turned down.
+pub fn clear() {
+    let _ = fs::remove_file(paths::session_file());
+}
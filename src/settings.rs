@@ -0,0 +1,147 @@
+//! User preferences, kept in `settings.toml` in the config directory.
+
+use crate::{
+    chain_presets::ChainPreset,
+    http, paths,
+    rpc_presets::{Network, RpcProvider},
+    supervisor, tool_versions,
+};
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Per-crate levels used until the user sets their own.
+const DEFAULT_LOG_OVERRIDES: &str = "risc0_steel=debug";
+
+/// `RUST_LOG`'s default level for the host and cargo commands the app runs.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub const ALL: &'static [LogLevel] = &[
+        LogLevel::Error,
+        LogLevel::Warn,
+        LogLevel::Info,
+        LogLevel::Debug,
+        LogLevel::Trace,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+}
+
+/// How install and test progress is shown.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressView {
+    /// Each step's description and the streamed output.
+    #[default]
+    Detailed,
+    /// One line per step with its status, and only the app's own messages as output.
+    Compact,
+}
+
+impl ProgressView {
+    pub fn next(self) -> Self {
+        match self {
+            ProgressView::Detailed => ProgressView::Compact,
+            ProgressView::Compact => ProgressView::Detailed,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProgressView::Detailed => "Detailed",
+            ProgressView::Compact => "Compact",
+        }
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Proxy for HTTP requests and git, overriding `HTTPS_PROXY`/`HTTP_PROXY`.
+    pub proxy: Option<String>,
+    /// Where test and deploy flows connect when `ETH_RPC_URL` isn't set. The API key lives
+    /// in the credentials store, not here.
+    pub rpc_provider: RpcProvider,
+    pub rpc_network: Network,
+    /// Chain picked in the chain picker; its details are passed to test and deploy scripts.
+    pub chain: ChainPreset,
+    /// Fetch the Forge dependencies' whole history instead of only their latest commit.
+    pub full_submodule_history: bool,
+    /// Clone new projects from the cached template as it is, without fetching what changed
+    /// upstream since it was cached.
+    pub skip_template_refresh: bool,
+    /// Registry mirror written into new projects' `.cargo/config.toml`.
+    pub cargo_mirror: Option<String>,
+    /// Sets `net.git-fetch-with-cli` there too, so git dependencies go through the git CLI
+    /// and its proxy and credential configuration.
+    pub cargo_git_fetch_with_cli: bool,
+    /// Version manager file new projects pin their Rust and Foundry versions in.
+    pub tool_versions: tool_versions::Format,
+    /// Show still indicators in place of the blinking cursor and spinners, for accessibility
+    /// and slow SSH connections.
+    pub reduced_motion: bool,
+    pub progress_view: ProgressView,
+    pub log_level: LogLevel,
+    /// Per-crate directives after the level, e.g. `risc0_steel=debug,risc0_zkvm=warn`. `None`
+    /// until changed, for the default.
+    pub log_overrides: Option<String>,
+}
+
+impl Settings {
+    /// The saved settings, or the defaults if there are none yet.
+    pub fn load() -> Result<Self> {
+        let file = paths::settings_file();
+        match fs::read_to_string(&file) {
+            Ok(content) => toml::from_str(&content)
+                .map_err(|e| eyre!("Invalid settings in {}: {}", file.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(eyre!("Could not read {}: {}", file.display(), e)),
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::create_dir_all(paths::config_dir())?;
+        fs::write(paths::settings_file(), toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn log_overrides(&self) -> &str {
+        self.log_overrides
+            .as_deref()
+            .unwrap_or(DEFAULT_LOG_OVERRIDES)
+    }
+
+    /// e.g. `info,risc0_steel=debug`.
+    pub fn rust_log(&self) -> String {
+        let overrides = self.log_overrides().trim().trim_matches(',');
+        if overrides.is_empty() {
+            self.log_level.label().to_string()
+        } else {
+            format!("{},{}", self.log_level.label(), overrides)
+        }
+    }
+
+    /// Makes process-wide settings take effect.
+    pub fn apply(&self) {
+        http::set_proxy(self.proxy.clone());
+        supervisor::set_rust_log(self.rust_log());
+    }
+}
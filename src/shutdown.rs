@@ -0,0 +1,100 @@
+//! The one way out when something goes wrong. Panics, fatal errors and a second interrupt all
+//! give the terminal back first, then stop our children and keep a crash report, and only then
+//! print, so nothing is lost on the alternate screen. A panic on a background thread only gets
+//! a crash report; its owner sees the thread's channel disconnect and reports that.
+
+use crate::{crash, redact, supervisor};
+use color_eyre::{
+    config::{HookBuilder, Theme},
+    Report, Result,
+};
+use crossterm::{
+    event::DisableMouseCapture,
+    execute,
+    terminal::{disable_raw_mode, LeaveAlternateScreen},
+};
+use std::{
+    io::stdout,
+    panic,
+    sync::OnceLock,
+    thread::{self, ThreadId},
+};
+
+/// The thread that installed the hooks, whose panic ends the app.
+static MAIN_THREAD: OnceLock<ThreadId> = OnceLock::new();
+
+/// Leaves raw mode, the alternate screen and mouse capture. Harmless when they aren't on.
+pub fn restore_terminal() {
+    let _ = execute!(stdout(), LeaveAlternateScreen, DisableMouseCapture);
+    let _ = disable_raw_mode();
+}
+
+/// Gives the terminal back and terminates everything we spawned, and nothing else. Returns how
+/// many children were stopped.
+pub fn stop() -> usize {
+    restore_terminal();
+    supervisor::terminate_all()
+}
+
+/// Installs color-eyre's error and panic reports, without colors in CI. A panic on the calling
+/// thread stops everything and writes a crash report before its report is printed.
+pub fn install_hooks(ci: bool) -> Result<()> {
+    MAIN_THREAD.get_or_init(|| thread::current().id());
+    crash::collect_tools();
+    let builder = if ci {
+        HookBuilder::default().theme(Theme::new())
+    } else {
+        HookBuilder::default()
+    };
+    let (panic_hook, eyre_hook) = builder.try_into_hooks()?;
+    eyre_hook.install()?;
+    panic::set_hook(Box::new(move |panic_info| {
+        let main = MAIN_THREAD.get() == Some(&thread::current().id());
+        if main {
+            stop();
+        }
+
+        let payload = panic_info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic_info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| String::from("unknown panic"));
+        let message = match panic_info.location() {
+            Some(location) => format!("{} at {}", payload, location),
+            None => payload,
+        };
+        let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+        let written = crash::write_report("panic", &message, &backtrace);
+        if !main {
+            // Printing would land on the app's screen; the report has it all
+            return;
+        }
+
+        eprintln!(
+            "{}",
+            redact::redact(&panic_hook.panic_report(panic_info).to_string())
+        );
+        match written {
+            Ok(path) => eprintln!(
+                "A crash report was written to {}. Please attach it when reporting this bug.",
+                path.display()
+            ),
+            Err(e) => eprintln!("Could not write a crash report: {}", e),
+        }
+    }));
+    Ok(())
+}
+
+/// Reports the error that ended the run. Only the app itself gets a crash report; subcommands
+/// report their own failures.
+pub fn report_error(error: &Report, crash_report: bool) {
+    stop();
+    eprintln!("Error: {}", redact::redact(&format!("{:?}", error)));
+    if crash_report {
+        match crash::write_report("error", &error.to_string(), &format!("{:?}", error)) {
+            Ok(path) => eprintln!("A crash report was written to {}.", path.display()),
+            Err(e) => eprintln!("Could not write a crash report: {}", e),
+        }
+    }
+}
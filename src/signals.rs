@@ -0,0 +1,38 @@
+//! SIGINT/SIGTERM handling. The first signal asks the app to take its normal abort path (stop
+//! children, restore the terminal, print a summary); a second one exits immediately.
+
+use crate::{exit_code::Outcome, shutdown};
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Installs the handler for SIGINT, SIGTERM and SIGHUP (Ctrl+C and Ctrl+Break on Windows).
+pub fn install() -> Result<()> {
+    ctrlc::set_handler(|| {
+        if INTERRUPTED.swap(true, Ordering::SeqCst) {
+            // The main loop didn't react to the first signal, e.g. it's blocked on a command
+            shutdown::stop();
+            eprintln!("Interrupted again, exiting immediately.");
+            std::process::exit(Outcome::UserAbort.code().into());
+        }
+    })?;
+    Ok(())
+}
+
+/// Requests an abort from inside the app. Raw mode delivers Ctrl+C as a key, not a signal.
+pub fn interrupt() {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Whether `key` is Ctrl+C, which raw mode delivers as a key press instead of SIGINT.
+pub fn is_interrupt_key(key: &KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+        && key.code == KeyCode::Char('c')
+}
@@ -0,0 +1,188 @@
+//! How the deploy wizard signs transactions: a raw key, a decrypted geth-style keystore, or a
+//! signer that forge talks to directly (a `cast wallet` account or a hardware wallet).
+
+use crate::redact;
+use color_eyre::{eyre::eyre, Result};
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SignerKind {
+    PrivateKey,
+    Keystore,
+    CastAccount,
+    Ledger,
+    Trezor,
+}
+
+impl SignerKind {
+    pub const ALL: &'static [SignerKind] = &[
+        SignerKind::PrivateKey,
+        SignerKind::Keystore,
+        SignerKind::CastAccount,
+        SignerKind::Ledger,
+        SignerKind::Trezor,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SignerKind::PrivateKey => "Private key",
+            SignerKind::Keystore => "Keystore file (JSON)",
+            SignerKind::CastAccount => "cast wallet account",
+            SignerKind::Ledger => "Ledger",
+            SignerKind::Trezor => "Trezor",
+        }
+    }
+
+    /// Label of the free-text field this kind needs, if any.
+    pub fn detail_label(self) -> Option<&'static str> {
+        match self {
+            SignerKind::PrivateKey => Some("Private key"),
+            SignerKind::Keystore => Some("Keystore path"),
+            SignerKind::CastAccount => Some("Account name"),
+            SignerKind::Ledger | SignerKind::Trezor => None,
+        }
+    }
+
+    pub fn needs_password(self) -> bool {
+        self == SignerKind::Keystore
+    }
+}
+
+pub enum Signer {
+    PrivateKey(String),
+    Keystore {
+        path: PathBuf,
+        address: Option<String>,
+        private_key: String,
+    },
+    /// An account imported with `cast wallet import`; forge prompts for its password itself.
+    CastAccount(String),
+    Ledger,
+    Trezor,
+}
+
+impl Signer {
+    /// Builds a signer from the wizard's fields, decrypting keystores up front so a wrong
+    /// password fails here instead of halfway through a deployment.
+    pub fn new(kind: SignerKind, detail: &str, password: &str) -> Result<Self> {
+        let detail = detail.trim();
+        if kind.detail_label().is_some() && detail.is_empty() {
+            return Err(eyre!(
+                "{} is required",
+                kind.detail_label().unwrap_or_default()
+            ));
+        }
+
+        let signer = match kind {
+            SignerKind::PrivateKey => Signer::PrivateKey(detail.to_string()),
+            SignerKind::Keystore => {
+                let path = expand_home(detail);
+                let key = eth_keystore::decrypt_key(&path, password)
+                    .map_err(|e| eyre!("Could not decrypt {}: {}", path.display(), e))?;
+                let private_key = format!(
+                    "0x{}",
+                    key.iter().map(|b| format!("{:02x}", b)).collect::<String>()
+                );
+                Signer::Keystore {
+                    address: keystore_address(&path),
+                    path,
+                    private_key,
+                }
+            }
+            SignerKind::CastAccount => Signer::CastAccount(detail.to_string()),
+            SignerKind::Ledger => Signer::Ledger,
+            SignerKind::Trezor => Signer::Trezor,
+        };
+        if let Some(key) = signer.private_key() {
+            redact::register(key);
+        }
+        Ok(signer)
+    }
+
+    /// The raw key, for signers whose key we hold.
+    pub fn private_key(&self) -> Option<&str> {
+        match self {
+            Signer::PrivateKey(key)
+            | Signer::Keystore {
+                private_key: key, ..
+            } => Some(key),
+            _ => None,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Signer::PrivateKey(_) => String::from("private key"),
+            Signer::Keystore { path, address, .. } => match address {
+                Some(address) => format!("keystore {} ({})", path.display(), address),
+                None => format!("keystore {}", path.display()),
+            },
+            Signer::CastAccount(name) => format!("cast wallet account '{}'", name),
+            Signer::Ledger => String::from("Ledger"),
+            Signer::Trezor => String::from("Trezor"),
+        }
+    }
+
+    /// Environment for the deploy command. Held keys go in `ETH_WALLET_PRIVATE_KEY`, which the
+    /// template's scripts read.
+    pub fn env(&self) -> Vec<(&'static str, String)> {
+        let mut env = Vec::new();
+        if let Some(key) = self.private_key() {
+            env.push(("ETH_WALLET_PRIVATE_KEY", key.to_string()));
+        }
+        if let Signer::Keystore {
+            address: Some(address),
+            ..
+        } = self
+        {
+            env.push(("ETH_WALLET_ADDRESS", address.clone()));
+        }
+        env
+    }
+
+    /// Extra `forge script` flags for signers forge drives itself.
+    pub fn forge_args(&self) -> Vec<String> {
+        match self {
+            Signer::CastAccount(name) => vec![String::from("--account"), name.clone()],
+            Signer::Ledger => vec![String::from("--ledger")],
+            Signer::Trezor => vec![String::from("--trezor")],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Accounts imported with `cast wallet import`, from foundry's keystore directory.
+pub fn cast_accounts() -> Vec<String> {
+    let Some(dir) = dirs::home_dir().map(|home| home.join(".foundry").join("keystores")) else {
+        return Vec::new();
+    };
+    let mut accounts: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    accounts.sort();
+    accounts
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Geth keystores record the account address in the clear.
+fn keystore_address(path: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let address = json.get("address")?.as_str()?;
+    Some(if address.starts_with("0x") {
+        address.to_string()
+    } else {
+        format!("0x{}", address)
+    })
+}
@@ -0,0 +1,101 @@
+//! Solidity compilers forge hasn't got. Forge keeps solc versions under svm's directory and
+//! downloads them as pragmas need them, unless it's offline or told not to; a build then fails
+//! with one of a few errors, and `forge build --use <version>` fetches the version.
+
+use regex::Regex;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// What forge says, lowercased, when the solc a build needs isn't there.
+const MISSING: &[&str] = &[
+    "no solc version exists that matches",
+    "solc version is not installed",
+    "solc not found",
+    "could not find solc",
+    "unable to find solc",
+    "failed to install solc",
+    "failed to resolve solc",
+];
+
+/// Where svm keeps installed compilers, `~/.svm/<version>/solc-<version>`, or under the data
+/// directory in newer releases.
+fn svm_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(dir) = std::env::var_os("SVM_HOME") {
+        dirs.push(PathBuf::from(dir));
+    }
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join(".svm"));
+    }
+    if let Some(data) = dirs::data_dir() {
+        dirs.push(data.join("svm"));
+    }
+    dirs
+}
+
+pub fn installed(version: &str) -> bool {
+    svm_dirs().iter().any(|dir| {
+        dir.join(version)
+            .join(format!("solc-{}", version))
+            .is_file()
+    })
+}
+
+/// The solc version a failed forge build was missing, if that's why it failed: the version
+/// named in the error, else the lowest one every pragma in the project accepts.
+pub fn missing_version(root: &Path, output: &[String]) -> Option<String> {
+    let error = output.iter().find(|line| {
+        let line = line.to_lowercase();
+        MISSING.iter().any(|missing| line.contains(missing))
+    })?;
+    let version = Regex::new(r"(\d+\.\d+\.\d+)").expect("valid regex");
+    let version = match version.captures(error) {
+        Some(captures) => captures[1].to_string(),
+        None => pragma_version(root)?,
+    };
+    (!installed(&version)).then_some(version)
+}
+
+/// The highest minimum version among the project's `pragma solidity` lines.
+fn pragma_version(root: &Path) -> Option<String> {
+    let pragma =
+        Regex::new(r"pragma\s+solidity\s+[\^~>=]*\s*(\d+)\.(\d+)\.(\d+)").expect("valid regex");
+    let mut sources = Vec::new();
+    for dir in ["src", "script", "test", "contracts"] {
+        collect(&root.join(dir), &mut sources);
+    }
+    sources
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .flat_map(|content| {
+            pragma
+                .captures_iter(&content)
+                .filter_map(|c| Some((c[1].parse().ok()?, c[2].parse().ok()?, c[3].parse().ok()?)))
+                .collect::<Vec<(u32, u32, u32)>>()
+        })
+        .max()
+        .map(|(major, minor, patch)| format!("{}.{}.{}", major, minor, patch))
+}
+
+fn collect(dir: &Path, sources: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+        if path.is_dir() {
+            collect(&path, sources);
+        } else if path.extension().is_some_and(|ext| ext == "sol") {
+            sources.push(path);
+        }
+    }
+}
+
+/// Builds with `version`, which makes forge download it first.
+pub fn install_command(version: &str) -> Command {
+    let mut command = Command::new("forge");
+    command.args(["build", "--use", version]);
+    command
+}
@@ -0,0 +1,54 @@
+//! Each install and test step's output in a file of its own, e.g. `03-moving-files.log` or
+//! `e2e-running-test.log`, under `logs/<session start>/` in the state directory. A failed step
+//! is then one file to read in full or attach to a bug report.
+
+use crate::paths;
+use color_eyre::Result;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// How many sessions' logs are kept.
+const KEPT_SESSIONS: usize = 20;
+
+pub struct StepLogs {
+    dir: PathBuf,
+}
+
+impl Default for StepLogs {
+    fn default() -> Self {
+        Self {
+            dir: paths::log_dir().join(chrono::Local::now().format("%Y%m%d-%H%M%S").to_string()),
+        }
+    }
+}
+
+impl StepLogs {
+    /// Writes `output` to `name`.log, replacing what an earlier run of the step left there.
+    /// The session's directory is made on the first write, dropping the oldest sessions'.
+    pub fn write(&self, name: &str, output: &[String]) -> Result<PathBuf> {
+        if !self.dir.exists() {
+            fs::create_dir_all(&self.dir)?;
+            prune(&paths::log_dir());
+        }
+        let file = self.dir.join(format!("{}.log", name));
+        fs::write(&file, output.join("\n") + "\n")?;
+        Ok(file)
+    }
+}
+
+fn prune(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut sessions: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    sessions.sort();
+    let excess = sessions.len().saturating_sub(KEPT_SESSIONS);
+    for session in &sessions[..excess] {
+        let _ = fs::remove_dir_all(session);
+    }
+}
@@ -0,0 +1,52 @@
+use color_eyre::Result;
+use serde::Serialize;
+use std::{collections::BTreeMap, fs, path::Path, path::PathBuf};
+
+/// Machine-readable record of a scaffolded project, written with `--output-json`.
+#[derive(Serialize, Default, Clone)]
+pub struct ProjectSummary {
+    pub project_path: PathBuf,
+    pub template: String,
+    /// Every example in a multi-example workspace, each in a directory of its own. Empty for a
+    /// project made from the template alone.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<String>,
+    pub repository: String,
+    pub branch: String,
+    /// risc0-ethereum crates pinned to a tag instead of the head of `branch`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+    /// Commit of the template repository the project was created from.
+    pub revision: Option<String>,
+    /// Cargo manifests whose risc0 dependencies were rewritten.
+    pub manifests: Vec<PathBuf>,
+    pub steps: Vec<StepDuration>,
+    pub total_seconds: f64,
+}
+
+#[derive(Serialize, Clone)]
+pub struct StepDuration {
+    pub step: String,
+    pub seconds: f64,
+}
+
+impl ProjectSummary {
+    pub fn record_step(&mut self, step: &str, elapsed: std::time::Duration) {
+        self.steps.push(StepDuration {
+            step: step.to_string(),
+            seconds: elapsed.as_secs_f64(),
+        });
+        self.total_seconds = self.steps.iter().map(|s| s.seconds).sum();
+    }
+
+    /// Prints the summary to stdout when `path` is `-`, otherwise writes it to `path`.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        if path == Path::new("-") {
+            println!("{}", json);
+        } else {
+            fs::write(path, json + "\n")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+//! Tracks every child process the app spawns so that cleanup, panics, and exit only ever
+//! terminate processes we started, never unrelated ones that happen to share a name.
+
+use crate::{clean, platform};
+use color_eyre::Result;
+use std::{
+    process::{Child, Command},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, MutexGuard,
+    },
+    time::{Duration, Instant},
+};
+
+struct Tracked {
+    pid: u32,
+    name: String,
+    started: Instant,
+    /// Owned handle for background children; foreground children are owned by their caller.
+    child: Option<Child>,
+}
+
+static CHILDREN: Mutex<Vec<Tracked>> = Mutex::new(Vec::new());
+static DEV_MODE: AtomicBool = AtomicBool::new(false);
+static RUST_LOG: Mutex<String> = Mutex::new(String::new());
+
+/// Foundry's and git's binaries, which read `RUST_LOG` too but aren't what it's set for.
+const NOT_HOST: &[&str] = &["forge", "cast", "anvil", "chisel", "git"];
+
+/// Snapshot of a supervised child, for display.
+pub struct ChildInfo {
+    pub pid: u32,
+    pub name: String,
+    pub running: bool,
+    pub uptime: Duration,
+}
+
+fn children() -> MutexGuard<'static, Vec<Tracked>> {
+    // A panic elsewhere must not stop us from cleaning up
+    CHILDREN.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Puts the child in its own process group so its descendants can be terminated with it.
+/// With dev mode on, also sets `RISC0_DEV_MODE` unless the command sets it itself (the
+/// benchmark does, per backend). Cargo and host commands get the log level from Settings,
+/// unless `RUST_LOG` is exported already.
+pub fn prepare(command: &mut Command) -> &mut Command {
+    if dev_mode() && !command.get_envs().any(|(key, _)| key == "RISC0_DEV_MODE") {
+        command.env("RISC0_DEV_MODE", "1");
+    }
+    let program = std::path::Path::new(command.get_program())
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let rust_log = RUST_LOG.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    if !rust_log.is_empty()
+        && !NOT_HOST.contains(&program.as_str())
+        && std::env::var_os("RUST_LOG").is_none()
+        && !command.get_envs().any(|(key, _)| key == "RUST_LOG")
+    {
+        command.env("RUST_LOG", rust_log);
+    }
+    platform::new_process_group(command)
+}
+
+/// The `RUST_LOG` cargo and host commands spawned from now on get.
+pub fn set_rust_log(rust_log: String) {
+    *RUST_LOG.lock().unwrap_or_else(|e| e.into_inner()) = rust_log;
+}
+
+/// Makes every child spawned from now on skip real proving.
+pub fn set_dev_mode(enabled: bool) {
+    DEV_MODE.store(enabled, Ordering::Relaxed);
+}
+
+pub fn dev_mode() -> bool {
+    DEV_MODE.load(Ordering::Relaxed)
+}
+
+/// Whether `RISC0_DEV_MODE` in our own environment turns dev mode on for every child anyway.
+pub fn dev_mode_inherited() -> bool {
+    std::env::var("RISC0_DEV_MODE")
+        .is_ok_and(|value| matches!(value.to_lowercase().as_str(), "1" | "true" | "yes"))
+}
+
+/// Spawns a long-running child (e.g. anvil) that the supervisor owns until terminated.
+pub fn spawn_background(name: &str, command: &mut Command) -> Result<u32> {
+    let child = prepare(command).spawn()?;
+    let pid = child.id();
+    // Also persisted so `steel-tui clean` can find it if we die without cleaning up
+    let _ = clean::record_pid(pid);
+    children().push(Tracked {
+        pid,
+        name: name.to_string(),
+        started: Instant::now(),
+        child: Some(child),
+    });
+    Ok(pid)
+}
+
+/// Registers a child whose handle stays with the caller. Call [`untrack`] once it is reaped.
+pub fn track_foreground(name: &str, child: &Child) {
+    children().push(Tracked {
+        pid: child.id(),
+        name: name.to_string(),
+        started: Instant::now(),
+        child: None,
+    });
+}
+
+pub fn untrack(pid: u32) {
+    children().retain(|tracked| tracked.pid != pid);
+}
+
+fn terminate_tracked(mut tracked: Tracked) {
+    platform::terminate_tree(tracked.pid);
+    if let Some(child) = tracked.child.as_mut() {
+        let _ = child.kill();
+        let _ = child.wait();
+        let _ = clean::forget_pid(tracked.pid);
+    }
+}
+
+/// Terminates one supervised child and its process group.
+pub fn terminate(pid: u32) {
+    let tracked = {
+        let mut children = children();
+        let index = children.iter().position(|t| t.pid == pid);
+        index.map(|i| children.remove(i))
+    };
+    if let Some(tracked) = tracked {
+        terminate_tracked(tracked);
+    }
+}
+
+/// Terminates every supervised child. Used on cleanup, panic, and exit. Returns how many
+/// children were still being tracked.
+pub fn terminate_all() -> usize {
+    let all: Vec<Tracked> = children().drain(..).collect();
+    let count = all.len();
+    for tracked in all {
+        terminate_tracked(tracked);
+    }
+    count
+}
+
+fn alive(tracked: &mut Tracked) -> bool {
+    match tracked.child.as_mut() {
+        Some(child) => matches!(child.try_wait(), Ok(None)),
+        // Foreground children are being waited on by their caller, so they're alive until untracked
+        None => true,
+    }
+}
+
+/// Whether a supervised child is still alive.
+pub fn is_running(pid: u32) -> bool {
+    children()
+        .iter_mut()
+        .find(|t| t.pid == pid)
+        .is_some_and(alive)
+}
+
+pub fn list() -> Vec<ChildInfo> {
+    children()
+        .iter_mut()
+        .map(|t| ChildInfo {
+            pid: t.pid,
+            name: t.name.clone(),
+            running: alive(t),
+            uptime: t.started.elapsed(),
+        })
+        .collect()
+}
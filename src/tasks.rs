@@ -0,0 +1,107 @@
+//! Bookkeeping for commands that run concurrently, each shown as its own progress row.
+
+use crate::transfer::Transfer;
+use std::{
+    process::{Child, Command},
+    sync::mpsc,
+    time::Instant,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TaskStatus {
+    Pending,
+    Running,
+    Done,
+    Failed,
+}
+
+impl TaskStatus {
+    pub fn icon(self) -> &'static str {
+        match self {
+            TaskStatus::Pending => "·",
+            TaskStatus::Running => "⏳",
+            TaskStatus::Done => "✓",
+            TaskStatus::Failed => "✗",
+        }
+    }
+}
+
+pub struct Task {
+    pub label: String,
+    pub command: Command,
+    pub status: TaskStatus,
+    /// Most recent output line, shown next to the label.
+    pub last_line: String,
+    pub started: Option<Instant>,
+    pub child: Option<Child>,
+    pub output: Option<mpsc::Receiver<String>>,
+    /// Everything the task printed, for error reports.
+    pub captured: Vec<String>,
+    /// git's transfer progress, for tasks that clone.
+    pub transfer: Transfer,
+}
+
+impl Task {
+    pub fn new(label: &str, command: Command) -> Self {
+        Self {
+            label: label.to_string(),
+            command,
+            status: TaskStatus::Pending,
+            last_line: String::new(),
+            started: None,
+            child: None,
+            output: None,
+            captured: Vec::new(),
+            transfer: Transfer::default(),
+        }
+    }
+}
+
+/// Forwards stdout and stderr line by line, interleaved as they arrive. A carriage return ends
+/// a line too, so progress that redraws itself in place (git's transfer progress) comes through
+/// as it updates. The channel disconnects once both streams are closed.
+pub fn stream_output(child: &mut Child) -> mpsc::Receiver<String> {
+    use std::io::{BufReader, Read};
+
+    fn forward(stream: impl Read + Send + 'static, tx: mpsc::Sender<String>) {
+        std::thread::spawn(move || {
+            let mut line = Vec::new();
+            let mut previous = 0;
+            for byte in BufReader::new(stream)
+                .bytes()
+                .map_while(std::result::Result::ok)
+            {
+                let ends = match byte {
+                    b'\n' => !(line.is_empty() && previous == b'\r'),
+                    b'\r' => !line.is_empty(),
+                    _ => {
+                        line.push(byte);
+                        false
+                    }
+                };
+                previous = byte;
+                if ends {
+                    if tx
+                        .send(String::from_utf8_lossy(&line).into_owned())
+                        .is_err()
+                    {
+                        return;
+                    }
+                    line.clear();
+                }
+            }
+            if !line.is_empty() {
+                let _ = tx.send(String::from_utf8_lossy(&line).into_owned());
+            }
+        });
+    }
+
+    let (tx, rx) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        forward(stdout, tx.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward(stderr, tx);
+    }
+    rx
+}
@@ -0,0 +1,102 @@
+//! Bare copies of the template and Forge dependency repositories, refreshed after every online
+//! install so a later one can run offline. Once cached, the template is brought up to date with
+//! a fetch and cloned locally instead of downloaded again.
+
+use crate::{http, manifest, paths};
+use color_eyre::{eyre::eyre, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Cache entry for the template clone: the release branch of risc0-ethereum, shallow.
+pub const TEMPLATE: &str = "template";
+
+/// Cache entry for the template clone from `url`: [`TEMPLATE`] for risc0-ethereum, one of its
+/// own for anything else, e.g. a fork.
+pub fn template_entry(url: &str) -> String {
+    if manifest::is_risc0_ethereum(url) {
+        return TEMPLATE.to_string();
+    }
+    let url = url.trim_end_matches('/').trim_end_matches(".git");
+    let path = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let name: String = path
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    format!("{}-{}", TEMPLATE, name.trim_matches('-'))
+}
+
+/// Where the bare repository for `name` lives.
+pub fn repo_path(name: &str) -> PathBuf {
+    paths::template_cache_dir().join(format!("{}.git", name))
+}
+
+pub fn is_cached(name: &str) -> bool {
+    repo_path(name).join("HEAD").exists()
+}
+
+/// Replaces the cached copy of `name` with a bare clone of the repository at `source`.
+/// Local clones hardlink objects, so this is quick even for large histories.
+pub fn store(name: &str, source: &Path) -> Result<()> {
+    fs::create_dir_all(paths::template_cache_dir())?;
+    let target = repo_path(name);
+    let staged = target.with_extension("git.new");
+    let _ = fs::remove_dir_all(&staged);
+
+    let output = http::git()
+        .args(["clone", "--quiet", "--bare"])
+        .arg(source)
+        .arg(&staged)
+        .output()?;
+    if !output.status.success() {
+        let _ = fs::remove_dir_all(&staged);
+        return Err(eyre!(
+            "Could not cache {}: {}",
+            name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let _ = fs::remove_dir_all(&target);
+    fs::rename(staged, target)?;
+    Ok(())
+}
+
+/// Whether the cached copy of `name` holds `branch`.
+pub fn has_branch(name: &str, branch: &str) -> bool {
+    http::git()
+        .arg("--git-dir")
+        .arg(repo_path(name))
+        .args(["rev-parse", "--quiet", "--verify"])
+        .arg(format!("refs/heads/{}", branch))
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Fetches `branch` of `url` into the cached copy of `name`; only what changed since it was
+/// cached is downloaded.
+pub fn refresh_command(name: &str, url: &str, branch: &str) -> Command {
+    let mut command = http::git();
+    command
+        .arg("--git-dir")
+        .arg(repo_path(name))
+        .args(["fetch", "--depth", "1", "--progress", url])
+        .arg(format!("+refs/heads/{0}:refs/heads/{0}", branch));
+    command
+}
+
+/// Where to clone `name` from: the cache when offline, `url` otherwise.
+pub fn clone_source(name: &str, url: &str, offline: bool) -> Result<String> {
+    if !offline {
+        return Ok(url.to_string());
+    }
+    if !is_cached(name) {
+        return Err(eyre!(
+            "Offline, and '{}' isn't in the template cache yet. Run one install online first.",
+            name
+        ));
+    }
+    Ok(repo_path(name).to_string_lossy().to_string())
+}
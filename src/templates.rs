@@ -0,0 +1,97 @@
+//! The templates new projects can be made from, read from `templates.toml`: the one bundled
+//! with the app, extended by the user's. A template is a directory of a repository at a branch,
+//! so adding one needs no code.
+
+use crate::{compat, manifest, paths};
+use color_eyre::{eyre::eyre, Result};
+use serde::Deserialize;
+use std::fs;
+
+const BUNDLED: &str = include_str!("templates.toml");
+
+#[derive(Deserialize)]
+struct File {
+    #[serde(default, rename = "template")]
+    templates: Vec<Template>,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Template {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_repository")]
+    pub repository: String,
+    pub branch: String,
+    /// The template's directory in the repository; `.` for the whole repository.
+    pub path: String,
+    /// The risc0-ethereum release whose dependency rules apply, for branches that aren't one.
+    pub release: Option<String>,
+    /// Commands run in the project once it's set up, e.g. `cargo fmt`.
+    #[serde(default)]
+    pub post_install: Vec<String>,
+}
+
+fn default_repository() -> String {
+    format!("{}.git", manifest::RISC0_ETHEREUM_REPO)
+}
+
+impl Template {
+    pub fn release(&self) -> Option<&'static compat::Release> {
+        compat::release(self.release.as_deref().unwrap_or(&self.branch))
+    }
+
+    /// Whether the template is the whole repository rather than a directory of it.
+    pub fn is_whole_repository(&self) -> bool {
+        matches!(self.path.trim_matches('/'), "" | ".")
+    }
+}
+
+/// The templates shipped with the app.
+pub fn bundled() -> Vec<Template> {
+    toml::from_str::<File>(BUNDLED)
+        .expect("the bundled templates.toml is valid")
+        .templates
+}
+
+/// The bundled templates, with the user's added or replacing those of the same name and branch.
+pub fn load() -> Result<Vec<Template>> {
+    let mut templates = bundled();
+    let path = paths::templates_file();
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(templates),
+        Err(e) => return Err(eyre!("Could not read {}: {}", path.display(), e)),
+    };
+    let file: File =
+        toml::from_str(&content).map_err(|e| eyre!("Invalid {}: {}", path.display(), e))?;
+    for template in file.templates {
+        if template.release().is_none() {
+            return Err(eyre!(
+                "'{}' in {}: {} isn't a risc0-ethereum release; set `release` to the one it follows",
+                template.name,
+                path.display(),
+                template.release.as_deref().unwrap_or(&template.branch)
+            ));
+        }
+        match templates
+            .iter_mut()
+            .find(|t| t.name == template.name && t.branch == template.branch)
+        {
+            Some(existing) => *existing = template,
+            None => templates.push(template),
+        }
+    }
+    Ok(templates)
+}
+
+/// Where the template called `name` lives in its repository, for projects made before it was
+/// looked up.
+pub fn path_of(name: &str) -> String {
+    load()
+        .unwrap_or_else(|_| bundled())
+        .into_iter()
+        .find(|template| template.name == name)
+        .map_or_else(|| format!("examples/{}", name), |template| template.path)
+}
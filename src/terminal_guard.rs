@@ -0,0 +1,35 @@
+//! Puts the terminal into the TUI's mode and takes it out again however the app exits: a normal
+//! return, an early `?`, or unwinding from a panic.
+
+use crate::shutdown;
+use color_eyre::Result;
+use crossterm::{
+    event::EnableMouseCapture,
+    execute,
+    terminal::{enable_raw_mode, EnterAlternateScreen},
+};
+use std::io::stdout;
+
+/// Raw mode, the alternate screen and mouse capture, for as long as the guard lives.
+pub struct TerminalGuard {
+    active: bool,
+}
+
+impl TerminalGuard {
+    /// Switches the terminal over. A failure halfway switches back what was switched.
+    pub fn enter() -> Result<Self> {
+        let mut guard = TerminalGuard { active: false };
+        enable_raw_mode()?;
+        guard.active = true;
+        execute!(stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        if self.active {
+            shutdown::restore_terminal();
+        }
+    }
+}
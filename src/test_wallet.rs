@@ -0,0 +1,28 @@
+//! Throwaway wallets for testnets, made on this machine so a real key never has to be pasted in.
+
+use crate::redact;
+use alloy::signers::local::PrivateKeySigner;
+
+pub struct TestWallet {
+    /// Checksummed, e.g. `0x3f2A…`.
+    pub address: String,
+    pub private_key: String,
+}
+
+impl TestWallet {
+    pub fn generate() -> Self {
+        let signer = PrivateKeySigner::random();
+        let private_key = signer.to_bytes().to_string();
+        redact::register(&private_key);
+        Self {
+            address: signer.address().to_string(),
+            private_key,
+        }
+    }
+
+    /// What it's called in the credentials store: the full address, so no two wallets share a
+    /// name and storing one never replaces another.
+    pub fn credential_name(&self) -> String {
+        format!("test-wallet-{}", self.address.to_lowercase())
+    }
+}
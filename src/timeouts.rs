@@ -0,0 +1,60 @@
+use std::{collections::HashMap, time::Duration};
+
+/// Limits applied to every spawned command.
+#[derive(Clone)]
+pub struct Timeouts {
+    /// Warn that a step appears stuck after this long without output.
+    pub stuck_after: Duration,
+    /// Hard limit for programs without an explicit override.
+    pub default_limit: Duration,
+    /// Hard limits by program name (`git`, `cargo`, ...).
+    pub per_program: HashMap<String, Duration>,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        let per_program = [
+            // Clones and submodule fetches of large repositories on slow links
+            ("git", 20 * 60),
+            // Guest builds can take a long time on a cold cache
+            ("cargo", 60 * 60),
+            ("forge", 20 * 60),
+            ("bash", 60 * 60),
+        ]
+        .into_iter()
+        .map(|(program, secs)| (program.to_string(), Duration::from_secs(secs)))
+        .collect();
+
+        Self {
+            stuck_after: Duration::from_secs(3 * 60),
+            default_limit: Duration::from_secs(10 * 60),
+            per_program,
+        }
+    }
+}
+
+impl Timeouts {
+    pub fn limit_for(&self, program: &str) -> Duration {
+        // Programs can be given as a path, and have an extension on Windows
+        let program = std::path::Path::new(program)
+            .file_stem()
+            .map_or(program.into(), |stem| stem.to_string_lossy());
+        self.per_program
+            .get(program.as_ref())
+            .copied()
+            .unwrap_or(self.default_limit)
+    }
+
+    /// Applies a `PROGRAM=SECS` override from the command line.
+    pub fn set_override(&mut self, spec: &str) -> Result<(), String> {
+        let (program, secs) = spec
+            .split_once('=')
+            .ok_or_else(|| format!("expected PROGRAM=SECS, got '{}'", spec))?;
+        let secs: u64 = secs
+            .parse()
+            .map_err(|_| format!("invalid number of seconds in '{}'", spec))?;
+        self.per_program
+            .insert(program.to_string(), Duration::from_secs(secs));
+        Ok(())
+    }
+}
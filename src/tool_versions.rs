@@ -0,0 +1,102 @@
+//! Pins the Rust and Foundry versions a project was created with in a version manager file,
+//! so teammates using asdf or mise get the same toolchains.
+
+use crate::tools;
+use color_eyre::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Format {
+    Off,
+    /// Read by both asdf and mise.
+    #[default]
+    ToolVersions,
+    Mise,
+}
+
+impl Format {
+    pub const ALL: &'static [Format] = &[Format::Off, Format::ToolVersions, Format::Mise];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Format::Off => "Off",
+            Format::ToolVersions => ".tool-versions (asdf, mise)",
+            Format::Mise => ".mise.toml (mise)",
+        }
+    }
+
+    fn file_name(self) -> Option<&'static str> {
+        match self {
+            Format::Off => None,
+            Format::ToolVersions => Some(".tool-versions"),
+            Format::Mise => Some(".mise.toml"),
+        }
+    }
+}
+
+/// `1.85.0` out of `rustc 1.85.0 (4d91de4e4 2025-02-17)`, or `1.0.0` out of
+/// `forge Version: 1.0.0-stable`.
+fn semver(output: &str) -> Option<String> {
+    Regex::new(r"\b(\d+\.\d+\.\d+)\b")
+        .expect("valid regex")
+        .captures(output)
+        .map(|caps| caps[1].to_string())
+}
+
+/// Installed versions worth pinning, by the name asdf and mise know the tool as. Rust is left
+/// out when `rust-toolchain.toml` already pins it.
+fn detect(root: &Path) -> Vec<(&'static str, String)> {
+    let mut pins = Vec::new();
+    if !root.join("rust-toolchain.toml").exists() && !root.join("rust-toolchain").exists() {
+        if let Some(version) = tools::version("rustc", &["--version"])
+            .ok()
+            .and_then(|v| semver(&v))
+        {
+            pins.push(("rust", version));
+        }
+    }
+    if let Some(version) = tools::version("forge", &["--version"])
+        .ok()
+        .and_then(|v| semver(&v))
+    {
+        pins.push(("foundry", version));
+    }
+    pins
+}
+
+/// Writes the pins into the project at `root`. Returns a line describing what was written, or
+/// `None` when the format is off or nothing could be detected.
+pub fn write(root: &Path, format: Format) -> Result<Option<String>> {
+    let Some(file_name) = format.file_name() else {
+        return Ok(None);
+    };
+    let pins = detect(root);
+    if pins.is_empty() {
+        return Ok(None);
+    }
+    let content = match format {
+        Format::Mise => {
+            let mut content = String::from("[tools]\n");
+            for (tool, version) in &pins {
+                content.push_str(&format!("{} = \"{}\"\n", tool, version));
+            }
+            content
+        }
+        _ => pins
+            .iter()
+            .map(|(tool, version)| format!("{} {}\n", tool, version))
+            .collect(),
+    };
+    fs::write(root.join(file_name), content)?;
+    Ok(Some(format!(
+        "Pinned {} in {}",
+        pins.iter()
+            .map(|(tool, version)| format!("{} {}", tool, version))
+            .collect::<Vec<_>>()
+            .join(", "),
+        file_name
+    )))
+}
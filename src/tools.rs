@@ -0,0 +1,35 @@
+//! Versions of the external tools the app drives, for crash reports and project metadata.
+
+use std::process::Command;
+
+/// Name, program, and arguments that print a version.
+const TOOLS: &[(&str, &str, &[&str])] = &[
+    ("rustc", "rustc", &["--version"]),
+    ("cargo", "cargo", &["--version"]),
+    ("cargo-risczero", "cargo", &["risczero", "--version"]),
+    ("forge", "forge", &["--version"]),
+    ("anvil", "anvil", &["--version"]),
+    ("git", "git", &["--version"]),
+];
+
+/// First line `program args` prints, or why it couldn't be run.
+pub fn version(program: &str, args: &[&str]) -> Result<String, String> {
+    match Command::new(program).args(args).output() {
+        Ok(output) if output.status.success() => Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string()),
+        Ok(output) => Err(format!("exited with {}", output.status)),
+        Err(e) => Err(format!("not available ({})", e)),
+    }
+}
+
+/// Version of every tool, by name.
+pub fn versions() -> Vec<(&'static str, Result<String, String>)> {
+    TOOLS
+        .iter()
+        .map(|(name, program, args)| (*name, version(program, args)))
+        .collect()
+}
@@ -0,0 +1,85 @@
+//! Encodes calls to a deployed contract's functions from arguments typed as Solidity literals
+//! (`42`, `0xab…`, `[1,2]`, `(true,"hi")`) and decodes what view functions return.
+
+use crate::journal;
+use alloy::{
+    dyn_abi::{FunctionExt, JsonAbiExt, Specifier},
+    json_abi::{Function, StateMutability},
+    primitives::{utils::parse_ether, Bytes, U256},
+};
+use color_eyre::{eyre::eyre, Result};
+
+/// Whether calling `function` only reads state, so it's called rather than sent.
+pub fn is_view(function: &Function) -> bool {
+    matches!(
+        function.state_mutability,
+        StateMutability::View | StateMutability::Pure
+    )
+}
+
+pub fn is_payable(function: &Function) -> bool {
+    function.state_mutability == StateMutability::Payable
+}
+
+/// Name and type of each parameter, e.g. `seal (bytes)`, for labelling its input.
+pub fn param_labels(function: &Function) -> Vec<String> {
+    function
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(i, input)| {
+            let name = if input.name.is_empty() {
+                format!("arg{}", i)
+            } else {
+                input.name.clone()
+            };
+            format!("{} ({})", name, input.selector_type())
+        })
+        .collect()
+}
+
+/// Selector followed by the ABI-encoded arguments.
+pub fn calldata(function: &Function, args: &[String]) -> Result<Bytes> {
+    let labels = param_labels(function);
+    let values = function
+        .inputs
+        .iter()
+        .zip(args)
+        .zip(&labels)
+        .map(|((input, arg), label)| {
+            let ty = input
+                .resolve()
+                .map_err(|e| eyre!("{}: unsupported type: {}", label, e))?;
+            // Parser errors repeat the input with a caret under it; the last line says why
+            ty.coerce_str(arg.trim()).map_err(|e| {
+                let e = e.to_string();
+                eyre!("{}: {}", label, e.lines().last().unwrap_or(&e))
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let encoded = function
+        .abi_encode_input(&values)
+        .map_err(|e| eyre!("Could not encode the call: {}", e))?;
+    Ok(encoded.into())
+}
+
+/// Ether to send, e.g. `0.01`; empty means none.
+pub fn value(ether: &str) -> Result<U256> {
+    let ether = ether.trim();
+    if ether.is_empty() {
+        return Ok(U256::ZERO);
+    }
+    parse_ether(ether).map_err(|e| eyre!("Invalid value '{}': {}", ether, e))
+}
+
+/// What a view function returned, one value per output.
+pub fn decode_output(function: &Function, output: &[u8]) -> Result<String> {
+    let values = function
+        .abi_decode_output(output)
+        .map_err(|e| eyre!("Could not decode the result: {}", e))?;
+    Ok(values
+        .iter()
+        .map(journal::format_value)
+        .collect::<Vec<_>>()
+        .join(", "))
+}
@@ -0,0 +1,66 @@
+//! Follows git's sideband progress (`Receiving objects:  45% (450/1000), 1.20 MiB | 600.00 KiB/s`)
+//! so clones can show how far along they are and how fast they're downloading.
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+fn pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"^(?:remote: )?([A-Z][a-z]+(?: [a-z]+)*):\s+(\d+)% \((\d+)/(\d+)\)(?:, ([\d.]+ [KMGT]?i?B)(?: \| ([\d.]+ [KMGT]?i?B/s))?)?",
+        )
+        .expect("valid regex")
+    })
+}
+
+/// The latest progress of one git command.
+#[derive(Default)]
+pub struct Transfer {
+    /// e.g. `Receiving objects`, `Resolving deltas`.
+    pub phase: String,
+    pub percent: u8,
+    pub done: u64,
+    pub total: u64,
+    /// Downloaded so far, as git prints it. Kept once the transfer moves on to resolving deltas.
+    pub received: Option<String>,
+    /// Throughput while receiving.
+    pub rate: Option<String>,
+}
+
+impl Transfer {
+    /// Updates from a line of git's output. Returns whether it was a progress line.
+    pub fn observe(&mut self, line: &str) -> bool {
+        let Some(caps) = pattern().captures(line.trim()) else {
+            return false;
+        };
+        self.phase = caps[1].to_string();
+        self.percent = caps[2].parse().unwrap_or_default();
+        self.done = caps[3].parse().unwrap_or_default();
+        self.total = caps[4].parse().unwrap_or_default();
+        if let Some(received) = caps.get(5) {
+            self.received = Some(received.as_str().to_string());
+        }
+        // The rate only means something while objects are coming in
+        self.rate = caps.get(6).map(|rate| rate.as_str().to_string());
+        true
+    }
+
+    pub fn seen(&self) -> bool {
+        !self.phase.is_empty()
+    }
+
+    /// e.g. `Receiving objects 45% (450/1000) · 1.20 MiB at 600.00 KiB/s`.
+    pub fn describe(&self) -> String {
+        let mut text = format!(
+            "{} {}% ({}/{})",
+            self.phase, self.percent, self.done, self.total
+        );
+        match (&self.received, &self.rate) {
+            (Some(received), Some(rate)) => text.push_str(&format!(" · {} at {}", received, rate)),
+            (Some(received), None) => text.push_str(&format!(" · {} received", received)),
+            _ => {}
+        }
+        text
+    }
+}
@@ -0,0 +1,72 @@
+//! Learn mode: short explanations shown next to each step of creating and testing a project,
+//! for people meeting Steel and RISC Zero for the first time.
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Lesson {
+    Steel,
+    Template,
+    Layout,
+    Guest,
+    Verifier,
+    Toolbox,
+    Bonsai,
+    EndToEnd,
+}
+
+impl Lesson {
+    pub fn title(self) -> &'static str {
+        match self {
+            Lesson::Steel => "What is Steel?",
+            Lesson::Template => "The template",
+            Lesson::Layout => "How the project is laid out",
+            Lesson::Guest => "What the guest does",
+            Lesson::Verifier => "Why there's a verifier contract",
+            Lesson::Toolbox => "What next",
+            Lesson::Bonsai => "What Bonsai provides",
+            Lesson::EndToEnd => "The end-to-end test",
+        }
+    }
+
+    pub fn text(self) -> &'static [&'static str] {
+        match self {
+            Lesson::Steel => &[
+                "Steel lets a program read Ethereum state (balances, contract storage, view calls) off-chain inside the RISC Zero zkVM, and prove it read it correctly.",
+                "A contract can then act on the result without re-running the computation or trusting whoever ran it.",
+                "Type a project name to create one from the ERC20 counter example.",
+            ],
+            Lesson::Template => &[
+                "The project starts from the erc20-counter example in risc0-ethereum, cloned at the release branch matching your RISC Zero toolchain.",
+                "Only that example is checked out, which keeps the download small.",
+            ],
+            Lesson::Layout => &[
+                "methods/ holds the guest: the program that runs inside the zkVM.",
+                "apps/ holds the host: it fetches the chain state, runs the guest, gets a proof and sends it on-chain.",
+                "contracts/ holds the Solidity that accepts the proofs.",
+            ],
+            Lesson::Guest => &[
+                "The guest calls balanceOf on an ERC20 through Steel and commits the result, plus the block it read from, to its journal.",
+                "The journal is the guest's public output. The proof (the receipt's seal) shows that this exact guest produced it.",
+                "Cargo.toml files are pointed at the risc0-ethereum release so the host, guest and contracts agree.",
+            ],
+            Lesson::Verifier => &[
+                "The counter contract only increments when given a proof that an account holds enough tokens.",
+                "It checks the seal through RISC Zero's verifier contract, against the guest's image ID: the hash that identifies the guest program.",
+                "It also checks the Steel commitment, so the proven state really is from that chain.",
+                "Forge dependencies (forge-std, OpenZeppelin, risc0-ethereum) are added as git submodules under lib/.",
+            ],
+            Lesson::Toolbox => &[
+                "The project is ready. The toolbox runs the end-to-end test, deploys, and has tools to inspect what the project builds.",
+                "A good first step is the end-to-end test: it builds everything, starts a local chain and proves a real balance.",
+            ],
+            Lesson::Bonsai => &[
+                "Proving is heavy. Bonsai is RISC Zero's proving service: the host sends it the guest and its input, and gets the receipt back.",
+                "It needs an API key, requested from RISC Zero. The local prover needs no key but a powerful machine.",
+                "The Boundless market is a decentralized alternative, paid per proof.",
+            ],
+            Lesson::EndToEnd => &[
+                "The test starts anvil, a local Ethereum node, and deploys a token, the verifier and the counter to it.",
+                "The host then proves the token balance and submits the proof; the counter increments only if the contract accepts it.",
+            ],
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{
+    exit_code::Outcome,
+    http, manifest,
+    project_file::{self, ProjectFile},
+};
+use color_eyre::{eyre::eyre, Result};
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+};
+
+const SUBMODULE: &str = "lib/risc0-ethereum";
+
+/// Finds the newest `release-X.Y` branch published in risc0-ethereum.
+pub fn latest_release() -> Result<String> {
+    let output = http::git()
+        .args([
+            "ls-remote",
+            "--heads",
+            manifest::RISC0_ETHEREUM_REPO,
+            "release-*",
+        ])
+        .output()?;
+    if !output.status.success() {
+        return Err(eyre!("Failed to list risc0-ethereum release branches"));
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.rsplit_once("refs/heads/").map(|(_, b)| b.to_string()))
+        .filter(|branch| manifest::release_version(branch).is_some())
+        .max_by_key(|branch| manifest::release_version(branch))
+        .ok_or_else(|| eyre!("No release branches found in risc0-ethereum"))
+}
+
+/// Prints a minimal line diff. Rewrites never add or remove lines, so a zip is enough.
+fn print_diff(path: &Path, old: &str, new: &str) {
+    println!("--- {}", path.display());
+    println!("+++ {}", path.display());
+    for (number, (before, after)) in old.lines().zip(new.lines()).enumerate() {
+        if before != after {
+            println!("@@ line {} @@", number + 1);
+            println!("-{}", before);
+            println!("+{}", after);
+        }
+    }
+}
+
+fn confirm(question: &str) -> Result<bool> {
+    print!("{} [y/N] ", question);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+fn git(project: &Path, args: &[&str]) -> Result<()> {
+    let status = http::git().args(args).current_dir(project).status()?;
+    if !status.success() {
+        return Err(eyre!("git {} failed", args.join(" ")));
+    }
+    Ok(())
+}
+
+/// Entry point for `steel-tui upgrade`.
+pub fn run(project: &Path, to: Option<String>, yes: bool, ci: bool) -> Result<Outcome> {
+    let mut manifests: Vec<(PathBuf, String)> = Vec::new();
+    for path in manifest::find_cargo_toml_files(project)? {
+        let content = fs::read_to_string(&path)?;
+        manifests.push((path, content));
+    }
+
+    // Manifests pinned to something we can't map to a release fall back to the recorded branch
+    let detected = manifests
+        .iter()
+        .find_map(|(_, content)| manifest::detect_branch(content));
+    let Some(current) = detected.or(ProjectFile::load(project)?.map(|m| m.project.branch)) else {
+        return Err(eyre!(
+            "No risc0-ethereum git dependencies found in '{}'. Was it created by steel-tui?",
+            project.display()
+        ));
+    };
+
+    let target = match to {
+        Some(branch) => branch,
+        None => latest_release()?,
+    };
+    let Some(target_version) = manifest::release_version(&target) else {
+        return Err(eyre!(
+            "--to {}: expected a risc0-ethereum release branch, e.g. release-2.1",
+            target
+        ));
+    };
+    println!("Current risc0-ethereum branch: {}", current);
+    println!("Target risc0-ethereum branch:  {}", target);
+
+    // A branch that isn't a release, e.g. main, can't be compared; move it to the release
+    if manifest::release_version(&current).is_some_and(|version| target_version <= version) {
+        println!("✓ Already up to date.");
+        return Ok(Outcome::Success);
+    }
+
+    let changes: Vec<(PathBuf, String, String)> = manifests
+        .into_iter()
+        .filter_map(|(path, old)| {
+            let new = manifest::rewrite_branch(&old, &target);
+            (new != old).then_some((path, old, new))
+        })
+        .collect();
+    let has_submodule = project.join(SUBMODULE).exists();
+
+    println!();
+    for (path, old, new) in &changes {
+        print_diff(path, old, new);
+    }
+    if has_submodule {
+        println!("--- .gitmodules");
+        println!("+++ .gitmodules");
+        println!("-submodule.{}.branch = {}", SUBMODULE, current);
+        println!("+submodule.{}.branch = {}", SUBMODULE, target);
+    }
+    println!();
+
+    if !yes {
+        if ci {
+            println!("✗ Not applying changes without confirmation. Pass --yes to apply.");
+            return Ok(Outcome::MissingInput);
+        }
+        if !confirm("Apply these changes?")? {
+            println!("Upgrade cancelled.");
+            return Ok(Outcome::UserAbort);
+        }
+    }
+
+    for (path, _, new) in &changes {
+        fs::write(path, new)?;
+        println!("✓ Updated {}", path.display());
+    }
+
+    if has_submodule {
+        let key = format!("submodule.{}.branch", SUBMODULE);
+        git(project, &["config", "-f", ".gitmodules", &key, &target])?;
+        git(project, &["submodule", "sync", SUBMODULE])?;
+        git(
+            project,
+            &["submodule", "update", "--init", "--remote", SUBMODULE],
+        )?;
+        println!("✓ Updated {} to {}", SUBMODULE, target);
+    }
+
+    if let Some(mut metadata) = ProjectFile::load(project)? {
+        metadata.project.branch = target.clone();
+        metadata.save(project)?;
+        println!("✓ Recorded {} in {}", target, project_file::FILE_NAME);
+    }
+
+    println!("Done. Run `cargo update` in the project to refresh Cargo.lock.");
+    Ok(Outcome::Success)
+}
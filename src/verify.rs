@@ -0,0 +1,107 @@
+//! Source verification of deployed contracts with `forge verify-contract`, against Etherscan,
+//! a Blockscout instance, or Sourcify.
+
+use std::process::Command;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum VerifierKind {
+    None,
+    Etherscan,
+    Blockscout,
+    Sourcify,
+}
+
+impl VerifierKind {
+    pub const ALL: &'static [VerifierKind] = &[
+        VerifierKind::None,
+        VerifierKind::Etherscan,
+        VerifierKind::Blockscout,
+        VerifierKind::Sourcify,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            VerifierKind::None => "Don't verify",
+            VerifierKind::Etherscan => "Etherscan",
+            VerifierKind::Blockscout => "Blockscout",
+            VerifierKind::Sourcify => "Sourcify",
+        }
+    }
+
+    /// Label of the free-text field this verifier needs, if any.
+    pub fn detail_label(self) -> Option<&'static str> {
+        match self {
+            VerifierKind::Etherscan => Some("API key"),
+            VerifierKind::Blockscout => Some("Instance URL"),
+            VerifierKind::None | VerifierKind::Sourcify => None,
+        }
+    }
+}
+
+pub enum Verifier {
+    Etherscan {
+        api_key: String,
+    },
+    /// Blockscout's API lives under `/api/` of the explorer.
+    Blockscout {
+        url: String,
+    },
+    Sourcify,
+}
+
+impl Verifier {
+    /// `None` when the user chose not to verify.
+    pub fn new(kind: VerifierKind, detail: &str) -> Option<Self> {
+        let detail = detail.trim().to_string();
+        match kind {
+            VerifierKind::None => None,
+            VerifierKind::Etherscan => Some(Verifier::Etherscan { api_key: detail }),
+            VerifierKind::Blockscout => Some(Verifier::Blockscout {
+                url: blockscout_api_url(&detail),
+            }),
+            VerifierKind::Sourcify => Some(Verifier::Sourcify),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verifier::Etherscan { .. } => "Etherscan",
+            Verifier::Blockscout { .. } => "Blockscout",
+            Verifier::Sourcify => "Sourcify",
+        }
+    }
+
+    /// `forge verify-contract` for one deployed contract. Constructor arguments are recovered
+    /// from the creation transaction through `rpc_url`.
+    pub fn command(&self, address: &str, contract: &str, chain_id: u64, rpc_url: &str) -> Command {
+        let mut command = Command::new("forge");
+        command
+            .args(["verify-contract", address, contract])
+            .args(["--chain", &chain_id.to_string()])
+            .args(["--rpc-url", rpc_url, "--guess-constructor-args", "--watch"]);
+        match self {
+            Verifier::Etherscan { api_key } => {
+                command.args(["--verifier", "etherscan"]);
+                // Passed through the environment so the key never appears in a command line
+                command.env("ETHERSCAN_API_KEY", api_key);
+            }
+            Verifier::Blockscout { url } => {
+                command.args(["--verifier", "blockscout", "--verifier-url", url]);
+            }
+            Verifier::Sourcify => {
+                command.args(["--verifier", "sourcify"]);
+            }
+        }
+        command
+    }
+}
+
+/// Accepts either the explorer URL or its API URL.
+fn blockscout_api_url(url: &str) -> String {
+    let url = url.trim_end_matches('/');
+    if url.ends_with("/api") {
+        format!("{}/", url)
+    } else {
+        format!("{}/api/", url)
+    }
+}
@@ -0,0 +1,116 @@
+//! Watches a project's Rust and Solidity sources and reports which build each change needs.
+
+use crate::platform;
+use color_eyre::Result;
+use notify::{RecursiveMode, Watcher as _};
+use std::{
+    collections::BTreeSet,
+    path::{Component, Path, PathBuf},
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+
+/// Build output, dependencies, and tool state; changes there never trigger a rebuild.
+const IGNORED_DIRS: &[&str] = &[".git", "target", "out", "cache", "broadcast", "lib"];
+
+/// Written by `cargo build` itself, so watching them would rebuild forever.
+const GENERATED: &[&str] = &["ImageID.sol", "Elf.sol"];
+
+/// Changes closer together than this are built together, e.g. an editor saving several files.
+const SETTLE: Duration = Duration::from_millis(300);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Build {
+    Cargo,
+    Forge,
+}
+
+impl Build {
+    pub fn label(self) -> &'static str {
+        match self {
+            Build::Cargo => "cargo build",
+            Build::Forge => "forge build",
+        }
+    }
+
+    /// Which build a change to `path` calls for, if any.
+    fn for_path(root: &Path, path: &Path) -> Option<Self> {
+        let relative = path.strip_prefix(root).ok()?;
+        let ignored = relative.components().any(|c| match c {
+            Component::Normal(name) => IGNORED_DIRS.iter().any(|dir| name == *dir),
+            _ => false,
+        });
+        let name = relative.file_name()?.to_string_lossy();
+        if ignored || GENERATED.contains(&name.as_ref()) {
+            return None;
+        }
+        match relative.extension()?.to_str()? {
+            "rs" => Some(Build::Cargo),
+            "sol" => Some(Build::Forge),
+            "toml" if name == "foundry.toml" => Some(Build::Forge),
+            "toml" if name == "Cargo.toml" => Some(Build::Cargo),
+            "txt" if name == "remappings.txt" => Some(Build::Forge),
+            _ => None,
+        }
+    }
+}
+
+pub struct Watcher {
+    // Stops watching when dropped
+    _watcher: notify::RecommendedWatcher,
+    events: mpsc::Receiver<PathBuf>,
+    root: PathBuf,
+    pending: BTreeSet<Build>,
+    /// Most recent relevant change, relative to the root.
+    pub last_change: Option<PathBuf>,
+    changed_at: Option<Instant>,
+}
+
+impl Watcher {
+    pub fn start(root: &Path) -> Result<Self> {
+        // Events carry absolute paths
+        let root = platform::canonicalize(root)?;
+        let (tx, events) = mpsc::channel();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                if let Ok(event) = event {
+                    if event.kind.is_create() || event.kind.is_modify() || event.kind.is_remove() {
+                        for path in event.paths {
+                            let _ = tx.send(path);
+                        }
+                    }
+                }
+            })?;
+        watcher.watch(&root, RecursiveMode::Recursive)?;
+        Ok(Self {
+            _watcher: watcher,
+            events,
+            root,
+            pending: BTreeSet::new(),
+            last_change: None,
+            changed_at: None,
+        })
+    }
+
+    /// Builds to run now: those some change called for, once changes have settled.
+    pub fn due(&mut self) -> Vec<Build> {
+        while let Ok(path) = self.events.try_recv() {
+            if let Some(build) = Build::for_path(&self.root, &path) {
+                self.pending.insert(build);
+                self.last_change = path.strip_prefix(&self.root).ok().map(Path::to_path_buf);
+                self.changed_at = Some(Instant::now());
+            }
+        }
+        match self.changed_at {
+            Some(at) if at.elapsed() >= SETTLE => {
+                self.changed_at = None;
+                // A guest change can change the image IDs the contracts are built with
+                if self.pending.contains(&Build::Cargo) {
+                    self.pending.insert(Build::Forge);
+                }
+                std::mem::take(&mut self.pending).into_iter().collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! Several examples in one project: each in a directory of its own, one Cargo workspace over all
+//! of them, and one `lib/` of Forge dependencies they share.
+
+use color_eyre::{eyre::eyre, Result};
+use regex::Regex;
+use std::{fs, path::Path};
+
+/// Sections whose entries are merged across examples; any other section is taken from the first
+/// example that has it.
+fn is_merged(header: &str) -> bool {
+    header == "[workspace.dependencies]" || header.starts_with("[patch")
+}
+
+/// Moves each example out of `examples/` into its own directory under `root` and replaces their
+/// workspaces with one. Returns what was done, one line each.
+pub fn arrange(root: &Path, examples: &[String]) -> Result<Vec<String>> {
+    for example in examples {
+        fs::rename(root.join("examples").join(example), root.join(example))?;
+    }
+    fs::remove_dir_all(root.join("examples"))?;
+
+    // The repository's own files; only the examples are wanted
+    for entry in fs::read_dir(root)? {
+        let path = entry?.path();
+        if path.is_file() {
+            fs::remove_file(path)?;
+        }
+    }
+
+    let mut report = merge(root, examples)?;
+
+    // One toolchain for the whole workspace
+    let mut toolchain = None;
+    for example in examples {
+        let file = root.join(example).join("rust-toolchain.toml");
+        if !file.exists() {
+            continue;
+        }
+        match toolchain {
+            None => {
+                fs::rename(&file, root.join("rust-toolchain.toml"))?;
+                toolchain = Some(example);
+            }
+            Some(_) => fs::remove_file(&file)?,
+        }
+    }
+    if let Some(example) = toolchain {
+        report.push(format!("✓ Using {}'s rust-toolchain.toml", example));
+    }
+    Ok(report)
+}
+
+/// One entry of a section: its key and its lines, continuation lines included.
+struct Entry {
+    key: String,
+    text: String,
+    example: String,
+}
+
+/// Writes the workspace manifest at `root` from the examples' and removes theirs.
+fn merge(root: &Path, examples: &[String]) -> Result<Vec<String>> {
+    let mut report = Vec::new();
+    let mut members = Vec::new();
+    let mut merged: Vec<(String, Vec<Entry>)> = Vec::new();
+    let mut taken: Vec<(String, String, String)> = Vec::new();
+
+    for example in examples {
+        let manifest = root.join(example).join("Cargo.toml");
+        let content = fs::read_to_string(&manifest)
+            .map_err(|e| eyre!("{} has no Cargo.toml: {}", example, e))?;
+        let table: toml::Table =
+            toml::from_str(&content).map_err(|e| eyre!("Invalid {}: {}", manifest.display(), e))?;
+        match (table.get("package"), table.get("workspace")) {
+            // A single crate joins as it is
+            (Some(_), None) => {
+                members.push(example.clone());
+                continue;
+            }
+            (Some(_), Some(_)) => {
+                return Err(eyre!(
+                    "{} is a package and a workspace at once, which can't be combined",
+                    example
+                ))
+            }
+            (None, workspace) => members.extend(
+                workspace
+                    .and_then(|workspace| workspace.get("members"))
+                    .and_then(|members| members.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|member| member.as_str())
+                    .map(|member| format!("{}/{}", example, member)),
+            ),
+        }
+
+        for (header, body) in sections(&content) {
+            if header.is_empty() || header == "[workspace]" {
+                continue;
+            }
+            if is_merged(&header) {
+                let index = match merged.iter().position(|(h, _)| *h == header) {
+                    Some(index) => index,
+                    None => {
+                        merged.push((header.clone(), Vec::new()));
+                        merged.len() - 1
+                    }
+                };
+                for mut entry in entries(&body, example) {
+                    entry.text = relocate(&entry.text, example);
+                    let existing = merged[index].1.iter().find(|e| e.key == entry.key);
+                    match existing {
+                        None => merged[index].1.push(entry),
+                        Some(existing) if existing.text == entry.text => {}
+                        Some(existing) => report.push(format!(
+                            "⚠ {} and {} want different {}; keeping {}'s",
+                            existing.example, example, entry.key, existing.example
+                        )),
+                    }
+                }
+            } else {
+                match taken.iter().find(|(h, _, _)| *h == header) {
+                    None => taken.push((header, body, example.clone())),
+                    Some((_, kept, from)) if kept.trim() != body.trim() => report.push(format!(
+                        "⚠ {} differs in {}; keeping {}'s",
+                        header, example, from
+                    )),
+                    Some(_) => {}
+                }
+            }
+        }
+
+        fs::remove_file(&manifest)?;
+        let _ = fs::remove_file(root.join(example).join("Cargo.lock"));
+    }
+
+    let mut manifest = String::from("[workspace]\nresolver = \"2\"\nmembers = [\n");
+    for member in &members {
+        manifest.push_str(&format!("    \"{}\",\n", member));
+    }
+    manifest.push_str("]\n");
+    for (header, entries) in &merged {
+        manifest.push_str(&format!("\n{}\n", header));
+        for entry in entries {
+            manifest.push_str(&entry.text);
+            manifest.push('\n');
+        }
+    }
+    for (header, body, _) in &taken {
+        manifest.push_str(&format!("\n{}\n{}\n", header, body.trim_end()));
+    }
+    fs::write(root.join("Cargo.toml"), manifest)?;
+    report.insert(
+        0,
+        format!(
+            "✓ One Cargo workspace for {} with {} member(s)",
+            examples.join(", "),
+            members.len()
+        ),
+    );
+    Ok(report)
+}
+
+/// The manifest split at its section headers, the lines before the first under `""`.
+fn sections(content: &str) -> Vec<(String, String)> {
+    let mut sections = vec![(String::new(), String::new())];
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') && !trimmed.starts_with("[[") && trimmed.ends_with(']') {
+            sections.push((trimmed.to_string(), String::new()));
+        } else if let Some((_, body)) = sections.last_mut() {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    sections
+}
+
+fn entries(body: &str, example: &str) -> Vec<Entry> {
+    let key = Regex::new(r"^\s*([A-Za-z0-9_.-]+)\s*=").expect("valid regex");
+    let mut entries: Vec<Entry> = Vec::new();
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        match (key.captures(line), entries.last_mut()) {
+            (Some(captures), _) => entries.push(Entry {
+                key: captures[1].to_string(),
+                text: line.to_string(),
+                example: example.to_string(),
+            }),
+            // The rest of a value spread over several lines
+            (None, Some(entry)) => {
+                entry.text.push('\n');
+                entry.text.push_str(line);
+            }
+            (None, None) => {}
+        }
+    }
+    entries
+}
+
+/// Points a path dependency within the example at where it is from the workspace root. Paths
+/// out of the example, into the risc0-ethereum repository, are rewritten to git dependencies
+/// later.
+fn relocate(text: &str, example: &str) -> String {
+    let path = Regex::new(r#"path\s*=\s*"([^"]+)""#).expect("valid regex");
+    path.replace_all(text, |captures: &regex::Captures| {
+        let target = &captures[1];
+        if target.starts_with("..") || target.starts_with('/') {
+            captures[0].to_string()
+        } else {
+            format!("path = \"{}/{}\"", example, target)
+        }
+    })
+    .to_string()
+}